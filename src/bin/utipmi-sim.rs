@@ -0,0 +1,93 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! utipmi-sim：按模型文件应答 utipmitool 请求的 BMC 模拟器
+//!
+//! ```text
+//! utipmi-sim -m doc/utipmi-sim-model.json &
+//! utipmitool -I dummy sdr list
+//! utipmitool -I lan -H 127.0.0.1 -p 6230 -U admin -P admin sel list
+//! ```
+
+use clap::Parser;
+use std::net::{IpAddr, SocketAddr};
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use utipmitool::interface::dummy::IPMI_DUMMY_DEFAULTSOCK;
+use utipmitool::logger::{init_logger, set_log_level, LogConfig, LogLevel};
+use utipmitool::sim::{serve_unix, SimBmc, SimLanServer, SimModel};
+
+#[derive(Parser, Debug)]
+#[command(name = "utipmi-sim", version, about = "BMC simulator for utipmitool")]
+struct SimArgs {
+    /// JSON model file
+    #[arg(short = 'm', long)]
+    model: PathBuf,
+    /// Unix socket served to -I dummy
+    #[arg(short = 's', long, default_value = IPMI_DUMMY_DEFAULTSOCK)]
+    socket: PathBuf,
+    /// UDP port served to -I lan, 0 disables RMCP
+    #[arg(short = 'p', long, default_value_t = 6230)]
+    port: u16,
+    /// Address the RMCP port binds to
+    #[arg(short = 'b', long, default_value = "127.0.0.1")]
+    bind: IpAddr,
+    /// Verbose output, repeat for more
+    #[arg(short = 'v', action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+fn main() {
+    let args = SimArgs::parse();
+    init_logger(LogConfig {
+        level: LogLevel::Debug,
+        use_colors: true,
+        use_timestamps: true,
+    });
+    set_log_level(args.verbose);
+
+    let bmc = match SimModel::load(&args.model).and_then(SimBmc::new) {
+        Ok(bmc) => Arc::new(Mutex::new(bmc)),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if args.port != 0 {
+        let addr = SocketAddr::new(args.bind, args.port);
+        let mut server = match SimLanServer::bind(addr, bmc.clone()) {
+            Ok(server) => server,
+            Err(e) => {
+                eprintln!("Unable to bind RMCP port {}: {}", addr, e);
+                std::process::exit(1);
+            }
+        };
+        println!("RMCP listening on {}", addr);
+        std::thread::spawn(move || {
+            if let Err(e) = server.run() {
+                eprintln!("RMCP server stopped: {}", e);
+                std::process::exit(1);
+            }
+        });
+    }
+
+    // 上次运行遗留的 socket 文件
+    let _ = std::fs::remove_file(&args.socket);
+    let listener = match UnixListener::bind(&args.socket) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Unable to bind {}: {}", args.socket.display(), e);
+            std::process::exit(1);
+        }
+    };
+    println!("dummy socket listening on {}", args.socket.display());
+    if let Err(e) = serve_unix(listener, bmc) {
+        eprintln!("dummy socket server stopped: {}", e);
+        std::process::exit(1);
+    }
+}
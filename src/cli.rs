@@ -14,12 +14,19 @@ use secrecy::SecretString;
 use std::path::PathBuf;
 
 //pub mod commands;
+use utipmitool::commands::channel::ChannelCommand;
 use utipmitool::commands::chassis::ChassisCommand;
+use utipmitool::commands::discover::{DiscoverArgs, PingArgs};
+use utipmitool::commands::event::EventArgs;
+use utipmitool::commands::fru::FruCommand;
 use utipmitool::commands::lan::LanCommand;
 use utipmitool::commands::mc::McCommand;
+use utipmitool::commands::raw::RawArgs;
 use utipmitool::commands::sdr::SdrCommand;
 use utipmitool::commands::sel::SelCommand;
 use utipmitool::commands::sensor::SensorCommand;
+use utipmitool::commands::session::SessionArgs;
+use utipmitool::commands::sol::SolCommand;
 use utipmitool::commands::user::UserCommand;
 
 //use crate::MainCommand;
@@ -35,18 +42,20 @@ pub enum InterfaceType {
     // Lipmi,
     // #[clap(name = "bmc")]
     // Bmc,
-    // #[clap(name = "lan")]
-    // Lan,
-    // #[clap(name = "lanplus")]
-    // LanPlus,
+    #[clap(name = "lan")]
+    Lan,
+    #[clap(name = "lanplus")]
+    LanPlus,
     // #[clap(name = "free")]
     // Free,
-    // #[clap(name = "serial-terminal")]
-    // SerialTerm,
-    // #[clap(name = "serial-basic")]
-    // SerialBm,
-    // #[clap(name = "dummy")]
-    // Dummy,
+    #[clap(name = "serial-terminal")]
+    SerialTerm,
+    #[clap(name = "serial-basic")]
+    SerialBm,
+    #[clap(name = "dummy")]
+    Dummy,
+    #[clap(name = "replay")]
+    Replay,
     // #[clap(name = "usb")]
     // Usb,
     // #[clap(name = "dbus")]
@@ -63,6 +72,19 @@ pub enum PrivilegeLevel {
     OEM,
 }
 
+impl PrivilegeLevel {
+    /// IPMI会话权限级别编码
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            PrivilegeLevel::Callback => 0x1,
+            PrivilegeLevel::User => 0x2,
+            PrivilegeLevel::Operator => 0x3,
+            PrivilegeLevel::Administrator => 0x4,
+            PrivilegeLevel::OEM => 0x5,
+        }
+    }
+}
+
 // 认证类型枚举
 #[derive(Debug, Clone, ValueEnum)]
 pub enum AuthType {
@@ -73,6 +95,19 @@ pub enum AuthType {
     Password,
 }
 
+impl AuthType {
+    /// IPMI v1.5会话认证类型编码
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            AuthType::None => 0x0,
+            AuthType::MD2 => 0x1,
+            AuthType::MD5 => 0x2,
+            AuthType::Password => 0x4,
+            AuthType::OEM => 0x5,
+        }
+    }
+}
+
 // 主命令结构
 #[derive(Parser, Debug)]
 #[command(
@@ -119,12 +154,26 @@ pub struct GlobalArgs {
     pub interface: InterfaceType,
     #[arg(short = 'd', default_value_t = 0)]
     pub devnum: u8, //open ioctl
+    /// Serial device for serial-basic/serial-terminal: <tty>[:<baud>][+rtscts|+xonxoff],
+    /// the simulator socket for dummy (default /tmp/.ipmi_dummy), or the file written by
+    /// --record for replay
     #[arg(short = 'D', long)]
     pub devfile: Option<PathBuf>, //串口
 
+    /// Record every request/response to FILE for later use with -I replay
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
     // 网络参数
+    /// Remote host; a comma list, IPv4 CIDR or @hostsfile runs the command on every host
     #[arg(short = 'H', long)]
     pub hostname: Option<String>,
+    /// Maximum number of hosts contacted at once when -H names several hosts
+    #[arg(long, value_name = "N", default_value_t = utipmitool::fleet::DEFAULT_PARALLEL)]
+    pub parallel: usize,
+    /// Group hosts with identical output (like dshbak -c) instead of prefixing each line
+    #[arg(long)]
+    pub group: bool,
     #[arg(short = 'p', long, default_value_t = 623)]
     pub port: u16,
 
@@ -218,6 +267,92 @@ pub struct GlobalArgs {
     pub sol_escape: Option<char>,
 }
 
+impl GlobalArgs {
+    /// 作为主机列表中的一台执行：目标换成 `host`，交互输入的密码/密钥已由父进程放入环境变量
+    pub fn use_fleet_host(&mut self, host: String) {
+        self.hostname = Some(host);
+        if self.password_prompt {
+            self.password_prompt = false;
+            self.password_env = true;
+        }
+        if self.key_prompt {
+            self.key_prompt = false;
+            self.kg_env = true;
+        }
+    }
+
+    /// 按 -P / -f / -a / -E 的顺序获取会话密码，未指定时为空密码
+    pub fn resolve_password(&self) -> Result<String, String> {
+        use secrecy::ExposeSecret;
+
+        if let Some(password) = &self.password {
+            return Ok(password.expose_secret().to_string());
+        }
+
+        if let Some(path) = &self.password_file {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                format!(
+                    "Unable to read password from file {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            let password = content.lines().next().unwrap_or("").to_string();
+            if password.len() > 20 {
+                return Err("Password is too long (> 20 bytes)".to_string());
+            }
+            return Ok(password);
+        }
+
+        if self.password_prompt {
+            return rpassword::prompt_password("Password: ")
+                .map_err(|e| format!("Unable to read password from console: {}", e));
+        }
+
+        if self.password_env {
+            return std::env::var("IPMI_PASSWORD")
+                .or_else(|_| std::env::var("IPMITOOL_PASSWORD"))
+                .map_err(|_| "Unable to read password from environment".to_string());
+        }
+
+        Ok(String::new())
+    }
+
+    /// 按 -k / -y / -K / -Y 获取 BMC 密钥 Kg，未指定时返回 None
+    pub fn resolve_kg(&self) -> Result<Option<Vec<u8>>, String> {
+        use secrecy::ExposeSecret;
+
+        let kg = if let Some(key) = &self.kg_key {
+            key.expose_secret().as_bytes().to_vec()
+        } else if let Some(hex) = &self.hex_key {
+            let hex = hex.trim_start_matches("0x").trim_start_matches("0X");
+            if hex.len() % 2 != 0 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err("Kg key is not a valid hexadecimal string".to_string());
+            }
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|e| format!("Invalid Kg key: {}", e))?
+        } else if self.kg_env {
+            std::env::var("IPMI_KGKEY")
+                .map_err(|_| "Unable to read Kg key from environment".to_string())?
+                .into_bytes()
+        } else if self.key_prompt {
+            rpassword::prompt_password("Key: ")
+                .map_err(|e| format!("Unable to read Kg key from console: {}", e))?
+                .into_bytes()
+        } else {
+            return Ok(None);
+        };
+
+        if kg.len() > 20 {
+            return Err("Kg key is too long (> 20 bytes)".to_string());
+        }
+        Ok(Some(kg))
+    }
+}
+
 fn parse_hex(s: &str) -> Result<u8, String> {
     u8::from_str_radix(s.trim_start_matches("0x"), 16)
         .map_err(|e| format!("无效的十六进制值: {}", e))
@@ -226,11 +361,7 @@ fn parse_hex(s: &str) -> Result<u8, String> {
 #[derive(Subcommand, Debug)]
 pub enum MainCommand {
     /// 原始IPMI命令
-    // Raw {
-    //     netfn: u8,
-    //     cmd: u8,
-    //     data: Vec<String>,
-    // },MainCommand::Chassis
+    Raw(RawArgs),
 
     /// 机箱控制
     Chassis {
@@ -250,12 +381,27 @@ pub enum MainCommand {
         subcmd: Option<SensorCommand>,
     },
 
+    /// FRU 信息管理
+    Fru {
+        #[command(subcommand)]
+        subcmd: FruCommand,
+    },
+
     /// SDR repository管理
     Sdr {
         #[command(subcommand)]
         subcmd: SdrCommand,
     },
 
+    /// 通道信息与用户通道访问权限
+    Channel {
+        #[command(subcommand)]
+        subcmd: ChannelCommand,
+    },
+
+    /// 查看与关闭 BMC 会话
+    Session(SessionArgs),
+
     /// User management  
     User {
         #[command(subcommand)]
@@ -283,6 +429,14 @@ pub enum MainCommand {
         #[command(subcommand)]
         subcmd: SelCommand,
     },
+    /// 通过 Platform Event Message 生成事件
+    Event(EventArgs),
+
+    /// RMCP/ASF presence ping，不建立会话
+    Ping(PingArgs),
+
+    /// 探测网段中的 BMC
+    Discover(DiscoverArgs),
 }
 
 // 启动设备
@@ -342,32 +496,6 @@ pub struct UserParams {
     privilege: Option<PrivilegeLevel>,
 }
 
-// SOL管理子命令
-#[derive(Subcommand, Debug)]
-pub enum SolCommand {
-    Info,
-    Activate {
-        #[arg(long)]
-        instance: Option<u8>,
-    },
-    Deactivate {
-        #[arg(long)]
-        instance: Option<u8>,
-    },
-    Set {
-        param: SolParam,
-        value: String,
-    },
-}
-
-#[derive(ValueEnum, Clone, Debug)]
-pub enum SolParam {
-    Enabled,
-    PrivilegeLevel,
-    BaudRate,
-    // 其他参数...
-}
-
 impl Cli {
     pub fn validate(&self) -> Result<(), String> {
         // 验证密码选项互斥
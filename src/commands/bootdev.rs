@@ -15,8 +15,8 @@ use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_CHASSIS};
 
 // 从bootparam模块导入相关类型和函数
 use super::bootparam::{
-    chassis_bootparam_clear_ack, chassis_bootparam_set_in_progress, ipmi_chassis_set_bootparam,
-    BootinfoAck, Progress,
+    chassis_bootparam_clear_ack, chassis_bootparam_set_in_progress, get_bootflag_options,
+    ipmi_chassis_set_bootparam, BootinfoAck, Progress,
 };
 
 pub fn ipmi_chassis_set_bootdev(
@@ -79,6 +79,25 @@ pub fn ipmi_chassis_set_bootdev(
     result
 }
 
+/// `chassis bootparam set bootflag <device> [options=...]`
+pub fn ipmi_chassis_set_bootflag(
+    intf: &mut dyn IpmiIntf,
+    device: &str,
+    options: Option<&str>,
+) -> CommandResult {
+    let (flags0, set_flag, clr_flag) = match options {
+        Some(opts) => get_bootflag_options(opts).map_err(IpmiError::InvalidData)?,
+        None => (0, 0, 0),
+    };
+
+    if set_flag != 0 || clr_flag != 0 {
+        ipmi_chassis_set_bootvalid(intf, set_flag, clr_flag)?;
+    }
+
+    let flags = [flags0, 0, 0, 0, 0];
+    ipmi_chassis_set_bootdev(intf, Some(device), Some(&flags))
+}
+
 pub fn ipmi_chassis_set_bootvalid(
     intf: &mut dyn IpmiIntf,
     set_flag: u8,
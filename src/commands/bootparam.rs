@@ -10,7 +10,8 @@ use crate::error::IpmiError;
 use crate::error::{completion_code_to_string, oem_id_to_string};
 use crate::helper::{buf2str, ipmi24toh};
 use crate::ipmi::constants::{
-    IPMI_CHASSIS_BOOTPARAM_INFO_ACK, IPMI_CHASSIS_BOOTPARAM_SET_IN_PROGRESS,
+    IPMI_CHASSIS_BOOTPARAM_INFO_ACK, IPMI_CHASSIS_BOOTPARAM_INIT_MBOX,
+    IPMI_CHASSIS_BOOTPARAM_SET_IN_PROGRESS,
 };
 use crate::ipmi::intf::IpmiIntf;
 use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_CHASSIS};
@@ -18,19 +19,8 @@ use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_CHASSIS};
 // 添加缺少的常量
 const IPMI_CC_PARAM_OUT_OF_RANGE: u8 = 0xc9;
 
-// Mbox结构体定义
-#[repr(C)]
-struct Mbox {
-    block: u8,
-    data: [u8; 16],
-    b0: MboxB0,
-}
-
-#[repr(C)]
-struct MboxB0 {
-    iana: [u8; 3],
-    data: [u8; 13],
-}
+// Boot Initiator Mailbox: block 0 以 3 字节 IANA 企业号开头
+const MBOX_IANA_LEN: usize = 3;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Progress {
@@ -134,6 +124,7 @@ pub fn ipmi_chassis_get_bootparam(
                 return Err(IpmiError::CompletionCode(rsp.ccode));
             }
 
+            let data_len = (rsp.data_len as usize).saturating_sub(2);
             if !skip_generic {
                 println!("Boot parameter version: {}", rsp.data[0]);
                 println!(
@@ -146,16 +137,13 @@ pub fn ipmi_chassis_get_bootparam(
                     }
                 );
                 if !skip_data {
-                    println!(
-                        "Boot parameter data: {}",
-                        buf2str(&rsp.data[2..], rsp.data.len() - 2)
-                    );
+                    println!("Boot parameter data: {}", buf2str(&rsp.data[2..], data_len));
                 }
             }
 
             match param_id {
                 0 => {
-                    println!("Set In Progress: ");
+                    print!(" Set In Progress : ");
                     match rsp.data[2] & 0x03 {
                         0 => println!("set complete"),
                         1 => println!("set in progress"),
@@ -164,7 +152,7 @@ pub fn ipmi_chassis_get_bootparam(
                     }
                 }
                 1 => {
-                    println!("Service Partition Selector: ");
+                    print!(" Service Partition Selector : ");
                     if rsp.data[2] == 0 {
                         println!("unspecified");
                     } else {
@@ -246,8 +234,19 @@ pub fn ipmi_chassis_get_bootparam(
                     } else {
                         println!("  - Options apply to only next boot");
                     }
-                    println!("  - Boot Device Selector: ");
-                    match rsp.data[3] & 0x0F {
+                    if rsp.data[2] & 0x20 != 0 {
+                        println!("  - BIOS EFI boot");
+                    } else {
+                        println!("  - BIOS PC Compatible (legacy) boot");
+                    }
+                    if rsp.data[3] & 0x80 != 0 {
+                        println!("  - CMOS Clear");
+                    }
+                    if rsp.data[3] & 0x40 != 0 {
+                        println!("  - Lock Keyboard");
+                    }
+                    print!("  - Boot Device Selector : ");
+                    match (rsp.data[3] >> 2) & 0x0F {
                         0x00 => println!("No override"),
                         0x01 => println!("Force PXE"),
                         0x02 => println!("Force Boot from default Hard-Drive"),
@@ -276,7 +275,7 @@ pub fn ipmi_chassis_get_bootparam(
                     println!("  Timestamp: {}", timestamp);
                 }
                 7 => {
-                    chassis_bootmailbox_parse(Some(&rsp.data[2..]), flags);
+                    chassis_bootmailbox_parse(&rsp.data[2..2 + data_len], flags);
                 }
                 _ => {
                     println!("Unsupported parameter {}", param_id);
@@ -417,6 +416,84 @@ pub fn get_bootparam_options(
     Ok(())
 }
 
+// Boot Flags 参数 data1 中可由 options= 设置的位 (不能加 no- 前缀)
+const BOOTFLAG_OPTIONS: [(&str, u8, &str); 2] = [
+    ("persistent", 0x40, "Boot flags apply to all future boots"),
+    (
+        "efiboot",
+        0x20,
+        "Request BIOS EFI boot instead of legacy boot",
+    ),
+];
+
+/// 解析 `bootparam set bootflag` 的 options=，返回 (boot flags data1, valid bit set, valid bit clear)
+pub fn get_bootflag_options(optstring: &str) -> Result<(u8, u8, u8), String> {
+    let list = optstring
+        .strip_prefix("options=")
+        .ok_or_else(|| format!("No options= keyword found \"{}\"", optstring))?;
+
+    let mut flags0 = 0u8;
+    let mut rest = Vec::new();
+    for token in list.split(',').map(str::trim) {
+        match BOOTFLAG_OPTIONS.iter().find(|(name, _, _)| *name == token) {
+            Some((_, bit, _)) => flags0 |= bit,
+            None => rest.push(token),
+        }
+    }
+
+    let (mut set_flag, mut clr_flag) = (0u8, 0u8);
+    if !rest.is_empty() {
+        let rest = format!("options={}", rest.join(","));
+        if let Err(mut e) = get_bootparam_options(&rest, &mut set_flag, &mut clr_flag) {
+            if rest
+                .split(',')
+                .any(|t| t.trim_start_matches("options=") == "help")
+            {
+                for (name, _, desc) in &BOOTFLAG_OPTIONS {
+                    e.push_str(&format!("  {:<10}: {}\n", name, desc));
+                }
+            }
+            return Err(e);
+        }
+    }
+    Ok((flags0, set_flag, clr_flag))
+}
+
+/// `chassis bootparam get`：参数 7 未指定 block 时读取整个 mailbox
+pub fn ipmi_chassis_bootparam_get(
+    intf: &mut dyn IpmiIntf,
+    param: u8,
+    block: Option<u8>,
+    text: bool,
+) -> CommandResult {
+    if param != IPMI_CHASSIS_BOOTPARAM_INIT_MBOX {
+        return ipmi_chassis_get_bootparam(intf, param, block, 0);
+    }
+
+    let mut flags = if text {
+        bp_flag(MBOX_PARSE_USE_TEXT)
+    } else {
+        0
+    };
+    if block.is_some() {
+        return ipmi_chassis_get_bootparam(intf, param, block, flags);
+    }
+
+    flags |= bp_flag(MBOX_PARSE_ALLBLOCKS)
+        | bp_flag(ChassisBootparamFlags::NoGenericInfo as u32)
+        | bp_flag(ChassisBootparamFlags::NoRangeError as u32);
+    for block in 0..=u8::MAX {
+        if let Err(e) = ipmi_chassis_get_bootparam(intf, param, Some(block), flags) {
+            // 读到第一个不存在的块即结束
+            if block == 0 {
+                return Err(e);
+            }
+            break;
+        }
+    }
+    Ok(())
+}
+
 pub fn chassis_bootparam_clear_ack(intf: &mut dyn IpmiIntf, flag: BootinfoAck) -> CommandResult {
     // 根据ipmitool实现，INFO_ACK需要发送2字节数据：[0x01, flag_value]
     // ipmitool源码：flags[0] = 0x01; flags[1] = 0x01;
@@ -425,42 +502,46 @@ pub fn chassis_bootparam_clear_ack(intf: &mut dyn IpmiIntf, flag: BootinfoAck) -
     ipmi_chassis_set_bootparam(intf, IPMI_CHASSIS_BOOTPARAM_INFO_ACK, &data)
 }
 
-fn chassis_bootmailbox_parse(buf: Option<&[u8]>, flags: u32) {
-    if buf.is_none() || buf.unwrap().is_empty() {
-        return;
-    }
-
-    let buf = buf.unwrap();
-    let mbox: &Mbox = unsafe { &*(buf.as_ptr() as *const Mbox) };
-    let mut blockdata = &mbox.data[..];
-    let mut datalen = buf.len() - std::mem::size_of::<u8>();
+/// 格式化一个 Boot Initiator Mailbox 块，buf 为 block selector 加块数据
+pub fn format_bootmailbox(buf: &[u8], flags: u32) -> Option<String> {
+    let (&block, mut blockdata) = buf.split_first()?;
 
-    let use_text = flags & (1 << MBOX_PARSE_USE_TEXT) != 0;
-    let all_blocks = flags & (1 << MBOX_PARSE_ALLBLOCKS) != 0;
+    let use_text = flags & bp_flag(MBOX_PARSE_USE_TEXT) != 0;
+    let all_blocks = flags & bp_flag(MBOX_PARSE_ALLBLOCKS) != 0;
 
+    let mut out = String::new();
     if !all_blocks {
-        println!(" Selector       : {}", mbox.block);
+        out.push_str(&format!(" Selector       : {}\n", block));
     }
 
-    if mbox.block == 0 {
-        let iana = ipmi24toh(&mbox.b0.iana);
-        println!(" IANA PEN       : {} [{}]", iana, oem_id_to_string(iana));
-        blockdata = &mbox.b0.data[..];
-        datalen -= std::mem::size_of::<[u8; 3]>();
+    if block == 0 {
+        let iana: &[u8; MBOX_IANA_LEN] = blockdata.get(..MBOX_IANA_LEN)?.try_into().ok()?;
+        let iana = ipmi24toh(iana);
+        out.push_str(&format!(
+            " IANA PEN       : {} [{}]\n",
+            iana,
+            oem_id_to_string(iana)
+        ));
+        blockdata = &blockdata[MBOX_IANA_LEN..];
     }
 
-    print!(" Block ");
     if all_blocks {
-        print!("{:3} Data : ", mbox.block);
+        out.push_str(&format!(" Block {:3} Data : ", block));
     } else {
-        print!("Data     : ");
+        out.push_str(" Block Data     : ");
     }
 
     if use_text {
-        let text = String::from_utf8_lossy(&blockdata[..datalen]);
-        println!("'{}'", text);
+        out.push_str(&format!("'{}'", String::from_utf8_lossy(blockdata)));
     } else {
-        println!("{}", buf2str(blockdata, datalen));
+        out.push_str(&buf2str(blockdata, blockdata.len()));
+    }
+    Some(out)
+}
+
+fn chassis_bootmailbox_parse(buf: &[u8], flags: u32) {
+    if let Some(text) = format_bootmailbox(buf, flags) {
+        println!("{}", text);
     }
 }
 
@@ -528,4 +609,37 @@ mod tests {
         );
         assert_eq!(expected_data[1], 0x01, "BiosPostAck should be 0x01");
     }
+
+    #[test]
+    fn test_get_bootflag_options() {
+        assert_eq!(
+            get_bootflag_options("options=persistent,efiboot"),
+            Ok((0x60, 0, 0))
+        );
+        assert_eq!(
+            get_bootflag_options("options=efiboot,no-PEF,timeout"),
+            Ok((0x20, 0x10, 0x08))
+        );
+        assert!(get_bootflag_options("options=bogus").is_err());
+        assert!(get_bootflag_options("options=help")
+            .unwrap_err()
+            .contains("persistent"));
+    }
+
+    #[test]
+    fn test_format_bootmailbox() {
+        let block0 = [0x00, 0x57, 0x01, 0x00, b'h', b'i'];
+        let text = format_bootmailbox(&block0, bp_flag(MBOX_PARSE_USE_TEXT)).unwrap();
+        assert!(text.starts_with(" Selector       : 0\n IANA PEN       : 343 "));
+        assert!(text.ends_with(" Block Data     : 'hi'"));
+
+        let block1 = [0x01, 0xde, 0xad];
+        assert_eq!(
+            format_bootmailbox(&block1, bp_flag(MBOX_PARSE_ALLBLOCKS)).unwrap(),
+            " Block   1 Data : de ad"
+        );
+        // block 0 不足 3 字节 IANA
+        assert_eq!(format_bootmailbox(&[0x00, 0x57], 0), None);
+        assert_eq!(format_bootmailbox(&[], 0), None);
+    }
 }
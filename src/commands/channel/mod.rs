@@ -0,0 +1,756 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! `channel`：通道信息、认证能力、用户通道访问权限与 RMCP+ 加密套件
+//!
+//! 参照 ipmitool ipmi_channel.c。
+
+use crate::commands::user::{
+    ipmi_get_user_access, ipmi_get_user_name, ipmi_uid, UserAccess, UserName, IPMI_SET_USER_ACCESS,
+    IPMI_UID_MAX,
+};
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::interface::lan::auth::get_auth_types_string;
+use crate::interface::lan::discover::ChannelAuthCap;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
+use crate::ipmi::strings::{
+    auth_alg_to_str, channel_medium_to_str, channel_protocol_to_str, crypt_alg_to_str,
+    integrity_alg_to_str, privlvl_to_str,
+};
+use clap::{Subcommand, ValueEnum};
+
+pub const IPMI_GET_CHANNEL_AUTH_CAP: u8 = 0x38;
+pub const IPMI_GET_CHANNEL_ACCESS: u8 = 0x41;
+pub const IPMI_GET_CHANNEL_INFO: u8 = 0x42;
+pub const IPMI_GET_CHANNEL_CIPHER_SUITES: u8 = 0x54;
+
+/// 当前通道 (接收请求的通道)
+const IPMI_CHANNEL_CURRENT: u8 = 0x0e;
+const IPMI_CHANNEL_MAX: u8 = 0x0f;
+
+// Get Channel Access 请求字节 2
+const CHANNEL_ACCESS_VOLATILE: u8 = 0x80;
+const CHANNEL_ACCESS_NON_VOLATILE: u8 = 0x40;
+
+// Get Channel Auth Cap 请求字节 1 的 bit 7：获取 IPMI v2.0 扩展数据
+const AUTH_CAP_V2_DATA: u8 = 0x80;
+// 认证状态 bit 5：KG 不是默认的全零值
+const AUTHSTATUS_KG_NONZERO: u8 = 0x20;
+
+// Set User Access 字节 1 的 bit 7：同时修改 bits 6:4
+const USER_ACCESS_CHANGE_BITS: u8 = 0x80;
+const USER_ACCESS_CALLBACK_ONLY: u8 = 0x40;
+const USER_ACCESS_LINK_AUTH: u8 = 0x20;
+const USER_ACCESS_IPMI_MSG: u8 = 0x10;
+
+// Get Channel Cipher Suites：按套件列出算法，每次最多返回 16 字节
+const CIPHER_LIST_ALGORITHMS: u8 = 0x80;
+const CIPHER_PAGE_SIZE: usize = 16;
+const CIPHER_MAX_PAGES: u8 = 0x40;
+const CIPHER_RECORD_STANDARD: u8 = 0xc0;
+const CIPHER_RECORD_OEM: u8 = 0xc1;
+const CIPHER_ALG_TAG_MASK: u8 = 0xc0;
+const CIPHER_ALG_AUTH: u8 = 0x00;
+const CIPHER_ALG_INTEGRITY: u8 = 0x40;
+const CIPHER_ALG_CRYPT: u8 = 0x80;
+const CIPHER_ALG_MASK: u8 = 0x3f;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ChannelCommand {
+    /// Show channel medium, protocol and access settings
+    Info {
+        /// Channel number (default: current channel)
+        #[arg(value_parser = parse_channel)]
+        channel: Option<u8>,
+    },
+    /// Show authentication capabilities for a privilege level
+    Authcap {
+        #[arg(value_parser = parse_channel)]
+        channel: u8,
+        /// Privilege level: 1-5 or callback|user|operator|administrator|oem
+        #[arg(value_parser = parse_privilege)]
+        privilege: u8,
+    },
+    /// Show per-channel user access
+    Getaccess {
+        #[arg(value_parser = parse_channel)]
+        channel: u8,
+        /// User ID (default: all users)
+        user_id: Option<u8>,
+    },
+    /// Change per-channel user access
+    Setaccess {
+        #[arg(value_parser = parse_channel)]
+        channel: u8,
+        user_id: u8,
+        /// callin=on|off ipmi=on|off link=on|off privilege=<level>
+        #[arg(required = true)]
+        options: Vec<String>,
+    },
+    /// List the RMCP+ cipher suites supported by a channel
+    Getciphers {
+        #[arg(value_enum)]
+        payload: CipherPayload,
+        #[arg(value_parser = parse_channel)]
+        channel: Option<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CipherPayload {
+    Ipmi = 0x00,
+    Sol = 0x01,
+}
+
+fn parse_channel(s: &str) -> Result<u8, String> {
+    let channel = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => s.parse::<u8>(),
+    }
+    .map_err(|_| format!("Invalid channel number: '{}'", s))?;
+    if channel > IPMI_CHANNEL_MAX {
+        return Err(format!(
+            "Invalid channel number: {}, must be 0-{}",
+            channel, IPMI_CHANNEL_MAX
+        ));
+    }
+    Ok(channel)
+}
+
+fn parse_privilege(s: &str) -> Result<u8, String> {
+    let privilege = match s.to_ascii_lowercase().as_str() {
+        "callback" => 1,
+        "user" => 2,
+        "operator" => 3,
+        "administrator" | "admin" => 4,
+        "oem" => 5,
+        "no_access" | "noaccess" => 0x0f,
+        other => other
+            .parse::<u8>()
+            .map_err(|_| format!("Invalid privilege level: '{}'", s))?,
+    };
+    match privilege {
+        1..=5 | 0x0f => Ok(privilege),
+        _ => Err(format!(
+            "Invalid privilege level: {}, must be 1-5 or 15 (no access)",
+            privilege
+        )),
+    }
+}
+
+/// 发送 App 命令，返回响应数据
+fn channel_request(
+    intf: &mut dyn IpmiIntf,
+    cmd: u8,
+    data: &mut [u8],
+    name: &str,
+) -> CommandResult<Vec<u8>> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = cmd;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf
+        .sendrecv(&req)
+        .ok_or_else(|| IpmiError::Interface(format!("{} command failed", name)))?;
+    if rsp.ccode != 0 {
+        return Err(IpmiError::Interface(format!(
+            "{} command failed: {}",
+            name,
+            IpmiError::CompletionCode(rsp.ccode)
+        )));
+    }
+    Ok(rsp.data[..rsp.data_len.max(0) as usize].to_vec())
+}
+
+fn enabled(on: bool) -> &'static str {
+    if on {
+        "enabled"
+    } else {
+        "disabled"
+    }
+}
+
+fn yes_no(on: bool) -> &'static str {
+    if on {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Get Channel Info 响应
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelInfo {
+    pub channel: u8,
+    pub medium: u8,
+    pub protocol: u8,
+    pub session_support: u8,
+    pub active_sessions: u8,
+    pub vendor_id: u32,
+}
+
+impl ChannelInfo {
+    pub fn from_response(data: &[u8]) -> CommandResult<Self> {
+        if data.len() < 7 {
+            return Err(IpmiError::InvalidData(format!(
+                "Invalid Get Channel Info response length: {}",
+                data.len()
+            )));
+        }
+        Ok(Self {
+            channel: data[0] & 0x0f,
+            medium: data[1] & 0x7f,
+            protocol: data[2] & 0x1f,
+            session_support: data[3] >> 6,
+            active_sessions: data[3] & 0x3f,
+            vendor_id: u32::from_le_bytes([data[4], data[5], data[6], 0]),
+        })
+    }
+
+    pub fn is_session_less(&self) -> bool {
+        self.session_support == 0
+    }
+
+    fn session_support_name(&self) -> &'static str {
+        match self.session_support {
+            0 => "session-less",
+            1 => "single-session",
+            2 => "multi-session",
+            _ => "session-based",
+        }
+    }
+
+    pub fn format(&self) -> String {
+        let mut out = format!("Channel 0x{:x} info:\n", self.channel);
+        out.push_str(&format!(
+            "  Channel Medium Type   : {}\n",
+            channel_medium_to_str(self.medium)
+        ));
+        out.push_str(&format!(
+            "  Channel Protocol Type : {}\n",
+            channel_protocol_to_str(self.protocol)
+        ));
+        out.push_str(&format!(
+            "  Session Support       : {}\n",
+            self.session_support_name()
+        ));
+        out.push_str(&format!(
+            "  Active Session Count  : {}\n",
+            self.active_sessions
+        ));
+        out.push_str(&format!("  Protocol Vendor ID    : {}\n", self.vendor_id));
+        out
+    }
+}
+
+/// Get Channel Access 响应字节 1 的格式化
+fn format_channel_access(title: &str, access: u8) -> String {
+    let mode = match access & 0x07 {
+        0 => "disabled",
+        1 => "pre-boot only",
+        2 => "always available",
+        3 => "shared",
+        _ => "unknown",
+    };
+    // 这三位为 1 表示禁用
+    format!(
+        "  {}\n    Alerting            : {}\n    Per-message Auth    : {}\n    User Level Auth     : {}\n    Access Mode         : {}\n",
+        title,
+        enabled(access & 0x20 == 0),
+        enabled(access & 0x10 == 0),
+        enabled(access & 0x08 == 0),
+        mode
+    )
+}
+
+fn ipmi_channel_info(intf: &mut dyn IpmiIntf, channel: u8) -> CommandResult {
+    let rsp = channel_request(
+        intf,
+        IPMI_GET_CHANNEL_INFO,
+        &mut [channel],
+        "Get Channel Info",
+    )?;
+    let info = ChannelInfo::from_response(&rsp)?;
+    print!("{}", info.format());
+    if info.is_session_less() {
+        return Ok(());
+    }
+
+    for (title, kind) in [
+        ("Volatile(active) Settings", CHANNEL_ACCESS_VOLATILE),
+        ("Non-Volatile Settings", CHANNEL_ACCESS_NON_VOLATILE),
+    ] {
+        let rsp = channel_request(
+            intf,
+            IPMI_GET_CHANNEL_ACCESS,
+            &mut [info.channel, kind],
+            "Get Channel Access",
+        )?;
+        let access = *rsp.first().ok_or_else(|| {
+            IpmiError::InvalidData("Get Channel Access returned no data".to_string())
+        })?;
+        print!("{}", format_channel_access(title, access));
+    }
+    Ok(())
+}
+
+pub fn format_auth_cap(cap: &ChannelAuthCap) -> String {
+    let mut out = format!("Channel number             : {}\n", cap.channel);
+    out.push_str(&format!(
+        "IPMI v1.5  auth types      : {}\n",
+        get_auth_types_string(cap.auth_types)
+    ));
+    out.push_str(&format!(
+        "KG status                  : {}\n",
+        if cap.auth_status & AUTHSTATUS_KG_NONZERO != 0 {
+            "non-zero"
+        } else {
+            "default (all zeroes)"
+        }
+    ));
+    out.push_str(&format!(
+        "Per message authentication : {}\n",
+        enabled(cap.per_msg_auth())
+    ));
+    out.push_str(&format!(
+        "User level authentication  : {}\n",
+        enabled(cap.user_level_auth())
+    ));
+    let logins = cap.logins();
+    out.push_str(&format!(
+        "Non-null user names exist  : {}\n",
+        yes_no(logins.contains(&"non-null"))
+    ));
+    out.push_str(&format!(
+        "Null user names exist      : {}\n",
+        yes_no(logins.contains(&"null"))
+    ));
+    out.push_str(&format!(
+        "Anonymous login enabled    : {}\n",
+        yes_no(logins.contains(&"anonymous"))
+    ));
+    if cap.v20_data {
+        out.push_str(&format!(
+            "Channel supports IPMI v1.5 : {}\n",
+            yes_no(cap.ext_caps & 0x01 != 0)
+        ));
+        out.push_str(&format!(
+            "Channel supports IPMI v2.0 : {}\n",
+            yes_no(cap.ext_caps & 0x02 != 0)
+        ));
+    }
+    if cap.oem_id != 0 {
+        out.push_str(&format!("IANA Number for OEM        : {}\n", cap.oem_id));
+        out.push_str(&format!(
+            "OEM Auxiliary Data         : 0x{:02x}\n",
+            cap.oem_aux
+        ));
+    }
+    out
+}
+
+/// 先请求 v2.0 扩展数据，只支持 v1.5 的 BMC 可能拒绝，此时不带该位重试
+fn ipmi_channel_authcap(intf: &mut dyn IpmiIntf, channel: u8, privilege: u8) -> CommandResult {
+    let rsp = match channel_request(
+        intf,
+        IPMI_GET_CHANNEL_AUTH_CAP,
+        &mut [AUTH_CAP_V2_DATA | channel, privilege],
+        "Get Channel Authentication Capabilities",
+    ) {
+        Ok(rsp) => rsp,
+        Err(_) => channel_request(
+            intf,
+            IPMI_GET_CHANNEL_AUTH_CAP,
+            &mut [channel, privilege],
+            "Get Channel Authentication Capabilities",
+        )?,
+    };
+    let cap = ChannelAuthCap::from_response(&rsp).map_err(|e| {
+        IpmiError::InvalidData(format!(
+            "Invalid Get Channel Authentication Capabilities response: {}",
+            e
+        ))
+    })?;
+    print!("{}", format_auth_cap(&cap));
+    Ok(())
+}
+
+fn get_user_access(intf: &mut dyn IpmiIntf, channel: u8, user_id: u8) -> CommandResult<UserAccess> {
+    let mut access = UserAccess {
+        channel,
+        user_id,
+        ..Default::default()
+    };
+    ipmi_get_user_access(intf, &mut access).map_err(|e| {
+        IpmiError::Interface(format!(
+            "Get User Access command failed (channel {}, user {}): {}",
+            channel, user_id, e
+        ))
+    })?;
+    Ok(access)
+}
+
+fn format_user_access(access: &UserAccess, name: &str) -> String {
+    let status = match access.enable_status {
+        0x40 => "enabled",
+        0x80 => "disabled",
+        _ => "unknown",
+    };
+    let mut out = format!("User ID              : {}\n", access.user_id);
+    out.push_str(&format!("User Name            : {}\n", name));
+    out.push_str(&format!(
+        "Fixed Name           : {}\n",
+        if access.user_id <= access.fixed_user_ids {
+            "Yes"
+        } else {
+            "No"
+        }
+    ));
+    out.push_str(&format!(
+        "Access Available     : {}\n",
+        if access.callin_callback != 0 {
+            "callback"
+        } else {
+            "call-in / callback"
+        }
+    ));
+    out.push_str(&format!(
+        "Link Authentication  : {}\n",
+        enabled(access.link_auth != 0)
+    ));
+    out.push_str(&format!(
+        "IPMI Messaging       : {}\n",
+        enabled(access.ipmi_messaging != 0)
+    ));
+    out.push_str(&format!(
+        "Privilege Level      : {}\n",
+        privlvl_to_str(access.privilege_limit)
+    ));
+    out.push_str(&format!("Enable Status        : {}\n", status));
+    out
+}
+
+fn ipmi_channel_getaccess(
+    intf: &mut dyn IpmiIntf,
+    channel: u8,
+    user_id: Option<u8>,
+) -> CommandResult {
+    let first = user_id.unwrap_or(1);
+    if !(1..=IPMI_UID_MAX).contains(&first) {
+        return Err(IpmiError::InvalidData(format!(
+            "Invalid user ID: {}, must be 1-{}",
+            first, IPMI_UID_MAX
+        )));
+    }
+
+    let mut access = get_user_access(intf, channel, first)?;
+    println!("Maximum User IDs     : {}", access.max_user_ids);
+    println!("Enabled User IDs     : {}", access.enabled_user_ids);
+
+    let last = user_id.unwrap_or(access.max_user_ids.min(IPMI_UID_MAX));
+    for uid in first..=last {
+        if uid != first {
+            access = get_user_access(intf, channel, uid)?;
+        }
+        // 未设置名称的用户 BMC 返回 0xCC，按空名称显示
+        let mut name = UserName {
+            user_id: uid,
+            ..Default::default()
+        };
+        let name = ipmi_get_user_name(intf, &mut name)
+            .map(|_| name.name_as_string())
+            .unwrap_or_default();
+        println!();
+        print!("{}", format_user_access(&access, &name));
+    }
+    Ok(())
+}
+
+/// 在当前访问权限上应用 `callin=`/`ipmi=`/`link=`/`privilege=` 选项
+fn apply_access_options(access: &mut UserAccess, options: &[String]) -> CommandResult {
+    for opt in options {
+        let (name, value) = opt.split_once('=').ok_or_else(|| {
+            IpmiError::InvalidData(format!(
+                "Invalid option '{}', expected callin=|ipmi=|link=|privilege=",
+                opt
+            ))
+        })?;
+        let on = || match value {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            _ => Err(IpmiError::InvalidData(format!(
+                "Invalid value '{}' for {}, expected on or off",
+                value, name
+            ))),
+        };
+        match name {
+            // callin=off 表示只允许回拨
+            "callin" => access.callin_callback = if on()? { 0 } else { USER_ACCESS_CALLBACK_ONLY },
+            "ipmi" => access.ipmi_messaging = if on()? { USER_ACCESS_IPMI_MSG } else { 0 },
+            "link" => access.link_auth = if on()? { USER_ACCESS_LINK_AUTH } else { 0 },
+            "privilege" => {
+                access.privilege_limit = parse_privilege(value).map_err(IpmiError::InvalidData)?
+            }
+            _ => {
+                return Err(IpmiError::InvalidData(format!(
+                    "Unknown option '{}', valid options are: callin, ipmi, link, privilege",
+                    name
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set_user_access_request(access: &UserAccess) -> [u8; 4] {
+    [
+        USER_ACCESS_CHANGE_BITS
+            | access.callin_callback
+            | access.link_auth
+            | access.ipmi_messaging
+            | (access.channel & 0x0f),
+        ipmi_uid(access.user_id),
+        access.privilege_limit & 0x0f,
+        access.session_limit & 0x0f,
+    ]
+}
+
+fn ipmi_channel_setaccess(
+    intf: &mut dyn IpmiIntf,
+    channel: u8,
+    user_id: u8,
+    options: &[String],
+) -> CommandResult {
+    if !(1..=IPMI_UID_MAX).contains(&user_id) {
+        return Err(IpmiError::InvalidData(format!(
+            "Invalid user ID: {}, must be 1-{}",
+            user_id, IPMI_UID_MAX
+        )));
+    }
+    // 未指定的选项保持原值
+    let mut access = get_user_access(intf, channel, user_id)?;
+    apply_access_options(&mut access, options)?;
+
+    channel_request(
+        intf,
+        IPMI_SET_USER_ACCESS,
+        &mut set_user_access_request(&access),
+        "Set User Access",
+    )?;
+    println!(
+        "Set User Access (channel {} id {}) successful.",
+        channel, user_id
+    );
+    Ok(())
+}
+
+/// 一个加密套件记录
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CipherSuite {
+    pub id: u8,
+    /// 标准套件为 None
+    pub iana: Option<u32>,
+    pub auth_alg: u8,
+    pub integrity_alg: u8,
+    pub crypt_alg: u8,
+}
+
+/// 解析按套件列出的算法记录 (IPMI v2.0 表 22-18)
+pub fn parse_cipher_suites(data: &[u8]) -> CommandResult<Vec<CipherSuite>> {
+    let mut suites = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let mut suite = CipherSuite::default();
+        match data[i] {
+            CIPHER_RECORD_STANDARD if i + 1 < data.len() => {
+                suite.id = data[i + 1];
+                i += 2;
+            }
+            CIPHER_RECORD_OEM if i + 4 < data.len() => {
+                suite.id = data[i + 1];
+                suite.iana = Some(u32::from_le_bytes([
+                    data[i + 2],
+                    data[i + 3],
+                    data[i + 4],
+                    0,
+                ]));
+                i += 5;
+            }
+            b => {
+                return Err(IpmiError::InvalidData(format!(
+                    "Invalid cipher suite record at offset {}: 0x{:02x}",
+                    i, b
+                )))
+            }
+        }
+        // 算法字节直到下一个记录起始字节
+        while i < data.len() && data[i] != CIPHER_RECORD_STANDARD && data[i] != CIPHER_RECORD_OEM {
+            let alg = data[i] & CIPHER_ALG_MASK;
+            match data[i] & CIPHER_ALG_TAG_MASK {
+                CIPHER_ALG_AUTH => suite.auth_alg = alg,
+                CIPHER_ALG_INTEGRITY => suite.integrity_alg = alg,
+                CIPHER_ALG_CRYPT => suite.crypt_alg = alg,
+                _ => {}
+            }
+            i += 1;
+        }
+        suites.push(suite);
+    }
+    Ok(suites)
+}
+
+pub fn format_cipher_suites(suites: &[CipherSuite]) -> String {
+    let mut out = format!(
+        "{:<4} {:<7} {:<15} {:<15} {:<15}\n",
+        "ID", "IANA", "Auth Alg", "Integrity Alg", "Confidentiality Alg"
+    );
+    for suite in suites {
+        let iana = suite
+            .iana
+            .map_or_else(|| "N/A".to_string(), |iana| iana.to_string());
+        out.push_str(&format!(
+            "{:<4} {:<7} {:<15} {:<15} {:<15}\n",
+            suite.id,
+            iana,
+            auth_alg_to_str(suite.auth_alg),
+            integrity_alg_to_str(suite.integrity_alg),
+            crypt_alg_to_str(suite.crypt_alg)
+        ));
+    }
+    out
+}
+
+fn ipmi_channel_getciphers(
+    intf: &mut dyn IpmiIntf,
+    payload: CipherPayload,
+    channel: u8,
+) -> CommandResult {
+    let mut records = Vec::new();
+    // 返回不足 16 字节的一页表示列表结束
+    for index in 0..CIPHER_MAX_PAGES {
+        let rsp = channel_request(
+            intf,
+            IPMI_GET_CHANNEL_CIPHER_SUITES,
+            &mut [channel, payload as u8, CIPHER_LIST_ALGORITHMS | index],
+            "Get Channel Cipher Suites",
+        )?;
+        let page = rsp.get(1..).unwrap_or_default();
+        records.extend_from_slice(page);
+        if page.len() < CIPHER_PAGE_SIZE {
+            break;
+        }
+    }
+    print!("{}", format_cipher_suites(&parse_cipher_suites(&records)?));
+    Ok(())
+}
+
+pub fn ipmi_channel_main(subcmd: ChannelCommand, mut intf: Box<dyn IpmiIntf>) -> CommandResult {
+    let intf = intf.as_mut();
+    match subcmd {
+        ChannelCommand::Info { channel } => {
+            ipmi_channel_info(intf, channel.unwrap_or(IPMI_CHANNEL_CURRENT))
+        }
+        ChannelCommand::Authcap { channel, privilege } => {
+            ipmi_channel_authcap(intf, channel, privilege)
+        }
+        ChannelCommand::Getaccess { channel, user_id } => {
+            ipmi_channel_getaccess(intf, channel, user_id)
+        }
+        ChannelCommand::Setaccess {
+            channel,
+            user_id,
+            options,
+        } => ipmi_channel_setaccess(intf, channel, user_id, &options),
+        ChannelCommand::Getciphers { payload, channel } => {
+            ipmi_channel_getciphers(intf, payload, channel.unwrap_or(IPMI_CHANNEL_CURRENT))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cipher_suites() {
+        let data = [
+            0xc0, 0x03, 0x01, 0x41, 0x81, // 套件 3: hmac_sha1/hmac_sha1_96/aes_cbc_128
+            0xc1, 0x80, 0xf2, 0x1b, 0x00, 0x03, 0x44, 0x81, // OEM 套件
+            0xc0, 0x00, 0x00, 0x40, 0x80,
+        ];
+        let suites = parse_cipher_suites(&data).unwrap();
+        assert_eq!(suites.len(), 3);
+        assert_eq!(
+            suites[0],
+            CipherSuite {
+                id: 3,
+                iana: None,
+                auth_alg: 1,
+                integrity_alg: 1,
+                crypt_alg: 1,
+            }
+        );
+        assert_eq!(suites[1].iana, Some(7154));
+        assert_eq!((suites[1].auth_alg, suites[1].integrity_alg), (3, 4));
+        assert!(parse_cipher_suites(&[0x01, 0x02]).is_err());
+        assert!(parse_cipher_suites(&[0xc0]).is_err());
+
+        let out = format_cipher_suites(&suites[..1]);
+        assert_eq!(
+            out.lines().nth(1).unwrap().trim_end(),
+            "3    N/A     hmac_sha1       hmac_sha1_96    aes_cbc_128"
+        );
+    }
+
+    #[test]
+    fn test_access_options() {
+        let opts = |s: &[&str]| s.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+        let mut access = UserAccess {
+            channel: 1,
+            user_id: 2,
+            ipmi_messaging: USER_ACCESS_IPMI_MSG,
+            privilege_limit: 4,
+            ..Default::default()
+        };
+        apply_access_options(
+            &mut access,
+            &opts(&["callin=off", "link=on", "ipmi=off", "privilege=operator"]),
+        )
+        .unwrap();
+        assert_eq!(set_user_access_request(&access), [0xe1, 0x02, 0x03, 0x00]);
+        assert!(apply_access_options(&mut access, &opts(&["ipmi=yes"])).is_err());
+        assert!(apply_access_options(&mut access, &opts(&["privilege=7"])).is_err());
+        assert!(apply_access_options(&mut access, &opts(&["session=1"])).is_err());
+
+        assert_eq!(parse_channel("0x0e"), Ok(0x0e));
+        assert_eq!(parse_channel("1"), Ok(1));
+        assert!(parse_channel("16").is_err());
+        assert_eq!(parse_privilege("admin"), Ok(4));
+        assert_eq!(parse_privilege("2"), Ok(2));
+    }
+
+    #[test]
+    fn test_channel_info_format() {
+        let info =
+            ChannelInfo::from_response(&[0x01, 0x04, 0x01, 0x82, 0xf2, 0x1b, 0x00, 0, 0]).unwrap();
+        assert_eq!(
+            info.format(),
+            "Channel 0x1 info:\n\
+             \x20 Channel Medium Type   : 802.3 LAN\n\
+             \x20 Channel Protocol Type : IPMB-1.0\n\
+             \x20 Session Support       : multi-session\n\
+             \x20 Active Session Count  : 2\n\
+             \x20 Protocol Vendor ID    : 7154\n"
+        );
+        assert!(format_channel_access("Non-Volatile Settings", 0x22)
+            .contains("Alerting            : disabled\n    Per-message Auth    : enabled"));
+        assert!(ChannelInfo::from_response(&[0x01]).is_err());
+    }
+}
@@ -14,10 +14,14 @@ use clap::ValueEnum;
 // use super::define_chassis::*;
 // use super::status::*;
 
+use crate::commands::bootdev::ipmi_chassis_set_bootflag;
 use crate::commands::bootparam::{
-    chassis_bootparam_clear_ack, chassis_bootparam_set_in_progress, ipmi_chassis_set_bootparam,
-    BootinfoAck, Progress,
+    chassis_bootparam_clear_ack, chassis_bootparam_set_in_progress, ipmi_chassis_bootparam_get,
+    ipmi_chassis_set_bootparam, BootinfoAck, Progress,
 };
+use crate::commands::poh::ipmi_chassis_poh;
+use crate::commands::policy::ipmi_chassis_power_policy;
+use crate::commands::selftest::ipmi_chassis_selftest;
 use crate::ipmi::constants::*;
 use crate::ipmi::intf::*;
 use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_CHASSIS};
@@ -52,6 +56,58 @@ pub enum ChassisCommand {
         clear_cmos: Option<bool>,
     },
     // ==== 修改点 END ==== //
+    /// Get the chassis power-on hours counter
+    Poh,
+    /// Get BMC self test results
+    Selftest,
+    /// Set or list the chassis power restore policy
+    Policy {
+        policy: PowerPolicy,
+    },
+    /// Get or set system boot options parameters
+    Bootparam {
+        #[command(subcommand)]
+        action: BootparamAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BootparamAction {
+    /// Print boot parameter <param>; parameter 7 (boot mailbox) prints all blocks unless <block> is given
+    Get {
+        param: u8,
+        block: Option<u8>,
+        /// Print boot mailbox data as text instead of hex
+        #[arg(long)]
+        text: bool,
+    },
+    /// Set a boot parameter
+    Set {
+        #[command(subcommand)]
+        param: BootparamSetParam,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BootparamSetParam {
+    /// Set boot flags: <none|force_pxe|force_disk|force_safe|force_diag|force_cdrom|force_bios|force_floppy> [options=help,persistent,efiboot,...]
+    #[command(name = "bootflag")]
+    BootFlag {
+        device: String,
+        options: Option<String>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum PowerPolicy {
+    /// List supported policies
+    List,
+    /// Always power on when AC power returns
+    AlwaysOn,
+    /// Restore previous power state when AC power returns
+    Previous,
+    /// Stay powered off when AC power returns
+    AlwaysOff,
 }
 
 // 电源操作
@@ -108,7 +164,7 @@ pub const MBOX_PARSE_USE_TEXT: u32 = PARAM_SPECIFIC;
 pub const MBOX_PARSE_ALLBLOCKS: u32 = PARAM_SPECIFIC + 1;
 const PARAM_SPECIFIC: u32 = 3;
 
-pub fn ipmi_chassis_main(cmd: ChassisCommand, intf: Box<dyn IpmiIntf>) -> Result<(), String> {
+pub fn ipmi_chassis_main(cmd: ChassisCommand, mut intf: Box<dyn IpmiIntf>) -> Result<(), String> {
     match cmd {
         ChassisCommand::Status => ipmi_chassis_status(intf),
         ChassisCommand::Power { action } => match action {
@@ -132,6 +188,26 @@ pub fn ipmi_chassis_main(cmd: ChassisCommand, intf: Box<dyn IpmiIntf>) -> Result
             // ==== 修改点 END ==== //
             ipmi_chassis_set_bootdev(intf, device.unwrap(), clear_cmos)
         }
+        ChassisCommand::Poh => ipmi_chassis_poh(intf.as_mut()),
+        ChassisCommand::Selftest => ipmi_chassis_selftest(intf.as_mut()),
+        ChassisCommand::Policy { policy } => {
+            let policy = match policy {
+                PowerPolicy::List => IPMI_CHASSIS_POLICY_NO_CHANGE,
+                PowerPolicy::AlwaysOn => IPMI_CHASSIS_POLICY_ALWAYS_ON,
+                PowerPolicy::Previous => IPMI_CHASSIS_POLICY_PREVIOUS,
+                PowerPolicy::AlwaysOff => IPMI_CHASSIS_POLICY_ALWAYS_OFF,
+            };
+            ipmi_chassis_power_policy(intf.as_mut(), policy)
+        }
+        ChassisCommand::Bootparam { action } => match action {
+            BootparamAction::Get { param, block, text } => {
+                ipmi_chassis_bootparam_get(intf.as_mut(), param, block, text)
+            }
+            BootparamAction::Set {
+                param: BootparamSetParam::BootFlag { device, options },
+            } => ipmi_chassis_set_bootflag(intf.as_mut(), &device, options.as_deref()),
+        }
+        .map_err(|e| e.to_string()),
     }
 }
 
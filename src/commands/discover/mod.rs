@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! `ping` 与 `discover`：不建立会话，用 RMCP/ASF presence ping 探测 BMC
+
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::fleet::parse_hosts;
+use crate::interface::lan::auth::get_auth_types_string;
+use crate::interface::lan::discover::{
+    get_channel_auth_cap, rmcp_ping, rmcp_sweep, ChannelAuthCap, PongReply,
+};
+use crate::interface::lan::rmcp::{ASF_INTERACT_DASH, ASF_INTERACT_SECURITY, RMCP_VERSION_1};
+use crate::log_debug;
+use clap::Args;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+/// 同时查询认证能力的主机数
+const AUTH_CAP_PARALLEL: usize = 32;
+
+/// ping 命令参数
+#[derive(Debug, Clone, Args)]
+pub struct PingArgs {
+    /// Host name or address of the BMC
+    pub host: String,
+}
+
+/// discover 命令参数
+#[derive(Debug, Clone, Args)]
+pub struct DiscoverArgs {
+    /// Addresses to probe: comma list, IPv4 CIDR or @hostsfile
+    pub targets: String,
+    /// Treat the targets as broadcast addresses and accept replies from any host
+    #[arg(short = 'b', long)]
+    pub broadcast: bool,
+    /// Also show the Get Channel Authentication Capabilities results
+    #[arg(short = 'a', long)]
+    pub auth: bool,
+}
+
+/// 来自全局参数的探测选项
+#[derive(Debug, Clone)]
+pub struct ProbeOptions {
+    pub port: u16,
+    pub timeout: Duration,
+    pub retries: u32,
+    pub ipv4_only: bool,
+    pub ipv6_only: bool,
+    pub csv: bool,
+}
+
+impl ProbeOptions {
+    fn resolve(&self, host: &str) -> CommandResult<SocketAddr> {
+        (host, self.port)
+            .to_socket_addrs()
+            .map_err(|e| IpmiError::Network(format!("Unable to resolve {}: {}", host, e)))?
+            .find(|addr| (!self.ipv4_only || addr.is_ipv4()) && (!self.ipv6_only || addr.is_ipv6()))
+            .ok_or_else(|| IpmiError::Network(format!("No address found for hostname: {}", host)))
+    }
+}
+
+fn rmcp_version(reply: &PongReply) -> &'static str {
+    if reply.pong.rmcp.version == RMCP_VERSION_1 {
+        "1.0"
+    } else {
+        "unknown"
+    }
+}
+
+fn interactions_to_string(sup_interact: u8) -> String {
+    let mut names = Vec::new();
+    if sup_interact & ASF_INTERACT_SECURITY != 0 {
+        names.push("RMCP security extensions");
+    }
+    if sup_interact & ASF_INTERACT_DASH != 0 {
+        names.push("DASH");
+    }
+    if names.is_empty() {
+        format!("0x{:02x}", sup_interact)
+    } else {
+        format!("0x{:02x} ({})", sup_interact, names.join(", "))
+    }
+}
+
+fn elapsed_ms(reply: &PongReply) -> String {
+    format!("{:.1}", reply.elapsed.as_secs_f64() * 1000.0)
+}
+
+/// 向单个 BMC 发送 presence ping 并打印 pong 内容
+pub fn ipmi_ping_main(args: &PingArgs, opts: &ProbeOptions) -> CommandResult {
+    let addr = opts.resolve(&args.host)?;
+    let reply = rmcp_ping(addr, opts.timeout, opts.retries)
+        .map_err(IpmiError::Network)?
+        .ok_or_else(|| IpmiError::Network(format!("No response from {}", addr)))?;
+
+    let pong = &reply.pong;
+    println!(
+        "Received IPMI/RMCP response from {} in {} ms",
+        reply.addr,
+        elapsed_ms(&reply)
+    );
+    println!(
+        "  IPMI                   : {}",
+        if pong.ipmi_supported() {
+            "supported"
+        } else {
+            "not supported"
+        }
+    );
+    println!("  ASF Version            : {}", pong.asf_version());
+    println!("  RMCP Version           : {}", rmcp_version(&reply));
+    println!("  RMCP Sequence          : {}", pong.rmcp.sequence);
+    println!("  IANA Enterprise        : {}", { pong.iana });
+    println!("  OEM Defined            : 0x{:08x}", { pong.oem });
+    println!("  Supported Entities     : 0x{:02x}", pong.sup_entities);
+    println!(
+        "  Supported Interactions : {}",
+        interactions_to_string(pong.sup_interact)
+    );
+    Ok(())
+}
+
+/// 探测一组地址，列出应答的 BMC
+pub fn ipmi_discover_main(args: &DiscoverArgs, opts: &ProbeOptions) -> CommandResult {
+    let hosts = parse_hosts(&args.targets).map_err(IpmiError::InvalidData)?;
+    let targets = hosts
+        .iter()
+        .map(|h| opts.resolve(h))
+        .collect::<CommandResult<Vec<_>>>()?;
+
+    let replies = rmcp_sweep(&targets, args.broadcast, opts.timeout, opts.retries)
+        .map_err(IpmiError::Network)?;
+    if replies.is_empty() {
+        eprintln!("No BMCs responded");
+        return Ok(());
+    }
+
+    // IPMI 版本只能从 Get Channel Authentication Capabilities 得知
+    let caps = query_auth_caps(&replies, opts);
+
+    let mut header = vec!["Address", "IPMI", "ASF", "IANA", "Time(ms)"];
+    if args.auth {
+        header.extend(["Auth Types", "Per-msg Auth", "User Auth", "Logins"]);
+    }
+    let rows: Vec<Vec<String>> = replies
+        .iter()
+        .zip(&caps)
+        .map(|(reply, cap)| discover_row(reply, cap.as_ref(), args.auth))
+        .collect();
+
+    if opts.csv {
+        println!("{}", header.join(","));
+        for row in rows {
+            println!("{}", row.join(","));
+        }
+    } else {
+        let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+        for row in &rows {
+            for (w, col) in widths.iter_mut().zip(row) {
+                *w = (*w).max(col.len());
+            }
+        }
+        let line = |cols: Vec<String>| {
+            let padded: Vec<String> = cols
+                .iter()
+                .zip(&widths)
+                .map(|(c, w)| format!("{:<w$}", c, w = w))
+                .collect();
+            println!("{}", padded.join("  ").trim_end());
+        };
+        line(header.iter().map(|h| h.to_string()).collect());
+        for row in rows {
+            line(row);
+        }
+    }
+    Ok(())
+}
+
+fn query_auth_caps(replies: &[PongReply], opts: &ProbeOptions) -> Vec<Option<ChannelAuthCap>> {
+    let mut caps = Vec::with_capacity(replies.len());
+    for chunk in replies.chunks(AUTH_CAP_PARALLEL) {
+        std::thread::scope(|s| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|r| {
+                    let addr = r.addr;
+                    let ipmi = r.pong.ipmi_supported();
+                    s.spawn(move || {
+                        if !ipmi {
+                            return None;
+                        }
+                        match get_channel_auth_cap(addr, opts.timeout, opts.retries) {
+                            Ok(cap) => Some(cap),
+                            Err(e) => {
+                                log_debug!(
+                                    "Get Channel Auth Capabilities from {} failed: {}",
+                                    addr,
+                                    e
+                                );
+                                None
+                            }
+                        }
+                    })
+                })
+                .collect();
+            caps.extend(handles.into_iter().map(|h| h.join().ok().flatten()));
+        });
+    }
+    caps
+}
+
+fn discover_row(reply: &PongReply, cap: Option<&ChannelAuthCap>, auth: bool) -> Vec<String> {
+    let pong = &reply.pong;
+    let ipmi = match cap {
+        Some(cap) => cap.ipmi_version().to_string(),
+        None if pong.ipmi_supported() => "yes".to_string(),
+        None => "no".to_string(),
+    };
+    let mut row = vec![
+        reply.addr.ip().to_string(),
+        ipmi,
+        pong.asf_version().to_string(),
+        { pong.iana }.to_string(),
+        elapsed_ms(reply),
+    ];
+    if auth {
+        match cap {
+            Some(cap) => {
+                let enabled = |on: bool| if on { "enabled" } else { "disabled" };
+                row.push(get_auth_types_string(cap.auth_types));
+                row.push(enabled(cap.per_msg_auth()).to_string());
+                row.push(enabled(cap.user_level_auth()).to_string());
+                row.push(cap.logins().join(" "));
+            }
+            None => row.extend(["-", "-", "-", "-"].map(String::from)),
+        }
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::lan::rmcp::{RmcpHeader, RmcpPong, ASF_ENTITY_IPMI};
+
+    #[test]
+    fn test_discover_row() {
+        let pong = RmcpPong::new(
+            &RmcpHeader::new_asf(0xff),
+            0,
+            343,
+            0,
+            ASF_ENTITY_IPMI | 0x01,
+        );
+        let reply = PongReply {
+            addr: "10.0.0.7:623".parse().unwrap(),
+            pong,
+            elapsed: Duration::from_micros(1300),
+        };
+        assert_eq!(
+            discover_row(&reply, None, false),
+            ["10.0.0.7", "yes", "1.0", "343", "1.3"]
+        );
+
+        let cap = ChannelAuthCap::from_response(&[0x01, 0x94, 0x04, 0x03, 0, 0, 0, 0]).unwrap();
+        assert_eq!(
+            discover_row(&reply, Some(&cap), true),
+            [
+                "10.0.0.7",
+                "2.0",
+                "1.0",
+                "343",
+                "1.3",
+                "MD5 PASSWORD",
+                "enabled",
+                "enabled",
+                "non-null"
+            ]
+        );
+        assert_eq!(interactions_to_string(0), "0x00");
+        assert_eq!(
+            interactions_to_string(0xa0),
+            "0xa0 (RMCP security extensions, DASH)"
+        );
+    }
+}
@@ -0,0 +1,537 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! `event`：通过 Platform Event Message 生成事件，用于验证告警链路
+//!
+//! 参照 ipmitool ipmi_event.c，支持三个预置的示例事件、按传感器名称与状态
+//! 构造事件，以及从文件逐行发送原始事件。
+
+use crate::commands::sdr::iter::SdrIterator;
+use crate::commands::sdr::sdr::{
+    ipmi_sdr_get_sensor_thresholds, SdrRecordCompactSensor, SdrRecordFullSensor,
+};
+use crate::commands::sdr::types::{SDR_RECORD_TYPE_COMPACT_SENSOR, SDR_RECORD_TYPE_FULL_SENSOR};
+use crate::commands::sel::add::{parse_event_fields, SEL_EVENT_FIELDS};
+use crate::commands::sel::define::{
+    IpmiEventSensorType, GENERIC_EVENT_TYPES, SENSOR_SPECIFIC_EVENT_TYPES,
+};
+use crate::commands::sel::sel::ipmi_get_sensor_type;
+use crate::commands::sensor::thresh::sensor_name;
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP, IPMI_NETFN_SE};
+use clap::{Args, ValueEnum};
+use std::path::Path;
+
+/// Platform Event Message (S/E netfn)
+pub const IPMI_PLATFORM_EVENT: u8 = 0x02;
+const IPMI_GET_CHANNEL_INFO: u8 = 0x42;
+const IPMI_CHANNEL_CURRENT: u8 = 0x0e;
+const IPMI_CHANNEL_MEDIUM_SYSTEM: u8 = 0x0c;
+
+const EVM_REV: u8 = 0x04;
+/// 系统接口上需要附带的 Generator ID (系统软件 ID 0x20)
+const EVENT_GENERATOR_SOFTWARE: u8 = 0x41;
+const EVENT_DIR_DEASSERT: u8 = 0x80;
+const EVENT_TYPE_THRESHOLD: u8 = 0x01;
+const EVENT_TYPE_SENSOR_SPECIFIC: u8 = 0x6f;
+/// Event Data 1 [7:6]=01、[5:4]=01：Data 2/3 为触发读数与触发阈值
+const EVENT_DATA_THRESHOLD_PRESENT: u8 = 0x50;
+/// Event Data 1 [7:6]=11：Data 2 为传感器相关的扩展码
+const EVENT_DATA_SENSOR_SPECIFIC_EXT: u8 = 0xc0;
+
+/// 阈值名称与其 "越过" 方向的事件偏移，下限取 going low，上限取 going high
+const THRESHOLD_STATES: [(&str, u8); 6] = [
+    ("lnc", 0x00),
+    ("lcr", 0x02),
+    ("lnr", 0x04),
+    ("unc", 0x07),
+    ("ucr", 0x09),
+    ("unr", 0x0b),
+];
+
+/// event 命令参数
+#[derive(Debug, Clone, Args)]
+pub struct EventArgs {
+    /// Sample event number (1-3), a sensor ID, or "file"
+    pub target: String,
+    /// Event state of the sensor (e.g. ucr, "Correctable ECC"), or the event file for "file"
+    pub state: Option<String>,
+    /// Event direction for sensor events
+    #[arg(value_enum, default_value = "assert")]
+    pub direction: EventDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EventDirection {
+    Assert,
+    Deassert,
+}
+
+/// Platform Event Message 的事件内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlatformEvent {
+    pub evm_rev: u8,
+    pub sensor_type: u8,
+    pub sensor_num: u8,
+    /// bit7 为方向 (1 = deassert)，bit6:0 为 Event/Reading Type
+    pub event_type: u8,
+    pub data: [u8; 3],
+}
+
+impl PlatformEvent {
+    pub fn from_bytes(b: &[u8; SEL_EVENT_FIELDS]) -> Self {
+        Self {
+            evm_rev: b[0],
+            sensor_type: b[1],
+            sensor_num: b[2],
+            event_type: b[3],
+            data: [b[4], b[5], b[6]],
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; SEL_EVENT_FIELDS] {
+        [
+            self.evm_rev,
+            self.sensor_type,
+            self.sensor_num,
+            self.event_type,
+            self.data[0],
+            self.data[1],
+            self.data[2],
+        ]
+    }
+
+    pub fn asserted(&self) -> bool {
+        self.event_type & EVENT_DIR_DEASSERT == 0
+    }
+}
+
+/// ipmitool 预置的示例事件
+fn sample_event(num: u8) -> Option<(&'static str, PlatformEvent)> {
+    let (desc, sensor_type, sensor_num, event_type, offset) = match num {
+        1 => (
+            "Temperature - Upper Critical - Going High",
+            0x01,
+            0x30,
+            EVENT_TYPE_THRESHOLD,
+            0x09,
+        ),
+        2 => (
+            "Voltage Threshold - Lower Critical - Going Low",
+            0x02,
+            0x60,
+            EVENT_TYPE_THRESHOLD,
+            0x02,
+        ),
+        3 => (
+            "Memory - Correctable ECC",
+            0x0c,
+            0x53,
+            EVENT_TYPE_SENSOR_SPECIFIC,
+            0x00,
+        ),
+        _ => return None,
+    };
+    Some((
+        desc,
+        PlatformEvent {
+            evm_rev: EVM_REV,
+            sensor_type,
+            sensor_num,
+            event_type,
+            data: [offset, 0xff, 0xff],
+        },
+    ))
+}
+
+/// 在 SDR 中找到的传感器
+#[derive(Debug, Clone)]
+struct EventSensor {
+    name: String,
+    owner_id: u8,
+    lun: u8,
+    channel: u8,
+    sensor_num: u8,
+    sensor_type: u8,
+    event_type: u8,
+}
+
+/// 传感器可用的事件状态表
+fn event_states(sensor_type: u8, event_type: u8) -> Vec<&'static IpmiEventSensorType> {
+    if event_type == EVENT_TYPE_SENSOR_SPECIFIC {
+        SENSOR_SPECIFIC_EVENT_TYPES
+            .iter()
+            .filter(|e| e.code == sensor_type)
+            .collect()
+    } else {
+        GENERIC_EVENT_TYPES
+            .iter()
+            .filter(|e| e.code == event_type)
+            .collect()
+    }
+}
+
+/// 把状态名称换算为事件偏移，返回 (Event Data 1, 对应的表项)
+fn find_state<'a>(
+    states: &[&'a IpmiEventSensorType],
+    event_type: u8,
+    state: &str,
+) -> Option<(u8, Option<&'a IpmiEventSensorType>)> {
+    if event_type == EVENT_TYPE_THRESHOLD {
+        if let Some(&(_, offset)) = THRESHOLD_STATES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(state))
+        {
+            return Some((offset, None));
+        }
+    }
+    states
+        .iter()
+        .find(|e| e.desc.trim().eq_ignore_ascii_case(state.trim()))
+        .map(|e| (e.offset, Some(*e)))
+}
+
+/// 根据传感器与状态构造事件，阈值事件附带触发读数与阈值 (`thresholds` 为 Get Sensor Thresholds 应答)
+fn sensor_event(
+    sensor: &EventSensor,
+    state: &str,
+    direction: EventDirection,
+    thresholds: Option<&[u8]>,
+) -> Result<PlatformEvent, String> {
+    let states = event_states(sensor.sensor_type, sensor.event_type);
+    let Some((offset, entry)) = find_state(&states, sensor.event_type, state) else {
+        let mut names: Vec<String> = Vec::new();
+        if sensor.event_type == EVENT_TYPE_THRESHOLD {
+            names.extend(THRESHOLD_STATES.iter().map(|(n, _)| n.to_string()));
+        }
+        names.extend(states.iter().map(|e| e.desc.trim().to_string()));
+        return Err(if names.is_empty() {
+            format!(
+                "Sensor {} has no known event states (event type 0x{:02x})",
+                sensor.name, sensor.event_type
+            )
+        } else {
+            format!(
+                "Invalid event state \"{}\" for sensor {}, valid states are:\n  {}",
+                state,
+                sensor.name,
+                names.join("\n  ")
+            )
+        });
+    };
+
+    let mut data = [offset, 0xff, 0xff];
+    if sensor.event_type == EVENT_TYPE_THRESHOLD {
+        // 阈值索引 0..5 对应 lnc, lcr, lnr, unc, ucr, unr
+        let idx = (offset >> 1) as usize;
+        if let Some(t) = thresholds.filter(|t| t.len() > idx + 1 && t[0] & (1 << idx) != 0) {
+            data = [
+                offset | EVENT_DATA_THRESHOLD_PRESENT,
+                t[idx + 1],
+                t[idx + 1],
+            ];
+        }
+    } else if let Some(e) = entry.filter(|e| e.data != 0xff) {
+        data = [offset | EVENT_DATA_SENSOR_SPECIFIC_EXT, e.data, 0xff];
+    }
+
+    let dir = match direction {
+        EventDirection::Assert => 0,
+        EventDirection::Deassert => EVENT_DIR_DEASSERT,
+    };
+    Ok(PlatformEvent {
+        evm_rev: EVM_REV,
+        sensor_type: sensor.sensor_type,
+        sensor_num: sensor.sensor_num,
+        event_type: dir | (sensor.event_type & 0x7f),
+        data,
+    })
+}
+
+/// 按名称在 SDR 中查找 Full/Compact 传感器
+fn find_event_sensor(intf: &mut dyn IpmiIntf, id: &str) -> CommandResult<EventSensor> {
+    let mut iter = SdrIterator::new(intf, false)
+        .ok_or_else(|| IpmiError::Interface("Unable to open SDR for reading".to_string()))?;
+    while let Some(header) = iter.next() {
+        let Some(rec) = (match header.record_type {
+            SDR_RECORD_TYPE_FULL_SENSOR | SDR_RECORD_TYPE_COMPACT_SENSOR => {
+                iter.ipmi_sdr_get_record(&header)
+            }
+            _ => None,
+        }) else {
+            continue;
+        };
+        let (cmn, name) = if header.record_type == SDR_RECORD_TYPE_FULL_SENSOR {
+            match SdrRecordFullSensor::from_le_bytes(&rec) {
+                Ok(full) => {
+                    let name = sensor_name(full.id_code, &full.id_string);
+                    (full.cmn, name)
+                }
+                Err(_) => continue,
+            }
+        } else {
+            match SdrRecordCompactSensor::from_le_bytes(&rec) {
+                Ok(compact) => {
+                    let name = sensor_name(compact.id_code, &compact.id_string);
+                    (compact.cmn, name)
+                }
+                Err(_) => continue,
+            }
+        };
+        if name.eq_ignore_ascii_case(id) {
+            return Ok(EventSensor {
+                name,
+                owner_id: cmn.keys.owner_id,
+                lun: cmn.keys.lun(),
+                channel: cmn.keys.channel(),
+                sensor_num: cmn.keys.sensor_num,
+                sensor_type: cmn.sensor.sensor_type,
+                event_type: cmn.event_type,
+            });
+        }
+    }
+    Err(IpmiError::InvalidData(format!(
+        "Sensor data record \"{}\" not found!",
+        id
+    )))
+}
+
+/// 当前通道是否为系统接口，系统接口上的请求需要附带 Generator ID
+fn on_system_interface(intf: &mut dyn IpmiIntf) -> bool {
+    let mut data = [IPMI_CHANNEL_CURRENT];
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_GET_CHANNEL_INFO;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode == 0 && rsp.data_len >= 2 => {
+            rsp.data[1] & 0x7f == IPMI_CHANNEL_MEDIUM_SYSTEM
+        }
+        _ => false,
+    }
+}
+
+fn send_platform_event(
+    intf: &mut dyn IpmiIntf,
+    event: &PlatformEvent,
+    system_intf: bool,
+) -> CommandResult {
+    let mut data = Vec::with_capacity(SEL_EVENT_FIELDS + 1);
+    if system_intf {
+        data.push(EVENT_GENERATOR_SOFTWARE);
+    }
+    data.extend_from_slice(&event.to_bytes());
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_SE);
+    req.msg.cmd = IPMI_PLATFORM_EVENT;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf
+        .sendrecv(&req)
+        .ok_or_else(|| IpmiError::Interface("Platform Event Message command failed".to_string()))?;
+    if rsp.ccode != 0 {
+        return Err(IpmiError::Interface(format!(
+            "Platform Event Message command failed: {}",
+            IpmiError::CompletionCode(rsp.ccode)
+        )));
+    }
+    Ok(())
+}
+
+fn direction_str(event: &PlatformEvent) -> &'static str {
+    if event.asserted() {
+        "Asserted"
+    } else {
+        "Deasserted"
+    }
+}
+
+fn ipmi_event_from_sensor(
+    intf: &mut dyn IpmiIntf,
+    id: &str,
+    state: &str,
+    direction: EventDirection,
+) -> CommandResult {
+    print!("Finding sensor {}... ", id);
+    let sensor = match find_event_sensor(intf, id) {
+        Ok(sensor) => {
+            println!("ok");
+            sensor
+        }
+        Err(e) => {
+            println!("not found!");
+            return Err(e);
+        }
+    };
+
+    let thresholds = if sensor.event_type == EVENT_TYPE_THRESHOLD {
+        ipmi_sdr_get_sensor_thresholds(
+            intf,
+            sensor.sensor_num,
+            sensor.owner_id,
+            sensor.lun,
+            sensor.channel,
+        )
+        .filter(|rsp| rsp.ccode == 0 && rsp.data_len >= 7)
+        .map(|rsp| rsp.data[..7].to_vec())
+    } else {
+        None
+    };
+
+    let event = sensor_event(&sensor, state, direction, thresholds.as_deref())
+        .map_err(IpmiError::InvalidData)?;
+    let system_intf = on_system_interface(intf);
+    send_platform_event(intf, &event, system_intf)?;
+    println!(
+        "Sent event: {} #0x{:02x} | {} | {}",
+        ipmi_get_sensor_type(intf, event.sensor_type),
+        event.sensor_num,
+        state,
+        direction_str(&event)
+    );
+    Ok(())
+}
+
+fn ipmi_event_from_file(intf: &mut dyn IpmiIntf, path: &Path) -> CommandResult {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        IpmiError::InvalidData(format!("Unable to read file {}: {}", path.display(), e))
+    })?;
+
+    let system_intf = on_system_interface(intf);
+    let mut failed = 0;
+    for (lineno, line) in content.lines().enumerate() {
+        let event = match parse_event_fields(line) {
+            Ok(Some(fields)) => PlatformEvent::from_bytes(&fields),
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("{}:{}: {}", path.display(), lineno + 1, e);
+                failed += 1;
+                continue;
+            }
+        };
+        match send_platform_event(intf, &event, system_intf) {
+            Ok(()) => println!(
+                "Sent event: {} #0x{:02x} | type 0x{:02x} data {:02x} {:02x} {:02x} | {}",
+                ipmi_get_sensor_type(intf, event.sensor_type),
+                event.sensor_num,
+                event.event_type & 0x7f,
+                event.data[0],
+                event.data[1],
+                event.data[2],
+                direction_str(&event)
+            ),
+            Err(e) => {
+                eprintln!("{}:{}: {}", path.display(), lineno + 1, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(IpmiError::Interface(format!(
+            "Failed to send {} events",
+            failed
+        )));
+    }
+    Ok(())
+}
+
+fn usage_error() -> IpmiError {
+    IpmiError::InvalidData(
+        "usage: event <1|2|3> | event <sensor-id> <state> [assert|deassert] | event file <file>"
+            .to_string(),
+    )
+}
+
+pub fn ipmi_event_main(args: EventArgs, mut intf: Box<dyn IpmiIntf>) -> CommandResult {
+    let intf = intf.as_mut();
+    if args.target == "file" {
+        let file = args.state.ok_or_else(usage_error)?;
+        return ipmi_event_from_file(intf, Path::new(&file));
+    }
+
+    match args.state {
+        Some(state) => ipmi_event_from_sensor(intf, &args.target, &state, args.direction),
+        None => {
+            let (desc, event) = args
+                .target
+                .parse::<u8>()
+                .ok()
+                .and_then(sample_event)
+                .ok_or_else(usage_error)?;
+            println!("Sending SAMPLE event: {}", desc);
+            let system_intf = on_system_interface(intf);
+            send_platform_event(intf, &event, system_intf)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sensor(sensor_type: u8, event_type: u8) -> EventSensor {
+        EventSensor {
+            name: "Test".to_string(),
+            owner_id: 0x20,
+            lun: 0,
+            channel: 0,
+            sensor_num: 0x30,
+            sensor_type,
+            event_type,
+        }
+    }
+
+    #[test]
+    fn test_sample_events() {
+        let (_, ev) = sample_event(1).unwrap();
+        assert_eq!(ev.to_bytes(), [0x04, 0x01, 0x30, 0x01, 0x09, 0xff, 0xff]);
+        let (_, ev) = sample_event(3).unwrap();
+        assert_eq!(ev.to_bytes(), [0x04, 0x0c, 0x53, 0x6f, 0x00, 0xff, 0xff]);
+        assert!(sample_event(4).is_none());
+    }
+
+    #[test]
+    fn test_threshold_event() {
+        let temp = sensor(0x01, EVENT_TYPE_THRESHOLD);
+        // 读取到 ucr = 0x50 (掩码 bit4)
+        let thresholds = [0x10, 0, 0, 0, 0, 0x50, 0];
+        let ev = sensor_event(&temp, "UCR", EventDirection::Assert, Some(&thresholds)).unwrap();
+        assert_eq!(ev.to_bytes(), [0x04, 0x01, 0x30, 0x01, 0x59, 0x50, 0x50]);
+
+        // 阈值不可读时只给出偏移
+        let ev = sensor_event(&temp, "lcr", EventDirection::Deassert, Some(&thresholds)).unwrap();
+        assert_eq!(ev.event_type, 0x81);
+        assert_eq!(ev.data, [0x02, 0xff, 0xff]);
+        assert!(!ev.asserted());
+
+        // 也接受事件表中的描述
+        let ev = sensor_event(
+            &temp,
+            "Upper Non-critical going low",
+            EventDirection::Assert,
+            None,
+        )
+        .unwrap();
+        assert_eq!(ev.data[0], 0x06);
+    }
+
+    #[test]
+    fn test_discrete_event() {
+        let mem = sensor(0x0c, EVENT_TYPE_SENSOR_SPECIFIC);
+        let ev = sensor_event(&mem, "correctable ecc", EventDirection::Assert, None).unwrap();
+        assert_eq!(ev.event_type, EVENT_TYPE_SENSOR_SPECIFIC);
+        assert_eq!(ev.data, [0x00, 0xff, 0xff]);
+
+        let err = sensor_event(&mem, "ucr", EventDirection::Assert, None).unwrap_err();
+        assert!(err.contains("Uncorrectable ECC"), "{}", err);
+    }
+}
@@ -0,0 +1,642 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! FRU 信息区解析与编辑 (Platform Management FRU Information Storage Definition v1.0)
+
+use crate::ipmi::time::ipmi_timestamp_string;
+use std::fmt::Write;
+
+pub const FRU_HEADER_LEN: usize = 8;
+pub const FRU_COMMON_HEADER_VERSION: u8 = 0x01;
+/// 类型/长度字节 0xC1 表示字段列表结束
+pub const FRU_END_OF_FIELDS: u8 = 0xC1;
+
+// 板卡制造日期以 1996-01-01 00:00 UTC 起的分钟数表示
+const FRU_BOARD_DATE_EPOCH: u32 = 820_454_400;
+
+// 语言代码 0 与 25 均表示英语，其余语言的 8-bit 字段为 UTF-16LE
+const FRU_LANG_ENGLISH: u8 = 25;
+
+const BCD_PLUS: &[u8; 16] = b"0123456789 -.:,_";
+
+const CHASSIS_TYPE_DESC: &[&str] = &[
+    "Unspecified",
+    "Other",
+    "Unknown",
+    "Desktop",
+    "Low Profile Desktop",
+    "Pizza Box",
+    "Mini Tower",
+    "Tower",
+    "Portable",
+    "LapTop",
+    "Notebook",
+    "Hand Held",
+    "Docking Station",
+    "All in One",
+    "Sub Notebook",
+    "Space-saving",
+    "Lunch Box",
+    "Main Server Chassis",
+    "Expansion Chassis",
+    "SubChassis",
+    "Bus Expansion Chassis",
+    "Peripheral Chassis",
+    "RAID Chassis",
+    "Rack Mount Chassis",
+    "Sealed-case PC",
+    "Multi-system Chassis",
+    "CompactPCI",
+    "AdvancedTCA",
+    "Blade",
+    "Blade Enclosure",
+    "Tablet",
+    "Convertible",
+    "Detachable",
+    "IoT Gateway",
+    "Embedded PC",
+    "Mini PC",
+    "Stick PC",
+];
+
+/// 零校验和：所有字节（含校验字节）之和为 0
+pub fn fru_checksum(data: &[u8]) -> u8 {
+    data.iter()
+        .fold(0u8, |acc, &b| acc.wrapping_add(b))
+        .wrapping_neg()
+}
+
+/// FRU 公共头，偏移量已换算为字节
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FruHeader {
+    pub version: u8,
+    pub internal: usize,
+    pub chassis: usize,
+    pub board: usize,
+    pub product: usize,
+    pub multi: usize,
+}
+
+impl FruHeader {
+    pub fn parse(image: &[u8]) -> Result<Self, String> {
+        if image.len() < FRU_HEADER_LEN {
+            return Err("FRU data too short for common header".to_string());
+        }
+        let h = &image[..FRU_HEADER_LEN];
+        if h[0] & 0x0f != FRU_COMMON_HEADER_VERSION {
+            return Err(format!("Unknown FRU header version 0x{:02x}", h[0]));
+        }
+        if fru_checksum(h) != 0 {
+            return Err("Invalid FRU common header checksum".to_string());
+        }
+        Ok(FruHeader {
+            version: h[0],
+            internal: h[1] as usize * 8,
+            chassis: h[2] as usize * 8,
+            board: h[3] as usize * 8,
+            product: h[4] as usize * 8,
+            multi: h[5] as usize * 8,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; FRU_HEADER_LEN] {
+        let mut h = [
+            self.version,
+            (self.internal / 8) as u8,
+            (self.chassis / 8) as u8,
+            (self.board / 8) as u8,
+            (self.product / 8) as u8,
+            (self.multi / 8) as u8,
+            0,
+            0,
+        ];
+        h[7] = fru_checksum(&h[..7]);
+        h
+    }
+
+    /// 按公共头中的顺序列出各区域偏移（0 表示不存在）
+    fn offsets_mut(&mut self) -> [&mut usize; 5] {
+        [
+            &mut self.internal,
+            &mut self.chassis,
+            &mut self.board,
+            &mut self.product,
+            &mut self.multi,
+        ]
+    }
+}
+
+/// 信息区类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FruAreaKind {
+    Chassis,
+    Board,
+    Product,
+}
+
+impl FruAreaKind {
+    /// 区域头部之后、第一个类型/长度字段之前的固定字节数
+    fn fixed_len(self) -> usize {
+        match self {
+            FruAreaKind::Chassis => 1, // chassis type
+            FruAreaKind::Board => 4,   // language + mfg date
+            FruAreaKind::Product => 1, // language
+        }
+    }
+
+    /// 规范定义的必选字段数，之后为自定义字段
+    fn mandatory_fields(self) -> usize {
+        match self {
+            FruAreaKind::Chassis => 2,
+            FruAreaKind::Board => 5,
+            FruAreaKind::Product => 7,
+        }
+    }
+
+    fn offset_in(self, header: &FruHeader) -> usize {
+        match self {
+            FruAreaKind::Chassis => header.chassis,
+            FruAreaKind::Board => header.board,
+            FruAreaKind::Product => header.product,
+        }
+    }
+}
+
+/// 一个类型/长度编码的字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FruField {
+    /// 类型/长度字节
+    pub type_length: u8,
+    pub data: Vec<u8>,
+}
+
+impl FruField {
+    /// 以 8-bit ASCII 编码字符串
+    ///
+    /// 长度为 1 的 8-bit ASCII 字段类型/长度字节为 0xC1，与字段结束标记冲突，
+    /// 因此单字符值补一个 NUL 凑成 2 字节，解码时会去掉。
+    pub fn from_ascii(value: &str) -> Result<Self, String> {
+        if value.len() > 0x3f {
+            return Err(format!(
+                "String \"{}\" is too long ({} > 63 bytes)",
+                value,
+                value.len()
+            ));
+        }
+        if !value.is_ascii() {
+            return Err(format!("String \"{}\" is not ASCII", value));
+        }
+        let mut data = value.as_bytes().to_vec();
+        if data.len() == 1 {
+            data.push(0);
+        }
+        Ok(FruField {
+            type_length: 0xc0 | data.len() as u8,
+            data,
+        })
+    }
+
+    pub fn decode(&self, lang: u8) -> String {
+        decode_type_length(self.type_length, &self.data, lang)
+    }
+}
+
+/// 连续的小写十六进制字符串
+pub(crate) fn hex_string(data: &[u8]) -> String {
+    data.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{:02x}", b);
+        out
+    })
+}
+
+/// 按类型/长度字节的类型码解码字段数据
+pub fn decode_type_length(type_length: u8, data: &[u8], lang: u8) -> String {
+    match type_length >> 6 {
+        // binary / unspecified
+        0 => hex_string(data),
+        // BCD plus，每字节两个字符
+        1 => data
+            .iter()
+            .flat_map(|&b| [BCD_PLUS[(b >> 4) as usize], BCD_PLUS[(b & 0x0f) as usize]])
+            .map(|c| c as char)
+            .collect(),
+        // 6-bit ASCII，3 字节打包 4 个字符
+        2 => {
+            let mut out = String::new();
+            let bits = data.len() * 8;
+            for i in 0..bits / 6 {
+                let bit = i * 6;
+                let lo = data[bit / 8] as u16;
+                let hi = data.get(bit / 8 + 1).copied().unwrap_or(0) as u16;
+                let v = (((hi << 8) | lo) >> (bit % 8)) & 0x3f;
+                out.push((v as u8 + 0x20) as char);
+            }
+            out.trim_end().to_string()
+        }
+        // 8-bit ASCII + Latin-1，非英语时为 16-bit Unicode
+        _ => {
+            if lang != 0 && lang != FRU_LANG_ENGLISH && data.len() % 2 == 0 {
+                let units: Vec<u16> = data
+                    .chunks(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+                    .trim_end_matches('\0')
+                    .to_string()
+            } else {
+                data.iter()
+                    .map(|&b| b as char)
+                    .collect::<String>()
+                    .trim_end_matches('\0')
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// 解析后的 chassis/board/product 信息区
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FruInfoArea {
+    pub kind: FruAreaKind,
+    pub version: u8,
+    /// 区域在 FRU 镜像中的字节偏移
+    pub offset: usize,
+    /// 区域长度（字节，8 的倍数）
+    pub len: usize,
+    /// chassis type / language + mfg date / language
+    pub fixed: Vec<u8>,
+    pub fields: Vec<FruField>,
+}
+
+impl FruInfoArea {
+    pub fn parse(image: &[u8], offset: usize, kind: FruAreaKind) -> Result<Self, String> {
+        let name = kind.name();
+        if offset + 2 > image.len() {
+            return Err(format!("{} area offset {} out of range", name, offset));
+        }
+        let len = image[offset + 1] as usize * 8;
+        if len == 0 || offset + len > image.len() {
+            return Err(format!("Invalid {} area length {}", name, len));
+        }
+        let area = &image[offset..offset + len];
+        if fru_checksum(area) != 0 {
+            log::warn!("Invalid {} area checksum", name);
+        }
+
+        let fixed_end = 2 + kind.fixed_len();
+        if fixed_end > len {
+            return Err(format!("{} area too short", name));
+        }
+        let fixed = area[2..fixed_end].to_vec();
+
+        let mut fields = Vec::new();
+        let mut pos = fixed_end;
+        // 最后一个字节为校验和
+        while pos < len - 1 && area[pos] != FRU_END_OF_FIELDS {
+            let type_length = area[pos];
+            let field_len = (type_length & 0x3f) as usize;
+            if pos + 1 + field_len > len - 1 {
+                return Err(format!(
+                    "{} area field at offset {} overruns area",
+                    name, pos
+                ));
+            }
+            fields.push(FruField {
+                type_length,
+                data: area[pos + 1..pos + 1 + field_len].to_vec(),
+            });
+            pos += 1 + field_len;
+        }
+
+        Ok(FruInfoArea {
+            kind,
+            version: area[0],
+            offset,
+            len,
+            fixed,
+            fields,
+        })
+    }
+
+    /// 重新编码区域：补齐到 8 字节并计算校验和
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = vec![self.version, 0];
+        out.extend_from_slice(&self.fixed);
+        for field in &self.fields {
+            out.push(field.type_length);
+            out.extend_from_slice(&field.data);
+        }
+        out.push(FRU_END_OF_FIELDS);
+        // 预留校验和字节后补齐到 8 的倍数
+        while (out.len() + 1) % 8 != 0 {
+            out.push(0);
+        }
+        out.push(0);
+        out[1] = (out.len() / 8) as u8;
+        let last = out.len() - 1;
+        out[last] = fru_checksum(&out[..last]);
+        out
+    }
+
+    fn lang(&self) -> u8 {
+        match self.kind {
+            FruAreaKind::Chassis => 0,
+            _ => self.fixed[0],
+        }
+    }
+
+    fn field_str(&self, index: usize) -> String {
+        self.fields
+            .get(index)
+            .map(|f| f.decode(self.lang()))
+            .unwrap_or_default()
+    }
+
+    /// 按 ipmitool 的格式输出，空字段不显示
+    pub fn format(&self, verbose: bool) -> String {
+        let mut out = String::new();
+        let mut line = |label: &str, value: String| {
+            if !value.is_empty() {
+                out.push_str(&format!(" {:<22}: {}\n", label, value));
+            }
+        };
+
+        let labels: &[&str] = match self.kind {
+            FruAreaKind::Chassis => {
+                let ty = self.fixed[0] as usize;
+                line(
+                    "Chassis Type",
+                    CHASSIS_TYPE_DESC
+                        .get(ty)
+                        .copied()
+                        .unwrap_or(CHASSIS_TYPE_DESC[2])
+                        .to_string(),
+                );
+                &["Chassis Part Number", "Chassis Serial"]
+            }
+            FruAreaKind::Board => {
+                let minutes = u32::from_le_bytes([self.fixed[1], self.fixed[2], self.fixed[3], 0]);
+                line(
+                    "Board Mfg Date",
+                    if minutes == 0 {
+                        "Unspecified".to_string()
+                    } else {
+                        ipmi_timestamp_string(FRU_BOARD_DATE_EPOCH + minutes * 60)
+                    },
+                );
+                &[
+                    "Board Mfg",
+                    "Board Product",
+                    "Board Serial",
+                    "Board Part Number",
+                    "Board FRU ID",
+                ]
+            }
+            FruAreaKind::Product => &[
+                "Product Manufacturer",
+                "Product Name",
+                "Product Part Number",
+                "Product Version",
+                "Product Serial",
+                "Product Asset Tag",
+                "Product FRU ID",
+            ],
+        };
+
+        for (i, label) in labels.iter().enumerate() {
+            // FRU File ID 只在 -v 时显示
+            if label.ends_with("FRU ID") && !verbose {
+                continue;
+            }
+            line(label, self.field_str(i));
+        }
+
+        let extra = match self.kind {
+            FruAreaKind::Chassis => "Chassis Extra",
+            FruAreaKind::Board => "Board Extra",
+            FruAreaKind::Product => "Product Extra",
+        };
+        for i in self.kind.mandatory_fields()..self.fields.len() {
+            line(extra, self.field_str(i));
+        }
+        out
+    }
+}
+
+impl FruAreaKind {
+    fn name(self) -> &'static str {
+        match self {
+            FruAreaKind::Chassis => "Chassis",
+            FruAreaKind::Board => "Board",
+            FruAreaKind::Product => "Product",
+        }
+    }
+}
+
+/// 计算区域占用长度；internal use 区域没有长度字段，延伸到下一个区域
+fn area_len(image: &[u8], header: &FruHeader, offset: usize) -> usize {
+    let next = [
+        header.internal,
+        header.chassis,
+        header.board,
+        header.product,
+        header.multi,
+    ]
+    .into_iter()
+    .filter(|&o| o > offset)
+    .min();
+
+    if offset == header.multi {
+        return crate::commands::fru::multirec::multirec_area_len(&image[offset..]);
+    }
+    if offset == header.internal {
+        return next.unwrap_or(image.len()) - offset;
+    }
+    image.get(offset + 1).map_or(0, |&l| l as usize * 8)
+}
+
+/// 修改信息区中的一个字段并返回新的 FRU 镜像
+///
+/// 区域长度变化时后续区域整体移动并更新公共头偏移，镜像总长度保持不变。
+pub fn fru_set_field(
+    image: &[u8],
+    kind: FruAreaKind,
+    index: usize,
+    value: &str,
+) -> Result<Vec<u8>, String> {
+    let mut header = FruHeader::parse(image)?;
+    let offset = kind.offset_in(&header);
+    if offset == 0 {
+        return Err(format!("{} area not present in FRU", kind.name()));
+    }
+
+    let mut area = FruInfoArea::parse(image, offset, kind)?;
+    if index >= area.fields.len() {
+        return Err(format!(
+            "{} area field index {} out of range (0-{})",
+            kind.name(),
+            index,
+            area.fields.len().saturating_sub(1)
+        ));
+    }
+    area.fields[index] = FruField::from_ascii(value)?;
+    let encoded = area.encode();
+
+    let old_len = area.len;
+    let new_len = encoded.len();
+    let mut out = Vec::with_capacity(image.len());
+    out.extend_from_slice(&image[..offset]);
+    out.extend_from_slice(&encoded);
+    out.extend_from_slice(&image[offset + old_len..]);
+
+    if new_len != old_len {
+        // 计算原镜像中所有区域的结束位置，确认空间足够
+        let used_end = [
+            header.internal,
+            header.chassis,
+            header.board,
+            header.product,
+            header.multi,
+        ]
+        .into_iter()
+        .filter(|&o| o != 0)
+        .map(|o| o + area_len(image, &header, o))
+        .max()
+        .unwrap_or(0);
+
+        if new_len > old_len {
+            let grow = new_len - old_len;
+            if used_end + grow > image.len() {
+                return Err(format!(
+                    "Not enough space in FRU for new {} area ({} bytes needed)",
+                    kind.name(),
+                    used_end + grow - image.len()
+                ));
+            }
+            out.truncate(image.len());
+        } else {
+            out.resize(image.len(), 0);
+        }
+
+        for o in header.offsets_mut() {
+            if *o > offset {
+                *o = *o + new_len - old_len;
+            }
+        }
+        out[..FRU_HEADER_LEN].copy_from_slice(&header.to_bytes());
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info_area(kind: FruAreaKind, fixed: &[u8], fields: &[&str]) -> Vec<u8> {
+        FruInfoArea {
+            kind,
+            version: 1,
+            offset: 0,
+            len: 0,
+            fixed: fixed.to_vec(),
+            fields: fields
+                .iter()
+                .map(|s| FruField::from_ascii(s).unwrap())
+                .collect(),
+        }
+        .encode()
+    }
+
+    fn build_image() -> Vec<u8> {
+        let board = info_area(
+            FruAreaKind::Board,
+            &[0, 0x10, 0x00, 0x00],
+            &["ACME", "Mainboard", "SN123", "PN-1", ""],
+        );
+        let product = info_area(
+            FruAreaKind::Product,
+            &[0],
+            &["ACME", "Server", "M1", "1.0", "PS1", "TAG", ""],
+        );
+        let header = FruHeader {
+            version: 1,
+            board: 8,
+            product: 8 + board.len(),
+            ..Default::default()
+        };
+        let mut image = header.to_bytes().to_vec();
+        image.extend_from_slice(&board);
+        image.extend_from_slice(&product);
+        image.resize(256, 0);
+        image
+    }
+
+    #[test]
+    fn test_decode_type_length() {
+        assert_eq!(decode_type_length(0xc3, b"abc", 0), "abc");
+        assert_eq!(decode_type_length(0x02, &[0xde, 0xad], 0), "dead");
+        assert_eq!(decode_type_length(0x42, &[0x12, 0xab], 0), "12 -");
+        // "IPMI" 的 6-bit ASCII 编码
+        assert_eq!(decode_type_length(0x83, &[0x29, 0xdc, 0xa6], 0), "IPMI");
+        // 非英语语言的 16-bit Unicode
+        assert_eq!(decode_type_length(0xc4, &[0x41, 0x00, 0x42, 0x00], 1), "AB");
+    }
+
+    #[test]
+    fn test_parse_and_format_areas() {
+        let image = build_image();
+        let header = FruHeader::parse(&image).unwrap();
+        let board = FruInfoArea::parse(&image, header.board, FruAreaKind::Board).unwrap();
+        assert_eq!(board.fields.len(), 5);
+        let text = board.format(false);
+        assert!(text.contains(" Board Mfg Date        : Mon Jan  1 00:16:00 1996\n"));
+        assert!(text.contains(" Board Product         : Mainboard\n"));
+        assert!(!text.contains("FRU ID"));
+
+        let product = FruInfoArea::parse(&image, header.product, FruAreaKind::Product).unwrap();
+        assert!(product
+            .format(false)
+            .contains(" Product Asset Tag     : TAG\n"));
+    }
+
+    #[test]
+    fn test_set_field_moves_following_areas() {
+        let image = build_image();
+        let header = FruHeader::parse(&image).unwrap();
+
+        // 同长度修改保持布局不变
+        let same = fru_set_field(&image, FruAreaKind::Board, 2, "SN999").unwrap();
+        assert_eq!(FruHeader::parse(&same).unwrap(), header);
+
+        let long = "A much longer board serial number";
+        let edited = fru_set_field(&image, FruAreaKind::Board, 2, long).unwrap();
+        assert_eq!(edited.len(), image.len());
+        let new_header = FruHeader::parse(&edited).unwrap();
+        assert!(new_header.product > header.product);
+
+        let board = FruInfoArea::parse(&edited, new_header.board, FruAreaKind::Board).unwrap();
+        assert_eq!(board.field_str(2), long);
+        assert_eq!(
+            fru_checksum(&edited[board.offset..board.offset + board.len]),
+            0
+        );
+        let product =
+            FruInfoArea::parse(&edited, new_header.product, FruAreaKind::Product).unwrap();
+        assert_eq!(product.field_str(1), "Server");
+
+        // 单字符值不能编码成结束标记 0xC1，其后的字段要保留
+        let short = fru_set_field(&image, FruAreaKind::Board, 1, "X").unwrap();
+        let board = FruInfoArea::parse(&short, header.board, FruAreaKind::Board).unwrap();
+        assert_eq!(board.fields.len(), 5);
+        assert_eq!(board.field_str(1), "X");
+        assert_eq!(board.field_str(2), "SN123");
+        assert_eq!(board.field_str(3), "PN-1");
+
+        assert!(fru_set_field(&image, FruAreaKind::Chassis, 0, "x").is_err());
+        assert!(fru_set_field(&image, FruAreaKind::Board, 9, "x").is_err());
+    }
+}
@@ -0,0 +1,444 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+pub mod area;
+pub mod multirec;
+
+use crate::commands::sdr::iter::SdrIterator;
+use crate::commands::sdr::sdr::SDR_RECORD_TYPE_FRU_DEVICE_LOCATOR;
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::*;
+use crate::log_debug;
+use area::{fru_set_field, FruAreaKind, FruHeader, FruInfoArea};
+use clap::{Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+
+// FRU 命令 (NetFn Storage)
+pub const GET_FRU_INFO: u8 = 0x10;
+pub const GET_FRU_DATA: u8 = 0x11;
+pub const SET_FRU_DATA: u8 = 0x12;
+
+const FRU_BUILTIN_ID: u8 = 0;
+// Read FRU Data 的 count 字段只有一个字节
+const FRU_MAX_READ_COUNT: usize = 0xff;
+// Write FRU Data 请求头: FRU ID + 2 字节偏移
+const FRU_WRITE_HEADER_LEN: usize = 3;
+
+// FRU 子命令
+#[derive(Debug, Clone, Subcommand)]
+pub enum FruCommand {
+    /// Print FRU inventory of all devices, or of a single FRU ID
+    Print { fru_id: Option<u8> },
+    /// Read a raw FRU image into a file
+    Read { fru_id: u8, file: PathBuf },
+    /// Write a raw FRU image from a file
+    Write { fru_id: u8, file: PathBuf },
+    /// Change one field of a chassis/board/product info area
+    Edit {
+        fru_id: u8,
+        mode: FruEditMode,
+        /// Info area: c (chassis), b (board) or p (product)
+        section: FruSection,
+        /// Field index within the area
+        index: usize,
+        /// New value, stored as 8-bit ASCII
+        value: String,
+    },
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum FruEditMode {
+    Field,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum FruSection {
+    #[value(name = "c")]
+    Chassis,
+    #[value(name = "b")]
+    Board,
+    #[value(name = "p")]
+    Product,
+}
+
+impl From<FruSection> for FruAreaKind {
+    fn from(section: FruSection) -> Self {
+        match section {
+            FruSection::Chassis => FruAreaKind::Chassis,
+            FruSection::Board => FruAreaKind::Board,
+            FruSection::Product => FruAreaKind::Product,
+        }
+    }
+}
+
+/// Get FRU Inventory Area Info 的结果
+#[derive(Debug, Clone, Copy)]
+pub struct FruInfo {
+    pub size: usize,
+    /// 设备按字 (16-bit) 访问
+    pub word_access: bool,
+}
+
+fn fru_request(intf: &mut dyn IpmiIntf, cmd: u8, data: &mut [u8]) -> CommandResult<IpmiRs> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+    req.msg.cmd = cmd;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    intf.sendrecv(&req)
+        .ok_or_else(|| IpmiError::Interface("No response from FRU device".to_string()))
+}
+
+pub fn ipmi_fru_get_info(intf: &mut dyn IpmiIntf, fru_id: u8) -> CommandResult<FruInfo> {
+    let rsp = fru_request(intf, GET_FRU_INFO, &mut [fru_id])?;
+    if rsp.ccode != 0 {
+        return Err(IpmiError::CompletionCode(rsp.ccode));
+    }
+    if rsp.data_len < 3 {
+        return Err(IpmiError::InvalidData(
+            "Short Get FRU Inventory Area Info response".to_string(),
+        ));
+    }
+    Ok(FruInfo {
+        size: u16::from_le_bytes([rsp.data[0], rsp.data[1]]) as usize,
+        word_access: rsp.data[2] & 0x01 != 0,
+    })
+}
+
+/// 数据过长类的完成码，缩小分块后重试
+fn fru_cc_too_big(ccode: u8) -> bool {
+    matches!(
+        ccode,
+        IPMI_CC_REQ_DATA_INV_LENGTH
+            | IPMI_CC_REQ_DATA_FIELD_EXCEED
+            | IPMI_CC_CANT_RET_NUM_REQ_BYTES
+    )
+}
+
+fn shrink_chunk(chunk: usize, unit: usize) -> Option<usize> {
+    let next = if chunk > 8 { chunk - 8 } else { chunk - unit };
+    (next >= unit).then_some(next)
+}
+
+/// 分块读取 FRU 数据，块大小受最大响应长度限制
+pub fn ipmi_fru_read(
+    intf: &mut dyn IpmiIntf,
+    fru_id: u8,
+    info: &FruInfo,
+    offset: usize,
+    len: usize,
+) -> CommandResult<Vec<u8>> {
+    let unit = if info.word_access { 2 } else { 1 };
+    // 响应第一个字节为实际读取的长度
+    let max_rsp = intf.context().get_max_response_data_size() as usize;
+    let mut chunk = max_rsp.saturating_sub(1).min(FRU_MAX_READ_COUNT);
+    chunk -= chunk % unit;
+
+    let end = (offset + len).min(info.size);
+    let mut data = Vec::with_capacity(end.saturating_sub(offset));
+    let mut pos = offset;
+    while pos < end {
+        let count = chunk.min(end - pos).max(unit);
+        let off = (pos / unit) as u16;
+        let mut rq = [fru_id, off as u8, (off >> 8) as u8, (count / unit) as u8];
+        let rsp = fru_request(intf, GET_FRU_DATA, &mut rq)?;
+        if fru_cc_too_big(rsp.ccode) {
+            chunk = shrink_chunk(chunk, unit).ok_or(IpmiError::CompletionCode(rsp.ccode))?;
+            log_debug!("FRU read chunk reduced to {} bytes", chunk);
+            continue;
+        }
+        if rsp.ccode != 0 {
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        if rsp.data_len < 1 {
+            return Err(IpmiError::InvalidData(
+                "Short Read FRU Data response".to_string(),
+            ));
+        }
+        let got = (rsp.data[0] as usize * unit).min(rsp.data_len as usize - 1);
+        if got == 0 {
+            return Err(IpmiError::InvalidData(format!(
+                "FRU device returned no data at offset {}",
+                pos
+            )));
+        }
+        let got = got.min(end - pos);
+        data.extend_from_slice(&rsp.data[1..1 + got]);
+        pos += got;
+    }
+    Ok(data)
+}
+
+/// 分块写入 FRU 数据，块大小受最大请求长度限制
+pub fn ipmi_fru_write(
+    intf: &mut dyn IpmiIntf,
+    fru_id: u8,
+    info: &FruInfo,
+    offset: usize,
+    data: &[u8],
+) -> CommandResult {
+    let unit = if info.word_access { 2 } else { 1 };
+    if offset + data.len() > info.size {
+        return Err(IpmiError::InvalidData(format!(
+            "Data ({} bytes at offset {}) exceeds FRU size {}",
+            data.len(),
+            offset,
+            info.size
+        )));
+    }
+    if offset % unit != 0 || data.len() % unit != 0 {
+        return Err(IpmiError::InvalidData(
+            "FRU device requires word-aligned writes".to_string(),
+        ));
+    }
+
+    let max_rq = intf.context().get_max_request_data_size() as usize;
+    let mut chunk = max_rq.saturating_sub(FRU_WRITE_HEADER_LEN);
+    chunk -= chunk % unit;
+
+    let mut pos = 0;
+    while pos < data.len() {
+        let count = chunk.min(data.len() - pos).max(unit);
+        let off = ((offset + pos) / unit) as u16;
+        let mut rq = vec![fru_id, off as u8, (off >> 8) as u8];
+        rq.extend_from_slice(&data[pos..pos + count]);
+        let rsp = fru_request(intf, SET_FRU_DATA, &mut rq)?;
+        if fru_cc_too_big(rsp.ccode) {
+            chunk = shrink_chunk(chunk, unit).ok_or(IpmiError::CompletionCode(rsp.ccode))?;
+            log_debug!("FRU write chunk reduced to {} bytes", chunk);
+            continue;
+        }
+        if rsp.ccode != 0 {
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        let written = if rsp.data_len >= 1 {
+            rsp.data[0] as usize * unit
+        } else {
+            count
+        };
+        if written == 0 {
+            return Err(IpmiError::InvalidData(format!(
+                "FRU device accepted no data at offset {}",
+                offset + pos
+            )));
+        }
+        pos += written.min(count);
+    }
+    Ok(())
+}
+
+fn read_fru_image(intf: &mut dyn IpmiIntf, fru_id: u8) -> CommandResult<(FruInfo, Vec<u8>)> {
+    let info = ipmi_fru_get_info(intf, fru_id)?;
+    if info.size < 1 {
+        return Err(IpmiError::InvalidData(format!(
+            "Invalid FRU size {}",
+            info.size
+        )));
+    }
+    log_debug!(
+        "FRU {} size {} bytes, accessed by {}",
+        fru_id,
+        info.size,
+        if info.word_access { "words" } else { "bytes" }
+    );
+    let image = ipmi_fru_read(intf, fru_id, &info, 0, info.size)?;
+    Ok((info, image))
+}
+
+/// 输出一个 FRU 设备的全部信息区
+fn ipmi_fru_print_device(intf: &mut dyn IpmiIntf, fru_id: u8) -> CommandResult {
+    let verbose = intf.context().output_config().verbose > 0;
+    let (_, image) = read_fru_image(intf, fru_id)?;
+    let header = FruHeader::parse(&image).map_err(IpmiError::InvalidData)?;
+
+    for (offset, kind) in [
+        (header.chassis, FruAreaKind::Chassis),
+        (header.board, FruAreaKind::Board),
+        (header.product, FruAreaKind::Product),
+    ] {
+        if offset == 0 {
+            continue;
+        }
+        match FruInfoArea::parse(&image, offset, kind) {
+            Ok(area) => print!("{}", area.format(verbose)),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    if header.multi != 0 && header.multi < image.len() {
+        match multirec::parse_multirec_area(&image[header.multi..]) {
+            Ok(records) => print!("{}", multirec::format_multirec(&records)),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+    Ok(())
+}
+
+fn print_device_or_error(intf: &mut dyn IpmiIntf, fru_id: u8) {
+    match ipmi_fru_print_device(intf, fru_id) {
+        Ok(()) => {}
+        Err(IpmiError::CompletionCode(cc)) => {
+            println!(" Device not present ({})", IpmiError::CompletionCode(cc))
+        }
+        Err(e) => println!(" Device not present ({})", e),
+    }
+}
+
+/// 从 SDR 中收集 BMC 上的逻辑 FRU 设备 (FRU Device Locator 记录)
+fn fru_locators(intf: &mut dyn IpmiIntf) -> Vec<(u8, String)> {
+    let mut devices = Vec::new();
+    let Some(mut iter) = SdrIterator::new(intf, false) else {
+        log::warn!("Unable to open SDR for reading");
+        return devices;
+    };
+
+    while let Some(header) = iter.next() {
+        if header.record_type != SDR_RECORD_TYPE_FRU_DEVICE_LOCATOR {
+            continue;
+        }
+        let Some(raw) = iter.ipmi_sdr_get_record(&header) else {
+            continue;
+        };
+        if raw.len() < 11 {
+            continue;
+        }
+        // 字节 2 bit7: 逻辑 FRU 设备；非逻辑设备需通过 Master Write-Read 访问
+        if raw[2] & 0x80 == 0 {
+            log_debug!("Skipping non-logical FRU device 0x{:02x}", raw[1]);
+            continue;
+        }
+        if raw[0] as u32 != IPMI_BMC_SLAVE_ADDR || raw[1] == FRU_BUILTIN_ID {
+            log_debug!(
+                "Skipping FRU device {} on controller 0x{:02x}",
+                raw[1],
+                raw[0]
+            );
+            continue;
+        }
+        let id_len = ((raw[10] & 0x1f) as usize).min(raw.len() - 11);
+        let name = String::from_utf8_lossy(&raw[11..11 + id_len])
+            .trim_end_matches('\0')
+            .to_string();
+        devices.push((raw[1], name));
+    }
+    devices
+}
+
+pub fn ipmi_fru_print(intf: &mut dyn IpmiIntf, fru_id: Option<u8>) -> CommandResult {
+    if let Some(id) = fru_id {
+        return ipmi_fru_print_device(intf, id);
+    }
+
+    println!("FRU Device Description : Builtin FRU Device (ID 0)");
+    print_device_or_error(intf, FRU_BUILTIN_ID);
+
+    for (id, name) in fru_locators(intf) {
+        println!();
+        println!("FRU Device Description : {} (ID {})", name, id);
+        print_device_or_error(intf, id);
+    }
+    Ok(())
+}
+
+fn ipmi_fru_read_to_file(intf: &mut dyn IpmiIntf, fru_id: u8, path: &Path) -> CommandResult {
+    let (info, image) = read_fru_image(intf, fru_id)?;
+    println!("Fru Size         : {} bytes", info.size);
+    std::fs::write(path, &image).map_err(|e| {
+        IpmiError::System(format!("Unable to write file {}: {}", path.display(), e))
+    })?;
+    println!("Done");
+    Ok(())
+}
+
+fn ipmi_fru_write_from_file(intf: &mut dyn IpmiIntf, fru_id: u8, path: &Path) -> CommandResult {
+    let data = std::fs::read(path)
+        .map_err(|e| IpmiError::System(format!("Unable to read file {}: {}", path.display(), e)))?;
+    let info = ipmi_fru_get_info(intf, fru_id)?;
+    println!("Fru Size         : {} bytes", info.size);
+    if data.len() > info.size {
+        return Err(IpmiError::InvalidData(format!(
+            "File size {} exceeds FRU size {}",
+            data.len(),
+            info.size
+        )));
+    }
+    println!("Size to Write    : {} bytes", data.len());
+    ipmi_fru_write(intf, fru_id, &info, 0, &data)?;
+    println!("Done");
+    Ok(())
+}
+
+/// 需要回写的字节范围，按访问单位对齐
+fn changed_range(old: &[u8], new: &[u8], unit: usize) -> Option<(usize, usize)> {
+    let first = old.iter().zip(new).position(|(a, b)| a != b)?;
+    let last = old.iter().zip(new).rposition(|(a, b)| a != b)?;
+    let start = first - first % unit;
+    let end = (last + 1).div_ceil(unit) * unit;
+    Some((start, end.min(new.len())))
+}
+
+fn ipmi_fru_edit_field(
+    intf: &mut dyn IpmiIntf,
+    fru_id: u8,
+    kind: FruAreaKind,
+    index: usize,
+    value: &str,
+) -> CommandResult {
+    let (info, image) = read_fru_image(intf, fru_id)?;
+    let edited = fru_set_field(&image, kind, index, value).map_err(IpmiError::InvalidData)?;
+
+    let unit = if info.word_access { 2 } else { 1 };
+    let Some((start, end)) = changed_range(&image, &edited, unit) else {
+        println!("FRU field unchanged");
+        return Ok(());
+    };
+    log_debug!("Writing FRU {} bytes {}-{}", fru_id, start, end);
+    ipmi_fru_write(intf, fru_id, &info, start, &edited[start..end])?;
+    println!("Done");
+    Ok(())
+}
+
+pub fn ipmi_fru_main(subcmd: FruCommand, mut intf: Box<dyn IpmiIntf>) -> CommandResult {
+    match subcmd {
+        FruCommand::Print { fru_id } => ipmi_fru_print(intf.as_mut(), fru_id),
+        FruCommand::Read { fru_id, file } => ipmi_fru_read_to_file(intf.as_mut(), fru_id, &file),
+        FruCommand::Write { fru_id, file } => {
+            ipmi_fru_write_from_file(intf.as_mut(), fru_id, &file)
+        }
+        FruCommand::Edit {
+            fru_id,
+            mode: FruEditMode::Field,
+            section,
+            index,
+            value,
+        } => ipmi_fru_edit_field(intf.as_mut(), fru_id, section.into(), index, &value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_range() {
+        let old = [0u8, 1, 2, 3, 4, 5];
+        assert_eq!(changed_range(&old, &old, 1), None);
+        let new = [0u8, 1, 9, 3, 9, 5];
+        assert_eq!(changed_range(&old, &new, 1), Some((2, 5)));
+        assert_eq!(changed_range(&old, &new, 2), Some((2, 6)));
+    }
+
+    #[test]
+    fn test_shrink_chunk() {
+        assert_eq!(shrink_chunk(24, 1), Some(16));
+        assert_eq!(shrink_chunk(8, 1), Some(7));
+        assert_eq!(shrink_chunk(2, 2), None);
+        assert_eq!(shrink_chunk(1, 1), None);
+    }
+}
@@ -0,0 +1,314 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! FRU MultiRecord 区解析 (FRU Information Storage Definition 第 16 节)
+
+use crate::commands::fru::area::{fru_checksum, hex_string};
+use crate::log_debug;
+
+const MULTIREC_HEADER_LEN: usize = 5;
+const MULTIREC_END_OF_LIST: u8 = 0x80;
+
+pub const FRU_RECORD_TYPE_POWER_SUPPLY_INFORMATION: u8 = 0x00;
+pub const FRU_RECORD_TYPE_DC_OUTPUT: u8 = 0x01;
+pub const FRU_RECORD_TYPE_DC_LOAD: u8 = 0x02;
+pub const FRU_RECORD_TYPE_MANAGEMENT_ACCESS: u8 = 0x03;
+
+const MANAGEMENT_ACCESS_DESC: &[&str] = &[
+    "System URL",
+    "System Name",
+    "System Ping Address",
+    "Component URL",
+    "Component Name",
+    "Component Ping Address",
+    "System Unique ID",
+];
+
+/// 一条 MultiRecord 记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FruMultiRecord {
+    pub record_type: u8,
+    pub version: u8,
+    pub data: Vec<u8>,
+}
+
+/// 解析 MultiRecord 区，遇到 end-of-list 标志或数据结束时停止
+pub fn parse_multirec_area(area: &[u8]) -> Result<Vec<FruMultiRecord>, String> {
+    let mut records = Vec::new();
+    let mut pos = 0;
+    while pos + MULTIREC_HEADER_LEN <= area.len() {
+        let h = &area[pos..pos + MULTIREC_HEADER_LEN];
+        if fru_checksum(h) != 0 {
+            return Err(format!(
+                "Invalid multirecord header checksum at offset {}",
+                pos
+            ));
+        }
+        let len = h[2] as usize;
+        let start = pos + MULTIREC_HEADER_LEN;
+        if start + len > area.len() {
+            return Err(format!("Multirecord at offset {} overruns FRU data", pos));
+        }
+        let data = &area[start..start + len];
+        if data.iter().fold(h[3], |acc, &b| acc.wrapping_add(b)) != 0 {
+            log::warn!("Invalid multirecord data checksum at offset {}", pos);
+        }
+        records.push(FruMultiRecord {
+            record_type: h[0],
+            version: h[1] & 0x0f,
+            data: data.to_vec(),
+        });
+        pos = start + len;
+        if h[1] & MULTIREC_END_OF_LIST != 0 {
+            break;
+        }
+    }
+    Ok(records)
+}
+
+/// MultiRecord 区的总长度，用于编辑时计算镜像已用空间
+pub fn multirec_area_len(area: &[u8]) -> usize {
+    let mut pos = 0;
+    while pos + MULTIREC_HEADER_LEN <= area.len() {
+        let eol = area[pos + 1] & MULTIREC_END_OF_LIST != 0;
+        pos += MULTIREC_HEADER_LEN + area[pos + 2] as usize;
+        if eol {
+            break;
+        }
+    }
+    pos.min(area.len())
+}
+
+fn le16(data: &[u8], off: usize) -> u16 {
+    u16::from_le_bytes([data[off], data[off + 1]])
+}
+
+/// 10mV 单位的电压，带符号
+fn volts(raw: u16) -> String {
+    format!("{:.2}", raw as i16 as f64 / 100.0)
+}
+
+fn format_power_supply(d: &[u8], out: &mut String) -> Result<(), String> {
+    if d.len() < 24 {
+        return Err("power supply record too short".to_string());
+    }
+    let flags = d[19];
+    let peak = le16(d, 20);
+    let lines = [
+        ("Capacity", format!("{} W", le16(d, 0) & 0x0fff)),
+        ("Peak VA", format!("{} VA", le16(d, 2))),
+        ("Inrush Current", format!("{} A", d[4])),
+        ("Inrush Interval", format!("{} ms", d[5])),
+        (
+            "Input Voltage Range 1",
+            format!("{}-{} V", volts(le16(d, 6)), volts(le16(d, 8))),
+        ),
+        (
+            "Input Voltage Range 2",
+            format!("{}-{} V", volts(le16(d, 10)), volts(le16(d, 12))),
+        ),
+        ("Input Frequency Range", format!("{}-{} Hz", d[14], d[15])),
+        ("A/C Dropout Tolerance", format!("{} ms", d[16])),
+        (
+            "Flags",
+            [
+                (0x01, "predictive fail"),
+                (0x02, "power factor correction"),
+                (0x04, "autoswitch voltage"),
+                (0x08, "hot swap"),
+                (0x10, "predictive fail pin"),
+            ]
+            .iter()
+            .filter(|(bit, _)| flags & bit != 0)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        ),
+        (
+            "Peak capacity",
+            format!("{} W, holdup {} s", peak & 0x0fff, peak >> 12),
+        ),
+        (
+            "Combined capacity",
+            format!(
+                "{} W (voltage 1 {}, voltage 2 {})",
+                le16(d, 22),
+                d[21] >> 4,
+                d[21] & 0x0f
+            ),
+        ),
+    ];
+    out.push_str(" Power Supply Record\n");
+    for (label, value) in lines {
+        out.push_str(&format!("  {:<27}: {}\n", label, value));
+    }
+    Ok(())
+}
+
+fn format_dc_output(d: &[u8], out: &mut String) -> Result<(), String> {
+    if d.len() < 13 {
+        return Err("DC output record too short".to_string());
+    }
+    out.push_str(" DC Output Record\n");
+    let lines = [
+        ("Output Number", format!("{}", d[0] & 0x0f)),
+        (
+            "Standby power",
+            (if d[0] & 0x80 != 0 { "Yes" } else { "No" }).to_string(),
+        ),
+        ("Nominal voltage", format!("{} V", volts(le16(d, 1)))),
+        ("Max negative deviation", format!("{} V", volts(le16(d, 3)))),
+        ("Max positive deviation", format!("{} V", volts(le16(d, 5)))),
+        ("Ripple and Noise pk-pk", format!("{} mV", le16(d, 7))),
+        (
+            "Minimum current draw",
+            format!("{:.3} A", le16(d, 9) as f64 / 1000.0),
+        ),
+        (
+            "Maximum current draw",
+            format!("{:.3} A", le16(d, 11) as f64 / 1000.0),
+        ),
+    ];
+    for (label, value) in lines {
+        out.push_str(&format!("  {:<27}: {}\n", label, value));
+    }
+    Ok(())
+}
+
+fn format_dc_load(d: &[u8], out: &mut String) -> Result<(), String> {
+    if d.len() < 13 {
+        return Err("DC load record too short".to_string());
+    }
+    out.push_str(" DC Load Record\n");
+    let lines = [
+        ("Output Number", format!("{}", d[0] & 0x0f)),
+        ("Nominal voltage", format!("{} V", volts(le16(d, 1)))),
+        ("Min voltage allowed", format!("{} V", volts(le16(d, 3)))),
+        ("Max voltage allowed", format!("{} V", volts(le16(d, 5)))),
+        ("Ripple and Noise pk-pk", format!("{} mV", le16(d, 7))),
+        (
+            "Minimum current load",
+            format!("{:.3} A", le16(d, 9) as f64 / 1000.0),
+        ),
+        (
+            "Maximum current load",
+            format!("{:.3} A", le16(d, 11) as f64 / 1000.0),
+        ),
+    ];
+    for (label, value) in lines {
+        out.push_str(&format!("  {:<27}: {}\n", label, value));
+    }
+    Ok(())
+}
+
+fn format_management_access(d: &[u8], out: &mut String) -> Result<(), String> {
+    let Some((&sub_type, payload)) = d.split_first() else {
+        return Err("management access record too short".to_string());
+    };
+    let Some(label) = sub_type
+        .checked_sub(1)
+        .and_then(|i| MANAGEMENT_ACCESS_DESC.get(i as usize))
+    else {
+        return Err(format!(
+            "unknown management access sub-type 0x{:02x}",
+            sub_type
+        ));
+    };
+
+    let value = if sub_type == 0x07 {
+        if payload.len() < 16 {
+            return Err("system unique ID too short".to_string());
+        }
+        let g = &payload[..16];
+        format!(
+            "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{}",
+            g[3],
+            g[2],
+            g[1],
+            g[0],
+            g[5],
+            g[4],
+            g[7],
+            g[6],
+            g[8],
+            g[9],
+            hex_string(&g[10..])
+        )
+    } else {
+        payload.iter().map(|&b| b as char).collect()
+    };
+    out.push_str(&format!(" {:<22}: {}\n", label, value));
+    Ok(())
+}
+
+/// 输出可识别的 MultiRecord 记录，其他类型仅记录调试信息
+pub fn format_multirec(records: &[FruMultiRecord]) -> String {
+    let mut out = String::new();
+    for record in records {
+        let result = match record.record_type {
+            FRU_RECORD_TYPE_POWER_SUPPLY_INFORMATION => format_power_supply(&record.data, &mut out),
+            FRU_RECORD_TYPE_DC_OUTPUT => format_dc_output(&record.data, &mut out),
+            FRU_RECORD_TYPE_DC_LOAD => format_dc_load(&record.data, &mut out),
+            FRU_RECORD_TYPE_MANAGEMENT_ACCESS => format_management_access(&record.data, &mut out),
+            other => {
+                log_debug!("Skipping FRU multirecord type 0x{:02x}", other);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            log::warn!("Invalid FRU multirecord: {}", e);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(record_type: u8, last: bool, data: &[u8]) -> Vec<u8> {
+        let mut h = vec![
+            record_type,
+            0x02 | if last { MULTIREC_END_OF_LIST } else { 0 },
+            data.len() as u8,
+            fru_checksum(data),
+            0,
+        ];
+        h[4] = fru_checksum(&h[..4]);
+        h.extend_from_slice(data);
+        h
+    }
+
+    #[test]
+    fn test_parse_multirec_area() {
+        // 12.00V 输出，待机电源，0.5A-2A
+        let dc = [
+            0x81, 0xb0, 0x04, 0x3c, 0x00, 0x3c, 0x00, 0x32, 0x00, 0xf4, 0x01, 0xd0, 0x07,
+        ];
+        let mut area = record(FRU_RECORD_TYPE_DC_OUTPUT, false, &dc);
+        area.extend(record(
+            FRU_RECORD_TYPE_MANAGEMENT_ACCESS,
+            true,
+            b"\x02node-01",
+        ));
+        let total = area.len();
+        area.extend_from_slice(&[0xff; 8]);
+
+        let records = parse_multirec_area(&area).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(multirec_area_len(&area), total);
+
+        let text = format_multirec(&records);
+        assert!(text.contains("  Output Number              : 1\n"));
+        assert!(text.contains("  Standby power              : Yes\n"));
+        assert!(text.contains("  Nominal voltage            : 12.00 V\n"));
+        assert!(text.contains("  Maximum current draw       : 2.000 A\n"));
+        assert!(text.contains(" System Name           : node-01\n"));
+
+        area[4] ^= 0xff;
+        assert!(parse_multirec_area(&area).is_err());
+    }
+}
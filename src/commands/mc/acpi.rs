@@ -0,0 +1,194 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! Get/Set ACPI Power State (IPMI v2.0 20.6/20.7)
+
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
+use clap::{Subcommand, ValueEnum};
+
+pub const IPMI_SET_ACPI_POWER_STATE: u8 = 0x06;
+pub const IPMI_GET_ACPI_POWER_STATE: u8 = 0x07;
+
+const ACPI_STATE_MASK: u8 = 0x7f;
+// Set 请求中置位表示修改该状态
+const ACPI_SET_STATE: u8 = 0x80;
+const ACPI_NO_CHANGE: u8 = 0x7f;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AcpiCommand {
+    /// Show the system and device ACPI power states
+    Get,
+    /// Record new ACPI power states in the BMC
+    Set {
+        /// System power state
+        #[arg(long, value_enum)]
+        system: Option<SystemPowerState>,
+        /// Device power state
+        #[arg(long, value_enum)]
+        device: Option<DevicePowerState>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SystemPowerState {
+    S0 = 0x00,
+    S1 = 0x01,
+    S2 = 0x02,
+    S3 = 0x03,
+    S4 = 0x04,
+    S5 = 0x05,
+    #[value(name = "s4-s5")]
+    S4S5 = 0x06,
+    G3 = 0x07,
+    Sleeping = 0x08,
+    G1 = 0x09,
+    Override = 0x0a,
+    LegacyOn = 0x20,
+    LegacyOff = 0x21,
+    Unknown = 0x2a,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DevicePowerState {
+    D0 = 0x00,
+    D1 = 0x01,
+    D2 = 0x02,
+    D3 = 0x03,
+    Unknown = 0x2a,
+}
+
+fn system_state_name(state: u8) -> &'static str {
+    match state {
+        0x00 => "S0/G0 (working)",
+        0x01 => "S1 (hardware context maintained)",
+        0x02 => "S2 (stopped clocks)",
+        0x03 => "S3 (suspend-to-RAM)",
+        0x04 => "S4 (suspend-to-disk)",
+        0x05 => "S5/G2 (soft-off)",
+        0x06 => "S4/S5 (soft-off)",
+        0x07 => "G3 (mechanical off)",
+        0x08 => "Sleeping (S1-S3)",
+        0x09 => "G1 (sleeping, S1-S4)",
+        0x0a => "S5 (entered by override)",
+        0x20 => "Legacy On",
+        0x21 => "Legacy Off",
+        0x2a => "Unknown",
+        _ => "Reserved",
+    }
+}
+
+fn device_state_name(state: u8) -> &'static str {
+    match state {
+        0x00 => "D0",
+        0x01 => "D1",
+        0x02 => "D2",
+        0x03 => "D3",
+        0x2a => "Unknown",
+        _ => "Reserved",
+    }
+}
+
+pub fn format_acpi_state(data: &[u8]) -> Result<String, String> {
+    if data.len() < 2 {
+        return Err(format!(
+            "Invalid ACPI Power State response length: {}",
+            data.len()
+        ));
+    }
+    let system = data[0] & ACPI_STATE_MASK;
+    let device = data[1] & ACPI_STATE_MASK;
+    Ok(format!(
+        "System Power State : {} (0x{:02x})\nDevice Power State : {} (0x{:02x})\n",
+        system_state_name(system),
+        system,
+        device_state_name(device),
+        device
+    ))
+}
+
+fn acpi_set_request(system: Option<SystemPowerState>, device: Option<DevicePowerState>) -> [u8; 2] {
+    [
+        system.map_or(ACPI_NO_CHANGE, |s| ACPI_SET_STATE | s as u8),
+        device.map_or(ACPI_NO_CHANGE, |d| ACPI_SET_STATE | d as u8),
+    ]
+}
+
+fn ipmi_mc_get_acpi(intf: &mut dyn IpmiIntf) -> Result<String, String> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_GET_ACPI_POWER_STATE;
+    req.msg.data_len = 0;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "Get ACPI Power State command failed: completion code 0x{:02x}",
+            rsp.ccode
+        )),
+        Some(rsp) => format_acpi_state(&rsp.data[..rsp.data_len.max(0) as usize]),
+        None => Err("Get ACPI Power State command failed: no response".to_string()),
+    }
+}
+
+fn ipmi_mc_set_acpi(
+    intf: &mut dyn IpmiIntf,
+    system: Option<SystemPowerState>,
+    device: Option<DevicePowerState>,
+) -> Result<(), String> {
+    if system.is_none() && device.is_none() {
+        return Err("Specify --system and/or --device".to_string());
+    }
+    let mut data = acpi_set_request(system, device);
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_SET_ACPI_POWER_STATE;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "Set ACPI Power State command failed: completion code 0x{:02x}",
+            rsp.ccode
+        )),
+        Some(_) => Ok(()),
+        None => Err("Set ACPI Power State command failed: no response".to_string()),
+    }
+}
+
+pub fn ipmi_mc_acpi_main(subcmd: AcpiCommand, intf: &mut dyn IpmiIntf) -> Result<(), String> {
+    match subcmd {
+        AcpiCommand::Get => print!("{}", ipmi_mc_get_acpi(intf)?),
+        AcpiCommand::Set { system, device } => {
+            ipmi_mc_set_acpi(intf, system, device)?;
+            print!("{}", ipmi_mc_get_acpi(intf)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acpi_power_state() {
+        assert_eq!(
+            format_acpi_state(&[0x85, 0x03]).unwrap(),
+            "System Power State : S5/G2 (soft-off) (0x05)\n\
+             Device Power State : D3 (0x03)\n"
+        );
+        assert!(format_acpi_state(&[0x00]).is_err());
+        assert_eq!(
+            acpi_set_request(Some(SystemPowerState::S3), None),
+            [0x83, 0x7f]
+        );
+        assert_eq!(
+            acpi_set_request(None, Some(DevicePowerState::Unknown)),
+            [0x7f, 0xaa]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 主机侧看门狗心跳守护进程
+//!
+//! 以 SMS/OS 用途装载 BMC 看门狗，按固定间隔发送 Reset Watchdog Timer，
+//! 收到 SIGTERM/SIGINT 后关闭计时器退出。日志写入 syslog。
+
+use super::watchdog::{
+    action_name, ipmi_mc_get_watchdog, ipmi_mc_reset_watchdog, ipmi_mc_set_watchdog,
+    timer_use_name, PretimeoutInterrupt, TimeoutAction, TimerUse, WatchdogDaemonArgs,
+    WatchdogSettings, WatchdogTimer, WDT_USE_MASK,
+};
+use crate::ipmi::intf::IpmiIntf;
+use nix::sys::signal::{signal, SigHandler, Signal};
+use std::ffi::c_int;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use utipmi_sys::syslog as sys;
+
+static STOP: AtomicBool = AtomicBool::new(false);
+
+// 检查退出标志的粒度
+const POLL_STEP: Duration = Duration::from_millis(100);
+
+extern "C" fn on_stop_signal(_: c_int) {
+    STOP.store(true, Ordering::SeqCst);
+}
+
+fn install_signal_handlers() -> Result<(), String> {
+    for sig in [Signal::SIGTERM, Signal::SIGINT] {
+        // SAFETY: 处理函数只写一个原子变量
+        unsafe { signal(sig, SigHandler::Handler(on_stop_signal)) }
+            .map_err(|e| format!("Failed to install {} handler: {}", sig, e))?;
+    }
+    Ok(())
+}
+
+/// 其他用途（BIOS FRB2/POST、OS Load 等）正在使用的计时器不能接管
+fn check_owner(current: &WatchdogTimer) -> Result<(), String> {
+    let use_ = current.timer_use & WDT_USE_MASK;
+    if current.is_running() && use_ != TimerUse::SmsOs as u8 {
+        return Err(format!(
+            "Watchdog timer is running for {} (0x{:02x}); refusing to take it over",
+            timer_use_name(use_),
+            current.timer_use
+        ));
+    }
+    Ok(())
+}
+
+fn daemon_settings(args: &WatchdogDaemonArgs) -> WatchdogSettings {
+    let pretimeout = if args.pretimeout == PretimeoutInterrupt::None {
+        0
+    } else {
+        args.pretimeout_interval
+    };
+    WatchdogSettings {
+        timer_use: TimerUse::SmsOs,
+        dont_log: false,
+        dont_stop: false,
+        action: args.action,
+        pretimeout_interrupt: args.pretimeout,
+        pretimeout,
+        // 清除上次运行遗留的 SMS/OS 超时标志
+        clear_flags: 1 << TimerUse::SmsOs as u8,
+        countdown: args.timeout,
+    }
+}
+
+/// 心跳间隔（单位 100 ms），必须短于超时时间
+fn heartbeat_interval(args: &WatchdogDaemonArgs) -> Result<u16, String> {
+    let interval = args.interval.unwrap_or(args.timeout / 3);
+    if interval == 0 || interval >= args.timeout {
+        return Err(format!(
+            "Heartbeat interval ({}.{} sec) must be positive and shorter than the timeout ({}.{} sec)",
+            interval / 10,
+            interval % 10,
+            args.timeout / 10,
+            args.timeout % 10
+        ));
+    }
+    Ok(interval)
+}
+
+fn arm(intf: &mut dyn IpmiIntf, settings: &WatchdogSettings) -> Result<(), String> {
+    ipmi_mc_set_watchdog(intf, settings)?;
+    ipmi_mc_reset_watchdog(intf)
+}
+
+/// 等待一个心跳间隔，收到退出信号时返回 false
+fn wait_heartbeat(interval: Duration) -> bool {
+    let deadline = Instant::now() + interval;
+    while !STOP.load(Ordering::SeqCst) {
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        thread::sleep(POLL_STEP.min(deadline - now));
+    }
+    false
+}
+
+pub(super) fn ipmi_mc_watchdog_daemon(
+    args: &WatchdogDaemonArgs,
+    intf: &mut dyn IpmiIntf,
+) -> Result<(), String> {
+    let settings = daemon_settings(args);
+    settings.validate()?;
+    let interval = heartbeat_interval(args)?;
+
+    let current = ipmi_mc_get_watchdog(intf)?;
+    check_owner(&current)?;
+    install_signal_handlers()?;
+
+    sys::openlog(c"utipmitool", sys::LOG_CONS | sys::LOG_PID, sys::LOG_DAEMON);
+    if current.expiration_flags & settings.clear_flags != 0 {
+        sys::syslog_msg(
+            sys::LOG_WARNING,
+            "SMS/OS watchdog expired since it was last armed",
+        );
+    }
+
+    if let Err(e) = arm(intf, &settings) {
+        sys::syslog_msg2(sys::LOG_ERR, "Failed to arm watchdog", &e);
+        sys::closelog();
+        return Err(e);
+    }
+    sys::syslog_msg(
+        sys::LOG_NOTICE,
+        &format!(
+            "Watchdog armed: timeout {}.{} sec, action {}, heartbeat every {}.{} sec",
+            args.timeout / 10,
+            args.timeout % 10,
+            action_name(args.action as u8),
+            interval / 10,
+            interval % 10
+        ),
+    );
+
+    let mut failing = false;
+    while wait_heartbeat(Duration::from_millis(interval as u64 * 100)) {
+        // BMC 复位后计时器回到未初始化状态，此时重新装载
+        let result = ipmi_mc_reset_watchdog(intf).or_else(|_| arm(intf, &settings));
+        match result {
+            Ok(()) if failing => {
+                sys::syslog_msg(sys::LOG_NOTICE, "Watchdog heartbeat restored");
+                failing = false;
+            }
+            Ok(()) => {}
+            Err(e) => {
+                if !failing {
+                    sys::syslog_msg2(sys::LOG_WARNING, "Watchdog heartbeat failed", &e);
+                }
+                failing = true;
+            }
+        }
+    }
+
+    let off = WatchdogSettings {
+        action: TimeoutAction::None,
+        pretimeout_interrupt: PretimeoutInterrupt::None,
+        pretimeout: 0,
+        clear_flags: 0,
+        ..settings
+    };
+    let result = ipmi_mc_set_watchdog(intf, &off);
+    match &result {
+        Ok(()) => sys::syslog_msg(sys::LOG_NOTICE, "Watchdog stopped"),
+        Err(e) => sys::syslog_msg2(sys::LOG_ERR, "Failed to stop watchdog", e),
+    }
+    sys::closelog();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(timeout: u16, interval: Option<u16>) -> WatchdogDaemonArgs {
+        WatchdogDaemonArgs {
+            timeout,
+            action: TimeoutAction::PowerCycle,
+            pretimeout: PretimeoutInterrupt::Nmi,
+            pretimeout_interval: 10,
+            interval,
+        }
+    }
+
+    #[test]
+    fn test_check_owner() {
+        let mut wdt = WatchdogTimer {
+            timer_use: 0x43,
+            ..Default::default()
+        };
+        assert!(check_owner(&wdt).unwrap_err().contains("OS Load"));
+        // 已停止的计时器可以接管
+        wdt.timer_use = 0x03;
+        assert!(check_owner(&wdt).is_ok());
+        wdt.timer_use = 0x44;
+        assert!(check_owner(&wdt).is_ok());
+    }
+
+    #[test]
+    fn test_daemon_settings() {
+        let a = args(3000, None);
+        assert_eq!(
+            daemon_settings(&a).to_bytes(),
+            [0x04, 0x23, 0x0a, 0x10, 0xb8, 0x0b]
+        );
+        assert_eq!(heartbeat_interval(&a), Ok(1000));
+        assert_eq!(heartbeat_interval(&args(3000, Some(50))), Ok(50));
+        assert!(heartbeat_interval(&args(3000, Some(3000))).is_err());
+        assert!(heartbeat_interval(&args(2, None)).is_err());
+    }
+}
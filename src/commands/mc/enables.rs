@@ -0,0 +1,144 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! BMC Global Enables (Get/Set BMC Global Enables)
+
+use super::{BMC_GET_GLOBAL_ENABLES, BMC_SET_GLOBAL_ENABLES};
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
+
+/// (选项名, 显示名, 位掩码)，bit 4 保留
+const GLOBAL_ENABLES: &[(&str, &str, u8)] = &[
+    ("recv_msg_intr", "Receive Message Queue Interrupt", 0x01),
+    (
+        "event_msg_intr",
+        "Event Message Buffer Full Interrupt",
+        0x02,
+    ),
+    ("event_msg", "Event Message Buffer", 0x04),
+    ("system_event_log", "System Event Logging", 0x08),
+    ("oem0", "OEM 0", 0x20),
+    ("oem1", "OEM 1", 0x40),
+    ("oem2", "OEM 2", 0x80),
+];
+
+pub fn format_global_enables(enables: u8) -> String {
+    let mut out = String::new();
+    for (_, desc, mask) in GLOBAL_ENABLES {
+        let state = if enables & mask != 0 {
+            "enabled"
+        } else {
+            "disabled"
+        };
+        out.push_str(&format!("{:<40} : {}\n", desc, state));
+    }
+    out
+}
+
+/// 在当前值上应用 `option=on|off` 列表
+fn apply_enable_options(mut enables: u8, options: &[String]) -> Result<u8, String> {
+    for opt in options {
+        let (name, value) = opt
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid option '{}', expected <option>=on|off", opt))?;
+        let (_, _, mask) = GLOBAL_ENABLES
+            .iter()
+            .find(|(n, _, _)| *n == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = GLOBAL_ENABLES.iter().map(|(n, _, _)| *n).collect();
+                format!(
+                    "Unknown option '{}', valid options are: {}",
+                    name,
+                    names.join(", ")
+                )
+            })?;
+        match value {
+            "on" => enables |= mask,
+            "off" => enables &= !mask,
+            _ => {
+                return Err(format!(
+                    "Invalid value '{}' for {}, expected on or off",
+                    value, name
+                ))
+            }
+        }
+    }
+    Ok(enables)
+}
+
+pub fn ipmi_mc_get_enables(intf: &mut dyn IpmiIntf) -> Result<u8, String> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = BMC_GET_GLOBAL_ENABLES;
+    req.msg.data_len = 0;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "Get Global Enables command failed: completion code 0x{:02x}",
+            rsp.ccode
+        )),
+        Some(rsp) if rsp.data_len < 1 => {
+            Err("Get Global Enables command failed: empty response".to_string())
+        }
+        Some(rsp) => Ok(rsp.data[0]),
+        None => Err("Get Global Enables command failed: no response".to_string()),
+    }
+}
+
+pub fn ipmi_mc_set_enables(intf: &mut dyn IpmiIntf, options: &[String]) -> Result<String, String> {
+    let current = ipmi_mc_get_enables(intf)?;
+    let enables = apply_enable_options(current, options)?;
+    if enables == current {
+        return Ok(format_global_enables(current));
+    }
+
+    let mut data = [enables];
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = BMC_SET_GLOBAL_ENABLES;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => {
+            return Err(format!(
+                "Set Global Enables command failed: completion code 0x{:02x}",
+                rsp.ccode
+            ))
+        }
+        Some(_) => {}
+        None => return Err("Set Global Enables command failed: no response".to_string()),
+    }
+
+    // 回读确认 BMC 实际接受的设置
+    Ok(format_global_enables(ipmi_mc_get_enables(intf)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_enables() {
+        let opts = |s: &[&str]| s.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            apply_enable_options(0x0c, &opts(&["recv_msg_intr=on", "system_event_log=off"])),
+            Ok(0x05)
+        );
+        assert_eq!(apply_enable_options(0x10, &opts(&["oem2=on"])), Ok(0x90));
+        assert!(apply_enable_options(0, &opts(&["oem3=on"])).is_err());
+        assert!(apply_enable_options(0, &opts(&["oem0=yes"])).is_err());
+        assert!(apply_enable_options(0, &opts(&["oem0"])).is_err());
+
+        let out = format_global_enables(0x09);
+        assert!(out.starts_with(
+            "Receive Message Queue Interrupt          : enabled\n\
+             Event Message Buffer Full Interrupt      : disabled\n"
+        ));
+        assert!(out.contains("System Event Logging                     : enabled\n"));
+        assert_eq!(out.lines().count(), 7);
+    }
+}
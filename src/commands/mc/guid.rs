@@ -0,0 +1,184 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! System GUID 解码
+//!
+//! Get System GUID 返回的 16 字节在不同 BMC 上字节序不一致：
+//! RFC 4122 按网络字节序，SMBIOS 前三个字段为小端，IPMI 规范整体为小端。
+
+use chrono::{TimeZone, Utc};
+use clap::ValueEnum;
+
+/// 1582-10-15 到 1970-01-01 的秒数
+const UUID_EPOCH_OFFSET: i64 = 12_219_292_800;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GuidEncoding {
+    /// Pick the byte order that yields a valid RFC 4122 version and variant
+    Auto,
+    Smbios,
+    Rfc4122,
+    Ipmi,
+    /// Print the raw bytes as returned by the BMC
+    Dump,
+}
+
+impl GuidEncoding {
+    fn name(&self) -> &'static str {
+        match self {
+            GuidEncoding::Auto => "Auto",
+            GuidEncoding::Smbios => "SMBIOS",
+            GuidEncoding::Rfc4122 => "RFC4122",
+            GuidEncoding::Ipmi => "IPMI",
+            GuidEncoding::Dump => "Dump",
+        }
+    }
+}
+
+/// 转换为 RFC 4122 (网络字节序) 表示
+fn canonical(raw: &[u8; 16], encoding: GuidEncoding) -> [u8; 16] {
+    let mut c = *raw;
+    match encoding {
+        GuidEncoding::Smbios => {
+            c[0..4].reverse();
+            c[4..6].reverse();
+            c[6..8].reverse();
+        }
+        GuidEncoding::Ipmi => c.reverse(),
+        _ => {}
+    }
+    c
+}
+
+fn version(c: &[u8; 16]) -> u8 {
+    c[6] >> 4
+}
+
+fn is_valid(c: &[u8; 16]) -> bool {
+    (1..=5).contains(&version(c)) && c[8] & 0xc0 == 0x80
+}
+
+fn detect(raw: &[u8; 16]) -> Option<GuidEncoding> {
+    [
+        GuidEncoding::Smbios,
+        GuidEncoding::Rfc4122,
+        GuidEncoding::Ipmi,
+    ]
+    .into_iter()
+    .find(|enc| is_valid(&canonical(raw, *enc)))
+}
+
+fn version_name(version: u8) -> &'static str {
+    match version {
+        1 => "Time-based",
+        2 => "DCE Security",
+        3 => "Name-based (MD5)",
+        4 => "Random",
+        5 => "Name-based (SHA-1)",
+        _ => "Unknown",
+    }
+}
+
+/// 版本 1 GUID 中的 60 位时间戳，换算为 Unix 秒
+fn timestamp(c: &[u8; 16]) -> Option<i64> {
+    if version(c) != 1 {
+        return None;
+    }
+    let low = u32::from_be_bytes([c[0], c[1], c[2], c[3]]) as u64;
+    let mid = u16::from_be_bytes([c[4], c[5]]) as u64;
+    let high = (u16::from_be_bytes([c[6], c[7]]) & 0x0fff) as u64;
+    let ticks = (high << 48) | (mid << 32) | low;
+    Some((ticks / 10_000_000) as i64 - UUID_EPOCH_OFFSET)
+}
+
+fn format_uuid(c: &[u8; 16]) -> String {
+    let mut out = String::with_capacity(36);
+    for (i, b) in c.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        out.push_str(&format!("{:02x}", b));
+    }
+    out
+}
+
+pub fn format_system_guid(data: &[u8], encoding: GuidEncoding) -> Result<String, String> {
+    let raw: [u8; 16] = data
+        .get(..16)
+        .and_then(|d| d.try_into().ok())
+        .ok_or_else(|| format!("Invalid GUID response length: {}", data.len()))?;
+
+    if encoding == GuidEncoding::Dump {
+        let bytes: Vec<String> = raw.iter().map(|b| format!("{:02x}", b)).collect();
+        return Ok(format!("System GUID Dump : {}\n", bytes.join(" ")));
+    }
+
+    let (encoding, detected) = match encoding {
+        GuidEncoding::Auto => match detect(&raw) {
+            Some(enc) => (enc, true),
+            None => (GuidEncoding::Smbios, false),
+        },
+        enc => (enc, true),
+    };
+    let c = canonical(&raw, encoding);
+
+    let mut out = format!("System GUID      : {}\n", format_uuid(&c));
+    out.push_str(&format!(
+        "GUID Encoding    : {}{}\n",
+        encoding.name(),
+        if detected { "" } else { " (assumed)" }
+    ));
+    out.push_str(&format!(
+        "GUID Version     : {} ({})\n",
+        version_name(version(&c)),
+        version(&c)
+    ));
+    let stamp = timestamp(&c)
+        .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+        .map(|t| t.format("%m/%d/%Y %H:%M:%S").to_string())
+        .unwrap_or_else(|| "N/A".to_string());
+    out.push_str(&format!("Timestamp        : {}\n", stamp));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 版本 1，时间 2024-01-01 00:00:00 UTC，SMBIOS 字节序
+    const SMBIOS_GUID: [u8; 16] = [
+        0x00, 0x80, 0xcc, 0xb4, 0x38, 0xa8, 0xee, 0x11, 0x80, 0x00, 0x52, 0x54, 0x00, 0x12, 0x34,
+        0x56,
+    ];
+
+    #[test]
+    fn test_guid_encodings() {
+        let out = format_system_guid(&SMBIOS_GUID, GuidEncoding::Auto).unwrap();
+        assert_eq!(
+            out,
+            "System GUID      : b4cc8000-a838-11ee-8000-525400123456\n\
+             GUID Encoding    : SMBIOS\n\
+             GUID Version     : Time-based (1)\n\
+             Timestamp        : 01/01/2024 00:00:00\n"
+        );
+
+        let mut rfc = SMBIOS_GUID;
+        rfc[0..4].reverse();
+        rfc[4..6].reverse();
+        rfc[6..8].reverse();
+        assert_eq!(detect(&rfc), Some(GuidEncoding::Rfc4122));
+        let mut ipmi = rfc;
+        ipmi.reverse();
+        assert_eq!(detect(&ipmi), Some(GuidEncoding::Ipmi));
+        assert!(format_system_guid(&ipmi, GuidEncoding::Auto)
+            .unwrap()
+            .starts_with("System GUID      : b4cc8000-a838-11ee-8000-525400123456\n"));
+
+        let dump = format_system_guid(&SMBIOS_GUID, GuidEncoding::Dump).unwrap();
+        assert!(dump.starts_with("System GUID Dump : 00 80 cc b4"));
+        assert!(format_system_guid(&SMBIOS_GUID[..15], GuidEncoding::Auto).is_err());
+    }
+}
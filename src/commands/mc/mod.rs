@@ -9,6 +9,19 @@ use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
 use clap::Subcommand;
 use ipmi_macros::AsBytes;
 
+pub mod acpi;
+mod daemon;
+pub mod enables;
+pub mod guid;
+pub mod sysinfo;
+pub mod watchdog;
+
+use acpi::{ipmi_mc_acpi_main, AcpiCommand};
+use enables::{format_global_enables, ipmi_mc_get_enables, ipmi_mc_set_enables};
+use guid::{format_system_guid, GuidEncoding};
+use sysinfo::{format_sysinfo, ipmi_mc_getsysinfo, ipmi_mc_setsysinfo, SysInfoParam};
+use watchdog::{ipmi_mc_watchdog_main, WatchdogCommand};
+
 // MC子命令
 #[derive(Debug, Clone, Subcommand)]
 pub enum McCommand {
@@ -20,6 +33,45 @@ pub enum McCommand {
         #[arg(default_value = "")]
         reset_type: String,
     },
+    /// Display the management controller system GUID
+    Guid {
+        /// GUID byte order
+        #[arg(value_enum, default_value = "auto")]
+        encoding: GuidEncoding,
+    },
+    /// Show the management controller self test results
+    Selftest,
+    /// Show the BMC global enables
+    Getenables,
+    /// Change BMC global enables
+    Setenables {
+        /// <option>=on|off where option is recv_msg_intr, event_msg_intr,
+        /// event_msg, system_event_log, oem0, oem1 or oem2
+        #[arg(required = true)]
+        options: Vec<String>,
+    },
+    /// Read a system info parameter
+    Getsysinfo {
+        #[arg(value_enum)]
+        param: SysInfoParam,
+    },
+    /// Write a system info parameter string
+    Setsysinfo {
+        #[arg(value_enum)]
+        param: SysInfoParam,
+        /// New value, at most 255 bytes
+        value: String,
+    },
+    /// Get or set the ACPI power state recorded in the BMC
+    Acpi {
+        #[command(subcommand)]
+        subcmd: AcpiCommand,
+    },
+    /// Get, set, reset or stop the BMC watchdog timer
+    Watchdog {
+        #[command(subcommand)]
+        subcmd: WatchdogCommand,
+    },
 }
 
 // IPMI constant definitions matching C reference
@@ -438,6 +490,66 @@ fn ipmi_mc_reset(intf: &mut dyn IpmiIntf, reset_type: &str) -> Result<String, St
     }
 }
 
+/// 发送不带数据的 App 命令，返回成功响应的数据
+fn ipmi_mc_simple_cmd(intf: &mut dyn IpmiIntf, cmd: u8, name: &str) -> Result<Vec<u8>, String> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = cmd;
+    req.msg.data_len = 0;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "{} command failed: completion code 0x{:02x}",
+            name, rsp.ccode
+        )),
+        Some(rsp) => Ok(rsp.data[..rsp.data_len.max(0) as usize].to_vec()),
+        None => Err(format!("{} command failed: no response", name)),
+    }
+}
+
+/// Get Self Test Results 第二字节 (0x57 时) 的故障位
+const SELFTEST_FAILURES: [&str; 8] = [
+    "controller operational firmware corrupted",
+    "controller update boot block firmware corrupted",
+    "Internal Use Area of BMC FRU corrupted",
+    "SDR repository empty",
+    "IPMB signal lines do not respond",
+    "BMC FRU device not accessible",
+    "SDR repository not accessible",
+    "SEL device not accessible",
+];
+
+fn format_selftest(data: &[u8]) -> Result<String, String> {
+    if data.len() < 2 {
+        return Err(format!(
+            "Invalid Self Test Results response length: {}",
+            data.len()
+        ));
+    }
+    let out = match data[0] {
+        0x55 => "Selftest: passed\n".to_string(),
+        0x56 => "Selftest: not implemented\n".to_string(),
+        0x57 => {
+            let mut out = "Selftest: device corrupted\n".to_string();
+            for (bit, desc) in SELFTEST_FAILURES.iter().enumerate().rev() {
+                if data[1] & (1 << bit) != 0 {
+                    out.push_str(&format!(" -> {}\n", desc));
+                }
+            }
+            out
+        }
+        0x58 => format!(
+            "Selftest: fatal hardware error\nFailure code: 0x{:02x}\n",
+            data[1]
+        ),
+        code => format!(
+            "Selftest: device-specific failure (0x{:02x})\nFailure code: 0x{:02x}\n",
+            code, data[1]
+        ),
+    };
+    Ok(out)
+}
+
 pub fn ipmi_mc_main(subcmd: McCommand, mut intf: Box<dyn IpmiIntf>) -> Result<(), String> {
     match subcmd {
         McCommand::Info => {
@@ -450,6 +562,36 @@ pub fn ipmi_mc_main(subcmd: McCommand, mut intf: Box<dyn IpmiIntf>) -> Result<()
             println!("{}", result);
             Ok(())
         }
+        McCommand::Guid { encoding } => {
+            let data = ipmi_mc_simple_cmd(intf.as_mut(), BMC_GET_GUID, "Get System GUID")?;
+            print!("{}", format_system_guid(&data, encoding)?);
+            Ok(())
+        }
+        McCommand::Selftest => {
+            let data =
+                ipmi_mc_simple_cmd(intf.as_mut(), BMC_GET_SELF_TEST, "Get Self Test Results")?;
+            print!("{}", format_selftest(&data)?);
+            Ok(())
+        }
+        McCommand::Getenables => {
+            print!(
+                "{}",
+                format_global_enables(ipmi_mc_get_enables(intf.as_mut())?)
+            );
+            Ok(())
+        }
+        McCommand::Setenables { options } => {
+            print!("{}", ipmi_mc_set_enables(intf.as_mut(), &options)?);
+            Ok(())
+        }
+        McCommand::Getsysinfo { param } => {
+            let value = ipmi_mc_getsysinfo(intf.as_mut(), param)?;
+            print!("{}", format_sysinfo(param, &value));
+            Ok(())
+        }
+        McCommand::Setsysinfo { param, value } => ipmi_mc_setsysinfo(intf.as_mut(), param, &value),
+        McCommand::Acpi { subcmd } => ipmi_mc_acpi_main(subcmd, intf.as_mut()),
+        McCommand::Watchdog { subcmd } => ipmi_mc_watchdog_main(subcmd, intf.as_mut()),
     }
 }
 
@@ -480,6 +622,22 @@ mod tests {
         assert_eq!(get_product_name(0, 0), "");
     }
 
+    #[test]
+    fn test_format_selftest() {
+        assert_eq!(
+            format_selftest(&[0x55, 0x00]).unwrap(),
+            "Selftest: passed\n"
+        );
+        assert_eq!(
+            format_selftest(&[0x57, 0x88]).unwrap(),
+            "Selftest: device corrupted\n -> SEL device not accessible\n -> SDR repository empty\n"
+        );
+        assert!(format_selftest(&[0x58, 0x12])
+            .unwrap()
+            .ends_with("Failure code: 0x12\n"));
+        assert!(format_selftest(&[0x55]).is_err());
+    }
+
     #[test]
     fn test_get_manufacturer_name() {
         assert_eq!(get_manufacturer_name(2), "IBM");
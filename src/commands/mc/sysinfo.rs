@@ -0,0 +1,251 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! Get/Set System Info Parameters (IPMI v2.0 22.14a)
+//!
+//! 字符串参数按 16 字节分块存放：块 0 依次为编码、总长度和前 14 字节，
+//! 后续每块 16 字节。
+
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
+use clap::ValueEnum;
+
+pub const IPMI_SET_SYS_INFO: u8 = 0x58;
+pub const IPMI_GET_SYS_INFO: u8 = 0x59;
+
+// 参数 0: Set In Progress
+const SYSINFO_PARAM_SET_STATE: u8 = 0x00;
+const SYSINFO_SET_COMPLETE: u8 = 0x00;
+const SYSINFO_SET_IN_PROGRESS: u8 = 0x01;
+
+// 字符串编码 (块 0 的第一个字节)
+const SYSINFO_ENC_ASCII: u8 = 0x00;
+const SYSINFO_ENC_UTF8: u8 = 0x01;
+const SYSINFO_ENC_UNICODE: u8 = 0x02;
+
+const SYSINFO_BLOCK_SIZE: usize = 16;
+const SYSINFO_FIRST_BLOCK_STRING: usize = 14;
+
+const CC_PARAM_NOT_SUPPORTED: u8 = 0x80;
+const CC_SET_IN_PROGRESS: u8 = 0x81;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum SysInfoParam {
+    SystemFwVersion = 1,
+    SystemName = 2,
+    PrimaryOsName = 3,
+    OsName = 4,
+    PresentOsVersion = 5,
+    BmcUrl = 6,
+}
+
+impl SysInfoParam {
+    fn name(&self) -> &'static str {
+        match self {
+            SysInfoParam::SystemFwVersion => "System Firmware Version",
+            SysInfoParam::SystemName => "System Name",
+            SysInfoParam::PrimaryOsName => "Primary Operating System Name",
+            SysInfoParam::OsName => "Operating System Name",
+            SysInfoParam::PresentOsVersion => "Present OS Version",
+            SysInfoParam::BmcUrl => "BMC URL",
+        }
+    }
+}
+
+/// 字符串需要的块数
+fn block_count(len: usize) -> usize {
+    if len <= SYSINFO_FIRST_BLOCK_STRING {
+        1
+    } else {
+        1 + (len - SYSINFO_FIRST_BLOCK_STRING).div_ceil(SYSINFO_BLOCK_SIZE)
+    }
+}
+
+/// 把字符串拆成 Set System Info Parameters 的各个块 (不含参数号与块号)
+fn encode_string(value: &str) -> Result<Vec<[u8; SYSINFO_BLOCK_SIZE]>, String> {
+    let bytes = value.as_bytes();
+    if bytes.len() > u8::MAX as usize {
+        return Err(format!(
+            "String is too long: {} bytes, at most {} allowed",
+            bytes.len(),
+            u8::MAX
+        ));
+    }
+    let encoding = if value.is_ascii() {
+        SYSINFO_ENC_ASCII
+    } else {
+        SYSINFO_ENC_UTF8
+    };
+
+    let mut stream = vec![encoding, bytes.len() as u8];
+    stream.extend_from_slice(bytes);
+    let mut blocks = vec![[0u8; SYSINFO_BLOCK_SIZE]; block_count(bytes.len())];
+    for (block, chunk) in blocks.iter_mut().zip(stream.chunks(SYSINFO_BLOCK_SIZE)) {
+        block[..chunk.len()].copy_from_slice(chunk);
+    }
+    Ok(blocks)
+}
+
+fn decode_string(encoding: u8, bytes: &[u8]) -> String {
+    match encoding & 0x0f {
+        SYSINFO_ENC_UNICODE => {
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        SYSINFO_ENC_UTF8 => String::from_utf8_lossy(bytes).into_owned(),
+        // ASCII+Latin1
+        _ => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+fn sysinfo_error(op: &str, ccode: u8) -> String {
+    match ccode {
+        CC_PARAM_NOT_SUPPORTED => format!("{} System Info Parameters: parameter not supported", op),
+        CC_SET_IN_PROGRESS if op == "Set" => {
+            "Set System Info Parameters: another set is already in progress".to_string()
+        }
+        _ => format!(
+            "{} System Info Parameters command failed: completion code 0x{:02x}",
+            op, ccode
+        ),
+    }
+}
+
+/// 读取一个块，返回参数版本之后的数据
+fn get_sysinfo_block(intf: &mut dyn IpmiIntf, param: u8, set: u8) -> Result<Vec<u8>, String> {
+    let mut data = [0x00, param, set, 0x00];
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_GET_SYS_INFO;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(sysinfo_error("Get", rsp.ccode)),
+        Some(rsp) if rsp.data_len < 1 => {
+            Err("Get System Info Parameters command failed: empty response".to_string())
+        }
+        Some(rsp) => Ok(rsp.data[1..rsp.data_len as usize].to_vec()),
+        None => Err("Get System Info Parameters command failed: no response".to_string()),
+    }
+}
+
+/// 返回完成码，只有无响应时才返回错误
+fn set_sysinfo_raw(intf: &mut dyn IpmiIntf, param: u8, payload: &[u8]) -> Result<u8, String> {
+    let mut data = Vec::with_capacity(payload.len() + 1);
+    data.push(param);
+    data.extend_from_slice(payload);
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_SET_SYS_INFO;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) => Ok(rsp.ccode),
+        None => Err("Set System Info Parameters command failed: no response".to_string()),
+    }
+}
+
+fn set_sysinfo(intf: &mut dyn IpmiIntf, param: u8, payload: &[u8]) -> Result<(), String> {
+    match set_sysinfo_raw(intf, param, payload)? {
+        0 => Ok(()),
+        ccode => Err(sysinfo_error("Set", ccode)),
+    }
+}
+
+pub fn ipmi_mc_getsysinfo(intf: &mut dyn IpmiIntf, param: SysInfoParam) -> Result<String, String> {
+    let param = param as u8;
+    // 块数据前是块号
+    let first = get_sysinfo_block(intf, param, 0)?;
+    if first.len() < 3 {
+        return Err(format!(
+            "Invalid System Info Parameters response length: {}",
+            first.len()
+        ));
+    }
+    let encoding = first[1];
+    let len = first[2] as usize;
+    let mut bytes = first[3..].to_vec();
+    for set in 1..block_count(len) {
+        let block = get_sysinfo_block(intf, param, set as u8)?;
+        bytes.extend_from_slice(block.get(1..).unwrap_or_default());
+    }
+    bytes.truncate(len);
+    Ok(decode_string(encoding, &bytes))
+}
+
+pub fn ipmi_mc_setsysinfo(
+    intf: &mut dyn IpmiIntf,
+    param: SysInfoParam,
+    value: &str,
+) -> Result<(), String> {
+    let blocks = encode_string(value)?;
+
+    // BMC 不支持 set in progress 时直接写入
+    let locked = match set_sysinfo_raw(intf, SYSINFO_PARAM_SET_STATE, &[SYSINFO_SET_IN_PROGRESS])? {
+        0 => true,
+        CC_PARAM_NOT_SUPPORTED => false,
+        ccode => return Err(sysinfo_error("Set", ccode)),
+    };
+
+    let mut result = Ok(());
+    for (set, block) in blocks.iter().enumerate() {
+        let mut payload = vec![set as u8];
+        payload.extend_from_slice(block);
+        result = set_sysinfo(intf, param as u8, &payload);
+        if result.is_err() {
+            break;
+        }
+    }
+
+    if locked {
+        let unlocked = set_sysinfo(intf, SYSINFO_PARAM_SET_STATE, &[SYSINFO_SET_COMPLETE]);
+        result = result.and(unlocked);
+    }
+    result
+}
+
+pub fn format_sysinfo(param: SysInfoParam, value: &str) -> String {
+    format!("{:<30}: {}\n", param.name(), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sysinfo_string_blocks() {
+        let blocks = encode_string("short").unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(&blocks[0][..7], &[0x00, 0x05, b's', b'h', b'o', b'r', b't']);
+
+        // 14 字节刚好放进块 0，15 字节需要第二块
+        assert_eq!(encode_string(&"a".repeat(14)).unwrap().len(), 1);
+        let value = format!("{}é", "b".repeat(30));
+        let blocks = encode_string(&value).unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(&blocks[0][..2], &[SYSINFO_ENC_UTF8, 32]);
+        assert_eq!(&blocks[2][..2], "é".as_bytes());
+
+        let mut bytes: Vec<u8> = blocks.iter().flatten().copied().collect();
+        bytes.drain(..2);
+        bytes.truncate(32);
+        assert_eq!(decode_string(blocks[0][0], &bytes), value);
+
+        assert!(encode_string(&"x".repeat(256)).is_err());
+        assert_eq!(
+            decode_string(SYSINFO_ENC_UNICODE, &[b'O', 0, b'S', 0]),
+            "OS"
+        );
+        assert_eq!(decode_string(SYSINFO_ENC_ASCII, &[0xe9]), "é");
+    }
+}
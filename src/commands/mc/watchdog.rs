@@ -0,0 +1,484 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! BMC Watchdog Timer (IPMI v2.0 第 27 章)
+
+use super::daemon::ipmi_mc_watchdog_daemon;
+use super::{BMC_GET_WATCHDOG_TIMER, BMC_RESET_WATCHDOG_TIMER, BMC_SET_WATCHDOG_TIMER};
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
+use clap::{Args, Subcommand, ValueEnum};
+
+// Timer Use 字节
+pub(super) const WDT_USE_MASK: u8 = 0x07;
+const WDT_USE_RUNNING: u8 = 0x40; // Get: 正在运行; Set: don't stop
+const WDT_USE_DONT_LOG: u8 = 0x80;
+// Timer Actions 字节
+const WDT_ACTION_MASK: u8 = 0x07;
+const WDT_INTR_MASK: u8 = 0x70;
+const WDT_INTR_SHIFT: u8 = 4;
+// Reset Watchdog Timer: 计时器尚未通过 Set 初始化
+const WDT_CC_NOT_INITIALIZED: u8 = 0x80;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum WatchdogCommand {
+    /// Show the current watchdog timer configuration and countdown
+    Get,
+    /// Configure the watchdog timer
+    Set {
+        /// Timer use
+        #[arg(long, value_enum, default_value = "sms-os")]
+        timer_use: TimerUse,
+        /// Do not log an event to the SEL when the timer expires
+        #[arg(long)]
+        dont_log: bool,
+        /// Keep a running timer running instead of stopping it
+        #[arg(long)]
+        dont_stop: bool,
+        /// Action taken when the timer expires
+        #[arg(long, value_enum, default_value = "none")]
+        action: TimeoutAction,
+        /// Interrupt raised when the pre-timeout interval is reached
+        #[arg(long, value_enum, default_value = "none")]
+        pretimeout_interrupt: PretimeoutInterrupt,
+        /// Pre-timeout interval in seconds
+        #[arg(long, default_value_t = 0)]
+        pretimeout: u8,
+        /// Timer expiration flags to clear (comma separated timer uses)
+        #[arg(long = "clear", value_enum, value_delimiter = ',')]
+        clear_flags: Vec<TimerUse>,
+        /// Initial countdown in seconds, 100 ms resolution
+        #[arg(long, default_value = "300", value_parser = parse_countdown)]
+        countdown: u16,
+    },
+    /// Restart the watchdog timer from its initial countdown
+    Reset,
+    /// Stop the watchdog timer
+    Off,
+    /// Arm the watchdog and keep resetting it until SIGTERM (open interface only)
+    Daemon(WatchdogDaemonArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct WatchdogDaemonArgs {
+    /// Watchdog timeout in seconds, 100 ms resolution
+    #[arg(long, default_value = "300", value_parser = parse_countdown)]
+    pub timeout: u16,
+    /// Action taken when the heartbeat stops
+    #[arg(long, value_enum, default_value = "hard-reset")]
+    pub action: TimeoutAction,
+    /// Interrupt raised before the timeout action
+    #[arg(long, value_enum, default_value = "none")]
+    pub pretimeout: PretimeoutInterrupt,
+    /// Pre-timeout interval in seconds
+    #[arg(long, default_value_t = 10)]
+    pub pretimeout_interval: u8,
+    /// Seconds between heartbeats [default: a third of the timeout]
+    #[arg(long, value_parser = parse_countdown)]
+    pub interval: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TimerUse {
+    BiosFrb2 = 1,
+    BiosPost = 2,
+    OsLoad = 3,
+    SmsOs = 4,
+    Oem = 5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TimeoutAction {
+    None = 0,
+    HardReset = 1,
+    PowerDown = 2,
+    PowerCycle = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PretimeoutInterrupt {
+    None = 0,
+    Smi = 1,
+    Nmi = 2,
+    Msg = 3,
+}
+
+/// 命令行倒计时（秒）转换为 100 ms 计数
+fn parse_countdown(s: &str) -> Result<u16, String> {
+    let secs: f64 = s
+        .parse()
+        .map_err(|_| format!("invalid countdown '{}'", s))?;
+    let ticks = (secs * 10.0).round();
+    if !(0.0..=u16::MAX as f64).contains(&ticks) {
+        return Err(format!(
+            "countdown must be between 0 and {:.1} seconds",
+            u16::MAX as f64 / 10.0
+        ));
+    }
+    Ok(ticks as u16)
+}
+
+pub(super) fn timer_use_name(use_: u8) -> &'static str {
+    match use_ & WDT_USE_MASK {
+        1 => "BIOS FRB2",
+        2 => "BIOS/POST",
+        3 => "OS Load",
+        4 => "SMS/OS",
+        5 => "OEM",
+        _ => "Reserved",
+    }
+}
+
+pub(super) fn action_name(action: u8) -> &'static str {
+    match action & WDT_ACTION_MASK {
+        0 => "No action",
+        1 => "Hard Reset",
+        2 => "Power Down",
+        3 => "Power Cycle",
+        _ => "Reserved",
+    }
+}
+
+fn pretimeout_interrupt_name(intr: u8) -> &'static str {
+    match intr {
+        0 => "None",
+        1 => "SMI",
+        2 => "NMI / Diagnostic Interrupt",
+        3 => "Messaging Interrupt",
+        _ => "Reserved",
+    }
+}
+
+/// Get Watchdog Timer 响应
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WatchdogTimer {
+    pub timer_use: u8,
+    pub actions: u8,
+    pub pretimeout: u8,
+    pub expiration_flags: u8,
+    /// 单位 100 ms
+    pub initial_countdown: u16,
+    /// 单位 100 ms
+    pub present_countdown: u16,
+}
+
+impl WatchdogTimer {
+    pub fn from_bytes(d: &[u8]) -> Result<Self, String> {
+        if d.len() < 8 {
+            return Err(format!(
+                "Get Watchdog Timer response too short: {} bytes",
+                d.len()
+            ));
+        }
+        Ok(WatchdogTimer {
+            timer_use: d[0],
+            actions: d[1],
+            pretimeout: d[2],
+            expiration_flags: d[3],
+            initial_countdown: u16::from_le_bytes([d[4], d[5]]),
+            present_countdown: u16::from_le_bytes([d[6], d[7]]),
+        })
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.timer_use & WDT_USE_RUNNING != 0
+    }
+
+    pub fn format(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "Watchdog Timer Use:     {} (0x{:02x})\n",
+            timer_use_name(self.timer_use),
+            self.timer_use
+        ));
+        out.push_str(&format!(
+            "Watchdog Timer Is:      {}\n",
+            if self.is_running() {
+                "Started/Running"
+            } else {
+                "Stopped"
+            }
+        ));
+        out.push_str(&format!(
+            "Watchdog Timer Logging: {}\n",
+            if self.timer_use & WDT_USE_DONT_LOG != 0 {
+                "Off"
+            } else {
+                "On"
+            }
+        ));
+        out.push_str(&format!(
+            "Watchdog Timer Action:  {} (0x{:02x})\n",
+            action_name(self.actions),
+            self.actions & WDT_ACTION_MASK
+        ));
+        out.push_str(&format!(
+            "Pre-timeout interrupt:  {}\n",
+            pretimeout_interrupt_name((self.actions & WDT_INTR_MASK) >> WDT_INTR_SHIFT)
+        ));
+        out.push_str(&format!(
+            "Pre-timeout interval:   {} seconds\n",
+            self.pretimeout
+        ));
+        if self.expiration_flags == 0 {
+            out.push_str("Timer Expiration Flags: None (0x00)\n");
+        } else {
+            out.push_str(&format!(
+                "Timer Expiration Flags: 0x{:02x}\n",
+                self.expiration_flags
+            ));
+            for bit in 1..=5u8 {
+                if self.expiration_flags & (1 << bit) != 0 {
+                    out.push_str(&format!(
+                        "                        * {}\n",
+                        timer_use_name(bit)
+                    ));
+                }
+            }
+        }
+        out.push_str(&format!(
+            "Initial Countdown:      {}.{} sec\n",
+            self.initial_countdown / 10,
+            self.initial_countdown % 10
+        ));
+        out.push_str(&format!(
+            "Present Countdown:      {}.{} sec\n",
+            self.present_countdown / 10,
+            self.present_countdown % 10
+        ));
+        out
+    }
+}
+
+/// Set Watchdog Timer 请求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogSettings {
+    pub timer_use: TimerUse,
+    pub dont_log: bool,
+    pub dont_stop: bool,
+    pub action: TimeoutAction,
+    pub pretimeout_interrupt: PretimeoutInterrupt,
+    pub pretimeout: u8,
+    /// 需要清除的 expiration flags 位掩码
+    pub clear_flags: u8,
+    /// 单位 100 ms
+    pub countdown: u16,
+}
+
+impl WatchdogSettings {
+    pub fn to_bytes(&self) -> [u8; 6] {
+        let mut use_ = self.timer_use as u8;
+        if self.dont_log {
+            use_ |= WDT_USE_DONT_LOG;
+        }
+        if self.dont_stop {
+            use_ |= WDT_USE_RUNNING;
+        }
+        let countdown = self.countdown.to_le_bytes();
+        [
+            use_,
+            ((self.pretimeout_interrupt as u8) << WDT_INTR_SHIFT) | self.action as u8,
+            self.pretimeout,
+            self.clear_flags,
+            countdown[0],
+            countdown[1],
+        ]
+    }
+
+    pub(super) fn validate(&self) -> Result<(), String> {
+        if self.pretimeout_interrupt != PretimeoutInterrupt::None
+            && self.pretimeout as u32 * 10 >= self.countdown as u32
+        {
+            return Err(format!(
+                "Pre-timeout interval ({} sec) must be less than the countdown ({}.{} sec)",
+                self.pretimeout,
+                self.countdown / 10,
+                self.countdown % 10
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn flags_mask(uses: &[TimerUse]) -> u8 {
+    uses.iter().fold(0, |mask, u| mask | (1 << *u as u8))
+}
+
+pub fn ipmi_mc_get_watchdog(intf: &mut dyn IpmiIntf) -> Result<WatchdogTimer, String> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = BMC_GET_WATCHDOG_TIMER;
+    req.msg.data_len = 0;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "Get Watchdog Timer command failed: completion code 0x{:02x}",
+            rsp.ccode
+        )),
+        Some(rsp) => WatchdogTimer::from_bytes(&rsp.data[..rsp.data_len.max(0) as usize]),
+        None => Err("Get Watchdog Timer command failed: no response".to_string()),
+    }
+}
+
+pub fn ipmi_mc_set_watchdog(
+    intf: &mut dyn IpmiIntf,
+    settings: &WatchdogSettings,
+) -> Result<(), String> {
+    settings.validate()?;
+    let mut data = settings.to_bytes();
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = BMC_SET_WATCHDOG_TIMER;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "Set Watchdog Timer command failed: completion code 0x{:02x}",
+            rsp.ccode
+        )),
+        Some(_) => Ok(()),
+        None => Err("Set Watchdog Timer command failed: no response".to_string()),
+    }
+}
+
+pub fn ipmi_mc_reset_watchdog(intf: &mut dyn IpmiIntf) -> Result<(), String> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = BMC_RESET_WATCHDOG_TIMER;
+    req.msg.data_len = 0;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode == WDT_CC_NOT_INITIALIZED => Err(
+            "Reset Watchdog Timer command failed: watchdog timer has not been initialized"
+                .to_string(),
+        ),
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "Reset Watchdog Timer command failed: completion code 0x{:02x}",
+            rsp.ccode
+        )),
+        Some(_) => Ok(()),
+        None => Err("Reset Watchdog Timer command failed: no response".to_string()),
+    }
+}
+
+/// 停止计时器：保留当前的 timer use 与初始倒计时，清除 don't stop 与超时动作
+fn ipmi_mc_watchdog_off(intf: &mut dyn IpmiIntf) -> Result<(), String> {
+    let current = ipmi_mc_get_watchdog(intf)?;
+    let timer_use = match current.timer_use & WDT_USE_MASK {
+        1 => TimerUse::BiosFrb2,
+        2 => TimerUse::BiosPost,
+        3 => TimerUse::OsLoad,
+        5 => TimerUse::Oem,
+        _ => TimerUse::SmsOs,
+    };
+    let settings = WatchdogSettings {
+        timer_use,
+        dont_log: current.timer_use & WDT_USE_DONT_LOG != 0,
+        dont_stop: false,
+        action: TimeoutAction::None,
+        pretimeout_interrupt: PretimeoutInterrupt::None,
+        pretimeout: 0,
+        clear_flags: 0,
+        countdown: current.initial_countdown,
+    };
+    ipmi_mc_set_watchdog(intf, &settings)
+}
+
+pub fn ipmi_mc_watchdog_main(
+    subcmd: WatchdogCommand,
+    intf: &mut dyn IpmiIntf,
+) -> Result<(), String> {
+    match subcmd {
+        WatchdogCommand::Get => {
+            print!("{}", ipmi_mc_get_watchdog(intf)?.format());
+        }
+        WatchdogCommand::Set {
+            timer_use,
+            dont_log,
+            dont_stop,
+            action,
+            pretimeout_interrupt,
+            pretimeout,
+            clear_flags,
+            countdown,
+        } => {
+            let settings = WatchdogSettings {
+                timer_use,
+                dont_log,
+                dont_stop,
+                action,
+                pretimeout_interrupt,
+                pretimeout,
+                clear_flags: flags_mask(&clear_flags),
+                countdown,
+            };
+            ipmi_mc_set_watchdog(intf, &settings)?;
+            println!("Watchdog Timer set");
+        }
+        WatchdogCommand::Reset => {
+            ipmi_mc_reset_watchdog(intf)?;
+            println!("IPMI Watchdog Timer Reset - countdown restarted");
+        }
+        WatchdogCommand::Off => {
+            ipmi_mc_watchdog_off(intf)?;
+            println!("Watchdog Timer Shutoff successful -- timer stopped");
+        }
+        WatchdogCommand::Daemon(args) => ipmi_mc_watchdog_daemon(&args, intf)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_timer_format() {
+        let rsp = [0x44, 0x21, 0x05, 0x10, 0xb8, 0x0b, 0x4a, 0x0b];
+        let wdt = WatchdogTimer::from_bytes(&rsp).unwrap();
+        assert!(wdt.is_running());
+        assert_eq!(wdt.initial_countdown, 3000);
+        assert_eq!(
+            wdt.format(),
+            "Watchdog Timer Use:     SMS/OS (0x44)\n\
+             Watchdog Timer Is:      Started/Running\n\
+             Watchdog Timer Logging: On\n\
+             Watchdog Timer Action:  Hard Reset (0x01)\n\
+             Pre-timeout interrupt:  NMI / Diagnostic Interrupt\n\
+             Pre-timeout interval:   5 seconds\n\
+             Timer Expiration Flags: 0x10\n                        * SMS/OS\n\
+             Initial Countdown:      300.0 sec\n\
+             Present Countdown:      289.0 sec\n"
+        );
+        assert!(WatchdogTimer::from_bytes(&rsp[..7]).is_err());
+    }
+
+    #[test]
+    fn test_watchdog_settings_encoding() {
+        let settings = WatchdogSettings {
+            timer_use: TimerUse::OsLoad,
+            dont_log: true,
+            dont_stop: true,
+            action: TimeoutAction::PowerCycle,
+            pretimeout_interrupt: PretimeoutInterrupt::Smi,
+            pretimeout: 10,
+            clear_flags: flags_mask(&[TimerUse::BiosFrb2, TimerUse::SmsOs]),
+            countdown: parse_countdown("60.5").unwrap(),
+        };
+        assert_eq!(settings.to_bytes(), [0xc3, 0x13, 0x0a, 0x12, 0x5d, 0x02]);
+        assert!(settings.validate().is_ok());
+
+        let too_long = WatchdogSettings {
+            pretimeout: 61,
+            ..settings
+        };
+        assert!(too_long.validate().is_err());
+        assert!(parse_countdown("6553.6").is_err());
+        assert!(parse_countdown("-1").is_err());
+    }
+}
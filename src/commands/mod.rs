@@ -41,14 +41,22 @@ macro_rules! command_error {
 // 子模块声明
 pub mod bootdev;
 pub mod bootparam;
+pub mod channel;
 pub mod chassis;
+pub mod discover;
+pub mod event;
+pub mod fru;
 pub mod identify;
 pub mod lan;
 pub mod mc;
 pub mod poh;
+pub mod policy;
+pub mod raw;
 pub mod restart_cause;
 pub mod sdr;
 pub mod sel;
 pub mod selftest;
 pub mod sensor;
+pub mod session;
+pub mod sol;
 pub mod user;
@@ -0,0 +1,63 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+use crate::error::completion_code_to_string;
+use crate::ipmi::constants::{
+    IPMI_CHASSIS_POLICY_ALWAYS_OFF, IPMI_CHASSIS_POLICY_ALWAYS_ON, IPMI_CHASSIS_POLICY_NO_CHANGE,
+    IPMI_CHASSIS_POLICY_PREVIOUS,
+};
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_CHASSIS};
+
+fn policy_name(policy: u8) -> &'static str {
+    match policy {
+        IPMI_CHASSIS_POLICY_ALWAYS_ON => "always-on",
+        IPMI_CHASSIS_POLICY_ALWAYS_OFF => "always-off",
+        IPMI_CHASSIS_POLICY_PREVIOUS => "previous",
+        _ => "unknown",
+    }
+}
+
+/// 设置电源恢复策略；NO_CHANGE 时仅列出 BMC 支持的策略
+pub fn ipmi_chassis_power_policy(intf: &mut dyn IpmiIntf, policy: u8) -> Result<(), String> {
+    let mut data = [policy];
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_CHASSIS);
+    req.msg.cmd = 0x6;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = 1;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) => {
+            if rsp.ccode != 0 {
+                return Err(format!(
+                    "Power Restore Policy command failed: {}",
+                    completion_code_to_string(rsp.ccode)
+                ));
+            }
+
+            if policy == IPMI_CHASSIS_POLICY_NO_CHANGE {
+                let supported: Vec<&str> = [
+                    IPMI_CHASSIS_POLICY_ALWAYS_OFF,
+                    IPMI_CHASSIS_POLICY_ALWAYS_ON,
+                    IPMI_CHASSIS_POLICY_PREVIOUS,
+                ]
+                .iter()
+                .filter(|&&p| rsp.data_len > 0 && rsp.data[0] & (1 << p) != 0)
+                .map(|&p| policy_name(p))
+                .collect();
+                println!("Supported chassis power policy:  {}", supported.join(" "));
+            } else {
+                println!(
+                    "Set chassis power restore policy to {}",
+                    policy_name(policy)
+                );
+            }
+            Ok(())
+        }
+        None => Err("Error in Power Restore Policy command".to_string()),
+    }
+}
@@ -0,0 +1,236 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::interface::open::open::printbuf;
+use crate::ipmi::intf::{IpmiIntf, IpmiIntfExt};
+use crate::ipmi::ipmi::*;
+use crate::log_info;
+use clap::Args;
+use std::path::PathBuf;
+
+// 与 ipmitool 一致的 raw 数据长度上限
+const RAW_MAX_DATA_LEN: usize = 256;
+
+/// raw 命令参数
+#[derive(Debug, Clone, Args)]
+pub struct RawArgs {
+    /// Read requests from a file, one "<netfn> <cmd> [data ...]" per line
+    #[arg(short = 'i', long = "input", conflicts_with_all = ["netfn", "cmd", "data"])]
+    pub input: Option<PathBuf>,
+    /// Network function, number (0x06) or name (app)
+    #[arg(required_unless_present = "input")]
+    pub netfn: Option<String>,
+    /// Command number
+    #[arg(required_unless_present = "input")]
+    pub cmd: Option<String>,
+    /// Request data bytes
+    pub data: Vec<String>,
+}
+
+/// 解析后的 raw 请求
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRequest {
+    pub netfn: u8,
+    pub cmd: u8,
+    pub data: Vec<u8>,
+}
+
+const NETFN_NAMES: &[(&str, u8)] = &[
+    ("chassis", IPMI_NETFN_CHASSIS),
+    ("bridge", IPMI_NETFN_BRIDGE),
+    ("se", IPMI_NETFN_SE),
+    ("sensor", IPMI_NETFN_SE),
+    ("app", IPMI_NETFN_APP),
+    ("firmware", IPMI_NETFN_FIRMWARE),
+    ("storage", IPMI_NETFN_STORAGE),
+    ("transport", IPMI_NETFN_TRANSPORT),
+    ("picmg", IPMI_NETFN_PICMG),
+    ("dcgrp", IPMI_NETFN_DCGRP),
+    ("oem", IPMI_NETFN_OEM),
+];
+
+/// 按 strtoul(base 0) 的规则解析一个字节：0x 前缀为十六进制，0 前缀为八进制
+pub fn parse_raw_byte(s: &str) -> Result<u8, String> {
+    let parsed = if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u8::from_str_radix(hex, 16)
+    } else if s.len() > 1 && s.starts_with('0') {
+        u8::from_str_radix(&s[1..], 8)
+    } else {
+        s.parse::<u8>()
+    };
+    parsed.map_err(|_| format!("Given value \"{}\" is invalid.", s))
+}
+
+fn parse_netfn(s: &str) -> Result<u8, String> {
+    if let Some((_, netfn)) = NETFN_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+    {
+        return Ok(*netfn);
+    }
+    let netfn = parse_raw_byte(s)?;
+    if netfn > 0x3f {
+        return Err(format!("Invalid NetFn 0x{:02x}", netfn));
+    }
+    Ok(netfn)
+}
+
+impl RawRequest {
+    /// 从 "<netfn> <cmd> [data ...]" 参数构造请求
+    pub fn parse<S: AsRef<str>>(args: &[S]) -> Result<Self, String> {
+        if args.len() < 2 {
+            return Err("Not enough parameters given.".to_string());
+        }
+        let netfn = parse_netfn(args[0].as_ref())?;
+        let cmd = parse_raw_byte(args[1].as_ref())?;
+        if args.len() - 2 > RAW_MAX_DATA_LEN {
+            return Err(format!(
+                "Raw command input limit ({} bytes) exceeded",
+                RAW_MAX_DATA_LEN
+            ));
+        }
+        let data = args[2..]
+            .iter()
+            .map(|s| parse_raw_byte(s.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RawRequest { netfn, cmd, data })
+    }
+}
+
+/// 按 ipmitool 的格式输出响应数据：每字节 " %02x"，每 16 字节换行
+pub fn format_raw_response(data: &[u8]) -> String {
+    let mut output = String::new();
+    for (i, byte) in data.iter().enumerate() {
+        if i % 16 == 0 && i != 0 {
+            output.push('\n');
+        }
+        output.push_str(&format!(" {:02x}", byte));
+    }
+    output.push('\n');
+    output
+}
+
+/// 执行 raw 命令，返回进程退出码 (完成码，无响应时为 1)
+pub fn ipmi_raw_main(args: RawArgs, mut intf: Box<dyn IpmiIntf>) -> CommandResult<i32> {
+    let Some(path) = args.input else {
+        let mut words = vec![args.netfn.unwrap_or_default(), args.cmd.unwrap_or_default()];
+        words.extend(args.data);
+        let request = RawRequest::parse(&words).map_err(IpmiError::InvalidData)?;
+        return Ok(ipmi_raw_send(intf.as_mut(), &request));
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| IpmiError::System(format!("Unable to read file {}: {}", path.display(), e)))?;
+
+    let mut status = 0;
+    for (lineno, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match RawRequest::parse(&words) {
+            Ok(request) => {
+                let rc = ipmi_raw_send(intf.as_mut(), &request);
+                if rc != 0 {
+                    status = rc;
+                }
+            }
+            Err(e) => {
+                eprintln!("{}:{}: {}", path.display(), lineno + 1, e);
+                status = 1;
+            }
+        }
+    }
+    Ok(status)
+}
+
+/// 发送一个 raw 请求并打印响应，返回退出码
+fn ipmi_raw_send(intf: &mut dyn IpmiIntf, request: &RawRequest) -> i32 {
+    let (channel, lun) = intf.with_context(|ctx| (ctx.target_channel(), ctx.target_lun()));
+
+    let mut data = request.data.clone();
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(request.netfn);
+    req.msg.lun_mut(lun);
+    req.msg.cmd = request.cmd;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    log_info!(
+        "RAW REQ (channel=0x{:x} netfn=0x{:x} lun=0x{:x} cmd=0x{:x} data_len={})",
+        channel,
+        request.netfn,
+        lun,
+        request.cmd,
+        data.len()
+    );
+    printbuf(&data, "RAW REQUEST");
+
+    let Some(rsp) = intf.sendrecv(&req) else {
+        eprintln!(
+            "Unable to send RAW command (channel=0x{:x} netfn=0x{:x} lun=0x{:x} cmd=0x{:x})",
+            channel, request.netfn, lun, request.cmd
+        );
+        return 1;
+    };
+
+    if rsp.ccode != 0 {
+        eprintln!(
+            "Unable to send RAW command (channel=0x{:x} netfn=0x{:x} lun=0x{:x} cmd=0x{:x} rsp=0x{:x}): {}",
+            channel,
+            request.netfn,
+            lun,
+            request.cmd,
+            rsp.ccode,
+            IpmiError::CompletionCode(rsp.ccode)
+        );
+        return rsp.ccode as i32;
+    }
+
+    let len = rsp.data_len.max(0) as usize;
+    printbuf(&rsp.data[..len], "RAW RSP");
+    print!("{}", format_raw_response(&rsp.data[..len]));
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_raw_request() {
+        let req = RawRequest::parse(&["0x06", "0x01"]).unwrap();
+        assert_eq!(
+            req,
+            RawRequest {
+                netfn: IPMI_NETFN_APP,
+                cmd: 0x01,
+                data: vec![]
+            }
+        );
+
+        let req = RawRequest::parse(&["storage", "0x23", "10", "0x0a", "012"]).unwrap();
+        assert_eq!(req.netfn, IPMI_NETFN_STORAGE);
+        assert_eq!(req.data, vec![10, 10, 10]);
+
+        assert!(RawRequest::parse(&["0x06"]).is_err());
+        assert!(RawRequest::parse(&["0x40", "0x01"]).is_err());
+        assert!(RawRequest::parse(&["0x06", "0x01", "0x100"]).is_err());
+    }
+
+    #[test]
+    fn test_format_raw_response() {
+        assert_eq!(format_raw_response(&[]), "\n");
+        let data: Vec<u8> = (0..18).collect();
+        assert_eq!(
+            format_raw_response(&data),
+            " 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f\n 10 11\n"
+        );
+    }
+}
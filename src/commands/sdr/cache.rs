@@ -0,0 +1,283 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 本地 SDR 缓存文件 (`sdr dump` 生成, `-S` 读取)
+//!
+//! 文件格式与 ipmitool 相同：依次存放每条记录的 5 字节头部和记录数据。
+//! dump 时另写一个 `<file>.info` 文件，保存当时 Get SDR Repository Info 的响应，
+//! 读取缓存时用其中的记录数和添加/删除时间戳判断缓存是否过期。
+
+use crate::commands::sdr::iter::SdrIterator;
+use crate::commands::sdr::sdr::{SdrRecordHeader, SdrRepoInfoRs, GET_SDR_REPO_INFO};
+use crate::commands::sdr::sdradd::SdrRecord;
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_STORAGE};
+use crate::log_debug;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const SDR_HEADER_LEN: usize = 5;
+
+/// 已加载的缓存文件路径及读取结果 (None 表示文件无效或已过期)
+type LoadedSdrCache = (PathBuf, Option<Arc<Vec<SdrRecord>>>);
+
+// 本次运行已加载过的缓存文件，避免每次创建 SdrIterator 都重新读取和校验
+static LOADED_SDR_CACHE: Mutex<Option<LoadedSdrCache>> = Mutex::new(None);
+
+/// 将 SDR 记录序列化为标准二进制格式
+pub fn sdr_records_to_bytes(records: &[SdrRecord]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for record in records {
+        let len = record.header.length as usize;
+        if record.raw.len() < len {
+            continue;
+        }
+        out.extend_from_slice(&record.header.as_bytes());
+        out.extend_from_slice(&record.raw[..len]);
+    }
+    out
+}
+
+/// 从标准二进制格式解析 SDR 记录
+pub fn sdr_records_from_bytes(data: &[u8]) -> Result<Vec<SdrRecord>, String> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let header = SdrRecordHeader::from_le_bytes(&data[offset..])
+            .map_err(|_| format!("Truncated SDR header at offset {}", offset))?;
+        let start = offset + SDR_HEADER_LEN;
+        let end = start + header.length as usize;
+        if end > data.len() {
+            return Err(format!(
+                "Truncated SDR record 0x{:04x} at offset {}",
+                header.id, offset
+            ));
+        }
+        records.push(SdrRecord {
+            header,
+            raw: data[start..end].to_vec(),
+        });
+        offset = end;
+    }
+    Ok(records)
+}
+
+/// 缓存文件对应的仓库信息文件路径 (`<file>.info`)
+fn sdr_cache_info_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".info");
+    PathBuf::from(name)
+}
+
+fn get_repo_info(intf: &mut dyn IpmiIntf) -> Option<SdrRepoInfoRs> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+    req.msg.cmd = GET_SDR_REPO_INFO;
+
+    let rsp = intf.sendrecv(&req)?;
+    if rsp.ccode != 0 {
+        return None;
+    }
+    SdrRepoInfoRs::from_le_bytes(&rsp.data[..rsp.data_len.max(0) as usize]).ok()
+}
+
+/// 检查缓存是否仍与 BMC 上的 SDR 仓库一致
+///
+/// `saved` 是 dump 时记录的仓库信息，记录数或添加/删除时间戳与当前仓库不同即视为过期。
+fn sdr_cache_is_current(saved: &SdrRepoInfoRs, current: &SdrRepoInfoRs, records: usize) -> bool {
+    if saved.count as usize != records || saved.count != current.count {
+        log_debug!(
+            "SDR cache holds {} records (dumped {}), repository has {}",
+            records,
+            saved.count,
+            current.count
+        );
+        return false;
+    }
+    saved.add_stamp == current.add_stamp && saved.erase_stamp == current.erase_stamp
+}
+
+fn read_sdr_cache(intf: &mut dyn IpmiIntf, path: &Path) -> Option<Vec<SdrRecord>> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            log::error!("Unable to open SDR cache file {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    let records = match sdr_records_from_bytes(&data) {
+        Ok(records) => records,
+        Err(e) => {
+            log::error!("Invalid SDR cache file {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    // 没有仓库信息文件 (如 ipmitool 生成的缓存) 或无法获取仓库信息 (如设备 SDR) 时直接信任缓存
+    let info_path = sdr_cache_info_path(path);
+    let saved = std::fs::read(&info_path)
+        .ok()
+        .and_then(|data| SdrRepoInfoRs::from_le_bytes(&data).ok());
+    match saved {
+        Some(saved) => {
+            if let Some(current) = get_repo_info(intf) {
+                if !sdr_cache_is_current(&saved, &current, records.len()) {
+                    log::warn!(
+                        "SDR cache file {} is out of date, reading SDR from BMC",
+                        path.display()
+                    );
+                    return None;
+                }
+            }
+        }
+        None => log_debug!(
+            "No SDR repository info in {}, skipping cache check",
+            info_path.display()
+        ),
+    }
+
+    log_debug!(
+        "Loaded {} SDR records from cache {}",
+        records.len(),
+        path.display()
+    );
+    Some(records)
+}
+
+/// 读取 `-S` 指定的缓存文件，文件无效或已过期时返回 None
+///
+/// 每次运行只读取并校验一次，之后直接返回第一次的结果。
+pub fn load_sdr_cache(intf: &mut dyn IpmiIntf, path: &Path) -> Option<Arc<Vec<SdrRecord>>> {
+    let mut loaded = LOADED_SDR_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((loaded_path, records)) = loaded.as_ref() {
+        if loaded_path == path {
+            return records.clone();
+        }
+    }
+    let records = read_sdr_cache(intf, path).map(Arc::new);
+    *loaded = Some((path.to_path_buf(), records.clone()));
+    records
+}
+
+/// 将 BMC 上的 SDR 记录以二进制格式写入文件
+pub fn ipmi_sdr_dump(intf: &mut dyn IpmiIntf, path: &Path) -> Result<(), IpmiError> {
+    // dump 总是从 BMC 读取，忽略 -S
+    let saved_cache = intf.context().sdr_cache.take();
+
+    // 先于读取记录获取仓库信息，读取期间仓库有变化时下次使用缓存会判定为过期
+    let info = get_repo_info(intf);
+    let records = match SdrIterator::new(intf, false) {
+        Some(mut iter) => iter.sdrr_get_records(),
+        None => {
+            intf.context().sdr_cache = saved_cache;
+            return Err(IpmiError::Interface(
+                "Unable to open SDR for reading".to_string(),
+            ));
+        }
+    };
+    intf.context().sdr_cache = saved_cache;
+    let records = records.map_err(|e| IpmiError::Interface(e.to_string()))?;
+
+    println!("Dumping Sensor Data Repository to '{}'", path.display());
+    let write_err = |path: &Path, e: std::io::Error| {
+        IpmiError::System(format!(
+            "Unable to write SDR cache file {}: {}",
+            path.display(),
+            e
+        ))
+    };
+    std::fs::write(path, sdr_records_to_bytes(&records)).map_err(|e| write_err(path, e))?;
+
+    let info_path = sdr_cache_info_path(path);
+    match info {
+        Some(info) => {
+            std::fs::write(&info_path, info.to_le_bytes()).map_err(|e| write_err(&info_path, e))
+        }
+        None => {
+            // 不能留下旧的仓库信息，否则会与新写入的记录不匹配
+            let _ = std::fs::remove_file(&info_path);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: u16, record_type: u8, body: &[u8]) -> SdrRecord {
+        SdrRecord {
+            header: SdrRecordHeader {
+                id,
+                version: 0x51,
+                record_type,
+                length: body.len() as u8,
+            },
+            raw: body.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_sdr_cache_round_trip() {
+        let records = vec![
+            record(1, 0x01, &[0x20, 0x00, 0x30]),
+            record(2, 0x12, &[0xaa]),
+        ];
+        let bytes = sdr_records_to_bytes(&records);
+        assert_eq!(
+            bytes,
+            vec![
+                0x01, 0x00, 0x51, 0x01, 0x03, 0x20, 0x00, 0x30, 0x02, 0x00, 0x51, 0x12, 0x01, 0xaa
+            ]
+        );
+
+        let parsed = sdr_records_from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].header.id, 1);
+        assert_eq!(parsed[0].raw, vec![0x20, 0x00, 0x30]);
+        assert_eq!(parsed[1].header.record_type, 0x12);
+
+        assert!(sdr_records_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(sdr_records_from_bytes(&bytes[..3]).is_err());
+    }
+
+    #[test]
+    fn test_sdr_cache_staleness() {
+        let saved = SdrRepoInfoRs {
+            version: 0x51,
+            count: 2,
+            add_stamp: 0x6500_0000,
+            erase_stamp: 0x10,
+            ..Default::default()
+        };
+        let current = SdrRepoInfoRs::from_le_bytes(&saved.to_le_bytes()).unwrap();
+        assert!(sdr_cache_is_current(&saved, &current, 2));
+        // 缓存文件记录数与 dump 时不一致
+        assert!(!sdr_cache_is_current(&saved, &current, 3));
+
+        let added = SdrRepoInfoRs {
+            add_stamp: 0x6500_0001,
+            ..SdrRepoInfoRs::from_le_bytes(&saved.to_le_bytes()).unwrap()
+        };
+        assert!(!sdr_cache_is_current(&saved, &added, 2));
+
+        let erased = SdrRepoInfoRs {
+            count: 1,
+            erase_stamp: 0x20,
+            ..SdrRepoInfoRs::from_le_bytes(&saved.to_le_bytes()).unwrap()
+        };
+        assert!(!sdr_cache_is_current(&saved, &erased, 2));
+    }
+
+    #[test]
+    fn test_sdr_cache_info_path() {
+        assert_eq!(
+            sdr_cache_info_path(Path::new("/tmp/sdr.bin")),
+            PathBuf::from("/tmp/sdr.bin.info")
+        );
+    }
+}
@@ -7,7 +7,9 @@
 #![allow(dead_code)]
 
 use crate::commands::mc::{IpmDevidRsp, BMC_GET_DEVICE_ID};
+use crate::commands::sdr::cache::load_sdr_cache;
 use crate::commands::sdr::sdr::*;
+use crate::commands::sdr::sdradd::SdrRecord;
 use crate::debug4;
 use crate::debug5;
 use crate::ipmi::ipmi::*;
@@ -18,6 +20,7 @@ use crate::{log_debug, log_error};
 use crate::error::IpmiError;
 use crate::ipmi::intf::IpmiIntf;
 use rand::Rng;
+use std::sync::Arc;
 use std::thread;
 
 static mut USE_BUILT_IN: bool = false; /* Uses DeviceSDRs instead of SDRR */
@@ -44,6 +47,9 @@ pub struct SdrIterator<'a> {
     total: i32,
     finished: bool,
     use_builtin: bool,
+    /// 从 -S 缓存文件加载的记录，存在时不再访问 BMC 的 SDR 仓库
+    cached: Option<Arc<Vec<SdrRecord>>>,
+    cache_pos: usize,
 }
 
 impl<'a> SdrIterator<'a> {
@@ -62,8 +68,19 @@ impl<'a> SdrIterator<'a> {
             total: 0,
             finished: false,
             use_builtin,
+            cached: None,
+            cache_pos: 0,
         };
 
+        // 指定了 -S 且缓存有效时直接使用缓存
+        if let Some(path) = iter.intf.context().sdr_cache.clone() {
+            if let Some(records) = load_sdr_cache(iter.intf, &path) {
+                iter.total = records.len() as i32;
+                iter.cached = Some(records);
+                return Some(iter);
+            }
+        }
+
         req.msg.netfn_mut(IPMI_NETFN_APP);
         req.msg.cmd = BMC_GET_DEVICE_ID;
         req.msg.data_len = 0;
@@ -292,6 +309,18 @@ impl<'a> SdrIterator<'a> {
     }
     //迭代器里获取的记录,其实header作为迭代器的参数，就不用传入了。
     pub fn ipmi_sdr_get_record(&mut self, header: &SdrRecordHeader) -> Option<Vec<u8>> {
+        if let Some(records) = &self.cached {
+            // 通常就是 next() 刚返回的那条记录
+            let current = self
+                .cache_pos
+                .checked_sub(1)
+                .and_then(|i| records.get(i))
+                .filter(|r| r.header.id == header.id);
+            return current
+                .or_else(|| records.iter().find(|r| r.header.id == header.id))
+                .map(|r| r.raw.clone());
+        }
+
         let len = header.length;
         if len < 1 {
             return None;
@@ -417,6 +446,15 @@ impl<'a> Iterator for SdrIterator<'a> {
             debug5!("SDR iterator finished");
             return None;
         }
+
+        if let Some(records) = &self.cached {
+            let record = records.get(self.cache_pos);
+            self.cache_pos += 1;
+            if record.is_none() {
+                self.finished = true;
+            }
+            return record.map(|r| r.header);
+        }
         // 获取header的时候会更新reservation_id，
         // ipmi_sdr_get_record的时候要使用reservation_id
         // 因为next要和reservation_id一起用，不能先next，在用ipmi_sdr_get_record读取。
@@ -6,6 +6,7 @@
 
 #![allow(clippy::module_inception)]
 
+pub mod cache;
 pub mod iter;
 pub mod sdr;
 pub mod sdradd;
@@ -23,6 +24,7 @@ use crate::ipmi::ipmi::IpmiRq;
 use crate::{debug2, log_info};
 use clap::Subcommand;
 use std::error::Error;
+use std::path::PathBuf;
 
 #[derive(Subcommand, Debug)]
 pub enum SdrCommand {
@@ -40,6 +42,11 @@ pub enum SdrCommand {
         #[arg(value_enum)]
         record_type: Option<SdrRecordType>,
     },
+    /// Dump raw SDR records to a file for use with -S
+    Dump {
+        /// Output file
+        file: PathBuf,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -128,6 +135,7 @@ pub fn ipmi_sdr_main(
             };
             ipmi_sdr_list(intf, type_filter)
         }
+        SdrCommand::Dump { file } => Ok(cache::ipmi_sdr_dump(intf.as_mut(), &file)?),
     }
 }
 
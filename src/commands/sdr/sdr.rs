@@ -541,8 +541,11 @@ pub struct SdrRepoInfoRs {
 }
 
 impl SdrRepoInfoRs {
+    /// Get SDR Repository Info 响应的线上长度 (结构体有对齐填充，不能用 size_of)
+    pub const WIRE_LEN: usize = 14;
+
     pub fn from_le_bytes(data: &[u8]) -> Result<Self, &'static str> {
-        if data.len() < std::mem::size_of::<Self>() {
+        if data.len() < Self::WIRE_LEN {
             return Err("Input data too short for SdrRepoInfoRs");
         }
         Ok(Self {
@@ -554,6 +557,17 @@ impl SdrRepoInfoRs {
             op_support: data[13],
         })
     }
+
+    pub fn to_le_bytes(&self) -> [u8; Self::WIRE_LEN] {
+        let mut out = [0u8; Self::WIRE_LEN];
+        out[0] = self.version;
+        out[1..3].copy_from_slice(&self.count.to_le_bytes());
+        out[3..5].copy_from_slice(&self.free.to_le_bytes());
+        out[5..9].copy_from_slice(&self.add_stamp.to_le_bytes());
+        out[9..13].copy_from_slice(&self.erase_stamp.to_le_bytes());
+        out[13] = self.op_support;
+        out
+    }
 }
 
 #[derive(Debug)]
@@ -782,6 +796,35 @@ impl SdrRecordFullSensor {
 
         result
     }
+
+    /// 将工程单位数值换算回原始读数 (sdr_convert_sensor_reading 的逆运算)
+    ///
+    /// 离散传感器、M 为 0 或非线性传感器无法换算，返回 None；超出原始值范围时取边界值。
+    pub fn sdr_convert_sensor_value_to_raw(&self, val: f64) -> Option<u8> {
+        let m = to_m(self.mtol) as f64;
+        let b = to_b(self.bacc) as f64;
+        let k1 = to_b_exp(self.bacc) as f64;
+        let k2 = to_r_exp(self.bacc) as f64;
+
+        if m == 0.0 || self.linearization & 0x7f != SDR_SENSOR_L_LINEAR {
+            return None;
+        }
+
+        let result = ((val / 10f64.powf(k2)) - (b * 10f64.powf(k1))) / m;
+        // 与 ipmitool 一致，小数部分 >= .5 时进位
+        let result = (result + 0.5).floor();
+
+        match self.cmn.unit.analog() {
+            0 => Some(result.clamp(0.0, 255.0) as u8),
+            1 => {
+                // 反码：负数为对应正数按位取反
+                let v = result.clamp(-127.0, 127.0) as i8;
+                Some(if v < 0 { !(v.unsigned_abs()) } else { v as u8 })
+            }
+            2 => Some(result.clamp(-128.0, 127.0) as i8 as u8),
+            _ => None,
+        }
+    }
 }
 
 impl SdrRecordCompactSensor {
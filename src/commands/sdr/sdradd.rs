@@ -82,12 +82,7 @@ impl<'a> SdrIterator<'a> {
             let raw_data = self.ipmi_sdr_get_record(&header).unwrap_or_default();
 
             let record = SdrRecord {
-                header: SdrRecordHeader {
-                    id: header.id,
-                    version: 0x51,
-                    record_type: header.record_type,
-                    length: header.length,
-                },
+                header,
                 raw: raw_data, // ✅ 修复：使用实际的SDR数据而不是空Vec
             };
             let _ = record.ipmi_sdr_print_name_from_rawentry();
@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 从事件文件向 SEL 添加记录，用于测试
+//!
+//! 文件每行一个事件，格式与 ipmitool 事件文件相同：
+//! `<evm rev> <sensor type> <sensor num> <event dir|type> <data1> <data2> <data3>`，
+//! `#` 之后为注释。
+
+use crate::commands::raw::parse_raw_byte;
+use crate::commands::sel::sel::IPMI_CMD_ADD_SEL_ENTRY;
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_STORAGE};
+use std::error::Error;
+use std::path::Path;
+
+const SEL_RECORD_TYPE_SYSTEM_EVENT: u8 = 0x02;
+// 系统软件 ID 0x20，Generator ID bit0 = 1 表示软件 ID
+const SEL_GENERATOR_ID_SOFTWARE: u16 = 0x0041;
+pub const SEL_EVENT_FIELDS: usize = 7;
+
+/// 解析事件文件中的一行，得到 7 个事件字节，空行与注释返回 None
+///
+/// `event file` 也用同样的格式发送 Platform Event Message。
+pub fn parse_event_fields(line: &str) -> Result<Option<[u8; SEL_EVENT_FIELDS]>, String> {
+    let line = line.split('#').next().unwrap_or("").trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let fields = line
+        .split_whitespace()
+        .map(parse_raw_byte)
+        .collect::<Result<Vec<_>, _>>()?;
+    let fields: [u8; SEL_EVENT_FIELDS] = fields.as_slice().try_into().map_err(|_| {
+        format!(
+            "Expected {} event bytes, got {}",
+            SEL_EVENT_FIELDS,
+            fields.len()
+        )
+    })?;
+    Ok(Some(fields))
+}
+
+/// 将事件文件中的一行编码为 16 字节 SEL 记录，空行与注释返回 None
+pub fn parse_sel_event_line(line: &str) -> Result<Option<[u8; 16]>, String> {
+    let Some(fields) = parse_event_fields(line)? else {
+        return Ok(None);
+    };
+
+    let gen_id = SEL_GENERATOR_ID_SOFTWARE.to_le_bytes();
+    let mut record = [0u8; 16];
+    // record id 与 timestamp 由 BMC 填写
+    record[2] = SEL_RECORD_TYPE_SYSTEM_EVENT;
+    record[7] = gen_id[0];
+    record[8] = gen_id[1];
+    record[9..16].copy_from_slice(&fields);
+    Ok(Some(record))
+}
+
+fn ipmi_sel_add_entry(intf: &mut dyn IpmiIntf, record: &[u8; 16]) -> Result<u16, Box<dyn Error>> {
+    let mut data = *record;
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+    req.msg.cmd = IPMI_CMD_ADD_SEL_ENTRY;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = match intf.sendrecv(&req) {
+        Some(rsp) => rsp,
+        None => return Err("Add SEL Entry command failed".into()),
+    };
+    if rsp.ccode != 0 {
+        return Err(format!(
+            "Add SEL Entry command failed: {}",
+            IpmiError::CompletionCode(rsp.ccode)
+        )
+        .into());
+    }
+    if rsp.data_len < 2 {
+        return Err("Add SEL Entry command returned short response".into());
+    }
+    Ok(u16::from_le_bytes([rsp.data[0], rsp.data[1]]))
+}
+
+pub fn ipmi_sel_add_from_file(intf: &mut dyn IpmiIntf, path: &Path) -> Result<(), Box<dyn Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read file {}: {}", path.display(), e))?;
+
+    let mut failed = 0;
+    for (lineno, line) in content.lines().enumerate() {
+        let record = match parse_sel_event_line(line) {
+            Ok(Some(record)) => record,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("{}:{}: {}", path.display(), lineno + 1, e);
+                failed += 1;
+                continue;
+            }
+        };
+        match ipmi_sel_add_entry(intf, &record) {
+            Ok(id) => println!("Added SEL entry 0x{:04x}", id),
+            Err(e) => {
+                eprintln!("{}:{}: {}", path.display(), lineno + 1, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("Failed to add {} SEL entries", failed).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sel_event_line() {
+        assert_eq!(parse_sel_event_line("  # comment"), Ok(None));
+        let record = parse_sel_event_line("0x04 0x02 0x30 0x01 0x57 0x00 0x00 # temp")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            record,
+            [0, 0, 0x02, 0, 0, 0, 0, 0x41, 0x00, 0x04, 0x02, 0x30, 0x01, 0x57, 0x00, 0x00]
+        );
+        assert!(parse_sel_event_line("0x04 0x02").is_err());
+        assert!(parse_sel_event_line("0x04 0x02 0x30 0x01 0x57 0x00 zz").is_err());
+    }
+}
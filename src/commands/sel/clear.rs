@@ -0,0 +1,155 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! SEL 清除与删除 (需要先获取 SEL reservation)
+
+use crate::commands::sel::sel::{
+    IPMI_CMD_CLEAR_SEL, IPMI_CMD_DELETE_SEL_ENTRY, IPMI_CMD_RESERVE_SEL,
+};
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IPMI_NETFN_STORAGE};
+use crate::log_debug;
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+// Clear SEL 请求中的 "CLR" 标识
+const SEL_CLEAR_MAGIC: [u8; 3] = [b'C', b'L', b'R'];
+const SEL_ERASE_INITIATE: u8 = 0xAA;
+const SEL_ERASE_GET_STATUS: u8 = 0x00;
+const SEL_ERASE_COMPLETED: u8 = 0x01;
+
+// 擦除状态轮询间隔与次数上限
+const SEL_ERASE_POLL_MS: u64 = 500;
+const SEL_ERASE_POLL_MAX: u32 = 60;
+
+fn sel_request(
+    intf: &mut dyn IpmiIntf,
+    cmd: u8,
+    data: &mut [u8],
+    what: &str,
+) -> Result<IpmiRs, Box<dyn Error>> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+    req.msg.cmd = cmd;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    match intf.sendrecv(&req) {
+        None => Err(format!("{} command failed", what).into()),
+        Some(rsp) if rsp.ccode != 0 => Err(format!(
+            "{} command failed: {}",
+            what,
+            IpmiError::CompletionCode(rsp.ccode)
+        )
+        .into()),
+        Some(rsp) => Ok(rsp),
+    }
+}
+
+/// 获取 SEL reservation ID
+pub fn ipmi_sel_reserve(intf: &mut dyn IpmiIntf) -> Result<u16, Box<dyn Error>> {
+    let rsp = sel_request(intf, IPMI_CMD_RESERVE_SEL, &mut [], "Reserve SEL")?;
+    if rsp.data_len < 2 {
+        return Err("Reserve SEL command returned short response".into());
+    }
+    let id = u16::from_le_bytes([rsp.data[0], rsp.data[1]]);
+    log_debug!("SEL Reservation ID: 0x{:04x}", id);
+    Ok(id)
+}
+
+fn sel_clear_request(reserve_id: u16, action: u8) -> [u8; 6] {
+    let id = reserve_id.to_le_bytes();
+    [
+        id[0],
+        id[1],
+        SEL_CLEAR_MAGIC[0],
+        SEL_CLEAR_MAGIC[1],
+        SEL_CLEAR_MAGIC[2],
+        action,
+    ]
+}
+
+/// 清除 SEL：发起擦除后轮询擦除状态直到完成
+pub fn ipmi_sel_clear(intf: &mut dyn IpmiIntf) -> Result<(), Box<dyn Error>> {
+    let reserve_id = ipmi_sel_reserve(intf)?;
+
+    let mut rq = sel_clear_request(reserve_id, SEL_ERASE_INITIATE);
+    sel_request(intf, IPMI_CMD_CLEAR_SEL, &mut rq, "Clear SEL")?;
+    println!("Clearing SEL.  Please allow a few seconds to erase.");
+
+    for _ in 0..SEL_ERASE_POLL_MAX {
+        let mut rq = sel_clear_request(reserve_id, SEL_ERASE_GET_STATUS);
+        let rsp = sel_request(intf, IPMI_CMD_CLEAR_SEL, &mut rq, "Get SEL erase status")?;
+        if rsp.data_len >= 1 && rsp.data[0] & 0x0f == SEL_ERASE_COMPLETED {
+            log_debug!("SEL erasure completed");
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(SEL_ERASE_POLL_MS));
+    }
+    Err("Timed out waiting for SEL erasure to complete".into())
+}
+
+/// 按 strtoul(base 0) 规则解析 SEL 记录 ID
+fn parse_sel_id(s: &str) -> Result<u16, String> {
+    let parsed = if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u16::from_str_radix(hex, 16)
+    } else {
+        s.parse::<u16>()
+    };
+    parsed.map_err(|_| format!("Given SEL ID '{}' is invalid.", s))
+}
+
+/// 删除一条或多条 SEL 记录，全部处理完后若有失败则返回错误
+pub fn ipmi_sel_delete(intf: &mut dyn IpmiIntf, ids: &[String]) -> Result<(), Box<dyn Error>> {
+    let ids = ids
+        .iter()
+        .map(|s| parse_sel_id(s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut failed = 0;
+    for id in ids {
+        // 每次删除都会使之前的 reservation 失效
+        let reserve_id = ipmi_sel_reserve(intf)?;
+        let r = reserve_id.to_le_bytes();
+        let e = id.to_le_bytes();
+        let mut rq = [r[0], r[1], e[0], e[1]];
+        match sel_request(intf, IPMI_CMD_DELETE_SEL_ENTRY, &mut rq, "Delete SEL Entry") {
+            Ok(_) => println!("Deleted entry {}", id),
+            Err(e) => {
+                eprintln!("Delete SEL entry {} failed: {}", id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("Failed to delete {} SEL entries", failed).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sel_clear_request() {
+        assert_eq!(
+            sel_clear_request(0x1234, SEL_ERASE_INITIATE),
+            [0x34, 0x12, b'C', b'L', b'R', 0xAA]
+        );
+    }
+
+    #[test]
+    fn test_parse_sel_id() {
+        assert_eq!(parse_sel_id("10"), Ok(10));
+        assert_eq!(parse_sel_id("0x1a"), Ok(0x1a));
+        assert!(parse_sel_id("65536").is_err());
+        assert!(parse_sel_id("abc").is_err());
+    }
+}
@@ -145,6 +145,25 @@ impl SelEntry {
         }
     }
 
+    /// 从 16 字节原始记录构造 (sel readraw)，调用方保证长度
+    pub fn from_raw_bytes(raw: &[u8]) -> Self {
+        SelEntry {
+            next_id: 0,
+            record_id: u16::from_le_bytes([raw[0], raw[1]]),
+            record_type: raw[2],
+            data: raw[3..16].try_into().unwrap(),
+        }
+    }
+
+    /// 16 字节原始记录 (sel writeraw)
+    pub fn to_raw_bytes(&self) -> [u8; 16] {
+        let mut raw = [0u8; 16];
+        raw[..2].copy_from_slice(&self.record_id.to_le_bytes());
+        raw[2] = self.record_type;
+        raw[3..].copy_from_slice(&self.data);
+        raw
+    }
+
     //将要输出的数据存储到SelDisplayData中
     pub fn format_output(&self, intf: &mut dyn IpmiIntf, out: &mut SelDisplayData, extend: bool) {
         //let evt = SelEventRecord::default();
@@ -4,26 +4,37 @@
  * SPDX-License-Identifier: GPL-2.0-or-later
  */
 
+pub mod add;
+pub mod clear;
 pub mod define;
 pub mod describe;
 pub mod entry;
 pub mod info;
+pub mod save;
 #[allow(clippy::module_inception)]
 pub mod sel;
 pub mod supermicro;
+pub mod time;
 
 use crate::ipmi::intf::IpmiIntf;
 use clap::Subcommand;
 use std::error::Error;
+use std::path::PathBuf;
 
+use crate::commands::sel::add::ipmi_sel_add_from_file;
+use crate::commands::sel::clear::{ipmi_sel_clear, ipmi_sel_delete};
 use crate::commands::sel::info::ipmi_sel_get_info;
-use crate::commands::sel::sel::ipmi_sel_list;
+use crate::commands::sel::save::ipmi_sel_readraw;
+use crate::commands::sel::sel::{ipmi_sel_list, ipmi_sel_save};
+use crate::commands::sel::time::{ipmi_sel_get_time, ipmi_sel_set_time};
+use crate::ipmi::time::ipmi_timestamp_numeric;
 
 #[derive(Subcommand, Debug)]
 pub enum SelTimeCommand {
     Get,
     Set {
-        time: String, // MM/DD/YYYY HH:MM:SS
+        /// "MM/DD/YYYY HH:MM:SS" (UTC) or "now"
+        time: String,
     },
 }
 // SEL管理子命令
@@ -48,11 +59,36 @@ pub enum SelCommand {
         // elist last <count>
         args: Vec<String>,
     },
-    // Clear,
-    // Time {
-    //     #[command(subcommand)]
-    //     action: SelTimeCommand,
-    // },
+    /// Erase all SEL entries
+    Clear,
+    /// Delete one or more SEL entries by record ID
+    Delete {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Get or set the SEL clock
+    Time {
+        #[command(subcommand)]
+        action: SelTimeCommand,
+    },
+    /// Add events from a file, one "<evm rev> <sensor type> <sensor num> <event type> <data1> <data2> <data3>" per line
+    Add {
+        file: PathBuf,
+    },
+    /// Save SEL entries to a text event file
+    Save {
+        file: PathBuf,
+    },
+    /// Save SEL entries to a file as raw 16-byte records
+    #[command(name = "writeraw")]
+    WriteRaw {
+        file: PathBuf,
+    },
+    /// Decode a raw SEL file captured with writeraw, without contacting a BMC
+    #[command(name = "readraw")]
+    ReadRaw {
+        file: PathBuf,
+    },
 }
 
 pub fn ipmi_sel_main(
@@ -69,6 +105,23 @@ pub fn ipmi_sel_main(
             let (order, count) = parse_sel_list_args(&args)?;
             ipmi_sel_list(intf.as_mut(), order, count, true)
         }
+        SelCommand::Clear => ipmi_sel_clear(intf.as_mut()),
+        SelCommand::Delete { ids } => ipmi_sel_delete(intf.as_mut(), &ids),
+        SelCommand::Time { action } => match action {
+            SelTimeCommand::Get => {
+                println!(
+                    "{}",
+                    ipmi_timestamp_numeric(ipmi_sel_get_time(intf.as_mut())?)
+                );
+                Ok(())
+            }
+            SelTimeCommand::Set { time } => ipmi_sel_set_time(intf.as_mut(), &time),
+        },
+        SelCommand::Add { file } => ipmi_sel_add_from_file(intf.as_mut(), &file),
+        SelCommand::Save { file } => ipmi_sel_save(intf.as_mut(), &file, false),
+        SelCommand::WriteRaw { file } => ipmi_sel_save(intf.as_mut(), &file, true),
+        // 正常情况下 main 在打开接口之前已处理 readraw
+        SelCommand::ReadRaw { file } => ipmi_sel_readraw(intf.context().clone(), &file),
     }
 }
 
@@ -0,0 +1,219 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! SEL 离线存档：`sel save` (文本事件文件)、`sel writeraw` / `sel readraw` (16 字节原始记录)
+
+use crate::commands::mc::BMC_GET_DEVICE_ID;
+use crate::commands::sel::entry::SelEntry;
+use crate::commands::sel::sel::{
+    ipmi_get_event_desc, ipmi_get_sensor_type, print_sel_entry_fast, SelEventRecord, SelType,
+    StandardSpecSelRec,
+};
+use crate::error::{IpmiError, IpmiResult};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// 原始 SEL 记录长度：record id(2) + record type(1) + data(13)
+pub const SEL_RAW_RECORD_LEN: usize = 16;
+
+/// `sel save` / `sel writeraw` 的输出文件
+pub struct SelSaveFile {
+    writer: BufWriter<File>,
+    raw: bool,
+}
+
+impl SelSaveFile {
+    pub fn create(path: &Path, raw: bool) -> Result<Self, Box<dyn Error>> {
+        let file = File::create(path)
+            .map_err(|e| format!("Unable to open file {}: {}", path.display(), e))?;
+        Ok(SelSaveFile {
+            writer: BufWriter::new(file),
+            raw,
+        })
+    }
+
+    pub fn write_entry(
+        &mut self,
+        intf: &mut dyn IpmiIntf,
+        entry: &SelEntry,
+    ) -> std::io::Result<()> {
+        if self.raw {
+            return self.writer.write_all(&entry.to_raw_bytes());
+        }
+        // 事件文件只记录标准系统事件，OEM 记录无法用 `sel add` 重放
+        if entry.record_type >= 0xC0 {
+            return Ok(());
+        }
+        let line = format_sel_event_line(intf, entry);
+        writeln!(self.writer, "{}", line)
+    }
+
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// 按 ipmitool 事件文件格式输出一条记录，前 7 个字节可被 `sel add` 读回
+pub fn format_sel_event_line(intf: &mut dyn IpmiIntf, entry: &SelEntry) -> String {
+    let std = StandardSpecSelRec::from(&entry.data);
+    let rec = SelEventRecord {
+        record_id: entry.record_id,
+        record_type: entry.record_type,
+        sel_type: SelType { standard_type: std },
+    };
+    let desc = ipmi_get_event_desc(intf, &rec).unwrap_or_default();
+    format!(
+        "0x{:02x} 0x{:02x} 0x{:02x} 0x{:02x} 0x{:02x} 0x{:02x} 0x{:02x} # {} #0x{:02x} {}",
+        std.evm_rev,
+        std.sensor_type,
+        std.sensor_num,
+        std.event_flag,
+        std.event_data[0],
+        std.event_data[1],
+        std.event_data[2],
+        ipmi_get_sensor_type(intf, std.sensor_type),
+        std.sensor_num,
+        desc
+    )
+}
+
+/// readraw 使用的离线接口：不访问任何 BMC
+///
+/// 解码流程会查询 Device ID 以确定 OEM，这里返回厂商 ID 为 0 的空响应，
+/// 其余命令均返回 Invalid Command，相关的 OEM 探测按不支持处理。
+struct OfflineIntf {
+    context: IpmiContext,
+}
+
+impl IpmiIntf for OfflineIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        &mut self.context
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn open(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn close(&mut self) {}
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        let (ccode, data_len) =
+            if req.msg.netfn() == IPMI_NETFN_APP && req.msg.cmd == BMC_GET_DEVICE_ID {
+                (IPMI_CC_OK, 15)
+            } else {
+                (IPMI_CC_INV_CMD, 0)
+            };
+        Some(IpmiRs {
+            ccode,
+            data: [0; IPMI_BUF_SIZE],
+            data_len,
+            msg: IpmiRsMsg::default(),
+            session: IpmiSession::default(),
+            payload: IpmiRsPayload::IpmiResponse {
+                rq_addr: 0,
+                netfn: req.msg.netfn() + 1,
+                rq_lun: 0,
+                rs_addr: 0,
+                rq_seq: 0,
+                rs_lun: 0,
+                cmd: req.msg.cmd,
+            },
+        })
+    }
+
+    fn send_sol(&mut self, _payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        None
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        None
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn set_my_addr(&mut self, _addr: u8) -> IpmiResult<()> {
+        Err(IpmiError::NotSupported(
+            "offline SEL decoding has no IPMB address".to_string(),
+        ))
+    }
+}
+
+/// 解析原始 SEL 文件，末尾不完整的记录视为错误
+pub fn sel_entries_from_raw(data: &[u8]) -> Result<Vec<SelEntry>, String> {
+    if data.len() % SEL_RAW_RECORD_LEN != 0 {
+        return Err(format!(
+            "File size {} is not a multiple of {} byte SEL records",
+            data.len(),
+            SEL_RAW_RECORD_LEN
+        ));
+    }
+    Ok(data
+        .chunks_exact(SEL_RAW_RECORD_LEN)
+        .map(SelEntry::from_raw_bytes)
+        .collect())
+}
+
+/// `sel readraw`：在本地解码原始 SEL 文件，不打开 BMC 接口
+pub fn ipmi_sel_readraw(ctx: IpmiContext, path: &Path) -> Result<(), Box<dyn Error>> {
+    let data = std::fs::read(path)
+        .map_err(|e| format!("Unable to read file {}: {}", path.display(), e))?;
+    let entries = sel_entries_from_raw(&data)?;
+
+    let mut intf = OfflineIntf { context: ctx };
+    let sdr_cache = HashMap::new();
+    for entry in &entries {
+        print_sel_entry_fast(&mut intf, entry, false, &sdr_cache);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sel_raw_round_trip() {
+        let raw: Vec<u8> = vec![
+            0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x66, 0x20, 0x00, 0x04, 0x02, 0x30, 0x01, 0x57,
+            0x00, 0x00, //
+            0x02, 0x00, 0xe0, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb,
+            0xcc, 0xdd,
+        ];
+        let entries = sel_entries_from_raw(&raw).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].record_id, 1);
+        assert_eq!(entries[0].record_type, 0x02);
+        assert_eq!(entries[1].record_type, 0xe0);
+        let back: Vec<u8> = entries.iter().flat_map(|e| e.to_raw_bytes()).collect();
+        assert_eq!(back, raw);
+
+        assert!(sel_entries_from_raw(&raw[..20]).is_err());
+    }
+
+    #[test]
+    fn test_format_sel_event_line() {
+        let mut intf = OfflineIntf {
+            context: IpmiContext::new(),
+        };
+        let entry = SelEntry::from_raw_bytes(&[
+            0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x66, 0x20, 0x00, 0x04, 0x01, 0x30, 0x81, 0x57,
+            0x00, 0x00,
+        ]);
+        let line = format_sel_event_line(&mut intf, &entry);
+        assert!(line.starts_with("0x04 0x01 0x30 0x81 0x57 0x00 0x00 # Temperature #0x30"));
+    }
+}
@@ -13,6 +13,7 @@ use crate::commands::sel::describe::*;
 use crate::commands::sel::entry::get_sensor_name_fast;
 use crate::commands::sel::entry::try_next_entry_id;
 use crate::commands::sel::entry::SelEntry;
+use crate::commands::sel::save::SelSaveFile;
 use crate::error::IpmiError;
 use crate::ipmi::intf::*;
 use crate::ipmi::ipmi::IPMI_OEM;
@@ -29,6 +30,7 @@ use crate::ipmi::vita::vita_discover;
 
 use std::error::Error;
 use std::fmt::Write;
+use std::path::Path;
 
 pub const ALL_OFFSETS_SPECIFIED: u8 = 0xff;
 
@@ -352,6 +354,18 @@ pub fn ipmi_sel_list(
     ipmi_sel_savelist_entries(intf, count, None, extend)
 }
 
+/// sel save / sel writeraw：列出全部记录的同时写入文件
+pub fn ipmi_sel_save(
+    intf: &mut dyn IpmiIntf,
+    path: &Path,
+    raw: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut save = SelSaveFile::create(path, raw)?;
+    ipmi_sel_savelist_entries(intf, 0, Some(&mut save), false)?;
+    save.finish()
+        .map_err(|e| format!("Unable to write file {}: {}", path.display(), e).into())
+}
+
 // 性能优化：SEL处理上下文，缓存OEM信息和设备信息
 thread_local! {
     static OEM_CACHE: std::cell::RefCell<Option<IPMI_OEM>> = const { std::cell::RefCell::new(None) };
@@ -388,7 +402,7 @@ pub fn get_cached_device_info(intf: &mut dyn IpmiIntf) -> Option<Vec<u8>> {
 
 // 性能优化：直接输出SEL条目，避免复杂的格式化
 #[inline]
-fn print_sel_entry_fast(
+pub(crate) fn print_sel_entry_fast(
     intf: &mut dyn IpmiIntf,
     entry: &SelEntry,
     extend: bool,
@@ -581,7 +595,7 @@ fn print_sel_entry_fast(
 pub fn ipmi_sel_savelist_entries(
     intf: &mut dyn IpmiIntf,
     count: i32,
-    _savefile: Option<&str>,
+    mut savefile: Option<&mut SelSaveFile>,
     extend: bool,
 ) -> Result<(), Box<dyn Error>> {
     // 性能优化：预先缓存OEM信息，避免每个条目都进行网络调用
@@ -682,31 +696,24 @@ pub fn ipmi_sel_savelist_entries(
         }
     }
 
-    // let mut file: Option<std::fs::File> = if let Some(path) = savefile {
-    //     match std::fs::File::create(path) {
-    //         Ok(f) => Some(f),
-    //         Err(e) => {
-    //             eprintln!("Failed to open file {}: {}", path, e);
-    //             return Err(e.into());
-    //         }
-    //     }
-    // } else {
-    //     None
-    // };
-
     while next_id != 0xffff {
         _curr_id = next_id;
         // 移除调试输出以提高性能
         // eprintln!("SEL Next ID: {:04x}", _curr_id);
 
+        let mut save_result = Ok(());
         next_id = try_next_entry_id(
             intf,
             next_id,
             Some(|intf: &mut dyn IpmiIntf, entry: &SelEntry| {
                 // 性能优化：直接输出，避免复杂的格式化
                 print_sel_entry_fast(intf, entry, extend, &sdr_cache);
+                if let Some(save) = savefile.as_deref_mut() {
+                    save_result = save.write_entry(intf, entry);
+                }
             }),
         )?;
+        save_result.map_err(|e| format!("Unable to write SEL entry: {}", e))?;
         if next_id == 0 {
             break;
         }
@@ -719,15 +726,6 @@ pub fn ipmi_sel_savelist_entries(
 
         //ipmi_sel_print_std_entry(intf, &mut evt);
 
-        // if let Some(ref mut fp) = file {
-        //     if binary {
-        //         let buf = evt.as_bytes();
-        //         fp.write_all(&buf[..16])?;
-        //     } else {
-        //         crate::ipmi_sel_print_event_file(intf, &evt, fp)?;
-        //     }
-        // }
-
         n += 1;
         if n == count {
             break;
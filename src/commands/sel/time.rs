@@ -0,0 +1,101 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! SEL 时钟读取与设置
+
+use crate::commands::sel::sel::{IPMI_CMD_GET_SEL_TIME, IPMI_CMD_SET_SEL_TIME};
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_STORAGE};
+use crate::ipmi::time::{ipmi_timestamp_numeric, ipmi_timestamp_parse};
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn ipmi_sel_get_time(intf: &mut dyn IpmiIntf) -> Result<u32, Box<dyn Error>> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+    req.msg.cmd = IPMI_CMD_GET_SEL_TIME;
+
+    let rsp = match intf.sendrecv(&req) {
+        Some(rsp) => rsp,
+        None => return Err("Get SEL Time command failed".into()),
+    };
+    if rsp.ccode != 0 {
+        return Err(format!(
+            "Get SEL Time command failed: {}",
+            IpmiError::CompletionCode(rsp.ccode)
+        )
+        .into());
+    }
+    if rsp.data_len != 4 {
+        return Err(format!(
+            "Get SEL Time command failed: invalid length {}",
+            rsp.data_len
+        )
+        .into());
+    }
+    Ok(u32::from_le_bytes([
+        rsp.data[0],
+        rsp.data[1],
+        rsp.data[2],
+        rsp.data[3],
+    ]))
+}
+
+/// 解析 `sel time set` 参数："now" 或 "MM/DD/YYYY HH:MM:SS" (UTC)
+pub fn parse_sel_time(value: &str) -> Result<u32, String> {
+    if value.eq_ignore_ascii_case("now") {
+        return SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| e.to_string())
+            .and_then(|d| u32::try_from(d.as_secs()).map_err(|e| e.to_string()));
+    }
+    ipmi_timestamp_parse(value).ok_or_else(|| {
+        format!(
+            "Specified time could not be parsed: \"{}\" (expected \"MM/DD/YYYY HH:MM:SS\" or now)",
+            value
+        )
+    })
+}
+
+pub fn ipmi_sel_set_time(intf: &mut dyn IpmiIntf, value: &str) -> Result<(), Box<dyn Error>> {
+    let stamp = parse_sel_time(value)?;
+    let mut data = stamp.to_le_bytes();
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+    req.msg.cmd = IPMI_CMD_SET_SEL_TIME;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = match intf.sendrecv(&req) {
+        Some(rsp) => rsp,
+        None => return Err("Set SEL Time command failed".into()),
+    };
+    if rsp.ccode != 0 {
+        return Err(format!(
+            "Set SEL Time command failed: {}",
+            IpmiError::CompletionCode(rsp.ccode)
+        )
+        .into());
+    }
+
+    // 与 ipmitool 一致，设置后回读并显示 BMC 时间
+    println!("{}", ipmi_timestamp_numeric(ipmi_sel_get_time(intf)?));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sel_time() {
+        assert_eq!(parse_sel_time("01/01/2025 00:00:00"), Ok(1_735_689_600));
+        assert!(parse_sel_time("now").unwrap() > 1_735_689_600);
+        assert!(parse_sel_time("yesterday").is_err());
+    }
+}
@@ -6,12 +6,14 @@
 
 #[allow(clippy::module_inception)]
 pub mod sensor;
+pub mod thresh;
 
 //use crate::commands::sdr::*;
 use crate::commands::sensor::sensor::ipmi_sensor_get;
 use crate::commands::sensor::sensor::ipmi_sensor_list;
+use crate::commands::sensor::thresh::ipmi_sensor_set_threshold;
 use crate::ipmi::intf::IpmiIntf;
-use clap::{Parser, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 use std::error::Error;
 
 #[derive(Subcommand, Debug)]
@@ -24,25 +26,62 @@ pub enum SensorCommand {
         #[arg(required = true, num_args = 1..)]
         ids: Vec<String>,
     },
-    // /// Manage sensor thresholds
-    // Thresh(ThreshArgs),
+    /// Set sensor thresholds (like `ipmitool sensor thresh`)
+    Thresh(ThreshArgs),
 }
 
-#[derive(Parser)]
+#[derive(Args, Debug)]
 pub struct ThreshArgs {
     /// Sensor ID (name)
     pub id: String,
 
-    #[command(subcommand)]
-    pub subcmd: ThreshSubcommand,
+    /// Threshold to set: unr|ucr|unc|lnc|lcr|lnr, or lower|upper for all three
+    pub threshold: String,
+
+    /// Threshold value, or three values for lower (lnr lcr lnc) / upper (unc ucr unr)
+    #[arg(required = true, num_args = 1..=3, allow_negative_numbers = true)]
+    pub values: Vec<f64>,
+}
+
+impl ThreshArgs {
+    /// 按 ipmitool 语法解析阈值参数
+    pub fn subcmd(&self) -> Result<ThreshSubcommand, String> {
+        let want = |n: usize| {
+            if self.values.len() == n {
+                Ok(())
+            } else {
+                Err(format!(
+                    "sensor thresh {} expects {} value(s), got {}",
+                    self.threshold,
+                    n,
+                    self.values.len()
+                ))
+            }
+        };
+        match self.threshold.to_ascii_lowercase().as_str() {
+            "lower" => want(3).map(|_| ThreshSubcommand::Lower {
+                values: self.values.clone(),
+            }),
+            "upper" => want(3).map(|_| ThreshSubcommand::Upper {
+                values: self.values.clone(),
+            }),
+            other => {
+                let threshold = ThresholdType::from_str(other, true)
+                    .map_err(|_| format!("Invalid threshold identifier {}", self.threshold))?;
+                want(1).map(|_| ThreshSubcommand::Single {
+                    threshold,
+                    setting: self.values[0],
+                })
+            }
+        }
+    }
 }
 
-#[derive(Subcommand)]
+#[derive(Debug)]
 pub enum ThreshSubcommand {
     /// Set individual threshold
     Single {
         /// Threshold type
-        #[arg(value_enum)]
         threshold: ThresholdType,
 
         /// Threshold value
@@ -52,19 +91,17 @@ pub enum ThreshSubcommand {
     /// Set all lower thresholds
     Lower {
         /// Values: lnr lcr lnc
-        #[arg(required = true, num_args = 3)]
         values: Vec<f64>,
     },
 
     /// Set all upper thresholds
     Upper {
         /// Values: unc ucr unr
-        #[arg(required = true, num_args = 3)]
         values: Vec<f64>,
     },
 }
 
-#[derive(clap::ValueEnum, Clone)]
+#[derive(clap::ValueEnum, Clone, Debug)]
 pub enum ThresholdType {
     /// Upper Non-Recoverable
     UNR,
@@ -82,23 +119,14 @@ pub enum ThresholdType {
 
 pub fn ipmi_sensor_main(
     command: SensorCommand,
-    intf: Box<dyn IpmiIntf>,
+    mut intf: Box<dyn IpmiIntf>,
 ) -> Result<(), Box<dyn Error>> {
     match command {
         SensorCommand::List => ipmi_sensor_list(intf),
         SensorCommand::Get { ids } => ipmi_sensor_get(intf, &ids),
-        // SensorCommand::Thresh(args) => {
-        //     match args.subcmd {
-        //         ThreshSubcommand::Single { threshold, setting } => {
-        //             ipmi_sensor_set_threshold_single(intf, &args.id, threshold, setting)
-        //         },
-        //         ThreshSubcommand::Lower { values } => {
-        //             ipmi_sensor_set_threshold_lower(intf, &args.id, &values)
-        //         },
-        //         ThreshSubcommand::Upper { values } => {
-        //             ipmi_sensor_set_threshold_upper(intf, &args.id, &values)
-        //         }
-        //     }
-        // }
+        SensorCommand::Thresh(args) => {
+            let subcmd = args.subcmd()?;
+            ipmi_sensor_set_threshold(intf.as_mut(), &args.id, &subcmd)
+        }
     }
 }
@@ -0,0 +1,351 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! `sensor thresh`：按工程单位设置传感器阈值 (Set Sensor Thresholds)
+
+use crate::commands::sdr::iter::SdrIterator;
+use crate::commands::sdr::sdr::{
+    SdrRecordCompactSensor, SdrRecordFullSensor, SET_SENSOR_THRESHOLDS,
+};
+use crate::commands::sdr::types::{SDR_RECORD_TYPE_COMPACT_SENSOR, SDR_RECORD_TYPE_FULL_SENSOR};
+use crate::commands::sdr::SetFlags;
+use crate::commands::sensor::sensor::*;
+use crate::commands::sensor::{ThreshSubcommand, ThresholdType};
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IPMI_NETFN_SE};
+use std::error::Error;
+
+impl ThresholdType {
+    fn label(&self) -> &'static str {
+        match self {
+            ThresholdType::UNR => "Upper Non-Recoverable",
+            ThresholdType::UCR => "Upper Critical",
+            ThresholdType::UNC => "Upper Non-Critical",
+            ThresholdType::LNC => "Lower Non-Critical",
+            ThresholdType::LCR => "Lower Critical",
+            ThresholdType::LNR => "Lower Non-Recoverable",
+        }
+    }
+
+    /// Set Sensor Thresholds 请求中的设置位
+    fn request_bit(&self) -> u8 {
+        match self {
+            ThresholdType::UNR => UPPER_NON_RECOV_SPECIFIED,
+            ThresholdType::UCR => UPPER_CRIT_SPECIFIED,
+            ThresholdType::UNC => UPPER_NON_CRIT_SPECIFIED,
+            ThresholdType::LNC => LOWER_NON_CRIT_SPECIFIED,
+            ThresholdType::LCR => LOWER_CRIT_SPECIFIED,
+            ThresholdType::LNR => LOWER_NON_RECOV_SPECIFIED,
+        }
+    }
+
+    /// SDR 中对应的可设置标志
+    fn settable_flag(&self) -> SetFlags {
+        match self {
+            ThresholdType::UNR => SetFlags::UNR,
+            ThresholdType::UCR => SetFlags::UCR,
+            ThresholdType::UNC => SetFlags::UNC,
+            ThresholdType::LNC => SetFlags::LNC,
+            ThresholdType::LCR => SetFlags::LCR,
+            ThresholdType::LNR => SetFlags::LNR,
+        }
+    }
+
+    /// 阈值在请求数据 [lnc, lcr, lnr, unc, ucr, unr] 中的位置
+    fn request_index(&self) -> usize {
+        match self {
+            ThresholdType::LNC => 0,
+            ThresholdType::LCR => 1,
+            ThresholdType::LNR => 2,
+            ThresholdType::UNC => 3,
+            ThresholdType::UCR => 4,
+            ThresholdType::UNR => 5,
+        }
+    }
+}
+
+impl ThreshSubcommand {
+    /// 展开为按发送顺序排列的 (阈值, 数值) 列表
+    pub fn settings(&self) -> Vec<(ThresholdType, f64)> {
+        match self {
+            ThreshSubcommand::Single { threshold, setting } => vec![(threshold.clone(), *setting)],
+            ThreshSubcommand::Lower { values } => vec![
+                (ThresholdType::LNR, values[0]),
+                (ThresholdType::LCR, values[1]),
+                (ThresholdType::LNC, values[2]),
+            ],
+            ThreshSubcommand::Upper { values } => vec![
+                (ThresholdType::UNC, values[0]),
+                (ThresholdType::UCR, values[1]),
+                (ThresholdType::UNR, values[2]),
+            ],
+        }
+    }
+}
+
+/// 一个已换算为原始值的阈值设置
+#[derive(Debug)]
+struct RawThreshold {
+    threshold: ThresholdType,
+    requested: f64,
+    raw: u8,
+    actual: f64,
+}
+
+pub(crate) fn sensor_name(id_code: u8, id_string: &[u8; 16]) -> String {
+    let id_len = (id_code & 0x1f) as usize;
+    String::from_utf8_lossy(&id_string[..id_len.min(16)])
+        .trim_matches('\0')
+        .trim()
+        .to_string()
+}
+
+/// 按名称在 SDR 中查找 Full Sensor 记录
+fn find_full_sensor(
+    intf: &mut dyn IpmiIntf,
+    id: &str,
+) -> Result<SdrRecordFullSensor, Box<dyn Error>> {
+    let mut iter = SdrIterator::new(intf, false).ok_or("Unable to open SDR for reading")?;
+    while let Some(header) = iter.next() {
+        if header.record_type != SDR_RECORD_TYPE_FULL_SENSOR
+            && header.record_type != SDR_RECORD_TYPE_COMPACT_SENSOR
+        {
+            continue;
+        }
+        let rec = match iter.ipmi_sdr_get_record(&header) {
+            Some(r) => r,
+            None => continue,
+        };
+        if header.record_type == SDR_RECORD_TYPE_COMPACT_SENSOR {
+            if let Ok(compact) = SdrRecordCompactSensor::from_le_bytes(&rec) {
+                if sensor_name(compact.id_code, &compact.id_string).eq_ignore_ascii_case(id) {
+                    // Compact SDR 没有换算因子，无法由工程单位得到原始值
+                    return Err(format!(
+                        "Invalid sensor type 0x{:02x}: thresholds can only be set on full sensor records",
+                        header.record_type
+                    )
+                    .into());
+                }
+            }
+            continue;
+        }
+        if let Ok(full) = SdrRecordFullSensor::from_le_bytes(&rec) {
+            if sensor_name(full.id_code, &full.id_string).eq_ignore_ascii_case(id) {
+                return Ok(full);
+            }
+        }
+    }
+    Err(format!("Sensor data record \"{}\" not found!", id).into())
+}
+
+/// 校验可设置位并换算所有阈值，任何一项失败都不发送请求
+fn convert_thresholds(
+    sensor: &SdrRecordFullSensor,
+    settings: &[(ThresholdType, f64)],
+) -> Result<Vec<RawThreshold>, String> {
+    if !sensor.cmn.is_threshold_sensor() || sensor.cmn.are_discrete() {
+        return Err("Sensor is not an analog threshold sensor".to_string());
+    }
+    let settable = sensor.cmn.mask.threshold.set();
+    settings
+        .iter()
+        .map(|(threshold, value)| {
+            if !settable.contains(threshold.settable_flag()) {
+                return Err(format!("{} threshold is not settable", threshold.label()));
+            }
+            let raw = sensor
+                .sdr_convert_sensor_value_to_raw(*value)
+                .ok_or_else(|| {
+                    format!(
+                        "Unable to convert {:.3} to a raw {} threshold",
+                        value,
+                        threshold.label()
+                    )
+                })?;
+            Ok(RawThreshold {
+                threshold: threshold.clone(),
+                requested: *value,
+                raw,
+                actual: sensor.sdr_convert_sensor_reading(raw),
+            })
+        })
+        .collect()
+}
+
+/// 发送 Set Sensor Thresholds，需要时桥接到传感器所属控制器
+pub fn ipmi_sensor_set_sensor_thresholds(
+    intf: &mut dyn IpmiIntf,
+    sensor: &SdrRecordFullSensor,
+    threshold: &ThresholdType,
+    raw: u8,
+) -> Option<IpmiRs> {
+    let keys = &sensor.cmn.keys;
+    let mut bridged_request = false;
+    let mut save_addr = 0;
+    let mut save_channel = 0;
+
+    if intf
+        .context()
+        .bridge_to_sensor(keys.owner_id, keys.channel())
+    {
+        bridged_request = true;
+        save_addr = intf.context().target_addr();
+        save_channel = intf.context().target_channel();
+        intf.context().set_target_addr(keys.owner_id as u32);
+        intf.context().set_target_channel(keys.channel());
+    }
+
+    // sensor number, set mask, lnc, lcr, lnr, unc, ucr, unr
+    let mut data = [0u8; 8];
+    data[0] = keys.sensor_num;
+    data[1] = threshold.request_bit();
+    data[2 + threshold.request_index()] = raw;
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_SE);
+    req.msg.lun_mut(keys.lun());
+    req.msg.cmd = SET_SENSOR_THRESHOLDS;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf.sendrecv(&req);
+
+    if bridged_request {
+        intf.context().set_target_addr(save_addr);
+        intf.context().set_target_channel(save_channel);
+    }
+
+    rsp
+}
+
+pub fn ipmi_sensor_set_threshold(
+    intf: &mut dyn IpmiIntf,
+    id: &str,
+    subcmd: &ThreshSubcommand,
+) -> Result<(), Box<dyn Error>> {
+    println!("Locating sensor record '{}'...", id);
+    let sensor = find_full_sensor(intf, id)?;
+    let name = sensor_name(sensor.id_code, &sensor.id_string);
+    let thresholds = convert_thresholds(&sensor, &subcmd.settings())?;
+
+    // 与 ipmitool 一致，lower/upper 逐个阈值发送
+    for t in &thresholds {
+        println!(
+            "Setting sensor \"{}\" {} threshold to {:.3}",
+            name,
+            t.threshold.label(),
+            t.actual
+        );
+        let error = t.actual - t.requested;
+        if error.abs() >= 0.0005 {
+            println!(
+                "  (requested {:.3}, raw 0x{:02x}, rounding error {:+.3})",
+                t.requested, t.raw, error
+            );
+        }
+
+        match ipmi_sensor_set_sensor_thresholds(intf, &sensor, &t.threshold, t.raw) {
+            None => return Err("Error setting threshold".into()),
+            Some(rsp) if rsp.ccode != 0 => {
+                return Err(format!(
+                    "Error setting threshold: {}",
+                    IpmiError::CompletionCode(rsp.ccode)
+                )
+                .into())
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::sensor::ThreshArgs;
+
+    /// 构造 Full Sensor 记录：M=2, B=0, Rexp=-1，即 reading = raw * 0.2
+    fn full_sensor(analog: u8, settable: u16) -> SdrRecordFullSensor {
+        let mut rec = [0u8; 51];
+        rec[2] = 0x30; // sensor number
+        rec[8] = 0x01; // event type: threshold
+        let set = settable.to_le_bytes();
+        rec[13] = set[0];
+        rec[14] = set[1];
+        rec[15] = analog << 6;
+        rec[19] = 2; // M
+        rec[24] = 0xf0; // Rexp = -1, Bexp = 0
+        rec[46] = 0xc4; // 8-bit ASCII, 4 bytes
+        rec[47..51].copy_from_slice(b"Temp");
+        SdrRecordFullSensor::from_le_bytes(&rec).unwrap()
+    }
+
+    #[test]
+    fn test_convert_value_to_raw() {
+        let sensor = full_sensor(0, 0);
+        assert_eq!(sensor.sdr_convert_sensor_value_to_raw(20.0), Some(100));
+        assert_eq!(sensor.sdr_convert_sensor_value_to_raw(20.05), Some(100));
+        assert_eq!(sensor.sdr_convert_sensor_value_to_raw(20.15), Some(101));
+        assert_eq!(sensor.sdr_convert_sensor_value_to_raw(100.0), Some(255));
+        assert_eq!(sensor.sdr_convert_sensor_value_to_raw(-5.0), Some(0));
+
+        let signed = full_sensor(2, 0);
+        assert_eq!(signed.sdr_convert_sensor_value_to_raw(-1.0), Some(0xfb));
+        assert_eq!(signed.sdr_convert_sensor_reading(0xfb), -1.0);
+
+        let ones = full_sensor(1, 0);
+        let raw = ones.sdr_convert_sensor_value_to_raw(-1.0).unwrap();
+        assert_eq!(raw, 0xfa);
+        assert_eq!(ones.sdr_convert_sensor_reading(raw), -1.0);
+    }
+
+    #[test]
+    fn test_convert_thresholds() {
+        let settable = (SetFlags::UNC | SetFlags::UCR | SetFlags::UNR).bits();
+        let sensor = full_sensor(0, settable);
+
+        let upper = ThreshSubcommand::Upper {
+            values: vec![10.0, 15.25, 20.0],
+        };
+        let raw = convert_thresholds(&sensor, &upper.settings()).unwrap();
+        assert_eq!(
+            raw.iter().map(|t| t.raw).collect::<Vec<_>>(),
+            vec![50, 76, 100]
+        );
+        assert!((raw[1].actual - 15.2).abs() < 1e-9);
+
+        let lower = ThreshSubcommand::Single {
+            threshold: ThresholdType::LCR,
+            setting: 5.0,
+        };
+        let err = convert_thresholds(&sensor, &lower.settings()).unwrap_err();
+        assert_eq!(err, "Lower Critical threshold is not settable");
+    }
+
+    #[test]
+    fn test_thresh_args() {
+        let args = |threshold: &str, values: &[f64]| ThreshArgs {
+            id: "Temp".to_string(),
+            threshold: threshold.to_string(),
+            values: values.to_vec(),
+        };
+        assert!(matches!(
+            args("UCR", &[90.0]).subcmd(),
+            Ok(ThreshSubcommand::Single {
+                threshold: ThresholdType::UCR,
+                ..
+            })
+        ));
+        assert!(matches!(
+            args("lower", &[1.0, 2.0, 3.0]).subcmd(),
+            Ok(ThreshSubcommand::Lower { .. })
+        ));
+        assert!(args("upper", &[1.0]).subcmd().is_err());
+        assert!(args("ucr", &[1.0, 2.0]).subcmd().is_err());
+        assert!(args("foo", &[1.0]).subcmd().is_err());
+    }
+}
@@ -0,0 +1,498 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! `session`：查看 BMC 上的活动会话，按会话 ID 或句柄关闭会话
+//!
+//! 参照 ipmitool ipmi_session.c。Get Session Info 只返回会话句柄，
+//! 不返回会话 ID，因此关闭他人会话时通常使用 `--close-handle`。
+
+use crate::commands::user::{ipmi_get_user_name, UserName};
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
+use crate::ipmi::strings::privlvl_to_str;
+use clap::{Args, Subcommand};
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+
+pub const IPMI_CLOSE_SESSION: u8 = 0x3c;
+pub const IPMI_GET_SESSION_INFO: u8 = 0x3d;
+
+// Get Session Info 请求字节 1：0 为当前会话，N 为第 N 个活动会话
+const SESSION_INDEX_CURRENT: u8 = 0x00;
+const SESSION_INDEX_HANDLE: u8 = 0xfe;
+const SESSION_INDEX_ID: u8 = 0xff;
+
+const CC_INV_DATA_FIELD_IN_REQ: u8 = 0xcc;
+const CC_INVALID_SESSION_ID: u8 = 0x87;
+const CC_INVALID_SESSION_HANDLE: u8 = 0x88;
+
+/// 活动会话的固定部分长度，LAN 通道之后还有 IP/MAC/端口 12 字节
+const SESSION_INFO_ACTIVE_LEN: usize = 6;
+const SESSION_INFO_LAN_LEN: usize = SESSION_INFO_ACTIVE_LEN + 12;
+
+#[derive(Debug, Clone, Args)]
+#[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+pub struct SessionArgs {
+    #[command(subcommand)]
+    pub subcmd: Option<SessionCommand>,
+
+    /// Close the session with this session ID
+    #[arg(long, value_name = "ID", value_parser = parse_session_id)]
+    pub close: Option<u32>,
+
+    /// Close the session with this session handle
+    #[arg(
+        long,
+        value_name = "HANDLE",
+        value_parser = parse_session_handle,
+        conflicts_with = "close"
+    )]
+    pub close_handle: Option<u8>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum SessionCommand {
+    /// Show session information
+    Info {
+        #[command(subcommand)]
+        target: SessionInfoTarget,
+    },
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum SessionInfoTarget {
+    /// Show the session this command runs in
+    Active,
+    /// List every active session
+    All,
+    /// Show every active session in detail
+    Detail,
+    /// Look up a session by session ID
+    Id {
+        #[arg(value_parser = parse_session_id)]
+        id: u32,
+    },
+    /// Look up a session by session handle
+    Handle {
+        #[arg(value_parser = parse_session_handle)]
+        handle: u8,
+    },
+}
+
+fn parse_session_id(s: &str) -> Result<u32, String> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => s.parse::<u32>(),
+    }
+    .map_err(|_| format!("Invalid session ID: '{}'", s))
+}
+
+fn parse_session_handle(s: &str) -> Result<u8, String> {
+    let handle = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => s.parse::<u8>(),
+    }
+    .map_err(|_| format!("Invalid session handle: '{}'", s))?;
+    if handle == 0 {
+        return Err("Invalid session handle: 0".to_string());
+    }
+    Ok(handle)
+}
+
+/// LAN 通道会话的远程控制台地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanConsole {
+    pub ip: Ipv4Addr,
+    pub mac: [u8; 6],
+    pub port: u16,
+}
+
+impl LanConsole {
+    fn mac_string(&self) -> String {
+        let mut out = String::with_capacity(17);
+        for (i, b) in self.mac.iter().enumerate() {
+            if i > 0 {
+                out.push(':');
+            }
+            out.push_str(&format!("{:02x}", b));
+        }
+        out
+    }
+}
+
+/// Get Session Info 响应
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    /// 0 表示该槽位没有活动会话，此时只有槽位数和活动会话数有效
+    pub handle: u8,
+    pub slot_count: u8,
+    pub active_count: u8,
+    pub user_id: u8,
+    pub privilege: u8,
+    /// 0: IPMI v1.5, 1: IPMI v2.0/RMCP+
+    pub protocol: u8,
+    pub channel: u8,
+    pub console: Option<LanConsole>,
+}
+
+impl SessionInfo {
+    pub fn from_response(data: &[u8]) -> CommandResult<Self> {
+        if data.len() < 3 {
+            return Err(IpmiError::InvalidData(format!(
+                "Invalid Get Session Info response length: {}",
+                data.len()
+            )));
+        }
+        let mut info = SessionInfo {
+            handle: data[0],
+            slot_count: data[1] & 0x3f,
+            active_count: data[2] & 0x3f,
+            user_id: 0,
+            privilege: 0,
+            protocol: 0,
+            channel: 0,
+            console: None,
+        };
+        if info.handle == 0 || data.len() < SESSION_INFO_ACTIVE_LEN {
+            info.handle = 0;
+            return Ok(info);
+        }
+        info.user_id = data[3] & 0x3f;
+        info.privilege = data[4] & 0x0f;
+        info.protocol = data[5] >> 4;
+        info.channel = data[5] & 0x0f;
+        // IP、MAC 高字节在前，端口按网络字节序 (与 ipmitool 一致)
+        if data.len() >= SESSION_INFO_LAN_LEN {
+            let d = &data[SESSION_INFO_ACTIVE_LEN..];
+            let mut mac = [0u8; 6];
+            mac.copy_from_slice(&d[4..10]);
+            info.console = Some(LanConsole {
+                ip: Ipv4Addr::new(d[0], d[1], d[2], d[3]),
+                mac,
+                port: u16::from_be_bytes([d[10], d[11]]),
+            });
+        }
+        Ok(info)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.handle != 0
+    }
+
+    fn session_type(&self) -> &'static str {
+        match self.protocol {
+            0 => "IPMIv1.5",
+            1 => "IPMIv2/RMCP+",
+            _ => "unknown",
+        }
+    }
+
+    /// 逐项输出，未激活的槽位只输出计数
+    pub fn format_detail(&self, user_name: &str) -> String {
+        let mut out = format!("session handle                : {}\n", self.handle);
+        out.push_str(&format!(
+            "slot count                    : {}\n",
+            self.slot_count
+        ));
+        out.push_str(&format!(
+            "active sessions               : {}\n",
+            self.active_count
+        ));
+        if !self.is_active() {
+            return out;
+        }
+        out.push_str(&format!(
+            "user id                       : {}\n",
+            self.user_id
+        ));
+        out.push_str(&format!("user name                     : {}\n", user_name));
+        out.push_str(&format!(
+            "privilege level               : {}\n",
+            privlvl_to_str(self.privilege)
+        ));
+        out.push_str(&format!(
+            "session type                  : {}\n",
+            self.session_type()
+        ));
+        out.push_str(&format!(
+            "channel number                : 0x{:02x}\n",
+            self.channel
+        ));
+        if let Some(console) = &self.console {
+            out.push_str(&format!("console ip                    : {}\n", console.ip));
+            out.push_str(&format!(
+                "console mac                   : {}\n",
+                console.mac_string()
+            ));
+            out.push_str(&format!(
+                "console port                  : {}\n",
+                console.port
+            ));
+        }
+        out
+    }
+
+    /// `session info all` 的一行
+    pub fn format_row(&self, user_name: &str) -> String {
+        let console = self
+            .console
+            .as_ref()
+            .map_or_else(|| "N/A".to_string(), |c| format!("{}:{}", c.ip, c.port));
+        format!(
+            "{:<6} {:<4} {:<16} {:<13} {:<12} {:<7} {}\n",
+            self.handle,
+            self.user_id,
+            user_name,
+            privlvl_to_str(self.privilege),
+            self.session_type(),
+            format!("0x{:02x}", self.channel),
+            console
+        )
+    }
+
+    /// handle,user id,user name,privilege,type,channel,ip,mac,port
+    pub fn format_csv(&self, user_name: &str) -> String {
+        let (ip, mac, port) = match &self.console {
+            Some(c) => (c.ip.to_string(), c.mac_string(), c.port.to_string()),
+            None => Default::default(),
+        };
+        format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            self.handle,
+            self.user_id,
+            user_name,
+            privlvl_to_str(self.privilege),
+            self.session_type(),
+            self.channel,
+            ip,
+            mac,
+            port
+        )
+    }
+}
+
+fn session_request(intf: &mut dyn IpmiIntf, cmd: u8, data: &mut [u8]) -> Result<Vec<u8>, u8> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = cmd;
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    match intf.sendrecv(&req) {
+        Some(rsp) if rsp.ccode != 0 => Err(rsp.ccode),
+        Some(rsp) => Ok(rsp.data[..rsp.data_len.max(0) as usize].to_vec()),
+        None => Err(0),
+    }
+}
+
+fn command_failed(name: &str, ccode: u8) -> IpmiError {
+    if ccode == 0 {
+        IpmiError::Interface(format!("{} command failed", name))
+    } else {
+        IpmiError::Interface(format!(
+            "{} command failed: {}",
+            name,
+            IpmiError::CompletionCode(ccode)
+        ))
+    }
+}
+
+fn get_session_info(intf: &mut dyn IpmiIntf, request: &[u8]) -> CommandResult<SessionInfo> {
+    let mut data = request.to_vec();
+    match session_request(intf, IPMI_GET_SESSION_INFO, &mut data) {
+        Ok(rsp) => SessionInfo::from_response(&rsp),
+        // 按 ID / 句柄查询时 0xCC 表示没有这个会话
+        Err(CC_INV_DATA_FIELD_IN_REQ) if request[0] == SESSION_INDEX_ID => {
+            let id = u32::from_le_bytes([request[1], request[2], request[3], request[4]]);
+            Err(IpmiError::InvalidData(format!(
+                "Session ID 0x{:08x} not found",
+                id
+            )))
+        }
+        Err(CC_INV_DATA_FIELD_IN_REQ) if request[0] == SESSION_INDEX_HANDLE => Err(
+            IpmiError::InvalidData(format!("Session handle {} not found", request[1])),
+        ),
+        Err(cc) => Err(command_failed("Get Session Info", cc)),
+    }
+}
+
+/// 依次查询第 1..N 个活动会话
+fn get_active_sessions(intf: &mut dyn IpmiIntf) -> CommandResult<Vec<SessionInfo>> {
+    let current = get_session_info(intf, &[SESSION_INDEX_CURRENT])?;
+    let mut sessions = Vec::new();
+    for index in 1..=current.slot_count {
+        if sessions.len() >= current.active_count as usize {
+            break;
+        }
+        let info = get_session_info(intf, &[index])?;
+        if info.is_active() {
+            sessions.push(info);
+        }
+    }
+    Ok(sessions)
+}
+
+/// 按用户 ID 查询用户名，结果缓存；未命名的用户显示为空
+fn user_name(intf: &mut dyn IpmiIntf, cache: &mut HashMap<u8, String>, uid: u8) -> String {
+    if uid == 0 {
+        return String::new();
+    }
+    cache
+        .entry(uid)
+        .or_insert_with(|| {
+            let mut name = UserName {
+                user_id: uid,
+                ..Default::default()
+            };
+            ipmi_get_user_name(intf, &mut name)
+                .map(|_| name.name_as_string())
+                .unwrap_or_default()
+        })
+        .clone()
+}
+
+fn print_sessions(intf: &mut dyn IpmiIntf, sessions: &[SessionInfo], table: bool) {
+    let csv = intf.context().output_config().csv;
+    let mut names = HashMap::new();
+    if table && !csv {
+        println!(
+            "{:<6} {:<4} {:<16} {:<13} {:<12} {:<7} Console",
+            "Handle", "UID", "User Name", "Privilege", "Type", "Channel"
+        );
+    }
+    for (i, info) in sessions.iter().enumerate() {
+        let name = user_name(intf, &mut names, info.user_id);
+        if csv {
+            print!("{}", info.format_csv(&name));
+        } else if table {
+            print!("{}", info.format_row(&name));
+        } else {
+            if i > 0 {
+                println!();
+            }
+            print!("{}", info.format_detail(&name));
+        }
+    }
+}
+
+fn ipmi_session_info(intf: &mut dyn IpmiIntf, target: SessionInfoTarget) -> CommandResult {
+    let (sessions, table) = match target {
+        SessionInfoTarget::Active => (
+            vec![get_session_info(intf, &[SESSION_INDEX_CURRENT])?],
+            false,
+        ),
+        SessionInfoTarget::All => (get_active_sessions(intf)?, true),
+        SessionInfoTarget::Detail => (get_active_sessions(intf)?, false),
+        SessionInfoTarget::Id { id } => {
+            let mut request = vec![SESSION_INDEX_ID];
+            request.extend_from_slice(&id.to_le_bytes());
+            (vec![get_session_info(intf, &request)?], false)
+        }
+        SessionInfoTarget::Handle { handle } => (
+            vec![get_session_info(intf, &[SESSION_INDEX_HANDLE, handle])?],
+            false,
+        ),
+    };
+    print_sessions(intf, &sessions, table);
+    Ok(())
+}
+
+/// 会话 ID 为 0 时由后面的句柄指定要关闭的会话
+fn close_session_request(id: Option<u32>, handle: Option<u8>) -> Vec<u8> {
+    let mut data = id.unwrap_or(0).to_le_bytes().to_vec();
+    if let Some(handle) = handle {
+        data.push(handle);
+    }
+    data
+}
+
+fn ipmi_session_close(
+    intf: &mut dyn IpmiIntf,
+    id: Option<u32>,
+    handle: Option<u8>,
+) -> CommandResult {
+    let target = match (id, handle) {
+        (Some(id), _) => format!("session ID 0x{:08x}", id),
+        (None, Some(handle)) => format!("session handle {}", handle),
+        (None, None) => unreachable!(),
+    };
+    let mut data = close_session_request(id, handle);
+    match session_request(intf, IPMI_CLOSE_SESSION, &mut data) {
+        Ok(_) => {
+            println!("Closed {}", target);
+            Ok(())
+        }
+        Err(CC_INVALID_SESSION_ID) | Err(CC_INVALID_SESSION_HANDLE) => Err(IpmiError::InvalidData(
+            format!("Close Session failed: invalid {}", target),
+        )),
+        Err(cc) => Err(command_failed("Close Session", cc)),
+    }
+}
+
+pub fn ipmi_session_main(args: SessionArgs, mut intf: Box<dyn IpmiIntf>) -> CommandResult {
+    let intf = intf.as_mut();
+    match args.subcmd {
+        Some(SessionCommand::Info { target }) => ipmi_session_info(intf, target),
+        None if args.close.is_some() || args.close_handle.is_some() => {
+            ipmi_session_close(intf, args.close, args.close_handle)
+        }
+        None => Err(IpmiError::InvalidData(
+            "Usage: session info <active|all|detail|id <id>|handle <handle>> | session --close <id> | session --close-handle <handle>"
+                .to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAN_SESSION: [u8; 18] = [
+        0x02, 0x04, 0x02, 0x02, 0x04, 0x01, // 句柄 2，用户 2，ADMINISTRATOR，通道 1
+        192, 168, 1, 20, 0x52, 0x54, 0x00, 0x12, 0x34, 0x56, 0xc3, 0x50,
+    ];
+
+    #[test]
+    fn test_session_info_decode() {
+        let info = SessionInfo::from_response(&LAN_SESSION).unwrap();
+        assert!(info.is_active());
+        assert_eq!((info.slot_count, info.active_count), (4, 2));
+        assert_eq!(
+            info.console,
+            Some(LanConsole {
+                ip: Ipv4Addr::new(192, 168, 1, 20),
+                mac: [0x52, 0x54, 0x00, 0x12, 0x34, 0x56],
+                port: 50000,
+            })
+        );
+        assert_eq!(
+            info.format_csv("admin"),
+            "2,2,admin,ADMINISTRATOR,IPMIv1.5,1,192.168.1.20,52:54:00:12:34:56,50000\n"
+        );
+        let detail = info.format_detail("admin");
+        assert!(detail.contains("privilege level               : ADMINISTRATOR\n"));
+        assert!(detail.ends_with("console port                  : 50000\n"));
+
+        // 空槽位只有计数
+        let empty = SessionInfo::from_response(&[0x00, 0x04, 0x01]).unwrap();
+        assert!(!empty.is_active());
+        assert_eq!(empty.format_detail("").lines().count(), 3);
+        assert!(SessionInfo::from_response(&[0x01]).is_err());
+    }
+
+    #[test]
+    fn test_close_session_request() {
+        assert_eq!(
+            close_session_request(Some(0x12345678), None),
+            vec![0x78, 0x56, 0x34, 0x12]
+        );
+        assert_eq!(close_session_request(None, Some(3)), vec![0, 0, 0, 0, 3]);
+        assert_eq!(parse_session_id("0xdeadbeef"), Ok(0xdeadbeef));
+        assert!(parse_session_handle("0").is_err());
+    }
+}
@@ -0,0 +1,840 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+pub mod terminal;
+
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::ipmi::constants::*;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP, IPMI_NETFN_TRANSPORT, IPMI_PAYLOAD_TYPE_SOL};
+use crate::ipmi::strings::{privlvl_to_str, set_in_progress_to_str};
+use clap::{Subcommand, ValueEnum};
+use terminal::{SolExit, SolKeepalive};
+
+// SOL管理子命令
+#[derive(Debug, Clone, Subcommand)]
+pub enum SolCommand {
+    /// Print SOL configuration parameters
+    Info {
+        /// Channel number (default: current channel)
+        channel: Option<u8>,
+    },
+    /// Start an interactive SOL session (lanplus only)
+    Activate {
+        #[arg(long)]
+        instance: Option<u8>,
+        /// Use SOL packets instead of Get Device ID as session keepalive
+        #[arg(long, conflicts_with = "nokeepalive")]
+        usesolkeepalive: bool,
+        /// Do not send session keepalives
+        #[arg(long)]
+        nokeepalive: bool,
+    },
+    /// Deactivate a SOL payload instance
+    Deactivate {
+        #[arg(long)]
+        instance: Option<u8>,
+    },
+    /// Set a SOL configuration parameter
+    Set {
+        param: SolParam,
+        value: String,
+        /// Channel number (default: current channel)
+        channel: Option<u8>,
+        /// Do not wrap the change in set-in-progress / set-complete
+        #[arg(long)]
+        noguard: bool,
+    },
+    /// Enable, disable or show SOL payload access for a user
+    Payload {
+        action: SolPayloadAction,
+        /// Channel number (default: current channel)
+        channel: Option<u8>,
+        /// User ID
+        #[arg(default_value_t = 1)]
+        userid: u8,
+    },
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum SolParam {
+    SetInProgress,
+    Enabled,
+    ForceEncryption,
+    ForceAuthentication,
+    PrivilegeLevel,
+    CharacterAccumulateLevel,
+    CharacterSendThreshold,
+    RetryCount,
+    RetryInterval,
+    NonVolatileBitRate,
+    VolatileBitRate,
+    /// 同时设置 volatile 与 non-volatile 波特率
+    BaudRate,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum SolPayloadAction {
+    Enable,
+    Disable,
+    Status,
+}
+
+// SOL 配置参数编号
+pub const SOL_PARAMETER_SET_IN_PROGRESS: u8 = 0x00;
+pub const SOL_PARAMETER_SOL_ENABLE: u8 = 0x01;
+pub const SOL_PARAMETER_SOL_AUTHENTICATION: u8 = 0x02;
+pub const SOL_PARAMETER_CHARACTER_INTERVAL: u8 = 0x03;
+pub const SOL_PARAMETER_SOL_RETRY: u8 = 0x04;
+pub const SOL_PARAMETER_SOL_NON_VOLATILE_BIT_RATE: u8 = 0x05;
+pub const SOL_PARAMETER_SOL_VOLATILE_BIT_RATE: u8 = 0x06;
+pub const SOL_PARAMETER_SOL_PAYLOAD_CHANNEL: u8 = 0x07;
+pub const SOL_PARAMETER_SOL_PAYLOAD_PORT: u8 = 0x08;
+
+// Payload 相关命令 (NetFn App)
+pub const IPMI_ACTIVATE_PAYLOAD: u8 = 0x48;
+pub const IPMI_DEACTIVATE_PAYLOAD: u8 = 0x49;
+pub const IPMI_SET_USER_PAYLOAD_ACCESS: u8 = 0x4c;
+pub const IPMI_GET_USER_PAYLOAD_ACCESS: u8 = 0x4d;
+
+const SOL_DEFAULT_CHANNEL: u8 = 0x0E;
+
+const SOL_AUTH_FORCE_ENCRYPTION: u8 = 0x80;
+const SOL_AUTH_FORCE_AUTHENTICATION: u8 = 0x40;
+const SOL_AUTH_PRIVILEGE_MASK: u8 = 0x0F;
+
+// Activate Payload 辅助数据
+const SOL_ACTIVATE_ENCRYPT: u8 = 0x80;
+const SOL_ACTIVATE_AUTHENTICATE: u8 = 0x40;
+const SOL_ACTIVATE_SERIAL_ALERT_DEFERRED: u8 = 0x08;
+
+/// SOL 配置参数值
+#[derive(Debug, Default, Clone)]
+pub struct SolConfig {
+    pub set_in_progress: u8,
+    pub enabled: bool,
+    pub force_encryption: bool,
+    pub force_authentication: bool,
+    pub privilege_level: u8,
+    /// 单位 5ms
+    pub character_accumulate_level: u8,
+    pub character_send_threshold: u8,
+    pub retry_count: u8,
+    /// 单位 10ms
+    pub retry_interval: u8,
+    pub non_volatile_bit_rate: u8,
+    pub volatile_bit_rate: u8,
+    pub payload_channel: u8,
+    pub payload_port: u16,
+}
+
+impl SolConfig {
+    pub fn format(&self, csv: bool) -> String {
+        if csv {
+            return format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                set_in_progress_to_str(self.set_in_progress),
+                self.enabled,
+                self.force_encryption,
+                self.force_authentication,
+                privlvl_to_str(self.privilege_level),
+                self.character_accumulate_level as u32 * 5,
+                self.character_send_threshold,
+                self.retry_count,
+                self.retry_interval as u32 * 10,
+                bit_rate_to_str(self.volatile_bit_rate),
+                bit_rate_to_str(self.non_volatile_bit_rate),
+                self.payload_channel,
+                self.payload_port
+            );
+        }
+
+        let mut output = String::new();
+        output.push_str(&format!(
+            "Set in progress                 : {}\n",
+            set_in_progress_to_str(self.set_in_progress)
+        ));
+        output.push_str(&format!(
+            "Enabled                         : {}\n",
+            self.enabled
+        ));
+        output.push_str(&format!(
+            "Force Encryption                : {}\n",
+            self.force_encryption
+        ));
+        output.push_str(&format!(
+            "Force Authentication            : {}\n",
+            self.force_authentication
+        ));
+        output.push_str(&format!(
+            "Privilege Level                 : {}\n",
+            privlvl_to_str(self.privilege_level)
+        ));
+        output.push_str(&format!(
+            "Character Accumulate Level (ms) : {}\n",
+            self.character_accumulate_level as u32 * 5
+        ));
+        output.push_str(&format!(
+            "Character Send Threshold        : {}\n",
+            self.character_send_threshold
+        ));
+        output.push_str(&format!(
+            "Retry Count                     : {}\n",
+            self.retry_count
+        ));
+        output.push_str(&format!(
+            "Retry Interval (ms)             : {}\n",
+            self.retry_interval as u32 * 10
+        ));
+        output.push_str(&format!(
+            "Volatile Bit Rate (kbps)        : {}\n",
+            bit_rate_to_str(self.volatile_bit_rate)
+        ));
+        output.push_str(&format!(
+            "Non-Volatile Bit Rate (kbps)    : {}\n",
+            bit_rate_to_str(self.non_volatile_bit_rate)
+        ));
+        output.push_str(&format!(
+            "Payload Channel                 : {} (0x{:02x})\n",
+            self.payload_channel, self.payload_channel
+        ));
+        output.push_str(&format!(
+            "Payload Port                    : {}\n",
+            self.payload_port
+        ));
+        output
+    }
+}
+
+/// SOL 波特率编码转换为 kbps 字符串
+pub fn bit_rate_to_str(rate: u8) -> &'static str {
+    match rate & 0x0F {
+        0x00 => "IPMI-Over-Serial-Setting",
+        0x06 => "9.6",
+        0x07 => "19.2",
+        0x08 => "38.4",
+        0x09 => "57.6",
+        0x0A => "115.2",
+        _ => "Unknown",
+    }
+}
+
+/// 解析 kbps 字符串为 SOL 波特率编码
+pub fn parse_bit_rate(value: &str) -> Option<u8> {
+    match value {
+        "serial" => Some(0x00),
+        "9.6" => Some(0x06),
+        "19.2" => Some(0x07),
+        "38.4" => Some(0x08),
+        "57.6" => Some(0x09),
+        "115.2" => Some(0x0A),
+        _ => None,
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_privilege(value: &str) -> Option<u8> {
+    match value {
+        "user" => Some(IPMI_SESSION_PRIV_USER),
+        "operator" => Some(IPMI_SESSION_PRIV_OPERATOR),
+        "admin" => Some(IPMI_SESSION_PRIV_ADMIN),
+        "oem" => Some(IPMI_SESSION_PRIV_OEM),
+        _ => None,
+    }
+}
+
+fn parse_set_in_progress(value: &str) -> Option<u8> {
+    match value {
+        "set-complete" => Some(IPMI_SET_IN_PROGRESS_SET_COMPLETE),
+        "set-in-progress" => Some(IPMI_SET_IN_PROGRESS_IN_PROGRESS),
+        "commit-write" => Some(IPMI_SET_IN_PROGRESS_COMMIT_WRITE),
+        _ => None,
+    }
+}
+
+fn sol_param_name(param: &SolParam) -> &'static str {
+    match param {
+        SolParam::SetInProgress => "set-in-progress",
+        SolParam::Enabled => "enabled",
+        SolParam::ForceEncryption => "force-encryption",
+        SolParam::ForceAuthentication => "force-authentication",
+        SolParam::PrivilegeLevel => "privilege-level",
+        SolParam::CharacterAccumulateLevel => "character-accumulate-level",
+        SolParam::CharacterSendThreshold => "character-send-threshold",
+        SolParam::RetryCount => "retry-count",
+        SolParam::RetryInterval => "retry-interval",
+        SolParam::NonVolatileBitRate => "non-volatile-bit-rate",
+        SolParam::VolatileBitRate => "volatile-bit-rate",
+        SolParam::BaudRate => "baud-rate",
+    }
+}
+
+fn invalid_value(param: &SolParam, value: &str, valid: &str) -> IpmiError {
+    IpmiError::InvalidData(format!(
+        "Invalid value {} for parameter {}, valid values are: {}",
+        value,
+        sol_param_name(param),
+        valid
+    ))
+}
+
+fn parse_u8(param: &SolParam, value: &str, max: u8) -> CommandResult<u8> {
+    match value.parse::<u8>() {
+        Ok(v) if v <= max => Ok(v),
+        _ => Err(invalid_value(param, value, &format!("0-{}", max))),
+    }
+}
+
+pub fn ipmi_sol_main(
+    subcmd: SolCommand,
+    mut intf: Box<dyn IpmiIntf>,
+    escape: char,
+) -> CommandResult {
+    match subcmd {
+        SolCommand::Info { channel } => {
+            let channel = channel.unwrap_or(SOL_DEFAULT_CHANNEL);
+            let config = ipmi_get_sol_info(intf.as_mut(), channel)?;
+            let csv = intf.context().output_config().csv;
+            print!("{}", config.format(csv));
+            Ok(())
+        }
+        SolCommand::Set {
+            param,
+            value,
+            channel,
+            noguard,
+        } => ipmi_sol_set_param(
+            intf.as_mut(),
+            channel.unwrap_or(SOL_DEFAULT_CHANNEL),
+            &param,
+            &value,
+            !noguard,
+        ),
+        SolCommand::Payload {
+            action,
+            channel,
+            userid,
+        } => {
+            let channel = channel.unwrap_or(SOL_DEFAULT_CHANNEL);
+            match action {
+                SolPayloadAction::Enable => {
+                    ipmi_sol_payload_access(intf.as_mut(), channel, userid, true)
+                }
+                SolPayloadAction::Disable => {
+                    ipmi_sol_payload_access(intf.as_mut(), channel, userid, false)
+                }
+                SolPayloadAction::Status => {
+                    ipmi_sol_payload_access_status(intf.as_mut(), channel, userid)
+                }
+            }
+        }
+        SolCommand::Activate {
+            instance,
+            usesolkeepalive,
+            nokeepalive,
+        } => {
+            let keepalive = if nokeepalive {
+                SolKeepalive::None
+            } else if usesolkeepalive {
+                SolKeepalive::Sol
+            } else {
+                SolKeepalive::Session
+            };
+            ipmi_sol_activate(intf.as_mut(), instance.unwrap_or(1), escape, keepalive)
+        }
+        SolCommand::Deactivate { instance } => {
+            ipmi_sol_deactivate(intf.as_mut(), instance.unwrap_or(1))
+        }
+    }
+}
+
+/// 读取 SOL 配置参数，返回去掉参数版本字节后的数据
+fn get_sol_param(intf: &mut dyn IpmiIntf, channel: u8, param: u8) -> CommandResult<Vec<u8>> {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_TRANSPORT);
+    req.msg.cmd = IPMI_GET_SOL_CONFIG_PARAMETERS;
+
+    let mut data = [channel, param, 0, 0];
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf
+        .sendrecv(&req)
+        .ok_or_else(|| IpmiError::Interface(format!("Error requesting SOL parameter {}", param)))?;
+    if rsp.ccode != 0 {
+        return Err(IpmiError::Interface(format!(
+            "Error requesting SOL parameter {}: {}",
+            param,
+            IpmiError::CompletionCode(rsp.ccode)
+        )));
+    }
+    if rsp.data_len < 2 {
+        return Err(IpmiError::InvalidData(format!(
+            "Invalid response length {} for SOL parameter {}",
+            rsp.data_len, param
+        )));
+    }
+
+    Ok(rsp.data[1..rsp.data_len as usize].to_vec())
+}
+
+fn set_sol_param(intf: &mut dyn IpmiIntf, channel: u8, param: u8, value: &[u8]) -> CommandResult {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_TRANSPORT);
+    req.msg.cmd = IPMI_SET_SOL_CONFIG_PARAMETERS;
+
+    let mut data = vec![channel, param];
+    data.extend_from_slice(value);
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf
+        .sendrecv(&req)
+        .ok_or_else(|| IpmiError::Interface(format!("Error setting SOL parameter {}", param)))?;
+    if rsp.ccode != 0 {
+        return Err(IpmiError::Interface(format!(
+            "Error setting SOL parameter {}: {}",
+            param,
+            IpmiError::CompletionCode(rsp.ccode)
+        )));
+    }
+    Ok(())
+}
+
+/// 读取全部 SOL 配置参数
+pub fn ipmi_get_sol_info(intf: &mut dyn IpmiIntf, channel: u8) -> CommandResult<SolConfig> {
+    let mut config = SolConfig::default();
+
+    let byte = |data: &[u8], idx: usize| data.get(idx).copied().unwrap_or(0);
+
+    let data = get_sol_param(intf, channel, SOL_PARAMETER_SET_IN_PROGRESS)?;
+    config.set_in_progress = byte(&data, 0) & 0x03;
+
+    let data = get_sol_param(intf, channel, SOL_PARAMETER_SOL_ENABLE)?;
+    config.enabled = byte(&data, 0) & 0x01 != 0;
+
+    let data = get_sol_param(intf, channel, SOL_PARAMETER_SOL_AUTHENTICATION)?;
+    let auth = byte(&data, 0);
+    config.force_encryption = auth & SOL_AUTH_FORCE_ENCRYPTION != 0;
+    config.force_authentication = auth & SOL_AUTH_FORCE_AUTHENTICATION != 0;
+    config.privilege_level = auth & SOL_AUTH_PRIVILEGE_MASK;
+
+    let data = get_sol_param(intf, channel, SOL_PARAMETER_CHARACTER_INTERVAL)?;
+    config.character_accumulate_level = byte(&data, 0);
+    config.character_send_threshold = byte(&data, 1);
+
+    let data = get_sol_param(intf, channel, SOL_PARAMETER_SOL_RETRY)?;
+    config.retry_count = byte(&data, 0) & 0x07;
+    config.retry_interval = byte(&data, 1);
+
+    let data = get_sol_param(intf, channel, SOL_PARAMETER_SOL_VOLATILE_BIT_RATE)?;
+    config.volatile_bit_rate = byte(&data, 0) & 0x0F;
+
+    let data = get_sol_param(intf, channel, SOL_PARAMETER_SOL_NON_VOLATILE_BIT_RATE)?;
+    config.non_volatile_bit_rate = byte(&data, 0) & 0x0F;
+
+    // 可选参数：不支持时沿用请求的通道与默认端口
+    config.payload_channel = get_sol_param(intf, channel, SOL_PARAMETER_SOL_PAYLOAD_CHANNEL)
+        .map(|d| byte(&d, 0))
+        .unwrap_or(channel);
+    config.payload_port = get_sol_param(intf, channel, SOL_PARAMETER_SOL_PAYLOAD_PORT)
+        .map(|d| u16::from_le_bytes([byte(&d, 0), byte(&d, 1)]))
+        .unwrap_or(623);
+
+    Ok(config)
+}
+
+/// 设置 SOL 配置参数；guard 为 true 时用 set-in-progress/set-complete 包裹
+pub fn ipmi_sol_set_param(
+    intf: &mut dyn IpmiIntf,
+    channel: u8,
+    param: &SolParam,
+    value: &str,
+    guard: bool,
+) -> CommandResult {
+    // 读-改-写的参数需要先取当前值
+    let writes: Vec<(u8, Vec<u8>)> = match param {
+        SolParam::SetInProgress => {
+            let v = parse_set_in_progress(value).ok_or_else(|| {
+                invalid_value(param, value, "set-complete, set-in-progress, commit-write")
+            })?;
+            vec![(SOL_PARAMETER_SET_IN_PROGRESS, vec![v])]
+        }
+        SolParam::Enabled => {
+            let v = parse_bool(value).ok_or_else(|| invalid_value(param, value, "true, false"))?;
+            vec![(SOL_PARAMETER_SOL_ENABLE, vec![v as u8])]
+        }
+        SolParam::ForceEncryption | SolParam::ForceAuthentication | SolParam::PrivilegeLevel => {
+            let current = get_sol_param(intf, channel, SOL_PARAMETER_SOL_AUTHENTICATION)?;
+            let mut auth = current.first().copied().unwrap_or(0);
+            match param {
+                SolParam::ForceEncryption | SolParam::ForceAuthentication => {
+                    let bit = if matches!(param, SolParam::ForceEncryption) {
+                        SOL_AUTH_FORCE_ENCRYPTION
+                    } else {
+                        SOL_AUTH_FORCE_AUTHENTICATION
+                    };
+                    if parse_bool(value)
+                        .ok_or_else(|| invalid_value(param, value, "true, false"))?
+                    {
+                        auth |= bit;
+                    } else {
+                        auth &= !bit;
+                    }
+                }
+                _ => {
+                    let level = parse_privilege(value)
+                        .ok_or_else(|| invalid_value(param, value, "user, operator, admin, oem"))?;
+                    auth = (auth & !SOL_AUTH_PRIVILEGE_MASK) | level;
+                }
+            }
+            vec![(SOL_PARAMETER_SOL_AUTHENTICATION, vec![auth])]
+        }
+        SolParam::CharacterAccumulateLevel | SolParam::CharacterSendThreshold => {
+            let v = parse_u8(param, value, u8::MAX)?;
+            let current = get_sol_param(intf, channel, SOL_PARAMETER_CHARACTER_INTERVAL)?;
+            let mut interval = [
+                current.first().copied().unwrap_or(0),
+                current.get(1).copied().unwrap_or(0),
+            ];
+            if matches!(param, SolParam::CharacterAccumulateLevel) {
+                interval[0] = v;
+            } else {
+                interval[1] = v;
+            }
+            vec![(SOL_PARAMETER_CHARACTER_INTERVAL, interval.to_vec())]
+        }
+        SolParam::RetryCount | SolParam::RetryInterval => {
+            let current = get_sol_param(intf, channel, SOL_PARAMETER_SOL_RETRY)?;
+            let mut retry = [
+                current.first().copied().unwrap_or(0) & 0x07,
+                current.get(1).copied().unwrap_or(0),
+            ];
+            if matches!(param, SolParam::RetryCount) {
+                retry[0] = parse_u8(param, value, 7)?;
+            } else {
+                retry[1] = parse_u8(param, value, u8::MAX)?;
+            }
+            vec![(SOL_PARAMETER_SOL_RETRY, retry.to_vec())]
+        }
+        SolParam::NonVolatileBitRate | SolParam::VolatileBitRate | SolParam::BaudRate => {
+            let rate = parse_bit_rate(value).ok_or_else(|| {
+                invalid_value(param, value, "serial, 9.6, 19.2, 38.4, 57.6, 115.2")
+            })?;
+            match param {
+                SolParam::NonVolatileBitRate => {
+                    vec![(SOL_PARAMETER_SOL_NON_VOLATILE_BIT_RATE, vec![rate])]
+                }
+                SolParam::VolatileBitRate => {
+                    vec![(SOL_PARAMETER_SOL_VOLATILE_BIT_RATE, vec![rate])]
+                }
+                _ => vec![
+                    (SOL_PARAMETER_SOL_NON_VOLATILE_BIT_RATE, vec![rate]),
+                    (SOL_PARAMETER_SOL_VOLATILE_BIT_RATE, vec![rate]),
+                ],
+            }
+        }
+    };
+
+    let guard = guard && !matches!(param, SolParam::SetInProgress);
+    if guard {
+        set_sol_param(
+            intf,
+            channel,
+            SOL_PARAMETER_SET_IN_PROGRESS,
+            &[IPMI_SET_IN_PROGRESS_IN_PROGRESS],
+        )
+        .map_err(|e| IpmiError::Interface(format!("Error: set-in-progress failed: {}", e)))?;
+    }
+
+    let mut result = Ok(());
+    for (id, data) in &writes {
+        result = set_sol_param(intf, channel, *id, data);
+        if result.is_err() {
+            break;
+        }
+    }
+
+    if guard {
+        // 无论设置是否成功都要结束 set-in-progress 状态
+        if let Err(e) = set_sol_param(
+            intf,
+            channel,
+            SOL_PARAMETER_SET_IN_PROGRESS,
+            &[IPMI_SET_IN_PROGRESS_SET_COMPLETE],
+        ) {
+            log::error!("Error: set-complete failed: {}", e);
+        }
+    }
+
+    result
+}
+
+/// 设置用户的 SOL payload 访问权限
+pub fn ipmi_sol_payload_access(
+    intf: &mut dyn IpmiIntf,
+    channel: u8,
+    userid: u8,
+    enable: bool,
+) -> CommandResult {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_SET_USER_PAYLOAD_ACCESS;
+
+    // byte 1 bit6: 0 = enable, 1 = disable；byte 2 bit1 对应 SOL payload
+    let operation = if enable { 0x00 } else { 0x40 };
+    let mut data = [channel & 0x0F, (userid & 0x3F) | operation, 0x02, 0, 0, 0];
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf.sendrecv(&req).ok_or_else(|| {
+        IpmiError::Interface(format!(
+            "Error {}abling SOL payload for user {} on channel {}",
+            if enable { "en" } else { "dis" },
+            userid,
+            channel
+        ))
+    })?;
+    if rsp.ccode != 0 {
+        return Err(IpmiError::Interface(format!(
+            "Error {}abling SOL payload for user {} on channel {}: {}",
+            if enable { "en" } else { "dis" },
+            userid,
+            channel,
+            IpmiError::CompletionCode(rsp.ccode)
+        )));
+    }
+    Ok(())
+}
+
+/// 查询用户的 SOL payload 访问权限
+pub fn ipmi_sol_payload_access_status(
+    intf: &mut dyn IpmiIntf,
+    channel: u8,
+    userid: u8,
+) -> CommandResult {
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_GET_USER_PAYLOAD_ACCESS;
+
+    let mut data = [channel & 0x0F, userid & 0x3F];
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf.sendrecv(&req).ok_or_else(|| {
+        IpmiError::Interface(format!(
+            "Error getting SOL payload status for user {} on channel {}",
+            userid, channel
+        ))
+    })?;
+    if rsp.ccode != 0 {
+        return Err(IpmiError::Interface(format!(
+            "Error getting SOL payload status for user {} on channel {}: {}",
+            userid,
+            channel,
+            IpmiError::CompletionCode(rsp.ccode)
+        )));
+    }
+    if rsp.data_len < 1 {
+        return Err(IpmiError::InvalidData(
+            "Invalid Get User Payload Access response".to_string(),
+        ));
+    }
+
+    println!(
+        "User {} on channel {} is {}abled",
+        userid,
+        channel,
+        if rsp.data[0] & 0x02 != 0 { "en" } else { "dis" }
+    );
+    Ok(())
+}
+
+/// 激活 SOL payload 并进入交互式终端
+pub fn ipmi_sol_activate(
+    intf: &mut dyn IpmiIntf,
+    instance: u8,
+    escape: char,
+    keepalive: SolKeepalive,
+) -> CommandResult {
+    let Some((authenticated, encrypted)) = intf.session_security() else {
+        return Err(IpmiError::NotSupported(
+            "Error: This command is only available over the lanplus interface".to_string(),
+        ));
+    };
+    if !escape.is_ascii() {
+        return Err(IpmiError::InvalidData(format!(
+            "Invalid SOL escape character: {}",
+            escape
+        )));
+    }
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_ACTIVATE_PAYLOAD;
+
+    let mut aux = SOL_ACTIVATE_SERIAL_ALERT_DEFERRED;
+    if encrypted {
+        aux |= SOL_ACTIVATE_ENCRYPT;
+    }
+    if authenticated {
+        aux |= SOL_ACTIVATE_AUTHENTICATE;
+    }
+    let mut data = [IPMI_PAYLOAD_TYPE_SOL, instance, aux, 0, 0, 0];
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf
+        .sendrecv(&req)
+        .ok_or_else(|| IpmiError::Interface("Error: Unable to activate SOL payload".to_string()))?;
+    match rsp.ccode {
+        0x00 => {}
+        0x80 => {
+            println!("Info: SOL payload already active on another session");
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        0x81 => {
+            println!("Info: SOL payload disabled");
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        0x82 => {
+            println!("Info: SOL payload activation limit reached");
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        0x83 => {
+            println!("Info: cannot activate SOL payload with encryption");
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        0x84 => {
+            println!("Info: cannot activate SOL payload without encryption");
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        ccode => {
+            return Err(IpmiError::Interface(format!(
+                "Error activating SOL payload: {}",
+                IpmiError::CompletionCode(ccode)
+            )));
+        }
+    }
+
+    if rsp.data_len < 10 {
+        return Err(IpmiError::InvalidData(format!(
+            "Invalid Activate Payload response length {}",
+            rsp.data_len
+        )));
+    }
+    // 响应: aux[4], 入站最大长度, 出站最大长度, 端口, VLAN
+    let max_inbound = u16::from_le_bytes([rsp.data[4], rsp.data[5]]);
+    let max_outbound = u16::from_le_bytes([rsp.data[6], rsp.data[7]]);
+    let port = u16::from_le_bytes([rsp.data[8], rsp.data[9]]);
+    crate::log_debug!(
+        "SOL payload activated: max inbound {}, max outbound {}, port {}",
+        max_inbound,
+        max_outbound,
+        port
+    );
+
+    println!("[SOL Session operational.  Use {}? for help]", escape);
+
+    let exit = terminal::run(intf, escape as u8, max_inbound as usize, keepalive)?;
+    match exit {
+        SolExit::Terminated => ipmi_sol_deactivate(intf, instance),
+        SolExit::ClosedByBmc => {
+            println!("\r\nSOL session closed by BMC");
+            Ok(())
+        }
+    }
+}
+
+/// 去激活 SOL payload
+pub fn ipmi_sol_deactivate(intf: &mut dyn IpmiIntf, instance: u8) -> CommandResult {
+    if intf.session_security().is_none() {
+        return Err(IpmiError::NotSupported(
+            "Error: This command is only available over the lanplus interface".to_string(),
+        ));
+    }
+
+    let mut req = IpmiRq::default();
+    req.msg.netfn_mut(IPMI_NETFN_APP);
+    req.msg.cmd = IPMI_DEACTIVATE_PAYLOAD;
+
+    let mut data = [IPMI_PAYLOAD_TYPE_SOL, instance, 0, 0, 0, 0];
+    req.msg.data = data.as_mut_ptr();
+    req.msg.data_len = data.len() as u16;
+
+    let rsp = intf.sendrecv(&req).ok_or_else(|| {
+        IpmiError::Interface("Error: Unable to deactivate SOL payload".to_string())
+    })?;
+    match rsp.ccode {
+        0x00 => Ok(()),
+        0x80 => {
+            println!("Info: SOL payload already de-activated");
+            Ok(())
+        }
+        0x81 => {
+            println!("Info: SOL payload type disabled");
+            Ok(())
+        }
+        ccode => Err(IpmiError::Interface(format!(
+            "Error de-activating SOL payload: {}",
+            IpmiError::CompletionCode(ccode)
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_rate_round_trip() {
+        for value in ["9.6", "19.2", "38.4", "57.6", "115.2"] {
+            let rate = parse_bit_rate(value).unwrap();
+            assert_eq!(bit_rate_to_str(rate), value);
+        }
+        assert_eq!(bit_rate_to_str(0), "IPMI-Over-Serial-Setting");
+        assert_eq!(parse_bit_rate("4.8"), None);
+    }
+
+    #[test]
+    fn test_sol_info_format() {
+        let config = SolConfig {
+            set_in_progress: IPMI_SET_IN_PROGRESS_SET_COMPLETE,
+            enabled: true,
+            force_encryption: true,
+            force_authentication: false,
+            privilege_level: IPMI_SESSION_PRIV_ADMIN,
+            character_accumulate_level: 10,
+            character_send_threshold: 96,
+            retry_count: 7,
+            retry_interval: 50,
+            non_volatile_bit_rate: 0x0A,
+            volatile_bit_rate: 0x0A,
+            payload_channel: 1,
+            payload_port: 623,
+        };
+
+        let text = config.format(false);
+        assert!(text.contains("Set in progress                 : set-complete\n"));
+        assert!(text.contains("Privilege Level                 : ADMINISTRATOR\n"));
+        assert!(text.contains("Character Accumulate Level (ms) : 50\n"));
+        assert!(text.contains("Retry Interval (ms)             : 500\n"));
+        assert!(text.contains("Payload Channel                 : 1 (0x01)\n"));
+
+        assert_eq!(
+            config.format(true),
+            "set-complete,true,true,false,ADMINISTRATOR,50,96,7,500,115.2,115.2,1,623\n"
+        );
+    }
+}
@@ -0,0 +1,372 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 交互式 SOL 终端：原始 tty 模式、转义序列处理与数据收发循环
+
+use crate::commands::CommandResult;
+use crate::error::IpmiError;
+use crate::ipmi::intf::IpmiIntf;
+use crate::ipmi::ipmi::{
+    IpmiPayloadData, IpmiRsPayload, IpmiV2Payload, SolPacket, IPMI_PAYLOAD_TYPE_SOL,
+};
+use crate::log_debug;
+use nix::poll::{poll, PollFd, PollFlags};
+use nix::sys::signal::{raise, Signal};
+use nix::sys::termios::{self, SetArg, Termios};
+use std::io::Write;
+use std::os::fd::{AsFd, AsRawFd};
+use std::time::{Duration, Instant};
+
+// 会话保活间隔
+const SOL_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+// 等待键盘输入的时间 (ms)
+const SOL_INPUT_POLL_MS: u8 = 10;
+// accepted_character_count 只有一个字节
+const SOL_MAX_CHARS_PER_PACKET: usize = 255;
+
+const CTRL_X: u8 = 0x18;
+const CTRL_Z: u8 = 0x1a;
+
+/// SOL 会话保活方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolKeepalive {
+    /// 通过接口 keepalive (Get Device ID)
+    Session,
+    /// 发送空 SOL 包
+    Sol,
+    /// 不发送保活
+    None,
+}
+
+/// SOL 终端退出原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolExit {
+    /// 用户输入转义序列结束
+    Terminated,
+    /// BMC 报告 SOL 已去激活
+    ClosedByBmc,
+}
+
+/// 转义序列产生的本地动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapeAction {
+    Terminate,
+    /// 挂起进程，restore_tty 表示恢复时是否重新设置原始模式
+    Suspend {
+        restore_tty: bool,
+    },
+    Break,
+    Help,
+}
+
+/// 键盘输入转义序列解析器，转义符只在行首 (CR/LF 之后) 生效
+#[derive(Debug)]
+pub struct EscapeParser {
+    escape: u8,
+    at_line_start: bool,
+    in_escape: bool,
+}
+
+impl EscapeParser {
+    pub fn new(escape: u8) -> Self {
+        EscapeParser {
+            escape,
+            at_line_start: true,
+            in_escape: false,
+        }
+    }
+
+    /// 处理一个输入字节，需要发送给 BMC 的字节追加到 out
+    pub fn feed(&mut self, b: u8, out: &mut Vec<u8>) -> Option<EscapeAction> {
+        let was_escape = self.in_escape;
+        self.in_escape = false;
+
+        let action = if was_escape {
+            match b {
+                b'.' => Some(EscapeAction::Terminate),
+                CTRL_Z => Some(EscapeAction::Suspend { restore_tty: true }),
+                CTRL_X => Some(EscapeAction::Suspend { restore_tty: false }),
+                b'B' => Some(EscapeAction::Break),
+                b'?' => Some(EscapeAction::Help),
+                _ if b == self.escape => {
+                    out.push(b);
+                    None
+                }
+                _ => {
+                    out.push(self.escape);
+                    out.push(b);
+                    None
+                }
+            }
+        } else if self.at_line_start && b == self.escape {
+            self.in_escape = true;
+            return None;
+        } else {
+            out.push(b);
+            None
+        };
+
+        self.at_line_start = b == b'\r' || b == b'\n';
+        action
+    }
+
+    pub fn help_text(&self) -> String {
+        let e = self.escape as char;
+        format!(
+            "{e}?\r\n Supported escape sequences:\r\n\
+             \t{e}.  - terminate connection\r\n\
+             \t{e}^Z - suspend ipmitool\r\n\
+             \t{e}^X - suspend ipmitool, but don't restore tty on restart\r\n\
+             \t{e}B  - send break\r\n\
+             \t{e}?  - this message\r\n\
+             \t{e}{e}  - send the escape character by typing it twice\r\n\
+             \t(Note that escapes are only recognized immediately after newline.)\r\n"
+        )
+    }
+}
+
+/// 终端原始模式，Drop 时恢复原设置
+struct RawTerminal {
+    saved: Option<Termios>,
+}
+
+impl RawTerminal {
+    fn enter() -> Self {
+        let stdin = std::io::stdin();
+        // 输入不是终端时 (如管道) 保持原样
+        let saved = match termios::tcgetattr(stdin.as_fd()) {
+            Ok(t) => t,
+            Err(_) => return RawTerminal { saved: None },
+        };
+        let mut raw = saved.clone();
+        termios::cfmakeraw(&mut raw);
+        if let Err(e) = termios::tcsetattr(stdin.as_fd(), SetArg::TCSADRAIN, &raw) {
+            log_debug!("Failed to set raw terminal mode: {}", e);
+            return RawTerminal { saved: None };
+        }
+        RawTerminal { saved: Some(saved) }
+    }
+
+    fn restore(&self) {
+        if let Some(saved) = &self.saved {
+            let _ = termios::tcsetattr(std::io::stdin().as_fd(), SetArg::TCSADRAIN, saved);
+        }
+    }
+
+    fn reapply(&self) {
+        if let Some(saved) = &self.saved {
+            let mut raw = saved.clone();
+            termios::cfmakeraw(&mut raw);
+            let _ = termios::tcsetattr(std::io::stdin().as_fd(), SetArg::TCSADRAIN, &raw);
+        }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+fn sol_payload(sol: SolPacket) -> IpmiV2Payload {
+    IpmiV2Payload {
+        payload_length: sol.character_count,
+        payload_type: IPMI_PAYLOAD_TYPE_SOL,
+        payload: IpmiPayloadData::SolPacket(Box::new(sol)),
+    }
+}
+
+fn send_chars(intf: &mut dyn IpmiIntf, chars: &[u8], max_chars: usize) -> CommandResult {
+    for chunk in chars.chunks(max_chars) {
+        let mut sol = SolPacket::default();
+        sol.data[..chunk.len()].copy_from_slice(chunk);
+        sol.character_count = chunk.len() as u16;
+        intf.send_sol(&sol_payload(sol))
+            .ok_or_else(|| IpmiError::Interface("Error sending SOL data".to_string()))?;
+    }
+    Ok(())
+}
+
+fn send_break(intf: &mut dyn IpmiIntf) -> CommandResult {
+    let sol = SolPacket {
+        generate_break: true,
+        ..Default::default()
+    };
+    intf.send_sol(&sol_payload(sol))
+        .ok_or_else(|| IpmiError::Interface("Error sending SOL break".to_string()))?;
+    Ok(())
+}
+
+fn write_stdout(data: &[u8]) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = stdout.write_all(data);
+    let _ = stdout.flush();
+}
+
+/// 输出 BMC 发来的全部字符，返回 BMC 是否已关闭 SOL
+fn drain_output(intf: &mut dyn IpmiIntf) -> bool {
+    while let Some(rsp) = intf.recv_sol() {
+        if rsp.data_len > 0 {
+            write_stdout(&rsp.data[..rsp.data_len as usize]);
+        }
+        if let IpmiRsPayload::SolPacket {
+            sol_inactive,
+            transmit_overrun,
+            break_detected,
+            ..
+        } = rsp.payload
+        {
+            if transmit_overrun != 0 {
+                log_debug!("SOL transmit overrun reported by BMC");
+            }
+            if break_detected != 0 {
+                log_debug!("Break detected on BMC serial port");
+            }
+            if sol_inactive != 0 {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// 运行交互式 SOL 终端直到用户退出或 BMC 关闭会话
+///
+/// max_inbound 为 Activate Payload 返回的 BMC 最大入站 payload 长度。
+pub fn run(
+    intf: &mut dyn IpmiIntf,
+    escape: u8,
+    max_inbound: usize,
+    keepalive: SolKeepalive,
+) -> CommandResult<SolExit> {
+    let max_chars = max_inbound
+        .saturating_sub(4)
+        .clamp(1, SOL_MAX_CHARS_PER_PACKET);
+    let mut parser = EscapeParser::new(escape);
+    let terminal = RawTerminal::enter();
+    let stdin = std::io::stdin();
+
+    let mut last_activity = Instant::now();
+    let mut input = [0u8; 256];
+    let mut pending = Vec::new();
+
+    loop {
+        let mut fds = [PollFd::new(stdin.as_fd(), PollFlags::POLLIN)];
+        let readable = match poll(&mut fds, SOL_INPUT_POLL_MS) {
+            Ok(n) => n > 0,
+            Err(nix::errno::Errno::EINTR) => false,
+            Err(e) => return Err(IpmiError::System(format!("poll failed: {}", e))),
+        };
+
+        if readable {
+            let n = match nix::unistd::read(stdin.as_raw_fd(), &mut input) {
+                Ok(0) => return Ok(SolExit::Terminated),
+                Ok(n) => n,
+                Err(nix::errno::Errno::EINTR) | Err(nix::errno::Errno::EAGAIN) => 0,
+                Err(e) => return Err(IpmiError::System(format!("read failed: {}", e))),
+            };
+
+            for &b in &input[..n] {
+                let Some(action) = parser.feed(b, &mut pending) else {
+                    continue;
+                };
+                // 先发送转义序列之前的字符
+                send_chars(intf, &pending, max_chars)?;
+                pending.clear();
+                match action {
+                    EscapeAction::Terminate => {
+                        write_stdout(
+                            format!("{}.\r\n[terminated ipmitool]\r\n", escape as char).as_bytes(),
+                        );
+                        return Ok(SolExit::Terminated);
+                    }
+                    EscapeAction::Suspend { restore_tty } => {
+                        terminal.restore();
+                        let _ = raise(Signal::SIGTSTP);
+                        if restore_tty {
+                            terminal.reapply();
+                        }
+                    }
+                    EscapeAction::Break => send_break(intf)?,
+                    EscapeAction::Help => write_stdout(parser.help_text().as_bytes()),
+                }
+            }
+
+            if !pending.is_empty() {
+                send_chars(intf, &pending, max_chars)?;
+                pending.clear();
+                last_activity = Instant::now();
+            }
+        }
+
+        if drain_output(intf) {
+            return Ok(SolExit::ClosedByBmc);
+        }
+
+        if last_activity.elapsed() >= SOL_KEEPALIVE_INTERVAL {
+            match keepalive {
+                SolKeepalive::Session => {
+                    intf.keepalive()
+                        .map_err(|e| IpmiError::Session(format!("SOL keepalive failed: {}", e)))?;
+                }
+                SolKeepalive::Sol => {
+                    intf.send_sol(&sol_payload(SolPacket::default()))
+                        .ok_or_else(|| IpmiError::Session("SOL keepalive failed".to_string()))?;
+                }
+                SolKeepalive::None => {}
+            }
+            last_activity = Instant::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(parser: &mut EscapeParser, input: &[u8]) -> (Vec<u8>, Vec<EscapeAction>) {
+        let mut out = Vec::new();
+        let mut actions = Vec::new();
+        for &b in input {
+            if let Some(action) = parser.feed(b, &mut out) {
+                actions.push(action);
+            }
+        }
+        (out, actions)
+    }
+
+    #[test]
+    fn test_escape_at_start_terminates() {
+        let mut parser = EscapeParser::new(b'~');
+        let (out, actions) = feed_all(&mut parser, b"~.");
+        assert!(out.is_empty());
+        assert_eq!(actions, vec![EscapeAction::Terminate]);
+    }
+
+    #[test]
+    fn test_escape_only_after_newline() {
+        let mut parser = EscapeParser::new(b'~');
+        let (out, actions) = feed_all(&mut parser, b"ls ~.\r~B");
+        assert_eq!(out, b"ls ~.\r");
+        assert_eq!(actions, vec![EscapeAction::Break]);
+    }
+
+    #[test]
+    fn test_escape_doubled_and_unknown() {
+        let mut parser = EscapeParser::new(b'~');
+        let (out, actions) = feed_all(&mut parser, b"~~\n~x\n~?");
+        assert_eq!(out, b"~\n~x\n");
+        assert_eq!(actions, vec![EscapeAction::Help]);
+    }
+
+    #[test]
+    fn test_escape_suspend_and_custom_char() {
+        let mut parser = EscapeParser::new(b'&');
+        let (out, actions) = feed_all(&mut parser, &[b'~', b'.', b'\n', b'&', CTRL_Z]);
+        assert_eq!(out, b"~.\n");
+        assert_eq!(actions, vec![EscapeAction::Suspend { restore_tty: true }]);
+    }
+}
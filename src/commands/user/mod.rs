@@ -485,7 +485,7 @@ pub fn ipmi_print_user_list(
     Ok(())
 }
 
-fn ipmi_get_user_access(
+pub(crate) fn ipmi_get_user_access(
     intf: &mut dyn IpmiIntf,
     user_access_rsp: &mut UserAccess,
 ) -> Result<(), Box<dyn Error>> {
@@ -524,7 +524,7 @@ fn ipmi_get_user_access(
     Ok(())
 }
 
-fn ipmi_get_user_name(
+pub(crate) fn ipmi_get_user_name(
     intf: &mut dyn IpmiIntf,
     user_name: &mut UserName,
 ) -> Result<(), Box<dyn Error>> {
@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! `-H` 主机列表解析：逗号分隔、IPv4 CIDR、`@文件`，可以混用
+
+use std::collections::HashSet;
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+/// 单个 CIDR 最多展开的地址数 (/16)
+pub const MAX_CIDR_HOSTS: u32 = 65536;
+
+/// `-H` 的值是否指定了多台主机
+pub fn is_host_list(spec: &str) -> bool {
+    spec.contains(',') || spec.starts_with('@') || parse_cidr(spec).is_some()
+}
+
+/// 展开主机列表，保持出现顺序并去重
+pub fn parse_hosts(spec: &str) -> Result<Vec<String>, String> {
+    let mut hosts = Vec::new();
+    let mut seen = HashSet::new();
+    for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        for host in expand_item(item)? {
+            if seen.insert(host.clone()) {
+                hosts.push(host);
+            }
+        }
+    }
+    if hosts.is_empty() {
+        return Err(format!("No hosts in \"{}\"", spec));
+    }
+    Ok(hosts)
+}
+
+fn expand_item(item: &str) -> Result<Vec<String>, String> {
+    if let Some(path) = item.strip_prefix('@') {
+        return read_hosts_file(Path::new(path));
+    }
+    if item.contains('/') {
+        let (addr, prefix) =
+            parse_cidr(item).ok_or_else(|| format!("Invalid CIDR \"{}\"", item))?;
+        return expand_cidr(addr, prefix);
+    }
+    Ok(vec![item.to_string()])
+}
+
+/// 每行一个条目 (主机或 CIDR)，`#` 之后为注释
+fn read_hosts_file(path: &Path) -> Result<Vec<String>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read hosts file {}: {}", path.display(), e))?;
+    let mut hosts = Vec::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('@') {
+            return Err(format!(
+                "{}: nested hosts files are not supported",
+                path.display()
+            ));
+        }
+        hosts.extend(expand_item(line)?);
+    }
+    Ok(hosts)
+}
+
+fn parse_cidr(item: &str) -> Option<(Ipv4Addr, u8)> {
+    let (addr, prefix) = item.split_once('/')?;
+    let addr = addr.parse().ok()?;
+    let prefix = prefix.parse().ok().filter(|p| *p <= 32)?;
+    Some((addr, prefix))
+}
+
+/// /31、/32 使用全部地址，其余去掉网络地址与广播地址
+fn expand_cidr(addr: Ipv4Addr, prefix: u8) -> Result<Vec<String>, String> {
+    let size = 1u64 << (32 - prefix);
+    if size > MAX_CIDR_HOSTS as u64 {
+        return Err(format!(
+            "{}/{} is too large (more than {} addresses)",
+            addr, prefix, MAX_CIDR_HOSTS
+        ));
+    }
+    let mask = if prefix == 0 {
+        0
+    } else {
+        u32::MAX << (32 - prefix)
+    };
+    let network = u32::from(addr) & mask;
+    let range = if prefix >= 31 {
+        0..size as u32
+    } else {
+        1..size as u32 - 1
+    };
+    Ok(range
+        .map(|i| Ipv4Addr::from(network + i).to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_lists() {
+        assert!(!is_host_list("bmc1"));
+        assert!(!is_host_list("fe80::1"));
+        assert!(is_host_list("bmc1,bmc2"));
+        assert!(is_host_list("10.0.0.0/30"));
+        assert!(is_host_list("@hosts"));
+
+        assert_eq!(
+            parse_hosts("bmc1, bmc2,,bmc1").unwrap(),
+            vec!["bmc1", "bmc2"]
+        );
+        assert_eq!(
+            parse_hosts("10.0.0.5/30,10.0.0.9/32").unwrap(),
+            vec!["10.0.0.5", "10.0.0.6", "10.0.0.9"]
+        );
+        assert_eq!(parse_hosts("10.0.0.0/31").unwrap().len(), 2);
+        assert_eq!(parse_hosts("10.0.0.0/24").unwrap().len(), 254);
+        assert!(parse_hosts("10.0.0.0/8").is_err());
+        assert!(parse_hosts("10.0.0.0/33").is_err());
+        assert!(parse_hosts(" , ").is_err());
+    }
+
+    #[test]
+    fn test_hosts_file() {
+        let path = std::env::temp_dir().join(format!("utipmi-hosts-{}", std::process::id()));
+        std::fs::write(&path, "# rack 1\nbmc1\n\nbmc2  # spare\n192.168.1.0/30\n").unwrap();
+        let hosts = parse_hosts(&format!("bmc0,@{}", path.display())).unwrap();
+        assert_eq!(
+            hosts,
+            vec!["bmc0", "bmc1", "bmc2", "192.168.1.1", "192.168.1.2"]
+        );
+        let _ = std::fs::remove_file(&path);
+        assert!(parse_hosts("@/nonexistent/hosts").is_err());
+    }
+}
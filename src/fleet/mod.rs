@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 对多台 BMC 并发执行同一条命令
+//!
+//! 命令实现直接向 stdout 打印，因此每台主机由一个子进程执行 (同一个程序、同样的参数)，
+//! 通过环境变量 [`FLEET_HOST_ENV`] 告诉子进程目标主机；父进程收集输出，
+//! 按主机加前缀或像 `dshbak -c` 那样合并相同的输出，最后汇总失败的主机。
+
+pub mod hosts;
+
+pub use hosts::{is_host_list, parse_hosts};
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// 子进程的目标主机，设置时 `-H` 中的主机列表被忽略
+pub const FLEET_HOST_ENV: &str = "UTIPMI_FLEET_HOST";
+
+/// 默认同时执行的主机数
+pub const DEFAULT_PARALLEL: usize = 32;
+
+/// 一台主机的执行结果
+#[derive(Debug, Clone)]
+pub struct HostResult {
+    pub host: String,
+    /// 退出码，Err 表示未能启动或被信号终止
+    pub status: Result<i32, String>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl HostResult {
+    pub fn failed(&self) -> bool {
+        !matches!(self.status, Ok(0))
+    }
+
+    /// stdout 每行加上 `host: ` 前缀
+    pub fn prefixed_stdout(&self) -> String {
+        prefix_lines(&self.host, &self.stdout)
+    }
+
+    pub fn prefixed_stderr(&self) -> String {
+        prefix_lines(&self.host, &self.stderr)
+    }
+}
+
+fn prefix_lines(host: &str, text: &str) -> String {
+    let mut out = String::new();
+    for line in text.lines() {
+        let _ = writeln!(out, "{}: {}", host, line);
+    }
+    out
+}
+
+/// 以子进程方式在每台主机上执行命令
+pub struct FleetRunner {
+    program: PathBuf,
+    args: Vec<OsString>,
+    envs: Vec<(String, String)>,
+    parallel: usize,
+}
+
+impl FleetRunner {
+    pub fn new(program: PathBuf, args: Vec<OsString>) -> Self {
+        Self {
+            program,
+            args,
+            envs: Vec::new(),
+            parallel: DEFAULT_PARALLEL,
+        }
+    }
+
+    /// 同时运行的子进程数上限，至少为 1
+    pub fn parallel(mut self, parallel: usize) -> Self {
+        self.parallel = parallel.max(1);
+        self
+    }
+
+    /// 额外传给每个子进程的环境变量
+    pub fn env(mut self, key: &str, value: &str) -> Self {
+        self.envs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// 执行全部主机，每完成一台调用一次 `on_done`，返回值按 `hosts` 的顺序排列
+    pub fn run<F>(&self, hosts: &[String], mut on_done: F) -> Result<Vec<HostResult>, String>
+    where
+        F: FnMut(&HostResult),
+    {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| format!("Unable to start tokio runtime: {}", e))?;
+
+        runtime.block_on(async {
+            let limit = Arc::new(Semaphore::new(self.parallel));
+            let mut tasks = JoinSet::new();
+            for (idx, host) in hosts.iter().enumerate() {
+                let limit = limit.clone();
+                let mut cmd = Command::new(&self.program);
+                cmd.args(&self.args)
+                    .env(FLEET_HOST_ENV, host)
+                    .envs(self.envs.iter().map(|(k, v)| (k, v)))
+                    .stdin(Stdio::null())
+                    .kill_on_drop(true);
+                let host = host.clone();
+                tasks.spawn(async move {
+                    let _permit = limit.acquire_owned().await;
+                    (idx, run_one(host, cmd).await)
+                });
+            }
+
+            let mut results: Vec<Option<HostResult>> = vec![None; hosts.len()];
+            while let Some(joined) = tasks.join_next().await {
+                let (idx, result) = joined.map_err(|e| format!("Host task failed: {}", e))?;
+                on_done(&result);
+                results[idx] = Some(result);
+            }
+            Ok(results.into_iter().flatten().collect())
+        })
+    }
+}
+
+async fn run_one(host: String, mut cmd: Command) -> HostResult {
+    match cmd.output().await {
+        Ok(output) => HostResult {
+            host,
+            status: output
+                .status
+                .code()
+                .ok_or_else(|| format!("terminated by {}", output.status)),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        },
+        Err(e) => HostResult {
+            host,
+            status: Err(format!("unable to run: {}", e)),
+            stdout: String::new(),
+            stderr: String::new(),
+        },
+    }
+}
+
+/// 像 `dshbak -c` 一样把 stdout 相同的主机合并为一组，组按首次出现的顺序输出
+pub fn group_outputs(results: &[HostResult]) -> String {
+    let mut groups: Vec<(Vec<&str>, &str)> = Vec::new();
+    let mut index: BTreeMap<&str, usize> = BTreeMap::new();
+    for r in results {
+        match index.get(r.stdout.as_str()) {
+            Some(&i) => groups[i].0.push(&r.host),
+            None => {
+                index.insert(&r.stdout, groups.len());
+                groups.push((vec![&r.host], &r.stdout));
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for (hosts, stdout) in groups {
+        let hosts = hosts.join(",");
+        let rule = "-".repeat(hosts.len().clamp(16, 80));
+        out.push_str(&format!("{}\n{}\n{}\n", rule, hosts, rule));
+        out.push_str(stdout);
+        if !stdout.is_empty() && !stdout.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// 失败主机的汇总，全部成功时返回 None
+pub fn failure_summary(results: &[HostResult]) -> Option<String> {
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| r.failed())
+        .map(|r| match &r.status {
+            Ok(code) => format!("  {}: exit status {}", r.host, code),
+            Err(e) => format!("  {}: {}", r.host, e),
+        })
+        .collect();
+    if failed.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} of {} hosts failed:\n{}",
+        failed.len(),
+        results.len(),
+        failed.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(host: &str, code: i32, stdout: &str) -> HostResult {
+        HostResult {
+            host: host.to_string(),
+            status: Ok(code),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_runner() {
+        // 用 sh 代替本程序：打印主机名，主机 bad 失败
+        let script = "sleep 0.1; echo \"power on $EXTRA\"; echo $UTIPMI_FLEET_HOST; \
+                      [ $UTIPMI_FLEET_HOST != bad ]";
+        let runner = FleetRunner::new(PathBuf::from("/bin/sh"), vec!["-c".into(), script.into()])
+            .parallel(2)
+            .env("EXTRA", "x");
+        let hosts: Vec<String> = ["h1", "bad", "h3", "h4"].map(String::from).to_vec();
+        let mut done = 0;
+        let results = runner.run(&hosts, |_| done += 1).unwrap();
+
+        assert_eq!(done, 4);
+        let order: Vec<&str> = results.iter().map(|r| r.host.as_str()).collect();
+        assert_eq!(order, ["h1", "bad", "h3", "h4"]);
+        assert_eq!(results[0].stdout, "power on x\nh1\n");
+        assert_eq!(results[0].prefixed_stdout(), "h1: power on x\nh1: h1\n");
+        assert!(!results[0].failed());
+        assert_eq!(results[1].status, Ok(1));
+
+        let summary = failure_summary(&results).unwrap();
+        assert_eq!(summary, "1 of 4 hosts failed:\n  bad: exit status 1");
+    }
+
+    #[test]
+    fn test_group_outputs() {
+        let results = [
+            result("bmc1", 0, "Chassis Power is on\n"),
+            result("bmc2", 0, "Chassis Power is off\n"),
+            result("bmc3", 0, "Chassis Power is on\n"),
+        ];
+        assert_eq!(
+            group_outputs(&results),
+            "----------------\nbmc1,bmc3\n----------------\nChassis Power is on\n\
+             ----------------\nbmc2\n----------------\nChassis Power is off\n"
+        );
+        assert!(failure_summary(&results).is_none());
+    }
+}
@@ -0,0 +1,235 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! dummy 接口：把每个请求经 Unix domain socket 转发给 BMC 模拟进程
+//!
+//! socket 路径由 `-D <path>` 指定，缺省为 `/tmp/.ipmi_dummy`，
+//! 用于在没有 `/dev/ipmi0` 的环境 (CI、开发机) 上跑通各命令。
+
+pub mod protocol;
+
+pub use protocol::{DummyRequest, DummyResponse};
+
+use crate::error::{IpmiError, IpmiResult};
+use crate::interface::open::open::{
+    IPMI_OPENIPMI_MAX_RQ_DATA_SIZE, IPMI_OPENIPMI_MAX_RS_DATA_SIZE,
+};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IpmiV2Payload};
+use crate::{log_debug, log_info};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const IPMI_DUMMY_DEFAULTSOCK: &str = "/tmp/.ipmi_dummy";
+
+const DUMMY_TIMEOUT: u64 = 2; // seconds
+const DUMMY_RETRY: u32 = 4;
+
+/// IPMI dummy 接口
+pub struct IpmiDummyIntf {
+    pub context: IpmiContext,
+    pub socket_path: PathBuf,
+    pub timeout: u64,
+    pub retry_count: u32,
+    stream: Option<UnixStream>,
+}
+
+impl IpmiDummyIntf {
+    pub fn new(socket_path: Option<PathBuf>, ctx: IpmiContext) -> Self {
+        Self {
+            context: ctx,
+            socket_path: socket_path.unwrap_or_else(|| PathBuf::from(IPMI_DUMMY_DEFAULTSOCK)),
+            timeout: DUMMY_TIMEOUT,
+            retry_count: DUMMY_RETRY,
+            stream: None,
+        }
+    }
+
+    pub fn set_timeout(&mut self, timeout: u64, retry: u32) {
+        if timeout > 0 {
+            self.timeout = timeout;
+        }
+        if retry > 0 {
+            self.retry_count = retry;
+        }
+    }
+
+    /// 在当前连接上完成一次请求/响应交换
+    fn exchange(&mut self, req: &DummyRequest) -> std::io::Result<DummyResponse> {
+        let stream = self
+            .stream
+            .as_mut()
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotConnected))?;
+        req.write_to(stream)?;
+        DummyResponse::read_from(stream)
+    }
+
+    fn send_command(&mut self, req: &IpmiRq) -> Result<IpmiRs, String> {
+        let dreq = DummyRequest::from_rq(req);
+        let retry_count = self.retry_count;
+
+        for attempt in 0..retry_count {
+            if self.stream.is_none() {
+                self.open().map_err(|e| e.to_string())?;
+            }
+            match self.exchange(&dreq) {
+                Ok(rsp) => return Ok(rsp.into_rs()),
+                Err(e) => {
+                    // 超时或断开后连接上的数据已不可信，重连后重发
+                    log_info!(
+                        "No response to command 0x{:02x} (attempt {}/{}): {}",
+                        req.msg.cmd,
+                        attempt + 1,
+                        retry_count,
+                        e
+                    );
+                    self.close();
+                }
+            }
+        }
+
+        Err("No response from dummy BMC".to_string())
+    }
+}
+
+impl IpmiIntf for IpmiDummyIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        &mut self.context
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        // 与 open 接口保持一致的分段大小，便于对照输出
+        self.context.protocol.max_request_data_size = IPMI_OPENIPMI_MAX_RQ_DATA_SIZE;
+        self.context.protocol.max_response_data_size = IPMI_OPENIPMI_MAX_RS_DATA_SIZE;
+        Ok(())
+    }
+
+    fn open(&mut self) -> IpmiResult<()> {
+        if self.stream.is_some() {
+            return Ok(());
+        }
+        let stream = UnixStream::connect(&self.socket_path).map_err(|e| {
+            IpmiError::Interface(format!(
+                "Could not connect to dummy socket {}: {}",
+                self.socket_path.display(),
+                e
+            ))
+        })?;
+        let timeout = Some(Duration::from_secs(self.timeout));
+        stream
+            .set_read_timeout(timeout)
+            .and_then(|_| stream.set_write_timeout(timeout))
+            .map_err(|e| IpmiError::System(format!("Failed to set socket timeout: {}", e)))?;
+        log_debug!("Using dummy socket {}", self.socket_path.display());
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.stream = None;
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        match self.send_command(req) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                log::error!("Failed to send command: {}", e);
+                None
+            }
+        }
+    }
+
+    fn send_sol(&mut self, _payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        None
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        None
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.context.base.my_addr = addr as u32;
+        Ok(())
+    }
+}
+
+impl Drop for IpmiDummyIntf {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipmi::ipmi::IPMI_NETFN_APP;
+    use std::os::unix::net::UnixListener;
+
+    #[test]
+    fn test_dummy_over_unix_socket() {
+        let dir = std::env::temp_dir().join(format!("utipmi-dummy-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bmc.sock");
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+
+        // 模拟 BMC：同一连接上应答两个请求
+        let bmc = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().unwrap();
+            let mut seen = Vec::new();
+            for _ in 0..2 {
+                let req = DummyRequest::read_from(&mut conn).unwrap();
+                let rsp = match req.cmd {
+                    0x01 => DummyResponse::reply(&req, 0x00, &[0x20, 0x01]),
+                    _ => DummyResponse::reply(&req, 0xc1, &[]),
+                };
+                rsp.write_to(&mut conn).unwrap();
+                seen.push(req);
+            }
+            seen
+        });
+
+        let mut intf = IpmiDummyIntf::new(Some(path.clone()), IpmiContext::default());
+        intf.set_timeout(2, 1);
+        intf.setup().unwrap();
+        intf.open().unwrap();
+
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = 0x01;
+        let rsp = intf.sendrecv(&req).unwrap();
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(rsp.msg.netfn, IPMI_NETFN_APP + 1);
+        assert_eq!(&rsp.data[..rsp.data_len as usize], &[0x20, 0x01]);
+
+        let mut data = [0x0e, 0x04];
+        req.msg.cmd = 0x38;
+        req.msg.data = data.as_mut_ptr();
+        req.msg.data_len = data.len() as u16;
+        let rsp = intf.sendrecv(&req).unwrap();
+        assert_eq!(rsp.ccode, 0xc1);
+
+        let seen = bmc.join().unwrap();
+        assert_eq!(seen[1].data, vec![0x0e, 0x04]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_dummy_missing_socket() {
+        let path = PathBuf::from("/nonexistent/utipmi-dummy.sock");
+        let mut intf = IpmiDummyIntf::new(Some(path), IpmiContext::default());
+        assert!(intf.open().is_err());
+        assert_eq!(
+            IpmiDummyIntf::new(None, IpmiContext::default()).socket_path,
+            PathBuf::from(IPMI_DUMMY_DEFAULTSOCK)
+        );
+    }
+}
@@ -0,0 +1,188 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! dummy 接口在 Unix socket 上的报文格式
+//!
+//! 沿用 ipmitool dummy 接口逐字段写入的布局，多字节字段为小端：
+//!
+//! - 请求：netfn(1) lun(1) cmd(1) target_cmd(1) data_len(2) data..
+//! - 响应：netfn(1) cmd(1) seq(1) lun(1) ccode(1) data_len(4, 有符号) data..
+//!
+//! 同一个连接上可以依次交换多对请求/响应。
+
+use std::io::{self, Read, Write};
+
+use crate::ipmi::ipmi::{
+    IpmiRq, IpmiRs, IpmiRsMsg, IpmiRsPayload, IpmiSession, IPMI_BMC_SLAVE_ADDR, IPMI_BUF_SIZE,
+    IPMI_REMOTE_SWID,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DummyRequest {
+    pub netfn: u8,
+    pub lun: u8,
+    pub cmd: u8,
+    pub target_cmd: u8,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DummyResponse {
+    pub netfn: u8,
+    pub cmd: u8,
+    pub seq: u8,
+    pub lun: u8,
+    pub ccode: u8,
+    pub data: Vec<u8>,
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl DummyRequest {
+    pub fn from_rq(req: &IpmiRq) -> Self {
+        DummyRequest {
+            netfn: req.msg.netfn(),
+            lun: req.msg.lun(),
+            cmd: req.msg.cmd,
+            target_cmd: req.msg.target_cmd,
+            data: req.msg.data().unwrap_or(&[]).to_vec(),
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let len = u16::try_from(self.data.len()).map_err(|_| invalid("request data too long"))?;
+        let mut buf = Vec::with_capacity(6 + self.data.len());
+        buf.extend_from_slice(&[self.netfn, self.lun, self.cmd, self.target_cmd]);
+        buf.extend_from_slice(&len.to_le_bytes());
+        buf.extend_from_slice(&self.data);
+        w.write_all(&buf)?;
+        w.flush()
+    }
+
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut hdr = [0u8; 6];
+        r.read_exact(&mut hdr)?;
+        let len = u16::from_le_bytes([hdr[4], hdr[5]]) as usize;
+        let mut data = vec![0u8; len];
+        r.read_exact(&mut data)?;
+        Ok(DummyRequest {
+            netfn: hdr[0],
+            lun: hdr[1],
+            cmd: hdr[2],
+            target_cmd: hdr[3],
+            data,
+        })
+    }
+}
+
+impl DummyResponse {
+    /// 针对请求构造响应，netfn 自动加 1
+    pub fn reply(req: &DummyRequest, ccode: u8, data: &[u8]) -> Self {
+        DummyResponse {
+            netfn: req.netfn | 0x01,
+            cmd: req.cmd,
+            seq: 0,
+            lun: req.lun,
+            ccode,
+            data: data.to_vec(),
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut buf = Vec::with_capacity(9 + self.data.len());
+        buf.extend_from_slice(&[self.netfn, self.cmd, self.seq, self.lun, self.ccode]);
+        buf.extend_from_slice(&(self.data.len() as i32).to_le_bytes());
+        buf.extend_from_slice(&self.data);
+        w.write_all(&buf)?;
+        w.flush()
+    }
+
+    pub fn read_from<R: Read>(r: &mut R) -> io::Result<Self> {
+        let mut hdr = [0u8; 9];
+        r.read_exact(&mut hdr)?;
+        let len = i32::from_le_bytes([hdr[5], hdr[6], hdr[7], hdr[8]]);
+        if len < 0 || len as usize > IPMI_BUF_SIZE {
+            return Err(invalid("invalid response data length"));
+        }
+        let mut data = vec![0u8; len as usize];
+        r.read_exact(&mut data)?;
+        Ok(DummyResponse {
+            netfn: hdr[0],
+            cmd: hdr[1],
+            seq: hdr[2],
+            lun: hdr[3],
+            ccode: hdr[4],
+            data,
+        })
+    }
+
+    pub fn into_rs(self) -> IpmiRs {
+        let mut rsp = IpmiRs {
+            ccode: self.ccode,
+            data: [0; IPMI_BUF_SIZE],
+            data_len: self.data.len() as i32,
+            msg: IpmiRsMsg {
+                netfn: self.netfn,
+                cmd: self.cmd,
+                seq: self.seq,
+                lun: self.lun,
+            },
+            session: IpmiSession::default(),
+            payload: IpmiRsPayload::IpmiResponse {
+                rq_addr: IPMI_REMOTE_SWID,
+                netfn: self.netfn,
+                rq_lun: 0,
+                rs_addr: IPMI_BMC_SLAVE_ADDR as u8,
+                rq_seq: self.seq,
+                rs_lun: self.lun,
+                cmd: self.cmd,
+            },
+        };
+        rsp.data[..self.data.len()].copy_from_slice(&self.data);
+        rsp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_wire_round_trip() {
+        let req = DummyRequest {
+            netfn: 0x0a,
+            lun: 0,
+            cmd: 0x23,
+            target_cmd: 0,
+            data: vec![0x00, 0x00, 0xff, 0xff, 0x00, 0x05],
+        };
+        let mut buf = Vec::new();
+        req.write_to(&mut buf).unwrap();
+        assert_eq!(&buf[..6], &[0x0a, 0x00, 0x23, 0x00, 0x06, 0x00]);
+        assert_eq!(
+            DummyRequest::read_from(&mut Cursor::new(&buf)).unwrap(),
+            req
+        );
+
+        let rsp = DummyResponse::reply(&req, 0xc5, &[]);
+        let mut buf = Vec::new();
+        rsp.write_to(&mut buf).unwrap();
+        assert_eq!(buf, [0x0b, 0x23, 0x00, 0x00, 0xc5, 0, 0, 0, 0]);
+        let rs = DummyResponse::read_from(&mut Cursor::new(&buf))
+            .unwrap()
+            .into_rs();
+        assert_eq!(rs.ccode, 0xc5);
+        assert_eq!(rs.msg.netfn, 0x0b);
+        assert_eq!(rs.data_len, 0);
+
+        // 负的或超长的数据长度视为损坏
+        let bad = [0x0b, 0x23, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+        assert!(DummyResponse::read_from(&mut Cursor::new(&bad)).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! IPMI v1.5 LAN 接口的异步版本
+//!
+//! 会话建立与关闭沿用 [`IpmiLanIntf`] 的同步实现 (在阻塞线程池上执行)，
+//! 会话建立后 socket 转交给 `tokio::net::UdpSocket`，请求收发全部异步完成。
+
+use super::bridge::PendingRequest;
+use super::lan::IpmiLanIntf;
+use crate::error::{IpmiError, IpmiResult};
+use crate::ipmi::async_intf::{run_blocking, AsyncIpmiIntf, IpmiFuture};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IPMI_BUF_SIZE};
+use crate::{log_debug, log_info};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+pub struct AsyncLanIntf {
+    inner: IpmiLanIntf,
+    socket: Option<UdpSocket>,
+}
+
+impl AsyncLanIntf {
+    /// `intf` 已设置好主机、凭据与超时
+    pub fn new(intf: IpmiLanIntf) -> Self {
+        Self {
+            inner: intf,
+            socket: None,
+        }
+    }
+
+    fn placeholder() -> IpmiLanIntf {
+        IpmiLanIntf::new(String::new(), 0, IpmiContext::default())
+    }
+
+    async fn open_session(&mut self) -> IpmiResult<()> {
+        if self.socket.is_some() {
+            return Ok(());
+        }
+
+        run_blocking(&mut self.inner, Self::placeholder(), |intf| intf.open()).await??;
+
+        let socket = self
+            .inner
+            .socket
+            .take()
+            .ok_or_else(|| IpmiError::Network("Session socket missing".to_string()))?;
+        socket.set_nonblocking(true)?;
+        self.socket = Some(UdpSocket::from_std(socket)?);
+        Ok(())
+    }
+
+    async fn close_session(&mut self) {
+        let Some(socket) = self.socket.take() else {
+            return;
+        };
+
+        // Close Session 由同步实现发送
+        let socket = socket
+            .into_std()
+            .and_then(|s| s.set_nonblocking(false).map(|_| s));
+        match socket {
+            Ok(socket) => {
+                self.inner.socket = Some(socket);
+                let _ =
+                    run_blocking(&mut self.inner, Self::placeholder(), |intf| intf.close()).await;
+            }
+            Err(e) => log_debug!("Unable to hand socket back for Close Session: {}", e),
+        }
+    }
+
+    async fn send_command(
+        &mut self,
+        packet: Vec<u8>,
+        initial: PendingRequest,
+    ) -> Result<IpmiRs, String> {
+        let socket = self.socket.as_ref().ok_or("Socket not initialized")?;
+        let timeout = Duration::from_secs(self.inner.timeout);
+        let retry_count = self.inner.retry_count;
+
+        let mut buffer = [0u8; IPMI_BUF_SIZE];
+        for attempt in 0..retry_count {
+            let mut pending = initial;
+            socket
+                .send(&packet)
+                .await
+                .map_err(|e| format!("Failed to send packet: {}", e))?;
+
+            let deadline = tokio::time::Instant::now() + timeout;
+            loop {
+                let len = match tokio::time::timeout_at(deadline, socket.recv(&mut buffer)).await {
+                    Ok(Ok(len)) => len,
+                    Ok(Err(e)) => return Err(format!("Failed to receive response: {}", e)),
+                    Err(_) => {
+                        log_info!(
+                            "No response to command 0x{:02x} (attempt {}/{})",
+                            initial.target_cmd,
+                            attempt + 1,
+                            retry_count
+                        );
+                        break;
+                    }
+                };
+
+                if let Some(rsp) = self.inner.accept_response(&buffer[..len], &mut pending) {
+                    return Ok(rsp);
+                }
+            }
+        }
+
+        Err("No response from remote controller".to_string())
+    }
+}
+
+impl AsyncIpmiIntf for AsyncLanIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        self.inner.context()
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        self.inner.setup()
+    }
+
+    fn open(&mut self) -> IpmiFuture<'_, IpmiResult<()>> {
+        Box::pin(self.open_session())
+    }
+
+    fn close(&mut self) -> IpmiFuture<'_, ()> {
+        Box::pin(self.close_session())
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> IpmiFuture<'_, Option<IpmiRs>> {
+        let prepared = if self.socket.is_some() {
+            let bridging_level = self.inner.context().get_bridging_level();
+            self.inner.prepare_request(req, bridging_level)
+        } else {
+            Err("Session not open".to_string())
+        };
+
+        Box::pin(async move {
+            let result = match prepared {
+                Ok((packet, pending)) => self.send_command(packet, pending).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(rsp) => Some(rsp),
+                Err(e) => {
+                    log::error!("Failed to send command: {}", e);
+                    None
+                }
+            }
+        })
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.inner.set_my_addr(addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::lan::auth::IPMI_SESSION_AUTHTYPE_MD5;
+    use crate::ipmi::async_intf::BlockingIntf;
+    use crate::ipmi::ipmi::{IPMI_NETFN_APP, IPMI_NETFN_STORAGE};
+    use crate::sim::{SimBmc, SimLanServer, SimModel};
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    fn start_server(sel_time: u32) -> SocketAddr {
+        let model = SimModel::from_json(&format!(
+            r#"{{ "users": [{{ "id": 2, "name": "admin", "password": "secret" }}],
+                  "sel_time": {} }}"#,
+            sel_time
+        ))
+        .unwrap();
+        let bmc = Arc::new(Mutex::new(SimBmc::new(model).unwrap()));
+        let mut server = SimLanServer::bind("127.0.0.1:0".parse().unwrap(), bmc).unwrap();
+        let addr = server.local_addr().unwrap();
+        std::thread::spawn(move || server.run());
+        addr
+    }
+
+    fn async_intf(addr: SocketAddr) -> AsyncLanIntf {
+        let mut lan =
+            IpmiLanIntf::new("127.0.0.1".to_string(), addr.port(), IpmiContext::default());
+        lan.set_credentials("admin".to_string(), "secret".to_string());
+        lan.set_authtype(IPMI_SESSION_AUTHTYPE_MD5);
+        lan.set_timeout(1, 1);
+        AsyncLanIntf::new(lan)
+    }
+
+    fn get_sel_time() -> IpmiRq {
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+        req.msg.cmd = 0x48;
+        req
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_async_lan_many_hosts() {
+        let hosts = [start_server(1700000000), start_server(1800000000)];
+        let tasks: Vec<_> = hosts
+            .into_iter()
+            .map(|addr| {
+                tokio::spawn(async move {
+                    let mut intf = async_intf(addr);
+                    intf.setup().unwrap();
+                    intf.open().await.unwrap();
+                    let mut times = Vec::new();
+                    for _ in 0..3 {
+                        // 请求是临时值，await 时已经释放，任务才是 Send
+                        let pending = intf.sendrecv(&get_sel_time());
+                        let rsp = pending.await.unwrap();
+                        assert_eq!(rsp.ccode, 0);
+                        times.push(u32::from_le_bytes(rsp.data[..4].try_into().unwrap()));
+                    }
+                    intf.close().await;
+                    // 关闭后不再隐式重连
+                    let pending = intf.sendrecv(&get_sel_time());
+                    assert!(pending.await.is_none());
+                    times
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for task in tasks {
+            results.push(task.await.unwrap());
+        }
+        assert_eq!(results[0], vec![1700000000; 3]);
+        assert_eq!(results[1], vec![1800000000; 3]);
+    }
+
+    #[test]
+    fn test_blocking_adapter() {
+        let addr = start_server(1700000000);
+        let mut intf = BlockingIntf::new(async_intf(addr)).unwrap();
+        intf.setup().unwrap();
+        intf.open().unwrap();
+
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = 0x01;
+        let rsp = intf.sendrecv(&req).unwrap();
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(rsp.data[0], 0x20);
+        intf.close();
+    }
+}
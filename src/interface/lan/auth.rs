@@ -79,36 +79,58 @@ impl IpmiAuth {
     }
 
     /// Calculate MD5 authentication code
+    ///
+    /// AuthCode = MD5(password + session id + IPMI message + session seq + password)
     fn calculate_md5_authcode(
         &self,
-        _session_id: u32,
-        _session_seq: u32,
-        _password: &[u8],
-        _msg_data: &[u8],
+        session_id: u32,
+        session_seq: u32,
+        password: &[u8],
+        msg_data: &[u8],
     ) -> Result<[u8; 16], String> {
-        // TODO: Implement proper MD5 authentication code calculation
-        // For now, return zeros to get the code compiling
-        Ok([0u8; 16])
+        use md5::{Digest, Md5};
+
+        let pass = pad_password(password);
+        let mut hasher = Md5::new();
+        hasher.update(pass);
+        hasher.update(session_id.to_le_bytes());
+        hasher.update(msg_data);
+        hasher.update(session_seq.to_le_bytes());
+        hasher.update(pass);
+
+        let mut authcode = [0u8; 16];
+        authcode.copy_from_slice(&hasher.finalize());
+        Ok(authcode)
     }
 
-    /// Calculate MD2 authentication code (simplified implementation)
+    /// Calculate MD2 authentication code
+    ///
+    /// Same layout as MD5, only the digest algorithm differs.
     fn calculate_md2_authcode(
         &self,
-        _session_id: u32,
-        _session_seq: u32,
-        _password: &[u8],
-        _msg_data: &[u8],
+        session_id: u32,
+        session_seq: u32,
+        password: &[u8],
+        msg_data: &[u8],
     ) -> Result<[u8; 16], String> {
-        // MD2 is deprecated and rarely used, return error for now
-        Err("MD2 authentication not implemented".to_string())
+        use md2::{Digest, Md2};
+
+        let pass = pad_password(password);
+        let mut hasher = Md2::new();
+        hasher.update(pass);
+        hasher.update(session_id.to_le_bytes());
+        hasher.update(msg_data);
+        hasher.update(session_seq.to_le_bytes());
+        hasher.update(pass);
+
+        let mut authcode = [0u8; 16];
+        authcode.copy_from_slice(&hasher.finalize());
+        Ok(authcode)
     }
 
     /// Calculate password authentication code (straight password)
     fn calculate_password_authcode(&self, password: &[u8]) -> Result<[u8; 16], String> {
-        let mut authcode = [0u8; 16];
-        let pwd_len = std::cmp::min(password.len(), 16);
-        authcode[..pwd_len].copy_from_slice(&password[..pwd_len]);
-        Ok(authcode)
+        Ok(pad_password(password))
     }
 
     /// Verify authentication code
@@ -127,6 +149,14 @@ impl IpmiAuth {
     }
 }
 
+/// Pad (or truncate) a password to the 16 byte IPMI v1.5 authcode field
+pub fn pad_password(password: &[u8]) -> [u8; 16] {
+    let mut pass = [0u8; 16];
+    let pwd_len = std::cmp::min(password.len(), 16);
+    pass[..pwd_len].copy_from_slice(&password[..pwd_len]);
+    pass
+}
+
 /// Parse authentication capabilities from BMC response
 pub fn parse_auth_capabilities(data: &[u8]) -> Result<HashMap<String, u8>, String> {
     if data.len() < 8 {
@@ -0,0 +1,283 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! LAN/LAN+ 会话上的 Send Message 封装 (单重与双重桥接)
+//!
+//! 参照 ipmitool lan.c/lanplus.c：桥接请求外层是发给 BMC 的 Send Message，
+//! 双重桥接再嵌套一层发给中转控制器的 Send Message。应答可能直接内嵌在
+//! Send Message 应答的数据中，也可能在空的 Send Message 应答之后单独到达。
+
+use super::lan::IpmiLanIntf;
+use crate::interface::open::open::ipmi_csum;
+use crate::ipmi::intf::IpmiContext;
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IPMI_BMC_SLAVE_ADDR, IPMI_NETFN_APP, IPMI_REMOTE_SWID};
+use crate::log_debug;
+
+/// Send Message 命令号
+pub(crate) const IPMI_SEND_MSG: u8 = 0x34;
+/// Send Message 通道字节中的 Track Request 标志
+const IPMI_SEND_MSG_TRACK: u8 = 0x40;
+
+/// 等待应答的请求，记录期望的序号、命令以及尚未解开的桥接层数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PendingRequest {
+    pub rq_seq: u8,
+    /// 当前期望的应答命令，桥接时先是 Send Message
+    pub cmd: u8,
+    /// 被桥接的原始命令
+    pub target_cmd: u8,
+    pub bridging_level: u8,
+}
+
+impl PendingRequest {
+    /// 不经桥接的请求
+    pub fn new(rq_seq: u8, cmd: u8) -> Self {
+        Self {
+            rq_seq,
+            cmd,
+            target_cmd: cmd,
+            bridging_level: 0,
+        }
+    }
+
+    /// 应答的序号与命令是否属于该请求
+    pub fn matches(&self, seq: u8, cmd: u8) -> bool {
+        seq == self.rq_seq && cmd == self.cmd
+    }
+
+    /// 逐层解开 Send Message 应答
+    ///
+    /// 返回 None 表示收到的是不带内嵌应答的 Send Message 应答，
+    /// 被桥接命令的应答将单独到达，调用方应继续等待。
+    pub fn unwrap_response(&mut self, mut rsp: IpmiRs) -> Option<IpmiRs> {
+        while self.bridging_level > 0
+            && rsp.msg.netfn == IPMI_NETFN_APP + 1
+            && rsp.msg.cmd == IPMI_SEND_MSG
+        {
+            self.bridging_level -= 1;
+            if self.bridging_level == 0 {
+                self.cmd = self.target_cmd;
+            }
+            // Send Message 本身失败，没有内嵌应答
+            if rsp.ccode != 0 {
+                return Some(rsp);
+            }
+            if rsp.data_len <= 0 {
+                return None;
+            }
+
+            let len = rsp.data_len as usize;
+            let session = std::mem::take(&mut rsp.session);
+            rsp = match IpmiLanIntf::parse_message(&rsp.data[..len], session) {
+                Ok(inner) => inner,
+                Err(e) => {
+                    log_debug!("Discarding bridged response: {}", e);
+                    return None;
+                }
+            };
+            self.rq_seq = rsp.msg.seq;
+        }
+        Some(rsp)
+    }
+}
+
+/// 构造请求的 IPMI 消息 (rsSA .. checksum)，`bridging_level` 为 1 或 2 时用 Send Message 封装
+pub(crate) fn build_request(
+    req: &IpmiRq,
+    rq_seq: u8,
+    bridging_level: u8,
+    ctx: &IpmiContext,
+) -> (Vec<u8>, PendingRequest) {
+    if bridging_level == 0 {
+        return (
+            IpmiLanIntf::build_message(req, rq_seq),
+            PendingRequest::new(rq_seq, req.msg.cmd),
+        );
+    }
+
+    let my_addr = ctx.my_addr() as u8;
+    let mut msg = Vec::with_capacity(24 + req.msg.data_len as usize);
+    // 每层消息体 (rqSA 起) 的起始位置，最后按从内到外的顺序补校验和
+    let mut bodies = Vec::with_capacity(2);
+
+    // 外层：发给 BMC 的 Send Message
+    msg.extend_from_slice(&[IPMI_BMC_SLAVE_ADDR as u8, IPMI_NETFN_APP << 2]);
+    msg.push(ipmi_csum(&msg));
+    bodies.push(msg.len());
+    msg.extend_from_slice(&[IPMI_REMOTE_SWID, rq_seq << 2, IPMI_SEND_MSG]);
+
+    if bridging_level == 2 {
+        // 双重桥接：经中转通道发给中转控制器的 Send Message
+        msg.push(IPMI_SEND_MSG_TRACK | ctx.transit_channel());
+        let hdr = msg.len();
+        msg.extend_from_slice(&[ctx.transit_addr() as u8, IPMI_NETFN_APP << 2]);
+        msg.push(ipmi_csum(&msg[hdr..]));
+        bodies.push(msg.len());
+        msg.extend_from_slice(&[my_addr, rq_seq << 2, IPMI_SEND_MSG]);
+    }
+
+    // 内层：发给目标控制器的原始请求
+    msg.push(IPMI_SEND_MSG_TRACK | ctx.target_channel());
+    let hdr = msg.len();
+    msg.extend_from_slice(&[ctx.target_addr() as u8, req.msg.netfn_lun]);
+    msg.push(ipmi_csum(&msg[hdr..]));
+    let body = msg.len();
+    msg.extend_from_slice(&[my_addr, rq_seq << 2, req.msg.cmd]);
+    if let Some(data) = req.msg.data() {
+        msg.extend_from_slice(data);
+    }
+    msg.push(ipmi_csum(&msg[body..]));
+
+    for start in bodies.into_iter().rev() {
+        let csum = ipmi_csum(&msg[start..]);
+        msg.push(csum);
+    }
+
+    (
+        msg,
+        PendingRequest {
+            rq_seq,
+            cmd: IPMI_SEND_MSG,
+            target_cmd: req.msg.cmd,
+            bridging_level,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipmi::ipmi::IpmiSession;
+
+    const GET_DEVICE_ID: u8 = 0x01;
+
+    fn bridged_ctx() -> IpmiContext {
+        let mut ctx = IpmiContext::default();
+        ctx.set_my_addr(0x20);
+        ctx.set_target_addr(0x82);
+        ctx.set_target_channel(7);
+        ctx
+    }
+
+    fn get_device_id() -> IpmiRq {
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = GET_DEVICE_ID;
+        req
+    }
+
+    /// 目标控制器的应答消息 (rqSA .. checksum)
+    fn response_msg(rq_addr: u8, rs_addr: u8, seq: u8, cmd: u8, ccode: u8, data: &[u8]) -> Vec<u8> {
+        let mut msg = vec![rq_addr, (IPMI_NETFN_APP + 1) << 2];
+        msg.push(ipmi_csum(&msg));
+        msg.extend_from_slice(&[rs_addr, seq << 2, cmd, ccode]);
+        msg.extend_from_slice(data);
+        msg.push(ipmi_csum(&msg[3..]));
+        msg
+    }
+
+    fn parse(msg: &[u8]) -> IpmiRs {
+        IpmiLanIntf::parse_message(msg, IpmiSession::default()).unwrap()
+    }
+
+    #[test]
+    fn test_single_bridging_encoding() {
+        let ctx = bridged_ctx();
+        assert_eq!(ctx.get_bridging_level(), 1);
+        let (msg, pending) = build_request(&get_device_id(), 5, 1, &ctx);
+
+        assert_eq!(
+            msg,
+            [
+                0x20, 0x18, 0xc8, 0x81, 0x14, 0x34, 0x47, // Send Message, channel 7
+                0x82, 0x18, 0x66, 0x20, 0x14, 0x01, 0xcb, // Get Device ID
+                0xf0
+            ]
+        );
+        assert_eq!(pending.cmd, IPMI_SEND_MSG);
+        assert_eq!(pending.target_cmd, GET_DEVICE_ID);
+        assert_eq!(ipmi_csum(&msg[3..]), 0);
+        assert_eq!(ipmi_csum(&msg[10..14]), 0);
+    }
+
+    #[test]
+    fn test_double_bridging_encoding() {
+        let mut ctx = bridged_ctx();
+        ctx.enable_bridging(0x72, 0);
+        assert_eq!(ctx.get_bridging_level(), 2);
+        let (msg, pending) = build_request(&get_device_id(), 5, 2, &ctx);
+
+        assert_eq!(&msg[..7], &[0x20, 0x18, 0xc8, 0x81, 0x14, 0x34, 0x40]);
+        assert_eq!(&msg[7..14], &[0x72, 0x18, 0x76, 0x20, 0x14, 0x34, 0x47]);
+        assert_eq!(&msg[14..17], &[0x82, 0x18, 0x66]);
+        assert_eq!(msg.len(), 23);
+        // 三层消息体的校验和都闭合
+        assert_eq!(ipmi_csum(&msg[17..21]), 0);
+        assert_eq!(ipmi_csum(&msg[10..22]), 0);
+        assert_eq!(ipmi_csum(&msg[3..]), 0);
+        assert_eq!(pending.bridging_level, 2);
+    }
+
+    #[test]
+    fn test_unbridged_request() {
+        let (msg, pending) = build_request(&get_device_id(), 5, 0, &IpmiContext::default());
+        assert_eq!(msg, IpmiLanIntf::build_message(&get_device_id(), 5));
+        assert_eq!(pending, PendingRequest::new(5, GET_DEVICE_ID));
+
+        let mut pending = pending;
+        let rsp = parse(&response_msg(0x81, 0x20, 5, GET_DEVICE_ID, 0, &[0x20]));
+        assert_eq!(pending.unwrap_response(rsp).unwrap().data_len, 1);
+    }
+
+    #[test]
+    fn test_unwrap_embedded_response() {
+        let mut pending = build_request(&get_device_id(), 5, 1, &bridged_ctx()).1;
+        let inner = response_msg(0x20, 0x82, 5, GET_DEVICE_ID, 0, &[0x20, 0x01]);
+        let outer = parse(&response_msg(0x81, 0x20, 5, IPMI_SEND_MSG, 0, &inner));
+        assert!(pending.matches(outer.msg.seq, outer.msg.cmd));
+
+        let rsp = pending.unwrap_response(outer).unwrap();
+        assert_eq!(rsp.msg.cmd, GET_DEVICE_ID);
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(&rsp.data[..rsp.data_len as usize], &[0x20, 0x01]);
+        assert_eq!(pending.bridging_level, 0);
+    }
+
+    #[test]
+    fn test_unwrap_separate_response() {
+        let mut pending = build_request(&get_device_id(), 5, 1, &bridged_ctx()).1;
+        let ack = parse(&response_msg(0x81, 0x20, 5, IPMI_SEND_MSG, 0, &[]));
+        assert!(pending.unwrap_response(ack).is_none());
+        // 之后到达的应答按原始命令匹配
+        assert_eq!(pending.cmd, GET_DEVICE_ID);
+
+        let late = parse(&response_msg(0x20, 0x82, 5, GET_DEVICE_ID, 0, &[0x20]));
+        assert!(pending.matches(late.msg.seq, late.msg.cmd));
+        assert_eq!(pending.unwrap_response(late).unwrap().data_len, 1);
+    }
+
+    #[test]
+    fn test_unwrap_double_and_errors() {
+        let mut ctx = bridged_ctx();
+        ctx.enable_bridging(0x72, 0);
+        let initial = build_request(&get_device_id(), 9, 2, &ctx).1;
+
+        let target = response_msg(0x20, 0x82, 9, GET_DEVICE_ID, 0xc1, &[]);
+        let transit = response_msg(0x20, 0x72, 9, IPMI_SEND_MSG, 0, &target);
+        let bmc = response_msg(0x81, 0x20, 9, IPMI_SEND_MSG, 0, &transit);
+        let mut pending = initial;
+        let rsp = pending.unwrap_response(parse(&bmc)).unwrap();
+        assert_eq!(rsp.msg.cmd, GET_DEVICE_ID);
+        assert_eq!(rsp.ccode, 0xc1);
+
+        // Send Message 失败时直接返回其完成码
+        let mut pending = initial;
+        let failed = parse(&response_msg(0x81, 0x20, 9, IPMI_SEND_MSG, 0x83, &[]));
+        let rsp = pending.unwrap_response(failed).unwrap();
+        assert_eq!(rsp.msg.cmd, IPMI_SEND_MSG);
+        assert_eq!(rsp.ccode, 0x83);
+    }
+}
@@ -0,0 +1,346 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! RMCP/ASF presence ping 与会话外的 Get Channel Authentication Capabilities
+//!
+//! 两者都不需要建立会话，用来确认主机上是否有 BMC 以及它支持的认证方式。
+
+use super::lan::IpmiLanIntf;
+use super::rmcp::{create_ping_packet, RmcpPong};
+use crate::ipmi::intf::{
+    IpmiContext, IPMI_AUTHSTATUS_ANONYMOUS_USERS_ENABLED, IPMI_AUTHSTATUS_NONNULL_USERS_ENABLED,
+    IPMI_AUTHSTATUS_NULL_USERS_ENABLED, IPMI_AUTHSTATUS_PER_MSG_DISABLED,
+    IPMI_AUTHSTATUS_PER_USER_DISABLED,
+};
+use crate::ipmi::ipmi::{IpmiRq, IPMI_BUF_SIZE, IPMI_NETFN_APP};
+use crate::log_debug;
+use std::collections::{HashMap, HashSet};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+const IPMI_GET_CHANNEL_AUTH_CAP: u8 = 0x38;
+const IPMI_LAN_CHANNEL_E: u8 = 0x0e;
+const IPMI_SESSION_PRIV_ADMIN: u8 = 0x4;
+/// 请求字节 1 的 bit 7：同时获取 IPMI v2.0 扩展数据
+const AUTH_CAP_V2_DATA: u8 = 0x80;
+
+/// ping 使用的 RMCP 序号，0xff 表示不需要 RMCP ACK
+const PING_RMCP_SEQ: u8 = 0xff;
+
+/// 一个 pong 应答
+#[derive(Debug, Clone)]
+pub struct PongReply {
+    pub addr: SocketAddr,
+    pub pong: RmcpPong,
+    /// 从发出 ping 到收到 pong 的时间
+    pub elapsed: Duration,
+}
+
+/// 会话外 Get Channel Authentication Capabilities 的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelAuthCap {
+    pub channel: u8,
+    /// 响应带有 IPMI v2.0 扩展数据
+    pub v20_data: bool,
+    /// 支持的 v1.5 认证类型位图
+    pub auth_types: u8,
+    pub auth_status: u8,
+    /// v2.0 扩展能力 (bit 1: 支持 IPMI v2.0 / RMCP+, bit 0: 支持 v1.5)
+    pub ext_caps: u8,
+    pub oem_id: u32,
+    pub oem_aux: u8,
+}
+
+impl ChannelAuthCap {
+    pub fn from_response(data: &[u8]) -> Result<Self, String> {
+        if data.len() < 8 {
+            return Err(format!("short response ({} bytes)", data.len()));
+        }
+        Ok(Self {
+            channel: data[0],
+            v20_data: data[1] & AUTH_CAP_V2_DATA != 0,
+            auth_types: data[1] & 0x3f,
+            auth_status: data[2],
+            ext_caps: data[3],
+            oem_id: u32::from_le_bytes([data[4], data[5], data[6], 0]),
+            oem_aux: data[7],
+        })
+    }
+
+    /// BMC 支持的最高 IPMI 版本
+    pub fn ipmi_version(&self) -> &'static str {
+        if self.v20_data && self.ext_caps & 0x02 != 0 {
+            "2.0"
+        } else {
+            "1.5"
+        }
+    }
+
+    pub fn per_msg_auth(&self) -> bool {
+        self.auth_status & IPMI_AUTHSTATUS_PER_MSG_DISABLED == 0
+    }
+
+    pub fn user_level_auth(&self) -> bool {
+        self.auth_status & IPMI_AUTHSTATUS_PER_USER_DISABLED == 0
+    }
+
+    /// 允许的登录方式：non-null / null 用户名、匿名
+    pub fn logins(&self) -> Vec<&'static str> {
+        [
+            (IPMI_AUTHSTATUS_NONNULL_USERS_ENABLED, "non-null"),
+            (IPMI_AUTHSTATUS_NULL_USERS_ENABLED, "null"),
+            (IPMI_AUTHSTATUS_ANONYMOUS_USERS_ENABLED, "anonymous"),
+        ]
+        .into_iter()
+        .filter(|(bit, _)| self.auth_status & bit != 0)
+        .map(|(_, name)| name)
+        .collect()
+    }
+}
+
+fn bind_for(target: &SocketAddr) -> std::io::Result<UdpSocket> {
+    if target.is_ipv4() {
+        UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+    } else {
+        UdpSocket::bind((Ipv6Addr::UNSPECIFIED, 0))
+    }
+}
+
+/// 向单台主机发送 presence ping，超时 (含重试) 后返回 Ok(None)
+pub fn rmcp_ping(
+    target: SocketAddr,
+    timeout: Duration,
+    retries: u32,
+) -> Result<Option<PongReply>, String> {
+    Ok(rmcp_sweep(&[target], false, timeout, retries)?
+        .into_iter()
+        .next())
+}
+
+/// 向一组地址发送 ping 并收集 pong，结果按地址排序
+///
+/// `broadcast` 时目标为广播地址，接受任意主机的应答且总是等满超时；
+/// 否则只接受目标本身的应答，全部应答后提前结束。每轮重试只发给尚未应答的目标。
+pub fn rmcp_sweep(
+    targets: &[SocketAddr],
+    broadcast: bool,
+    timeout: Duration,
+    retries: u32,
+) -> Result<Vec<PongReply>, String> {
+    let Some(first) = targets.first() else {
+        return Ok(Vec::new());
+    };
+    let socket = bind_for(first).map_err(|e| format!("Unable to create socket: {}", e))?;
+    if broadcast {
+        socket
+            .set_broadcast(true)
+            .map_err(|e| format!("Unable to enable broadcast: {}", e))?;
+    }
+
+    let wanted: HashSet<SocketAddr> = targets.iter().copied().collect();
+    let mut replies: HashMap<SocketAddr, PongReply> = HashMap::new();
+    let mut buffer = [0u8; IPMI_BUF_SIZE];
+
+    for attempt in 0..retries.max(1) {
+        let tag = attempt as u8;
+        let packet = create_ping_packet(PING_RMCP_SEQ, tag);
+        let sent = Instant::now();
+        for target in targets {
+            if broadcast || !replies.contains_key(target) {
+                if let Err(e) = socket.send_to(&packet, target) {
+                    log_debug!("Unable to send ping to {}: {}", target, e);
+                }
+            }
+        }
+
+        let deadline = sent + timeout;
+        loop {
+            if !broadcast && replies.len() == wanted.len() {
+                break;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            socket
+                .set_read_timeout(Some(deadline - now))
+                .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
+            let (len, peer) = match socket.recv_from(&mut buffer) {
+                Ok(r) => r,
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    break;
+                }
+                Err(e) => {
+                    // ICMP 不可达等错误只影响单个目标
+                    log_debug!("Receive error: {}", e);
+                    continue;
+                }
+            };
+            let pong = match RmcpPong::from_bytes(&buffer[..len]) {
+                Ok(pong) => pong,
+                Err(e) => {
+                    log_debug!("Discarding packet from {}: {}", peer, e);
+                    continue;
+                }
+            };
+            if (!broadcast && !wanted.contains(&peer)) || replies.contains_key(&peer) {
+                continue;
+            }
+            replies.insert(
+                peer,
+                PongReply {
+                    addr: peer,
+                    pong,
+                    elapsed: sent.elapsed(),
+                },
+            );
+        }
+    }
+
+    let mut replies: Vec<PongReply> = replies.into_values().collect();
+    replies.sort_by_key(|r| r.addr);
+    Ok(replies)
+}
+
+/// 会话外发送 Get Channel Authentication Capabilities
+///
+/// 先请求 v2.0 扩展数据，只支持 v1.5 的 BMC 可能返回错误码，此时不带该位重试。
+pub fn get_channel_auth_cap(
+    target: SocketAddr,
+    timeout: Duration,
+    retries: u32,
+) -> Result<ChannelAuthCap, String> {
+    let socket = bind_for(&target).map_err(|e| format!("Unable to create socket: {}", e))?;
+    socket
+        .connect(target)
+        .map_err(|e| format!("Unable to connect to {}: {}", target, e))?;
+
+    let mut lan = IpmiLanIntf::new(
+        target.ip().to_string(),
+        target.port(),
+        IpmiContext::default(),
+    );
+    let mut last_err = String::new();
+    for v2 in [AUTH_CAP_V2_DATA, 0] {
+        let mut data = [v2 | IPMI_LAN_CHANNEL_E, IPMI_SESSION_PRIV_ADMIN];
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = IPMI_GET_CHANNEL_AUTH_CAP;
+        req.msg.data = data.as_mut_ptr();
+        req.msg.data_len = data.len() as u16;
+        let (packet, mut pending) = lan.prepare_request(&req, 0)?;
+
+        let rsp = exchange(&socket, &packet, timeout, retries, |buf| {
+            lan.accept_response(buf, &mut pending)
+        })?;
+        if rsp.ccode != 0 {
+            last_err = format!("completion code 0x{:02x}", rsp.ccode);
+            continue;
+        }
+        return ChannelAuthCap::from_response(&rsp.data[..rsp.data_len.max(0) as usize]);
+    }
+    Err(last_err)
+}
+
+/// 发送 `packet` 并等待 `accept` 认可的应答，超时重发
+fn exchange<T, F>(
+    socket: &UdpSocket,
+    packet: &[u8],
+    timeout: Duration,
+    retries: u32,
+    mut accept: F,
+) -> Result<T, String>
+where
+    F: FnMut(&[u8]) -> Option<T>,
+{
+    let mut buffer = [0u8; IPMI_BUF_SIZE];
+    for _ in 0..retries.max(1) {
+        socket
+            .send(packet)
+            .map_err(|e| format!("Failed to send packet: {}", e))?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            socket
+                .set_read_timeout(Some(deadline - now))
+                .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
+            match socket.recv(&mut buffer) {
+                Ok(len) => {
+                    if let Some(rsp) = accept(&buffer[..len]) {
+                        return Ok(rsp);
+                    }
+                }
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    break;
+                }
+                Err(e) => return Err(format!("Failed to receive response: {}", e)),
+            }
+        }
+    }
+    Err("No response from remote controller".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{SimBmc, SimLanServer, SimModel};
+    use std::sync::{Arc, Mutex};
+
+    fn start_server() -> SocketAddr {
+        let model = SimModel::from_json(r#"{ "device": { "manufacturer_id": 343 } }"#).unwrap();
+        let bmc = Arc::new(Mutex::new(SimBmc::new(model).unwrap()));
+        let mut server = SimLanServer::bind("127.0.0.1:0".parse().unwrap(), bmc).unwrap();
+        let addr = server.local_addr().unwrap();
+        std::thread::spawn(move || server.run());
+        addr
+    }
+
+    #[test]
+    fn test_ping_and_auth_cap() {
+        let addr = start_server();
+        let timeout = Duration::from_millis(500);
+
+        let reply = rmcp_ping(addr, timeout, 2).unwrap().unwrap();
+        assert_eq!(reply.addr, addr);
+        assert!(reply.pong.ipmi_supported());
+        assert_eq!({ reply.pong.iana }, 343);
+
+        // 未监听的端口上没有应答
+        let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let silent_addr = silent.local_addr().unwrap();
+        let found = rmcp_sweep(&[silent_addr, addr], false, timeout, 1).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].addr, addr);
+
+        let cap = get_channel_auth_cap(addr, timeout, 2).unwrap();
+        assert_eq!(cap.ipmi_version(), "1.5");
+        assert!(cap.per_msg_auth());
+        assert_eq!(cap.logins(), vec!["non-null"]);
+    }
+
+    #[test]
+    fn test_auth_cap_decode() {
+        let cap = ChannelAuthCap::from_response(&[0x01, 0x95, 0x13, 0x02, 0x57, 0x01, 0x00, 0x00])
+            .unwrap();
+        assert_eq!(cap.channel, 1);
+        assert_eq!(cap.ipmi_version(), "2.0");
+        assert_eq!(cap.auth_types, 0x15);
+        assert!(!cap.per_msg_auth());
+        assert!(cap.user_level_auth());
+        assert_eq!(cap.logins(), vec!["null", "anonymous"]);
+        assert_eq!(cap.oem_id, 343);
+        assert!(ChannelAuthCap::from_response(&[0x01, 0x95]).is_err());
+    }
+}
@@ -6,15 +6,25 @@
 
 #![allow(dead_code)]
 
-use super::auth::IpmiAuth;
+use super::auth::{
+    get_auth_types_string, is_auth_type_supported, IpmiAuth, IPMI_SESSION_AUTHTYPE_MD2,
+    IPMI_SESSION_AUTHTYPE_MD5, IPMI_SESSION_AUTHTYPE_NONE, IPMI_SESSION_AUTHTYPE_PASSWORD,
+};
+use super::bridge::{build_request, PendingRequest};
 use super::rmcp::{RmcpHeader, RMCP_UDP_PORT};
-use crate::error::{IpmiError, IpmiResult};
+use crate::error::{completion_code_to_string, IpmiError, IpmiResult};
+use crate::interface::open::open::ipmi_csum;
 /// IPMI LAN Interface Implementation
 ///
 /// Based on reference/ipmitool-c/src/plugins/lan/lan.c
 /// Implements IPMI v1.5 LAN interface over UDP/RMCP
-use crate::ipmi::intf::{IpmiContext, IpmiIntf};
-use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IpmiRsPayload, IpmiV2Payload, IPMI_BUF_SIZE};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf, IPMI_AUTHSTATUS_PER_MSG_DISABLED};
+use crate::ipmi::ipmi::{
+    IpmiRq, IpmiRs, IpmiRsMsg, IpmiRsPayload, IpmiSession, IpmiV2Payload, IPMI_BMC_SLAVE_ADDR,
+    IPMI_BUF_SIZE, IPMI_NETFN_APP, IPMI_REMOTE_SWID,
+};
+use crate::ipmi::strings::privlvl_to_str;
+use crate::{log_debug, log_info};
 
 use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
 use std::time::Duration;
@@ -35,14 +45,24 @@ const IPMI_ACTIVATE_SESSION: u8 = 0x3a;
 const IPMI_SET_SESSION_PRIVILEGE: u8 = 0x3b;
 const IPMI_CLOSE_SESSION: u8 = 0x3c;
 
+// Privilege levels used during session setup
+const IPMI_SESSION_PRIV_USER: u8 = 0x2;
+const IPMI_SESSION_PRIV_ADMIN: u8 = 0x4;
+
+// RMCP header (4) + session header (10) + authcode (16) + message
+const IPMI_LAN_SESSION_HDR_MIN: usize = 14;
+
 /// IPMI LAN Session Information
 #[derive(Debug, Clone, Default)]
 pub struct IpmiLanSession {
     pub active: bool,
     pub session_id: u32,
+    /// Last session sequence number received from the BMC
     pub in_seq: u32,
+    /// Session sequence number used for the next outbound packet
     pub out_seq: u32,
     pub auth: IpmiAuth,
+    pub authstatus: u8,
     pub challenge: [u8; 16],
     pub timeout: u64,
     pub privilege_level: u8,
@@ -61,37 +81,42 @@ impl IpmiLanSession {
 pub struct IpmiLanIntf {
     pub context: IpmiContext,
     pub socket: Option<UdpSocket>,
-    pub target_addr: SocketAddr,
+    pub target_addr: Option<SocketAddr>,
     pub session: IpmiLanSession,
     pub hostname: String,
+    pub port: u16,
     pub username: String,
     pub password: String,
     pub privilege_level: u8,
+    /// Authentication type requested with -A, None selects the best one offered
+    pub authtype_set: Option<u8>,
+    /// 0 = any, otherwise AF_INET/AF_INET6 (-4/-6)
+    pub ai_family: i32,
     pub timeout: u64,
     pub retry_count: u32,
+    /// 6-bit IPMI message sequence number (rqSeq)
+    curr_seq: u8,
 }
 
 impl IpmiLanIntf {
     /// Create new LAN interface
-    pub fn new(hostname: String) -> Result<Self, String> {
-        let target_addr = format!("{}:{}", hostname, RMCP_UDP_PORT)
-            .to_socket_addrs()
-            .map_err(|e| format!("Failed to resolve hostname {}: {}", hostname, e))?
-            .next()
-            .ok_or_else(|| format!("No address found for hostname: {}", hostname))?;
-
-        Ok(Self {
-            context: IpmiContext::default(),
+    pub fn new(hostname: String, port: u16, ctx: IpmiContext) -> Self {
+        Self {
+            context: ctx,
             socket: None,
-            target_addr,
+            target_addr: None,
             session: IpmiLanSession::new(),
             hostname,
+            port: if port == 0 { RMCP_UDP_PORT } else { port },
             username: String::new(),
             password: String::new(),
-            privilege_level: 2, // USER level
+            privilege_level: IPMI_SESSION_PRIV_ADMIN,
+            authtype_set: None,
+            ai_family: 0,
             timeout: IPMI_LAN_TIMEOUT,
             retry_count: IPMI_LAN_RETRY,
-        })
+            curr_seq: 0,
+        }
     }
 
     /// Set authentication credentials
@@ -105,104 +130,120 @@ impl IpmiLanIntf {
         self.privilege_level = level;
     }
 
-    /// Send IPMI command before session is established
-    fn send_command_pre_session(&self, req: &IpmiRq) -> Result<IpmiRs, String> {
-        let socket = self
-            .socket
-            .as_ref()
-            .ok_or_else(|| "Socket not initialized".to_string())?;
-
-        // Build RMCP + IPMI packet for pre-session command
-        let packet = self.build_pre_session_packet(req)?;
-
-        // Send with retry logic
-        for attempt in 0..self.retry_count {
-            // Send packet
-            socket
-                .send_to(&packet, self.target_addr)
-                .map_err(|e| format!("Failed to send packet: {}", e))?;
+    /// Force a specific authentication type instead of auto-selecting one
+    pub fn set_authtype(&mut self, authtype: u8) {
+        self.authtype_set = Some(authtype);
+    }
 
-            // Wait for response
-            socket
-                .set_read_timeout(Some(Duration::from_secs(self.timeout)))
-                .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
-
-            let mut buffer = [0u8; 1024];
-            match socket.recv_from(&mut buffer) {
-                Ok((len, addr)) => {
-                    if addr == self.target_addr {
-                        return self.parse_response(&buffer[..len]);
-                    }
-                }
-                Err(e) if attempt < self.retry_count - 1 => {
-                    log::error!("Attempt {} failed: {}, retrying...", attempt + 1, e);
-                    continue;
-                }
-                Err(e) => return Err(format!("Failed to receive response: {}", e)),
-            }
+    /// Set per-request timeout (seconds) and number of retries
+    pub fn set_timeout(&mut self, timeout: u64, retry: u32) {
+        if timeout > 0 {
+            self.timeout = timeout;
+        }
+        if retry > 0 {
+            self.retry_count = retry;
         }
-
-        Err("Failed to get response after all retries".to_string())
     }
 
-    /// Build RMCP packet for pre-session commands
-    fn build_pre_session_packet(&self, req: &IpmiRq) -> Result<Vec<u8>, String> {
-        let mut packet = Vec::new();
+    /// Restrict hostname resolution to AF_INET or AF_INET6
+    pub fn set_address_family(&mut self, family: i32) {
+        self.ai_family = family;
+    }
 
-        // RMCP header
-        let rmcp = RmcpHeader::new_ipmi(0xff); // No sequence for pre-session
-        packet.extend_from_slice(&rmcp.to_bytes());
+    /// Resolve hostname honouring the requested address family
+    fn resolve_target(&self) -> IpmiResult<SocketAddr> {
+        if self.hostname.is_empty() {
+            return Err(IpmiError::Network("No hostname specified!".to_string()));
+        }
 
-        // IPMI session header (all zeros for pre-session)
-        packet.extend_from_slice(&[0u8; 9]); // Auth type + seq + session ID + auth code placeholder
+        let addrs = (self.hostname.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| {
+                IpmiError::Network(format!(
+                    "Address lookup for {} failed: {}",
+                    self.hostname, e
+                ))
+            })?;
 
-        // IPMI message
-        packet.push(0x20); // rsSA (BMC slave address)
-        packet.push(req.msg.netfn() | (req.msg.lun() << 2));
-        packet.push(0); // Checksum placeholder
-        packet.push(0x81); // rqSA (software ID)
-        packet.push(req.msg.lun() << 2); // rqSEQ + rqLUN
-        packet.push(req.msg.cmd);
+        addrs
+            .into_iter()
+            .find(|addr| match self.ai_family {
+                nix::libc::AF_INET => addr.is_ipv4(),
+                nix::libc::AF_INET6 => addr.is_ipv6(),
+                _ => true,
+            })
+            .ok_or_else(|| {
+                IpmiError::Network(format!("No address found for hostname: {}", self.hostname))
+            })
+    }
 
-        // Add request data
-        if req.msg.data_len > 0 {
-            let data =
-                unsafe { std::slice::from_raw_parts(req.msg.data, req.msg.data_len as usize) };
-            packet.extend_from_slice(data);
+    /// Build IPMI message (rsSA .. checksum) for a request
+    pub(crate) fn build_message(req: &IpmiRq, rq_seq: u8) -> Vec<u8> {
+        let mut msg = Vec::with_capacity(7 + req.msg.data_len as usize);
+
+        msg.push(IPMI_BMC_SLAVE_ADDR as u8); // rsSA
+        msg.push(req.msg.netfn_lun);
+        msg.push(ipmi_csum(&msg[0..2]));
+        msg.push(IPMI_REMOTE_SWID); // rqSA
+        msg.push(rq_seq << 2); // rqSeq + rqLUN
+        msg.push(req.msg.cmd);
+        if let Some(data) = req.msg.data() {
+            msg.extend_from_slice(data);
         }
+        let csum = ipmi_csum(&msg[3..]);
+        msg.push(csum);
 
-        // Calculate and update checksums
-        self.update_checksums(&mut packet);
+        msg
+    }
 
-        Ok(packet)
+    /// Build RMCP + IPMI v1.5 session packet
+    ///
+    /// Before the session is active the session header is all zeros, afterwards
+    /// it carries the session ID, outbound sequence number and authcode.
+    fn build_packet(&mut self, req: &IpmiRq, rq_seq: u8) -> Result<Vec<u8>, String> {
+        self.build_session_packet(&Self::build_message(req, rq_seq))
     }
 
-    /// Update IPMI message checksums
-    fn update_checksums(&self, packet: &mut Vec<u8>) {
-        if packet.len() < 16 {
-            return;
-        }
+    /// Wrap an IPMI message (rsSA .. checksum) into an RMCP + session packet
+    fn build_session_packet(&mut self, msg: &[u8]) -> Result<Vec<u8>, String> {
+        let mut packet = Vec::with_capacity(IPMI_LAN_SESSION_HDR_MIN + 16 + msg.len());
 
-        // First checksum (header checksum)
-        let hdr_start = 13; // After RMCP + session header
-        let hdr_sum = (0x100u16 - (packet[hdr_start] as u16 + packet[hdr_start + 1] as u16)) as u8;
-        packet[hdr_start + 2] = hdr_sum;
+        // RMCP header
+        let rmcp = RmcpHeader::new_ipmi(0xff); // No RMCP ACK requested
+        packet.extend_from_slice(&rmcp.to_bytes());
 
-        // Second checksum (message checksum)
-        if packet.len() > 16 {
-            let msg_start = 16;
-            let mut sum = 0u8;
-            for item in packet.iter().skip(msg_start) {
-                sum = sum.wrapping_add(*item);
-            }
-            let msg_sum = (0x100u16 as u8).wrapping_sub(sum);
-            packet.push(msg_sum);
+        // IPMI session header
+        let (authtype, seq, session_id) = if self.session.active {
+            (
+                self.session.auth.authtype,
+                self.session.out_seq,
+                self.session.session_id,
+            )
+        } else {
+            (IPMI_SESSION_AUTHTYPE_NONE, 0, 0)
+        };
+        packet.push(authtype);
+        packet.extend_from_slice(&seq.to_le_bytes());
+        packet.extend_from_slice(&session_id.to_le_bytes());
+
+        if authtype != IPMI_SESSION_AUTHTYPE_NONE {
+            let password = self.password.clone();
+            let authcode =
+                self.session
+                    .auth
+                    .calculate_authcode(session_id, seq, password.as_bytes(), msg)?;
+            packet.extend_from_slice(&authcode);
         }
+
+        packet.push(msg.len() as u8);
+        packet.extend_from_slice(msg);
+
+        Ok(packet)
     }
 
     /// Parse IPMI response packet
     fn parse_response(&self, data: &[u8]) -> Result<IpmiRs, String> {
-        if data.len() < 16 {
+        if data.len() < IPMI_LAN_SESSION_HDR_MIN {
             return Err("Response packet too short".to_string());
         }
 
@@ -212,53 +253,488 @@ impl IpmiLanIntf {
             return Err("Not an IPMI response".to_string());
         }
 
-        // Skip session header (9 bytes) for pre-session commands
-        let msg_start = 13;
-        if data.len() < msg_start + 7 {
+        // Session header
+        let authtype = data[4];
+        let seq = u32::from_le_bytes([data[5], data[6], data[7], data[8]]);
+        let id = u32::from_le_bytes([data[9], data[10], data[11], data[12]]);
+        let mut offset = 13;
+        if authtype != IPMI_SESSION_AUTHTYPE_NONE {
+            offset += 16;
+        }
+        if data.len() <= offset {
+            return Err("Response packet too short".to_string());
+        }
+        let msg_len = data[offset] as usize;
+        offset += 1;
+
+        if data.len() < offset + msg_len {
             return Err("IPMI message too short".to_string());
         }
 
-        // Parse IPMI message
-        let _rs_sa = data[msg_start];
-        let netfn_lun = data[msg_start + 1];
-        let _checksum1 = data[msg_start + 2];
-        let _rq_sa = data[msg_start + 3];
-        let _seq_lun = data[msg_start + 4];
-        let cmd = data[msg_start + 5];
-        let ccode = data[msg_start + 6];
+        Self::parse_message(
+            &data[offset..offset + msg_len],
+            IpmiSession {
+                authtype,
+                seq,
+                id,
+                msglen: msg_len as u16,
+                ..Default::default()
+            },
+        )
+    }
 
-        // Extract response data
-        let data_start = msg_start + 7;
-        let data_len = if data.len() > data_start + 1 {
-            data.len() - data_start - 1 // Subtract final checksum
-        } else {
-            0
-        };
+    /// Decode an IPMI response message (rqSA .. checksum) into IpmiRs
+    pub(crate) fn parse_message(msg: &[u8], session: IpmiSession) -> Result<IpmiRs, String> {
+        if msg.len() < 8 {
+            return Err("IPMI message too short".to_string());
+        }
+
+        let rq_addr = msg[0];
+        let netfn_lun = msg[1];
+        let rs_addr = msg[3];
+        let seq_lun = msg[4];
+        let cmd = msg[5];
+        let ccode = msg[6];
+
+        // Response data excludes completion code and trailing checksum
+        let data_len = msg.len() - 8;
 
         let mut rsp = IpmiRs {
             ccode,
             data: [0; IPMI_BUF_SIZE],
             data_len: data_len as i32,
-            msg: Default::default(),
-            session: Default::default(),
+            msg: IpmiRsMsg {
+                netfn: netfn_lun >> 2,
+                cmd,
+                seq: seq_lun >> 2,
+                lun: seq_lun & 0x03,
+            },
+            session,
             payload: IpmiRsPayload::IpmiResponse {
-                rq_addr: 0x81,
+                rq_addr,
                 netfn: netfn_lun >> 2,
-                rq_lun: 0,
-                rs_addr: 0x20,
-                rq_seq: 0,
-                rs_lun: netfn_lun & 0x03,
+                rq_lun: netfn_lun & 0x03,
+                rs_addr,
+                rq_seq: seq_lun >> 2,
+                rs_lun: seq_lun & 0x03,
                 cmd,
             },
         };
 
         if data_len > 0 {
-            // Copy response data to the data array
-            rsp.data[..data_len].copy_from_slice(&data[data_start..data_start + data_len]);
+            rsp.data[..data_len].copy_from_slice(&msg[7..7 + data_len]);
         }
 
         Ok(rsp)
     }
+
+    /// Build the packet for the next request and advance the sequence numbers
+    ///
+    /// Returns the packet together with the request state used to match the
+    /// response; retries resend the same packet. A non-zero `bridging_level`
+    /// wraps the request in Send Message.
+    pub(crate) fn prepare_request(
+        &mut self,
+        req: &IpmiRq,
+        bridging_level: u8,
+    ) -> Result<(Vec<u8>, PendingRequest), String> {
+        self.curr_seq = (self.curr_seq + 1) & 0x3f;
+        let (msg, pending) = build_request(req, self.curr_seq, bridging_level, &self.context);
+        let packet = self.build_session_packet(&msg)?;
+
+        // Sequence number advances once per request, retries reuse it
+        if self.session.active {
+            self.session.out_seq = self.session.out_seq.wrapping_add(1);
+            if self.session.out_seq == 0 {
+                self.session.out_seq = 1;
+            }
+        }
+        Ok((packet, pending))
+    }
+
+    /// Check a received packet against the outstanding request
+    ///
+    /// Returns None for packets that should be discarded while waiting, and for
+    /// Send Message acknowledgements whose bridged response arrives separately.
+    pub(crate) fn accept_response(
+        &mut self,
+        data: &[u8],
+        pending: &mut PendingRequest,
+    ) -> Option<IpmiRs> {
+        let rsp = match self.parse_response(data) {
+            Ok(rsp) => rsp,
+            Err(e) => {
+                log_debug!("Discarding packet: {}", e);
+                return None;
+            }
+        };
+
+        if !pending.matches(rsp.msg.seq, rsp.msg.cmd) {
+            log_debug!(
+                "Discarding response seq 0x{:02x} cmd 0x{:02x} (expected 0x{:02x}/0x{:02x})",
+                rsp.msg.seq,
+                rsp.msg.cmd,
+                pending.rq_seq,
+                pending.cmd
+            );
+            return None;
+        }
+
+        if self.session.active {
+            self.session.in_seq = rsp.session.seq;
+        }
+        pending.unwrap_response(rsp)
+    }
+
+    /// Send a request and wait for the matching response, retrying on timeout
+    fn send_command(&mut self, req: &IpmiRq, bridging_level: u8) -> Result<IpmiRs, String> {
+        if self.socket.is_none() {
+            return Err("Socket not initialized".to_string());
+        }
+
+        let (packet, initial) = self.prepare_request(req, bridging_level)?;
+
+        self.socket
+            .as_ref()
+            .unwrap()
+            .set_read_timeout(Some(Duration::from_secs(self.timeout)))
+            .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
+
+        let mut buffer = [0u8; IPMI_BUF_SIZE];
+        for attempt in 0..self.retry_count {
+            let mut pending = initial;
+            let socket = self.socket.as_ref().unwrap();
+            socket
+                .send(&packet)
+                .map_err(|e| format!("Failed to send packet: {}", e))?;
+
+            loop {
+                let socket = self.socket.as_ref().unwrap();
+                let len = match socket.recv(&mut buffer) {
+                    Ok(len) => len,
+                    Err(e)
+                        if e.kind() == std::io::ErrorKind::WouldBlock
+                            || e.kind() == std::io::ErrorKind::TimedOut =>
+                    {
+                        log_info!(
+                            "No response to command 0x{:02x} (attempt {}/{})",
+                            req.msg.cmd,
+                            attempt + 1,
+                            self.retry_count
+                        );
+                        break;
+                    }
+                    Err(e) => return Err(format!("Failed to receive response: {}", e)),
+                };
+
+                if let Some(rsp) = self.accept_response(&buffer[..len], &mut pending) {
+                    return Ok(rsp);
+                }
+            }
+        }
+
+        Err("No response from remote controller".to_string())
+    }
+
+    /// Build a request with the given APP netfn command and payload and send it
+    ///
+    /// Session management always talks to the BMC itself and is never bridged.
+    fn send_app_command(&mut self, cmd: u8, data: &mut [u8]) -> Result<IpmiRs, String> {
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = cmd;
+        if !data.is_empty() {
+            req.msg.data = data.as_mut_ptr();
+            req.msg.data_len = data.len() as u16;
+        }
+        self.send_command(&req, 0)
+    }
+
+    /// Get Channel Authentication Capabilities and select the session authtype
+    fn get_auth_capabilities(&mut self) -> IpmiResult<()> {
+        let mut msg_data = [IPMI_LAN_CHANNEL_E, self.privilege_level];
+        let rsp = self
+            .send_app_command(IPMI_GET_CHANNEL_AUTH_CAP, &mut msg_data)
+            .map_err(|e| {
+                IpmiError::Session(format!("Get Auth Capabilities command failed: {}", e))
+            })?;
+
+        if rsp.ccode != 0 {
+            return Err(IpmiError::Session(format!(
+                "Get Auth Capabilities command failed: {}",
+                completion_code_to_string(rsp.ccode)
+            )));
+        }
+        if rsp.data_len < 3 {
+            return Err(IpmiError::Session(
+                "Get Auth Capabilities command failed: short response".to_string(),
+            ));
+        }
+
+        let auth_support = rsp.data[1];
+        self.session.authstatus = rsp.data[2];
+
+        log_debug!("Channel {:02x} Authentication Capabilities:", rsp.data[0]);
+        log_debug!(
+            "  Privilege Level : {}",
+            privlvl_to_str(self.privilege_level)
+        );
+        log_debug!(
+            "  Auth Types      : {}",
+            get_auth_types_string(auth_support)
+        );
+        log_debug!(
+            "  Per-msg auth    : {}",
+            if self.session.authstatus & IPMI_AUTHSTATUS_PER_MSG_DISABLED != 0 {
+                "disabled"
+            } else {
+                "enabled"
+            }
+        );
+
+        let authtype = match self.authtype_set {
+            Some(authtype) => {
+                if !is_auth_type_supported(auth_support, authtype) {
+                    let name =
+                        IpmiAuth::new_with_password(authtype, String::new()).get_auth_type_name();
+                    return Err(IpmiError::Authentication(format!(
+                        "Authentication type {} not supported",
+                        name
+                    )));
+                }
+                authtype
+            }
+            None => [
+                IPMI_SESSION_AUTHTYPE_MD5,
+                IPMI_SESSION_AUTHTYPE_PASSWORD,
+                IPMI_SESSION_AUTHTYPE_MD2,
+                IPMI_SESSION_AUTHTYPE_NONE,
+            ]
+            .into_iter()
+            .find(|&t| is_auth_type_supported(auth_support, t))
+            .ok_or_else(|| IpmiError::Authentication("No supported authtypes found".to_string()))?,
+        };
+
+        self.session.auth = IpmiAuth::new_with_password(authtype, self.password.clone());
+        log_debug!(
+            "Proceeding with AuthType {}",
+            self.session.auth.get_auth_type_name()
+        );
+
+        Ok(())
+    }
+
+    /// Get Session Challenge: obtain temporary session ID and challenge string
+    fn get_session_challenge(&mut self) -> IpmiResult<()> {
+        let mut msg_data = [0u8; 17];
+        msg_data[0] = self.session.auth.authtype;
+        let name = self.username.as_bytes();
+        let len = std::cmp::min(name.len(), 16);
+        msg_data[1..1 + len].copy_from_slice(&name[..len]);
+
+        let rsp = self
+            .send_app_command(IPMI_GET_SESSION_CHALLENGE, &mut msg_data)
+            .map_err(|e| {
+                IpmiError::Session(format!("Get Session Challenge command failed: {}", e))
+            })?;
+
+        match rsp.ccode {
+            0 => {}
+            0x81 => return Err(IpmiError::Authentication("Invalid user name".to_string())),
+            0x82 => {
+                return Err(IpmiError::Authentication(
+                    "NULL user name not enabled".to_string(),
+                ))
+            }
+            cc => {
+                return Err(IpmiError::Session(format!(
+                    "Get Session Challenge command failed: {}",
+                    completion_code_to_string(cc)
+                )))
+            }
+        }
+        if rsp.data_len < 20 {
+            return Err(IpmiError::Session(
+                "Get Session Challenge command failed: short response".to_string(),
+            ));
+        }
+
+        self.session.session_id =
+            u32::from_le_bytes([rsp.data[0], rsp.data[1], rsp.data[2], rsp.data[3]]);
+        self.session.challenge.copy_from_slice(&rsp.data[4..20]);
+
+        log_debug!("Opening Session");
+        log_debug!("  Session ID      : {:08x}", self.session.session_id);
+
+        Ok(())
+    }
+
+    /// Activate Session using the challenge from Get Session Challenge
+    fn activate_session(&mut self) -> IpmiResult<()> {
+        let mut msg_data = [0u8; 22];
+        msg_data[0] = self.session.auth.authtype;
+        msg_data[1] = self.privilege_level;
+        msg_data[2..18].copy_from_slice(&self.session.challenge);
+        // Initial outbound sequence number the BMC uses towards us
+        msg_data[18..22].copy_from_slice(&rand::random::<u32>().to_le_bytes());
+
+        // Activate Session is the first authenticated packet (sequence 0)
+        self.session.active = true;
+        self.session.out_seq = 0;
+
+        let rsp = match self.send_app_command(IPMI_ACTIVATE_SESSION, &mut msg_data) {
+            Ok(rsp) => rsp,
+            Err(e) => {
+                self.session.active = false;
+                return Err(IpmiError::Session(format!(
+                    "Activate Session command failed: {}",
+                    e
+                )));
+            }
+        };
+
+        if rsp.ccode != 0 {
+            self.session.active = false;
+            let reason = match rsp.ccode {
+                0x81 => "No session slot available".to_string(),
+                0x82 => "No slot available for given user - limit reached".to_string(),
+                0x83 => "No slot available to support user due to maximum privilege capacity"
+                    .to_string(),
+                0x84 => "Session sequence out of range".to_string(),
+                0x85 => "Invalid session ID in request".to_string(),
+                0x86 => "Requested privilege level exceeds limit".to_string(),
+                0xd4 => "Requested privilege level exceeds limit".to_string(),
+                0xd5 => "Command not supported in present state".to_string(),
+                cc => completion_code_to_string(cc).to_string(),
+            };
+            return Err(IpmiError::Session(format!(
+                "Activate Session error: {}",
+                reason
+            )));
+        }
+        if rsp.data_len < 10 {
+            self.session.active = false;
+            return Err(IpmiError::Session(
+                "Activate Session error: short response".to_string(),
+            ));
+        }
+
+        self.session.session_id =
+            u32::from_le_bytes([rsp.data[1], rsp.data[2], rsp.data[3], rsp.data[4]]);
+        self.session.out_seq =
+            u32::from_le_bytes([rsp.data[5], rsp.data[6], rsp.data[7], rsp.data[8]]);
+        if self.session.out_seq == 0 {
+            self.session.out_seq = 1;
+        }
+
+        if self.session.authstatus & IPMI_AUTHSTATUS_PER_MSG_DISABLED != 0 {
+            self.session.auth.authtype = IPMI_SESSION_AUTHTYPE_NONE;
+        } else if self.session.auth.authtype != (rsp.data[0] & 0x0f) {
+            self.session.active = false;
+            return Err(IpmiError::Authentication(format!(
+                "Invalid Session AuthType {} in response",
+                IpmiAuth::new_with_password(rsp.data[0] & 0x0f, String::new()).get_auth_type_name()
+            )));
+        }
+
+        log_debug!("Session Activated");
+        log_debug!(
+            "  Auth Type       : {}",
+            self.session.auth.get_auth_type_name()
+        );
+        log_debug!("  Max Priv Level  : {}", privlvl_to_str(rsp.data[9] & 0x0f));
+        log_debug!("  Session ID      : {:08x}", self.session.session_id);
+        log_debug!("  Inbound Seq     : {:08x}", self.session.out_seq);
+
+        Ok(())
+    }
+
+    /// Raise the session privilege level above the default USER level
+    fn set_session_privlvl(&mut self) -> IpmiResult<()> {
+        if self.privilege_level <= IPMI_SESSION_PRIV_USER {
+            self.session.privilege_level = self.privilege_level;
+            return Ok(());
+        }
+
+        let mut msg_data = [self.privilege_level];
+        let rsp = self
+            .send_app_command(IPMI_SET_SESSION_PRIVILEGE, &mut msg_data)
+            .map_err(|e| {
+                IpmiError::Session(format!(
+                    "Set Session Privilege Level to {} failed: {}",
+                    privlvl_to_str(self.privilege_level),
+                    e
+                ))
+            })?;
+
+        if rsp.ccode != 0 {
+            let reason = match rsp.ccode {
+                0x80 => "Requested privilege level not available for this user",
+                0x81 => "Requested privilege level exceeds user and/or channel limit",
+                0x82 => "Cannot disable User Level authentication",
+                cc => completion_code_to_string(cc),
+            };
+            return Err(IpmiError::Session(format!(
+                "Set Session Privilege Level to {} failed: {}",
+                privlvl_to_str(self.privilege_level),
+                reason
+            )));
+        }
+
+        if rsp.data_len < 1 {
+            return Err(IpmiError::Session(format!(
+                "Set Session Privilege Level to {} failed: short response",
+                privlvl_to_str(self.privilege_level)
+            )));
+        }
+
+        self.session.privilege_level = rsp.data[0] & 0x0f;
+        log_debug!(
+            "Set Session Privilege Level to {}",
+            privlvl_to_str(self.session.privilege_level)
+        );
+
+        Ok(())
+    }
+
+    /// Close the active session
+    fn close_session(&mut self) {
+        if !self.session.active {
+            return;
+        }
+
+        let session_id = self.session.session_id;
+        let mut msg_data = session_id.to_le_bytes();
+        match self.send_app_command(IPMI_CLOSE_SESSION, &mut msg_data) {
+            Ok(rsp) if rsp.ccode == 0 => log_debug!("Closed Session {:08x}", session_id),
+            Ok(rsp) if rsp.ccode == 0x87 => {
+                log::error!(
+                    "Failed to Close Session: invalid session ID {:08x}",
+                    session_id
+                )
+            }
+            Ok(rsp) => log::error!(
+                "Close Session command failed: {}",
+                completion_code_to_string(rsp.ccode)
+            ),
+            Err(e) => log::error!("Close Session command failed: {}", e),
+        }
+
+        self.session.active = false;
+        self.session.session_id = 0;
+    }
+
+    /// Run the full IPMI v1.5 session establishment sequence
+    fn activate(&mut self) -> IpmiResult<()> {
+        self.get_auth_capabilities()?;
+        self.get_session_challenge()?;
+        self.activate_session()?;
+        if let Err(e) = self.set_session_privlvl() {
+            self.close_session();
+            return Err(e);
+        }
+        Ok(())
+    }
 }
 
 impl IpmiIntf for IpmiLanIntf {
@@ -279,35 +755,41 @@ impl IpmiIntf for IpmiLanIntf {
             return Ok(()); // 已经打开
         }
 
+        let remote = self.resolve_target()?;
+
         // 创建 UDP socket
-        let local_addr: SocketAddr = "0.0.0.0:0"
-            .parse()
-            .map_err(|e| IpmiError::System(format!("Invalid local address: {}", e)))?;
+        let local_addr: SocketAddr = if remote.is_ipv6() {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        }
+        .parse()
+        .map_err(|e| IpmiError::System(format!("Invalid local address: {}", e)))?;
 
         let socket = UdpSocket::bind(local_addr)
             .map_err(|e| IpmiError::Network(format!("Failed to bind UDP socket: {}", e)))?;
 
         // 连接到远程地址
-        if !self.hostname.is_empty() {
-            let remote_addr = format!("{}:{}", self.hostname, RMCP_UDP_PORT);
-            let remote: SocketAddr = remote_addr.parse().map_err(|e| {
-                IpmiError::Network(format!("Invalid remote address {}: {}", remote_addr, e))
-            })?;
+        socket
+            .connect(remote)
+            .map_err(|e| IpmiError::Network(format!("Failed to connect to {}: {}", remote, e)))?;
 
-            socket.connect(remote).map_err(|e| {
-                IpmiError::Network(format!("Failed to connect to {}: {}", remote_addr, e))
-            })?;
+        self.target_addr = Some(remote);
+        self.socket = Some(socket);
+
+        // 建立 IPMI v1.5 会话
+        if let Err(e) = self.activate() {
+            self.socket = None;
+            return Err(e);
         }
 
-        self.socket = Some(socket);
         Ok(())
     }
 
     fn close(&mut self) {
         // 关闭会话（如果有活动会话）
-        if self.session.session_id != 0 {
-            // TODO: 发送关闭会话命令
-            self.session.session_id = 0;
+        if self.socket.is_some() {
+            self.close_session();
         }
 
         // 关闭 socket
@@ -315,8 +797,12 @@ impl IpmiIntf for IpmiLanIntf {
     }
 
     fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
-        // Use pre-session method for now (simplified implementation)
-        match self.send_command_pre_session(req) {
+        if self.socket.is_none() && self.open().is_err() {
+            return None;
+        }
+
+        let bridging_level = self.context.get_bridging_level();
+        match self.send_command(req, bridging_level) {
             Ok(rsp) => Some(rsp),
             Err(e) => {
                 log::error!("Failed to send command: {}", e);
@@ -336,12 +822,17 @@ impl IpmiIntf for IpmiLanIntf {
     }
 
     fn keepalive(&mut self) -> IpmiResult<()> {
-        if self.session.session_id == 0 {
+        if !self.session.active {
             return Ok(()); // 没有活动会话
         }
 
-        // TODO: 发送 keep-alive 消息以维持会话
-        // 通常发送 Get Channel Authentication Capabilities 命令
+        // Get Device ID 作为会话保活消息
+        let rsp = self
+            .send_app_command(0x01, &mut [])
+            .map_err(IpmiError::Network)?;
+        if rsp.ccode != 0 {
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
         Ok(())
     }
 
@@ -360,3 +851,80 @@ impl IpmiIntf for IpmiLanIntf {
             std::cmp::min(size, IPMI_LAN_MAX_RESPONSE_SIZE);
     }
 }
+
+impl Drop for IpmiLanIntf {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_intf() -> IpmiLanIntf {
+        IpmiLanIntf::new("127.0.0.1".to_string(), 0, IpmiContext::default())
+    }
+
+    #[test]
+    fn test_presession_packet_layout() {
+        let mut intf = test_intf();
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = IPMI_GET_CHANNEL_AUTH_CAP;
+        let mut data = [IPMI_LAN_CHANNEL_E, IPMI_SESSION_PRIV_ADMIN];
+        req.msg.data = data.as_mut_ptr();
+        req.msg.data_len = data.len() as u16;
+
+        let packet = intf.build_packet(&req, 1).unwrap();
+        assert_eq!(&packet[0..4], &[0x06, 0x00, 0xff, 0x07]);
+        // authtype NONE, seq 0, session id 0, no authcode
+        assert_eq!(&packet[4..13], &[0u8; 9]);
+        assert_eq!(packet[13], 9);
+        assert_eq!(
+            &packet[14..],
+            &[0x20, 0x18, 0xc8, 0x81, 0x04, 0x38, 0x0e, 0x04, 0x31]
+        );
+    }
+
+    #[test]
+    fn test_active_session_packet_has_authcode() {
+        let mut intf = test_intf();
+        intf.password = "secret".to_string();
+        intf.session.active = true;
+        intf.session.session_id = 0x11223344;
+        intf.session.out_seq = 5;
+        intf.session.auth =
+            IpmiAuth::new_with_password(IPMI_SESSION_AUTHTYPE_PASSWORD, "secret".to_string());
+
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = 0x01;
+
+        let packet = intf.build_packet(&req, 2).unwrap();
+        assert_eq!(packet[4], IPMI_SESSION_AUTHTYPE_PASSWORD);
+        assert_eq!(&packet[5..9], &5u32.to_le_bytes());
+        assert_eq!(&packet[9..13], &0x11223344u32.to_le_bytes());
+        assert_eq!(&packet[13..19], b"secret");
+        assert_eq!(packet[29], 7);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let intf = test_intf();
+        // Get Device ID response, seq 3, ccode 0, two data bytes
+        let msg = [0x81, 0x1c, 0x63, 0x20, 0x0c, 0x01, 0x00, 0x20, 0x01, 0x00];
+        let mut packet = vec![0x06, 0x00, 0xff, 0x07, 0x00];
+        packet.extend_from_slice(&[0u8; 8]);
+        packet.push(msg.len() as u8);
+        packet.extend_from_slice(&msg);
+
+        let rsp = intf.parse_response(&packet).unwrap();
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(rsp.msg.seq, 3);
+        assert_eq!(rsp.msg.cmd, 0x01);
+        assert_eq!(rsp.msg.netfn, IPMI_NETFN_APP + 1);
+        assert_eq!(rsp.data_len, 2);
+        assert_eq!(&rsp.data[..2], &[0x20, 0x01]);
+    }
+}
@@ -4,9 +4,13 @@
  * SPDX-License-Identifier: GPL-2.0-or-later
  */
 
+pub mod async_lan;
 pub mod auth;
+pub(crate) mod bridge;
+pub mod discover;
 #[allow(clippy::module_inception)]
 pub mod lan;
 pub mod rmcp;
 
+pub use async_lan::AsyncLanIntf;
 pub use lan::IpmiLanIntf;
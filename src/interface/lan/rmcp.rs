@@ -25,6 +25,19 @@ pub const RMCP_CLASS_ASF: u8 = 0x06;
 pub const RMCP_CLASS_IPMI: u8 = 0x07;
 pub const RMCP_CLASS_OEM: u8 = 0x08;
 
+// ASF 消息 (presence ping/pong)
+pub const ASF_RMCP_IANA: u32 = 0x000011be; // 4542, ASF 标准组织号
+pub const ASF_TYPE_PING: u8 = 0x80;
+pub const ASF_TYPE_PONG: u8 = 0x40;
+pub const ASF_PONG_DATA_LEN: u8 = 16;
+pub const RMCP_PONG_LEN: usize = 28;
+
+// Pong 的 Supported Entities / Supported Interactions 位
+pub const ASF_ENTITY_IPMI: u8 = 0x80;
+pub const ASF_ENTITY_VERSION_MASK: u8 = 0x0f;
+pub const ASF_INTERACT_SECURITY: u8 = 0x80;
+pub const ASF_INTERACT_DASH: u8 = 0x20;
+
 /// RMCP Header Structure
 /// Matches C struct rmcp_hdr exactly
 #[repr(C, packed)]
@@ -101,6 +114,8 @@ impl RmcpHeader {
 }
 
 /// ASF Header Structure for ping/pong messages
+///
+/// 与 IPMI 会话头不同，ASF 字段均为网络字节序
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy)]
 pub struct AsfHeader {
@@ -114,7 +129,7 @@ pub struct AsfHeader {
 impl Default for AsfHeader {
     fn default() -> Self {
         Self {
-            iana: 0x000011be, // Intel IANA
+            iana: ASF_RMCP_IANA,
             msg_type: 0,
             msg_tag: 0,
             reserved: 0,
@@ -126,8 +141,8 @@ impl Default for AsfHeader {
 impl AsfHeader {
     pub fn new_ping(tag: u8) -> Self {
         Self {
-            iana: 0x000011be,
-            msg_type: 0x80, // Ping
+            iana: ASF_RMCP_IANA,
+            msg_type: ASF_TYPE_PING,
             msg_tag: tag,
             reserved: 0,
             data_len: 0,
@@ -136,7 +151,7 @@ impl AsfHeader {
 
     pub fn to_bytes(&self) -> [u8; 8] {
         let mut bytes = [0u8; 8];
-        bytes[0..4].copy_from_slice(&self.iana.to_le_bytes());
+        bytes[0..4].copy_from_slice(&self.iana.to_be_bytes());
         bytes[4] = self.msg_type;
         bytes[5] = self.msg_tag;
         bytes[6] = self.reserved;
@@ -149,7 +164,7 @@ impl AsfHeader {
             return Err("ASF header too short".to_string());
         }
 
-        let iana = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+        let iana = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
 
         Ok(Self {
             iana,
@@ -160,8 +175,12 @@ impl AsfHeader {
         })
     }
 
+    pub fn is_ping(&self) -> bool {
+        self.iana == ASF_RMCP_IANA && self.msg_type == ASF_TYPE_PING
+    }
+
     pub fn is_pong(&self) -> bool {
-        self.msg_type == 0x81
+        self.iana == ASF_RMCP_IANA && self.msg_type == ASF_TYPE_PONG
     }
 }
 
@@ -179,16 +198,40 @@ pub struct RmcpPong {
 }
 
 impl RmcpPong {
+    /// 应答 `ping` 的 pong，`iana`/`oem` 为厂商号与厂商自定义数据
+    pub fn new(ping: &RmcpHeader, tag: u8, iana: u32, oem: u32, sup_entities: u8) -> Self {
+        Self {
+            rmcp: RmcpHeader::new_asf(ping.sequence),
+            asf: AsfHeader {
+                msg_type: ASF_TYPE_PONG,
+                msg_tag: tag,
+                data_len: ASF_PONG_DATA_LEN,
+                ..Default::default()
+            },
+            iana,
+            oem,
+            sup_entities,
+            sup_interact: 0,
+            reserved: [0; 6],
+        }
+    }
+
     pub fn from_bytes(data: &[u8]) -> Result<Self, String> {
-        if data.len() < 26 {
+        if data.len() < RMCP_PONG_LEN {
             return Err("RMCP pong response too short".to_string());
         }
 
         let rmcp = RmcpHeader::from_bytes(&data[0..4])?;
+        if !rmcp.is_asf() {
+            return Err("Not an ASF message".to_string());
+        }
         let asf = AsfHeader::from_bytes(&data[4..12])?;
+        if !asf.is_pong() {
+            return Err(format!("Not an ASF pong (type 0x{:02x})", asf.msg_type));
+        }
 
-        let iana = u32::from_le_bytes([data[12], data[13], data[14], data[15]]);
-        let oem = u32::from_le_bytes([data[16], data[17], data[18], data[19]]);
+        let iana = u32::from_be_bytes([data[12], data[13], data[14], data[15]]);
+        let oem = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
         let sup_entities = data[20];
         let sup_interact = data[21];
         let mut reserved = [0u8; 6];
@@ -204,6 +247,29 @@ impl RmcpPong {
             reserved,
         })
     }
+
+    pub fn to_bytes(&self) -> [u8; RMCP_PONG_LEN] {
+        let mut bytes = [0u8; RMCP_PONG_LEN];
+        bytes[0..4].copy_from_slice(&self.rmcp.to_bytes());
+        bytes[4..12].copy_from_slice(&self.asf.to_bytes());
+        bytes[12..16].copy_from_slice(&{ self.iana }.to_be_bytes());
+        bytes[16..20].copy_from_slice(&{ self.oem }.to_be_bytes());
+        bytes[20] = self.sup_entities;
+        bytes[21] = self.sup_interact;
+        bytes
+    }
+
+    pub fn ipmi_supported(&self) -> bool {
+        self.sup_entities & ASF_ENTITY_IPMI != 0
+    }
+
+    /// Supported Entities 低 4 位给出的 ASF 版本
+    pub fn asf_version(&self) -> &'static str {
+        match self.sup_entities & ASF_ENTITY_VERSION_MASK {
+            0x01 => "1.0",
+            _ => "unknown",
+        }
+    }
 }
 
 /// Handle RMCP protocol messages
@@ -250,3 +316,37 @@ pub fn create_ping_packet(sequence: u8, tag: u8) -> Vec<u8> {
 
     packet
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ping_pong_wire_format() {
+        let ping = create_ping_packet(0xff, 7);
+        assert_eq!(
+            ping,
+            [0x06, 0x00, 0xff, 0x06, 0x00, 0x00, 0x11, 0xbe, 0x80, 0x07, 0x00, 0x00]
+        );
+
+        let hdr = RmcpHeader::from_bytes(&ping).unwrap();
+        let asf = AsfHeader::from_bytes(&ping[4..]).unwrap();
+        assert!(hdr.is_asf() && asf.is_ping());
+
+        let pong = RmcpPong::new(&hdr, asf.msg_tag, 343, 0, ASF_ENTITY_IPMI | 0x01);
+        let bytes = pong.to_bytes();
+        assert_eq!(
+            &bytes[4..12],
+            &[0x00, 0x00, 0x11, 0xbe, 0x40, 0x07, 0x00, 0x10]
+        );
+        assert_eq!(&bytes[12..16], &[0x00, 0x00, 0x01, 0x57]);
+
+        let parsed = RmcpPong::from_bytes(&bytes).unwrap();
+        assert_eq!({ parsed.iana }, 343);
+        assert_eq!(parsed.asf.msg_tag, 7);
+        assert!(parsed.ipmi_supported());
+        assert_eq!(parsed.asf_version(), "1.0");
+        assert!(RmcpPong::from_bytes(&bytes[..26]).is_err());
+        assert!(RmcpPong::from_bytes(&ping).is_err());
+    }
+}
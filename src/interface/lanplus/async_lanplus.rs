@@ -0,0 +1,180 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! IPMI v2.0 RMCP+ LAN 接口的异步版本
+//!
+//! RAKP 握手与 Close Session 沿用 [`IpmiLanplusIntf`] 的同步实现 (在阻塞线程池上执行)，
+//! 会话建立后的请求收发在 `tokio::net::UdpSocket` 上异步完成。不支持 SOL。
+
+use super::lanplus::IpmiLanplusIntf;
+use crate::error::{IpmiError, IpmiResult};
+use crate::interface::lan::bridge::PendingRequest;
+use crate::ipmi::async_intf::{run_blocking, AsyncIpmiIntf, IpmiFuture};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IPMI_BUF_SIZE, IPMI_PAYLOAD_TYPE_IPMI};
+use crate::{log_debug, log_info};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+pub struct AsyncLanplusIntf {
+    inner: IpmiLanplusIntf,
+    socket: Option<UdpSocket>,
+}
+
+impl AsyncLanplusIntf {
+    /// `intf` 已设置好主机、凭据、密码套件与超时
+    pub fn new(intf: IpmiLanplusIntf) -> Self {
+        Self {
+            inner: intf,
+            socket: None,
+        }
+    }
+
+    fn placeholder() -> IpmiLanplusIntf {
+        IpmiLanplusIntf::new(String::new(), 0, IpmiContext::default())
+    }
+
+    async fn open_session(&mut self) -> IpmiResult<()> {
+        if self.socket.is_some() {
+            return Ok(());
+        }
+
+        run_blocking(&mut self.inner, Self::placeholder(), |intf| intf.open()).await??;
+
+        let socket = self
+            .inner
+            .socket
+            .take()
+            .ok_or_else(|| IpmiError::Network("Session socket missing".to_string()))?;
+        socket.set_nonblocking(true)?;
+        self.socket = Some(UdpSocket::from_std(socket)?);
+        Ok(())
+    }
+
+    async fn close_session(&mut self) {
+        let Some(socket) = self.socket.take() else {
+            return;
+        };
+
+        // Close Session 由同步实现发送
+        let socket = socket
+            .into_std()
+            .and_then(|s| s.set_nonblocking(false).map(|_| s));
+        match socket {
+            Ok(socket) => {
+                self.inner.socket = Some(socket);
+                let _ =
+                    run_blocking(&mut self.inner, Self::placeholder(), |intf| intf.close()).await;
+            }
+            Err(e) => log_debug!("Unable to hand socket back for Close Session: {}", e),
+        }
+    }
+
+    /// 每次重试重新组包，使用新的会话序号
+    async fn send_command(
+        &mut self,
+        msg: Vec<u8>,
+        initial: PendingRequest,
+    ) -> Result<IpmiRs, String> {
+        let timeout = Duration::from_secs(self.inner.timeout);
+        let retry_count = self.inner.retry_count;
+
+        let mut buffer = [0u8; IPMI_BUF_SIZE];
+        for attempt in 0..retry_count {
+            let mut pending = initial;
+            let packet = self
+                .inner
+                .build_request_packet(IPMI_PAYLOAD_TYPE_IPMI, &msg)?;
+            let socket = self.socket.as_ref().ok_or("Socket not initialized")?;
+            socket
+                .send(&packet)
+                .await
+                .map_err(|e| format!("Failed to send packet: {}", e))?;
+
+            let mut deadline = tokio::time::Instant::now() + timeout;
+            loop {
+                let socket = self.socket.as_ref().ok_or("Socket not initialized")?;
+                let len = match tokio::time::timeout_at(deadline, socket.recv(&mut buffer)).await {
+                    Ok(Ok(len)) => len,
+                    Ok(Err(e)) => return Err(format!("Failed to receive response: {}", e)),
+                    Err(_) => {
+                        log_info!(
+                            "No response to command 0x{:02x} (attempt {}/{})",
+                            initial.target_cmd,
+                            attempt + 1,
+                            retry_count
+                        );
+                        break;
+                    }
+                };
+
+                let Some(pkt) = self.inner.accept_packet(&buffer[..len]) else {
+                    continue;
+                };
+                if !IpmiLanplusIntf::is_response_to(&pkt, &pending) {
+                    log_debug!(
+                        "Discarding unexpected packet with payload type 0x{:02x}",
+                        pkt.payload_type
+                    );
+                    continue;
+                }
+                let rsp = IpmiLanplusIntf::command_response(&pkt)?;
+                match pending.unwrap_response(rsp) {
+                    Some(rsp) => return Ok(rsp),
+                    // 桥接应答随后单独到达，重新计时等待
+                    None => deadline = tokio::time::Instant::now() + timeout,
+                }
+            }
+        }
+
+        Err("No response from remote controller".to_string())
+    }
+}
+
+impl AsyncIpmiIntf for AsyncLanplusIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        self.inner.context()
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        self.inner.setup()
+    }
+
+    fn open(&mut self) -> IpmiFuture<'_, IpmiResult<()>> {
+        Box::pin(self.open_session())
+    }
+
+    fn close(&mut self) -> IpmiFuture<'_, ()> {
+        Box::pin(self.close_session())
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> IpmiFuture<'_, Option<IpmiRs>> {
+        let prepared = if self.socket.is_some() {
+            let bridging_level = self.inner.context().get_bridging_level();
+            Ok(self.inner.next_request_message(req, bridging_level))
+        } else {
+            Err("Session not open".to_string())
+        };
+
+        Box::pin(async move {
+            let result = match prepared {
+                Ok((msg, pending)) => self.send_command(msg, pending).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(rsp) => Some(rsp),
+                Err(e) => {
+                    log::error!("Failed to send command: {}", e);
+                    None
+                }
+            }
+        })
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.inner.set_my_addr(addr)
+    }
+}
@@ -0,0 +1,305 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+/// IPMI v2.0 RMCP+ 加密相关实现
+///
+/// Based on reference/ipmitool-c/src/plugins/lanplus/lanplus_crypt.c
+/// and lanplus_crypt_impl.c
+use crate::ipmi::constants::*;
+use aes::cipher::{block_padding::NoPadding, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use md5::{Digest, Md5};
+use sha1::Sha1;
+use sha2::Sha256;
+
+pub const IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE: usize = 16;
+
+type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
+type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+
+/// 根据密码套件 ID 查询 (认证, 完整性, 加密) 算法
+pub fn cipher_suite_algorithms(cipher_suite_id: u8) -> Option<(u8, u8, u8)> {
+    let algs = match cipher_suite_id {
+        0 => (IPMI_AUTH_RAKP_NONE, IPMI_INTEGRITY_NONE, IPMI_CRYPT_NONE),
+        1 => (
+            IPMI_AUTH_RAKP_HMAC_SHA1,
+            IPMI_INTEGRITY_NONE,
+            IPMI_CRYPT_NONE,
+        ),
+        2 => (
+            IPMI_AUTH_RAKP_HMAC_SHA1,
+            IPMI_INTEGRITY_HMAC_SHA1_96,
+            IPMI_CRYPT_NONE,
+        ),
+        3 => (
+            IPMI_AUTH_RAKP_HMAC_SHA1,
+            IPMI_INTEGRITY_HMAC_SHA1_96,
+            IPMI_CRYPT_AES_CBC_128,
+        ),
+        4 => (
+            IPMI_AUTH_RAKP_HMAC_SHA1,
+            IPMI_INTEGRITY_HMAC_SHA1_96,
+            IPMI_CRYPT_XRC4_128,
+        ),
+        5 => (
+            IPMI_AUTH_RAKP_HMAC_SHA1,
+            IPMI_INTEGRITY_HMAC_SHA1_96,
+            IPMI_CRYPT_XRC4_40,
+        ),
+        6 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_NONE,
+            IPMI_CRYPT_NONE,
+        ),
+        7 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_HMAC_MD5_128,
+            IPMI_CRYPT_NONE,
+        ),
+        8 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_HMAC_MD5_128,
+            IPMI_CRYPT_AES_CBC_128,
+        ),
+        9 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_HMAC_MD5_128,
+            IPMI_CRYPT_XRC4_128,
+        ),
+        10 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_HMAC_MD5_128,
+            IPMI_CRYPT_XRC4_40,
+        ),
+        11 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_MD5_128,
+            IPMI_CRYPT_NONE,
+        ),
+        12 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_MD5_128,
+            IPMI_CRYPT_AES_CBC_128,
+        ),
+        13 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_MD5_128,
+            IPMI_CRYPT_XRC4_128,
+        ),
+        14 => (
+            IPMI_AUTH_RAKP_HMAC_MD5,
+            IPMI_INTEGRITY_MD5_128,
+            IPMI_CRYPT_XRC4_40,
+        ),
+        #[cfg(feature = "crypto-sha256")]
+        15 => (
+            IPMI_AUTH_RAKP_HMAC_SHA256,
+            IPMI_INTEGRITY_NONE,
+            IPMI_CRYPT_NONE,
+        ),
+        #[cfg(feature = "crypto-sha256")]
+        16 => (
+            IPMI_AUTH_RAKP_HMAC_SHA256,
+            IPMI_INTEGRITY_HMAC_SHA256_128,
+            IPMI_CRYPT_NONE,
+        ),
+        #[cfg(feature = "crypto-sha256")]
+        17 => (
+            IPMI_AUTH_RAKP_HMAC_SHA256,
+            IPMI_INTEGRITY_HMAC_SHA256_128,
+            IPMI_CRYPT_AES_CBC_128,
+        ),
+        _ => return None,
+    };
+    Some(algs)
+}
+
+/// ipmitool 默认使用的密码套件
+pub fn default_cipher_suite() -> u8 {
+    if cfg!(feature = "crypto-sha256") {
+        17
+    } else {
+        3
+    }
+}
+
+/// 对认证算法计算 HMAC，返回完整摘要
+pub fn lanplus_hmac(auth_alg: u8, key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+    macro_rules! hmac_digest {
+        ($d:ty) => {{
+            let mut mac =
+                <Hmac<$d>>::new_from_slice(key).map_err(|e| format!("Invalid HMAC key: {}", e))?;
+            mac.update(data);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }};
+    }
+
+    match auth_alg {
+        IPMI_AUTH_RAKP_HMAC_SHA1 => hmac_digest!(Sha1),
+        IPMI_AUTH_RAKP_HMAC_MD5 => hmac_digest!(Md5),
+        IPMI_AUTH_RAKP_HMAC_SHA256 => hmac_digest!(Sha256),
+        _ => Err(format!(
+            "Unsupported authentication algorithm 0x{:02x}",
+            auth_alg
+        )),
+    }
+}
+
+/// RAKP 消息 4 中完整性校验值的长度
+pub fn rakp4_icv_len(auth_alg: u8) -> usize {
+    match auth_alg {
+        IPMI_AUTH_RAKP_HMAC_SHA1 => 12,
+        IPMI_AUTH_RAKP_HMAC_MD5 => 16,
+        IPMI_AUTH_RAKP_HMAC_SHA256 => 16,
+        _ => 0,
+    }
+}
+
+/// 会话报文尾部 AuthCode 的长度
+pub fn integrity_authcode_len(integrity_alg: u8) -> usize {
+    match integrity_alg {
+        IPMI_INTEGRITY_HMAC_SHA1_96 => 12,
+        IPMI_INTEGRITY_HMAC_MD5_128 => 16,
+        IPMI_INTEGRITY_MD5_128 => 16,
+        IPMI_INTEGRITY_HMAC_SHA256_128 => 16,
+        _ => 0,
+    }
+}
+
+/// 生成会话报文的完整性 AuthCode
+///
+/// HMAC 类算法使用 K1 作为密钥，MD5-128 按规范使用 password + data + password
+pub fn integrity_authcode(
+    integrity_alg: u8,
+    k1: &[u8],
+    password: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, String> {
+    let mut code = match integrity_alg {
+        IPMI_INTEGRITY_HMAC_SHA1_96 => lanplus_hmac(IPMI_AUTH_RAKP_HMAC_SHA1, k1, data)?,
+        IPMI_INTEGRITY_HMAC_MD5_128 => lanplus_hmac(IPMI_AUTH_RAKP_HMAC_MD5, k1, data)?,
+        IPMI_INTEGRITY_HMAC_SHA256_128 => lanplus_hmac(IPMI_AUTH_RAKP_HMAC_SHA256, k1, data)?,
+        IPMI_INTEGRITY_MD5_128 => {
+            let mut pwd = [0u8; 20];
+            let len = std::cmp::min(password.len(), pwd.len());
+            pwd[..len].copy_from_slice(&password[..len]);
+            let mut hasher = Md5::new();
+            hasher.update(pwd);
+            hasher.update(data);
+            hasher.update(pwd);
+            hasher.finalize().to_vec()
+        }
+        _ => {
+            return Err(format!(
+                "Unsupported integrity algorithm 0x{:02x}",
+                integrity_alg
+            ))
+        }
+    };
+    code.truncate(integrity_authcode_len(integrity_alg));
+    Ok(code)
+}
+
+/// AES-CBC-128 加密负载，返回 IV + 密文
+///
+/// 明文尾部按规范追加 1,2,3... 填充字节和填充长度，使总长度为 16 的整数倍
+pub fn encrypt_aes_cbc_128(k2: &[u8], iv: &[u8; 16], payload: &[u8]) -> Result<Vec<u8>, String> {
+    if k2.len() < 16 {
+        return Err("Invalid K2 length".to_string());
+    }
+
+    let block = IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE;
+    let pad_len = (block - (payload.len() + 1) % block) % block;
+    let mut buf = Vec::with_capacity(payload.len() + pad_len + 1);
+    buf.extend_from_slice(payload);
+    buf.extend((1..=pad_len as u8).collect::<Vec<u8>>());
+    buf.push(pad_len as u8);
+
+    let len = buf.len();
+    Aes128CbcEnc::new(k2[..16].into(), iv.into())
+        .encrypt_padded_mut::<NoPadding>(&mut buf, len)
+        .map_err(|e| format!("AES encryption failed: {}", e))?;
+
+    let mut out = Vec::with_capacity(16 + len);
+    out.extend_from_slice(iv);
+    out.extend_from_slice(&buf);
+    Ok(out)
+}
+
+/// 解密 IV + 密文形式的 AES-CBC-128 负载并去除填充
+pub fn decrypt_aes_cbc_128(k2: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+    let block = IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE;
+    if k2.len() < 16 {
+        return Err("Invalid K2 length".to_string());
+    }
+    if data.len() < 2 * block || data.len() % block != 0 {
+        return Err(format!("Invalid encrypted payload length {}", data.len()));
+    }
+
+    let (iv, ciphertext) = data.split_at(block);
+    let mut buf = ciphertext.to_vec();
+    let plain = Aes128CbcDec::new(k2[..16].into(), iv.into())
+        .decrypt_padded_mut::<NoPadding>(&mut buf)
+        .map_err(|e| format!("AES decryption failed: {}", e))?;
+
+    let pad_len = plain[plain.len() - 1] as usize;
+    if pad_len + 1 > plain.len() {
+        return Err("Invalid confidentiality pad length".to_string());
+    }
+    let payload_len = plain.len() - pad_len - 1;
+    for (i, &b) in plain[payload_len..payload_len + pad_len].iter().enumerate() {
+        if b as usize != i + 1 {
+            return Err("Invalid confidentiality pad".to_string());
+        }
+    }
+
+    Ok(plain[..payload_len].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha1_rfc2202() {
+        let key = [0x0bu8; 20];
+        let mac = lanplus_hmac(IPMI_AUTH_RAKP_HMAC_SHA1, &key, b"Hi There").unwrap();
+        assert_eq!(
+            mac,
+            [
+                0xb6, 0x17, 0x31, 0x86, 0x55, 0x05, 0x72, 0x64, 0xe2, 0x8b, 0xc0, 0xb6, 0xfb, 0x37,
+                0x8c, 0x8e, 0xf1, 0x46, 0xbe, 0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aes_cbc_128_roundtrip() {
+        let k2 = [0x42u8; 20];
+        let iv = [0x01u8; 16];
+        for len in [0usize, 1, 15, 16, 31] {
+            let payload: Vec<u8> = (0..len as u8).collect();
+            let enc = encrypt_aes_cbc_128(&k2, &iv, &payload).unwrap();
+            assert_eq!(&enc[..16], &iv);
+            assert_eq!((enc.len() - 16) % 16, 0);
+            assert_eq!(decrypt_aes_cbc_128(&k2, &enc).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn test_cipher_suite_lookup() {
+        assert_eq!(
+            cipher_suite_algorithms(3),
+            Some((
+                IPMI_AUTH_RAKP_HMAC_SHA1,
+                IPMI_INTEGRITY_HMAC_SHA1_96,
+                IPMI_CRYPT_AES_CBC_128
+            ))
+        );
+        assert_eq!(cipher_suite_algorithms(18), None);
+        assert_eq!(integrity_authcode_len(IPMI_INTEGRITY_HMAC_SHA1_96), 12);
+    }
+}
@@ -0,0 +1,1607 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+#![allow(dead_code)]
+
+use super::crypto::{
+    cipher_suite_algorithms, decrypt_aes_cbc_128, default_cipher_suite, encrypt_aes_cbc_128,
+    integrity_authcode, integrity_authcode_len, lanplus_hmac, rakp4_icv_len,
+    IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE,
+};
+use crate::error::{completion_code_to_string, IpmiError, IpmiResult};
+use crate::interface::lan::bridge::{build_request, PendingRequest};
+use crate::interface::lan::rmcp::{RmcpHeader, RMCP_UDP_PORT};
+use crate::interface::lan::IpmiLanIntf;
+/// IPMI LANPLUS Interface Implementation
+///
+/// Based on reference/ipmitool-c/src/plugins/lanplus/lanplus.c
+/// Implements IPMI v2.0 RMCP+ sessions (Open Session, RAKP 1-4)
+use crate::ipmi::constants::{
+    IPMI_AUTH_RAKP_NONE, IPMI_CRYPT_AES_CBC_128, IPMI_CRYPT_NONE, IPMI_INTEGRITY_NONE,
+};
+use crate::ipmi::intf::{
+    IpmiContext, IpmiIntf, IpmiV2Data, LanplusSessionState, SolData, IPMI_AUTHCODE_BUFFER_SIZE,
+    IPMI_KG_BUFFER_SIZE,
+};
+use crate::ipmi::ipmi::{
+    IpmiPayloadData, IpmiRq, IpmiRs, IpmiRsMsg, IpmiRsPayload, IpmiSession, IpmiV2Payload,
+    SolPacket, IPMI_BUF_SIZE, IPMI_NETFN_APP, IPMI_PAYLOAD_TYPE_IPMI, IPMI_PAYLOAD_TYPE_RAKP_1,
+    IPMI_PAYLOAD_TYPE_RAKP_2, IPMI_PAYLOAD_TYPE_RAKP_3, IPMI_PAYLOAD_TYPE_RAKP_4,
+    IPMI_PAYLOAD_TYPE_RMCP_OPEN_REQUEST, IPMI_PAYLOAD_TYPE_RMCP_OPEN_RESPONSE,
+    IPMI_PAYLOAD_TYPE_SOL,
+};
+use crate::ipmi::strings::{
+    auth_alg_to_str, crypt_alg_to_str, integrity_alg_to_str, privlvl_to_str,
+    rakp_return_code_to_str,
+};
+use crate::{log_debug, log_info};
+
+use std::collections::VecDeque;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+// IPMI LANPLUS Constants
+const IPMI_LANPLUS_TIMEOUT: u64 = 2; // seconds
+const IPMI_LANPLUS_RETRY: u32 = 4;
+const IPMI_LANPLUS_CHANNEL_E: u8 = 0x0e;
+// 请求 IPMI v2.0 扩展认证能力数据
+const IPMI_LANPLUS_CHANNEL_V2_DATA: u8 = 0x80;
+
+// Session header values
+const IPMI_SESSION_AUTHTYPE_NONE: u8 = 0x00;
+const IPMI_SESSION_AUTHTYPE_RMCP_PLUS: u8 = 0x06;
+const IPMI_PAYLOAD_ENCRYPTED: u8 = 0x80;
+const IPMI_PAYLOAD_AUTHENTICATED: u8 = 0x40;
+const IPMI_PAYLOAD_TYPE_MASK: u8 = 0x3f;
+const IPMI_LANPLUS_NEXT_HEADER: u8 = 0x07;
+const IPMI_LANPLUS_INTEGRITY_PAD: u8 = 0xff;
+
+// 默认最大请求/响应数据长度，与 ipmitool lanplus 保持一致
+const IPMI_LANPLUS_MAX_REQUEST_SIZE: u16 = 38; // 45 - 7
+const IPMI_LANPLUS_MAX_RESPONSE_SIZE: u16 = 34; // 42 - 8
+
+// RMCP header (4) + session header (12)
+const IPMI_LANPLUS_SESSION_HDR: usize = 16;
+// RMCP header (4) + v1.5 session header (10)
+const IPMI_LAN_SESSION_HDR: usize = 14;
+
+// Remote console session ID, same value ipmitool uses
+const IPMI_LANPLUS_CONSOLE_ID: u32 = 0xa0a2a3a4;
+// Name-only lookup bit in the RAKP 1 requested role
+const IPMI_LANPLUS_LOOKUP_NAME_ONLY: u8 = 0x10;
+const IPMI_LANPLUS_MAX_USERNAME: usize = 16;
+
+// SOL ACK 等待时间、NACK 后重发间隔以及 recv_sol 轮询时间
+const IPMI_LANPLUS_SOL_ACK_TIMEOUT_MS: u64 = 1000;
+const IPMI_LANPLUS_SOL_NACK_DELAY_MS: u64 = 100;
+const IPMI_LANPLUS_SOL_POLL_MS: u64 = 10;
+
+// IPMI Commands used by LANPLUS interface
+const IPMI_GET_CHANNEL_AUTH_CAP: u8 = 0x38;
+const IPMI_SET_SESSION_PRIVILEGE: u8 = 0x3b;
+const IPMI_CLOSE_SESSION: u8 = 0x3c;
+
+// Privilege levels used during session setup
+const IPMI_SESSION_PRIV_USER: u8 = 0x2;
+const IPMI_SESSION_PRIV_ADMIN: u8 = 0x4;
+
+/// A decoded RMCP+ (or pre-session IPMI v1.5) packet
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LanplusPacket {
+    pub authtype: u8,
+    pub payload_type: u8,
+    pub encrypted: bool,
+    pub authenticated: bool,
+    pub session_id: u32,
+    pub seq: u32,
+    pub payload: Vec<u8>,
+}
+
+/// IPMI LANPLUS Session Information
+#[derive(Default)]
+pub struct IpmiLanplusSession {
+    pub active: bool,
+    /// Last session sequence number received from the BMC
+    pub in_seq: u32,
+    /// Session sequence number used for the next outbound packet
+    pub out_seq: u32,
+    pub privilege_level: u8,
+    pub message_tag: u8,
+    pub v2_data: IpmiV2Data,
+    pub sol_data: SolData,
+    /// SOL packets received while waiting for other responses
+    pub sol_queue: VecDeque<IpmiRs>,
+}
+
+/// IPMI LANPLUS Interface
+pub struct IpmiLanplusIntf {
+    pub context: IpmiContext,
+    pub socket: Option<UdpSocket>,
+    pub target_addr: Option<SocketAddr>,
+    pub session: IpmiLanplusSession,
+    pub hostname: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub privilege_level: u8,
+    /// Cipher suite requested with -C
+    pub cipher_suite_id: u8,
+    /// 0 = any, otherwise AF_INET/AF_INET6 (-4/-6)
+    pub ai_family: i32,
+    pub timeout: u64,
+    pub retry_count: u32,
+    /// 6-bit IPMI message sequence number (rqSeq)
+    curr_seq: u8,
+}
+
+impl IpmiLanplusIntf {
+    /// Create new LANPLUS interface
+    pub fn new(hostname: String, port: u16, ctx: IpmiContext) -> Self {
+        Self {
+            context: ctx,
+            socket: None,
+            target_addr: None,
+            session: IpmiLanplusSession::default(),
+            hostname,
+            port: if port == 0 { RMCP_UDP_PORT } else { port },
+            username: String::new(),
+            password: String::new(),
+            privilege_level: IPMI_SESSION_PRIV_ADMIN,
+            cipher_suite_id: default_cipher_suite(),
+            ai_family: 0,
+            timeout: IPMI_LANPLUS_TIMEOUT,
+            retry_count: IPMI_LANPLUS_RETRY,
+            curr_seq: 0,
+        }
+    }
+
+    /// Set authentication credentials
+    pub fn set_credentials(&mut self, username: String, password: String) {
+        self.username = username;
+        self.password = password;
+    }
+
+    /// Set privilege level
+    pub fn set_privilege_level(&mut self, level: u8) {
+        self.privilege_level = level;
+    }
+
+    /// Select the cipher suite used for the session (-C)
+    pub fn set_cipher_suite(&mut self, cipher_suite_id: u8) {
+        self.cipher_suite_id = cipher_suite_id;
+    }
+
+    /// Set the BMC key (Kg), an all-zero key means "use the password"
+    pub fn set_kg(&mut self, kg: &[u8]) {
+        let kg_buf = &mut self.session.v2_data.kg;
+        kg_buf.fill(0);
+        let len = std::cmp::min(kg.len(), IPMI_KG_BUFFER_SIZE - 1);
+        kg_buf[..len].copy_from_slice(&kg[..len]);
+    }
+
+    /// Set per-request timeout (seconds) and number of retries
+    pub fn set_timeout(&mut self, timeout: u64, retry: u32) {
+        if timeout > 0 {
+            self.timeout = timeout;
+        }
+        if retry > 0 {
+            self.retry_count = retry;
+        }
+    }
+
+    /// Restrict hostname resolution to AF_INET or AF_INET6
+    pub fn set_address_family(&mut self, family: i32) {
+        self.ai_family = family;
+    }
+
+    /// Resolve hostname honouring the requested address family
+    fn resolve_target(&self) -> IpmiResult<SocketAddr> {
+        if self.hostname.is_empty() {
+            return Err(IpmiError::Network("No hostname specified!".to_string()));
+        }
+
+        let addrs = (self.hostname.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| {
+                IpmiError::Network(format!(
+                    "Address lookup for {} failed: {}",
+                    self.hostname, e
+                ))
+            })?;
+
+        addrs
+            .into_iter()
+            .find(|addr| match self.ai_family {
+                nix::libc::AF_INET => addr.is_ipv4(),
+                nix::libc::AF_INET6 => addr.is_ipv6(),
+                _ => true,
+            })
+            .ok_or_else(|| {
+                IpmiError::Network(format!("No address found for hostname: {}", self.hostname))
+            })
+    }
+
+    /// Password as used for RAKP HMAC keys (at most 20 bytes)
+    fn password_key(&self) -> &[u8] {
+        let pwd = self.password.as_bytes();
+        &pwd[..std::cmp::min(pwd.len(), IPMI_AUTHCODE_BUFFER_SIZE)]
+    }
+
+    /// Username as sent in RAKP 1 (at most 16 bytes)
+    fn username_bytes(&self) -> &[u8] {
+        let name = self.username.as_bytes();
+        &name[..std::cmp::min(name.len(), IPMI_LANPLUS_MAX_USERNAME)]
+    }
+
+    /// Build a pre-session IPMI v1.5 packet (authtype NONE)
+    fn build_v15_packet(msg: &[u8]) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(IPMI_LAN_SESSION_HDR + 1 + msg.len());
+        packet.extend_from_slice(&RmcpHeader::new_ipmi(0xff).to_bytes());
+        packet.push(IPMI_SESSION_AUTHTYPE_NONE);
+        packet.extend_from_slice(&[0u8; 8]); // seq + session id
+        packet.push(msg.len() as u8);
+        packet.extend_from_slice(msg);
+        packet
+    }
+
+    /// Build an RMCP+ packet for the given payload
+    ///
+    /// IPMI and SOL payloads of an active session are encrypted and carry an
+    /// integrity trailer according to the negotiated algorithms; everything
+    /// else (Open Session, RAKP) is sent in the clear with session ID 0.
+    fn build_v2_packet(&mut self, payload_type: u8, payload: &[u8]) -> Result<Vec<u8>, String> {
+        let secure = self.session.active
+            && matches!(payload_type, IPMI_PAYLOAD_TYPE_IPMI | IPMI_PAYLOAD_TYPE_SOL);
+        let v2 = &self.session.v2_data;
+        let encrypt = secure && v2.crypt_alg != IPMI_CRYPT_NONE;
+        let authenticate = secure && v2.integrity_alg != IPMI_INTEGRITY_NONE;
+        let (session_id, seq) = if secure {
+            (v2.bmc_id, self.session.out_seq)
+        } else {
+            (0, 0)
+        };
+
+        let body = if encrypt {
+            let iv: [u8; 16] = rand::random();
+            encrypt_aes_cbc_128(&v2.k2[..v2.k2_len as usize], &iv, payload)?
+        } else {
+            payload.to_vec()
+        };
+
+        let mut packet = Vec::with_capacity(IPMI_LANPLUS_SESSION_HDR + body.len() + 24);
+        packet.extend_from_slice(&RmcpHeader::new_ipmi(0xff).to_bytes());
+
+        let mut type_byte = payload_type;
+        if encrypt {
+            type_byte |= IPMI_PAYLOAD_ENCRYPTED;
+        }
+        if authenticate {
+            type_byte |= IPMI_PAYLOAD_AUTHENTICATED;
+        }
+        packet.push(IPMI_SESSION_AUTHTYPE_RMCP_PLUS);
+        packet.push(type_byte);
+        packet.extend_from_slice(&session_id.to_le_bytes());
+        packet.extend_from_slice(&seq.to_le_bytes());
+        packet.extend_from_slice(&(body.len() as u16).to_le_bytes());
+        packet.extend_from_slice(&body);
+
+        if authenticate {
+            // AuthType .. Next Header 的长度需要 4 字节对齐
+            let session_len = packet.len() - 4;
+            let pad = (4 - (session_len + 2) % 4) % 4;
+            packet.extend(std::iter::repeat(IPMI_LANPLUS_INTEGRITY_PAD).take(pad));
+            packet.push(pad as u8);
+            packet.push(IPMI_LANPLUS_NEXT_HEADER);
+
+            let authcode = integrity_authcode(
+                v2.integrity_alg,
+                &v2.k1[..v2.k1_len as usize],
+                self.password_key(),
+                &packet[4..],
+            )?;
+            packet.extend_from_slice(&authcode);
+        }
+
+        if secure {
+            self.session.out_seq = self.session.out_seq.wrapping_add(1);
+            if self.session.out_seq == 0 {
+                self.session.out_seq = 1;
+            }
+        }
+
+        Ok(packet)
+    }
+
+    /// Parse an incoming packet, verifying integrity and decrypting the payload
+    fn parse_packet(&self, data: &[u8]) -> Result<LanplusPacket, String> {
+        if data.len() < IPMI_LAN_SESSION_HDR {
+            return Err("Response packet too short".to_string());
+        }
+
+        let rmcp = RmcpHeader::from_bytes(&data[0..4])?;
+        if !rmcp.is_ipmi() {
+            return Err("Not an IPMI response".to_string());
+        }
+
+        let authtype = data[4];
+        if authtype != IPMI_SESSION_AUTHTYPE_RMCP_PLUS {
+            // 已协商完整性/加密的会话中不接受 v1.5 报文，否则可以绕过校验
+            if self.session.active && self.session_protected() {
+                return Err("IPMI v1.5 packet received in a protected RMCP+ session".to_string());
+            }
+            // Pre-session IPMI v1.5 response
+            let seq = u32::from_le_bytes([data[5], data[6], data[7], data[8]]);
+            let session_id = u32::from_le_bytes([data[9], data[10], data[11], data[12]]);
+            let mut offset = 13;
+            if authtype != IPMI_SESSION_AUTHTYPE_NONE {
+                offset += 16;
+            }
+            if data.len() <= offset {
+                return Err("Response packet too short".to_string());
+            }
+            let msg_len = data[offset] as usize;
+            offset += 1;
+            if data.len() < offset + msg_len {
+                return Err("IPMI message too short".to_string());
+            }
+            return Ok(LanplusPacket {
+                authtype,
+                payload_type: IPMI_PAYLOAD_TYPE_IPMI,
+                session_id,
+                seq,
+                payload: data[offset..offset + msg_len].to_vec(),
+                ..Default::default()
+            });
+        }
+
+        if data.len() < IPMI_LANPLUS_SESSION_HDR {
+            return Err("Response packet too short".to_string());
+        }
+
+        let type_byte = data[5];
+        let encrypted = type_byte & IPMI_PAYLOAD_ENCRYPTED != 0;
+        let authenticated = type_byte & IPMI_PAYLOAD_AUTHENTICATED != 0;
+        let session_id = u32::from_le_bytes([data[6], data[7], data[8], data[9]]);
+        let seq = u32::from_le_bytes([data[10], data[11], data[12], data[13]]);
+        let payload_len = u16::from_le_bytes([data[14], data[15]]) as usize;
+        if data.len() < IPMI_LANPLUS_SESSION_HDR + payload_len {
+            return Err("RMCP+ payload too short".to_string());
+        }
+
+        let v2 = &self.session.v2_data;
+        if (encrypted || authenticated) && !self.session.active {
+            return Err("Secure payload received outside of an active session".to_string());
+        }
+        let payload_type = type_byte & IPMI_PAYLOAD_TYPE_MASK;
+        if self.session.active
+            && (payload_type == IPMI_PAYLOAD_TYPE_IPMI || payload_type == IPMI_PAYLOAD_TYPE_SOL)
+        {
+            if v2.integrity_alg != IPMI_INTEGRITY_NONE && !authenticated {
+                return Err("Unauthenticated payload received in an RMCP+ session".to_string());
+            }
+            if v2.crypt_alg != IPMI_CRYPT_NONE && !encrypted {
+                return Err("Unencrypted payload received in an RMCP+ session".to_string());
+            }
+        }
+
+        if authenticated {
+            let authcode_len = integrity_authcode_len(v2.integrity_alg);
+            if authcode_len == 0 || data.len() < IPMI_LANPLUS_SESSION_HDR + payload_len + 2 {
+                return Err("Unexpected authenticated payload".to_string());
+            }
+            let trailer_end = data.len() - authcode_len;
+            let expected = integrity_authcode(
+                v2.integrity_alg,
+                &v2.k1[..v2.k1_len as usize],
+                self.password_key(),
+                &data[4..trailer_end],
+            )?;
+            if expected[..] != data[trailer_end..] {
+                return Err("Integrity check failed on RMCP+ packet".to_string());
+            }
+        }
+
+        let body = &data[IPMI_LANPLUS_SESSION_HDR..IPMI_LANPLUS_SESSION_HDR + payload_len];
+        let payload = if encrypted {
+            if v2.crypt_alg != IPMI_CRYPT_AES_CBC_128 {
+                return Err("Unexpected encrypted payload".to_string());
+            }
+            decrypt_aes_cbc_128(&v2.k2[..v2.k2_len as usize], body)?
+        } else {
+            body.to_vec()
+        };
+
+        Ok(LanplusPacket {
+            authtype,
+            payload_type,
+            encrypted,
+            authenticated,
+            session_id,
+            seq,
+            payload,
+        })
+    }
+
+    /// 会话是否协商了完整性或加密算法
+    fn session_protected(&self) -> bool {
+        let v2 = &self.session.v2_data;
+        v2.integrity_alg != IPMI_INTEGRITY_NONE || v2.crypt_alg != IPMI_CRYPT_NONE
+    }
+
+    /// Send a raw packet to the BMC
+    fn send_packet(&self, packet: &[u8]) -> Result<(), String> {
+        let socket = self.socket.as_ref().ok_or("Socket not initialized")?;
+        socket
+            .send(packet)
+            .map_err(|e| format!("Failed to send packet: {}", e))?;
+        Ok(())
+    }
+
+    /// Receive and decode one packet of this session, Ok(None) on timeout
+    fn recv_packet(&mut self, timeout: Duration) -> Result<Option<LanplusPacket>, String> {
+        self.socket
+            .as_ref()
+            .ok_or("Socket not initialized")?
+            .set_read_timeout(Some(timeout))
+            .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
+
+        let mut buffer = [0u8; IPMI_BUF_SIZE];
+        loop {
+            let socket = self.socket.as_ref().ok_or("Socket not initialized")?;
+            let len = match socket.recv(&mut buffer) {
+                Ok(len) => len,
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    return Ok(None);
+                }
+                Err(e) => return Err(format!("Failed to receive response: {}", e)),
+            };
+
+            if let Some(pkt) = self.accept_packet(&buffer[..len]) {
+                return Ok(Some(pkt));
+            }
+        }
+    }
+
+    /// Decode a received datagram, None if it is not for this session
+    pub(crate) fn accept_packet(&mut self, data: &[u8]) -> Option<LanplusPacket> {
+        let pkt = match self.parse_packet(data) {
+            Ok(pkt) => pkt,
+            Err(e) => {
+                log_debug!("Discarding packet: {}", e);
+                return None;
+            }
+        };
+
+        if self.session.active
+            && pkt.authtype == IPMI_SESSION_AUTHTYPE_RMCP_PLUS
+            && pkt.session_id != self.session.v2_data.console_id
+        {
+            log_debug!(
+                "Discarding packet for session 0x{:08x} (expected 0x{:08x})",
+                pkt.session_id,
+                self.session.v2_data.console_id
+            );
+            return None;
+        }
+
+        if self.session.active && pkt.seq != 0 {
+            self.session.in_seq = pkt.seq;
+        }
+        Some(pkt)
+    }
+
+    /// Build the packet for one transmission of a payload
+    ///
+    /// Get Channel Auth Capabilities 在会话建立前使用 v1.5 格式
+    pub(crate) fn build_request_packet(
+        &mut self,
+        payload_type: u8,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        if payload_type == IPMI_PAYLOAD_TYPE_IPMI && !self.session.active {
+            Ok(Self::build_v15_packet(payload))
+        } else {
+            self.build_v2_packet(payload_type, payload)
+        }
+    }
+
+    /// Send a payload and wait for a packet accepted by `accept`, retrying on timeout
+    ///
+    /// Each retry rebuilds the packet so active sessions use a fresh sequence number.
+    /// SOL data arriving in the meantime is acknowledged and queued for recv_sol.
+    pub(crate) fn transact<F>(
+        &mut self,
+        payload_type: u8,
+        payload: &[u8],
+        accept: F,
+    ) -> Result<LanplusPacket, String>
+    where
+        F: Fn(&LanplusPacket) -> bool,
+    {
+        if self.socket.is_none() {
+            return Err("Socket not initialized".to_string());
+        }
+
+        for attempt in 0..self.retry_count {
+            let packet = self.build_request_packet(payload_type, payload)?;
+            self.send_packet(&packet)?;
+
+            loop {
+                let pkt = match self.recv_packet(Duration::from_secs(self.timeout))? {
+                    Some(pkt) => pkt,
+                    None => {
+                        log_info!(
+                            "No response to payload type 0x{:02x} (attempt {}/{})",
+                            payload_type,
+                            attempt + 1,
+                            self.retry_count
+                        );
+                        break;
+                    }
+                };
+
+                if self.session.active
+                    && pkt.payload_type == IPMI_PAYLOAD_TYPE_SOL
+                    && payload_type != IPMI_PAYLOAD_TYPE_SOL
+                {
+                    self.queue_sol_packet(&pkt);
+                    continue;
+                }
+
+                if !accept(&pkt) {
+                    log_debug!(
+                        "Discarding unexpected packet with payload type 0x{:02x}",
+                        pkt.payload_type
+                    );
+                    continue;
+                }
+
+                return Ok(pkt);
+            }
+        }
+
+        Err("No response from remote controller".to_string())
+    }
+
+    fn next_sol_seq(&mut self) -> u8 {
+        // SOL 包序号为 1..15，0 表示仅 ACK
+        let sol = &mut self.session.sol_data;
+        sol.sequence_number = (sol.sequence_number % 15) + 1;
+        sol.sequence_number
+    }
+
+    /// Decode an incoming SOL payload into IpmiRs, character data goes to rsp.data
+    fn decode_sol_packet(pkt: &LanplusPacket) -> Option<IpmiRs> {
+        let p = &pkt.payload;
+        if p.len() < 4 {
+            return None;
+        }
+        let count = std::cmp::min(p.len() - 4, IPMI_BUF_SIZE);
+
+        let mut rsp = IpmiRs {
+            ccode: 0,
+            data: [0; IPMI_BUF_SIZE],
+            data_len: count as i32,
+            msg: IpmiRsMsg::default(),
+            session: IpmiSession {
+                authtype: pkt.authtype,
+                seq: pkt.seq,
+                id: pkt.session_id,
+                b_encrypted: pkt.encrypted as u8,
+                b_authenticated: pkt.authenticated as u8,
+                payloadtype: pkt.payload_type,
+                msglen: p.len() as u16,
+            },
+            payload: IpmiRsPayload::SolPacket {
+                packet_sequence_number: p[0] & 0x0f,
+                acked_packet_number: p[1] & 0x0f,
+                accepted_character_count: p[2],
+                is_nack: (p[3] >> 6) & 0x01,
+                transfer_unavailable: (p[3] >> 5) & 0x01,
+                sol_inactive: (p[3] >> 4) & 0x01,
+                transmit_overrun: (p[3] >> 3) & 0x01,
+                break_detected: (p[3] >> 2) & 0x01,
+            },
+        };
+        rsp.data[..count].copy_from_slice(&p[4..4 + count]);
+        Some(rsp)
+    }
+
+    fn sol_inactive(rsp: &IpmiRs) -> bool {
+        matches!(rsp.payload, IpmiRsPayload::SolPacket { sol_inactive, .. } if sol_inactive != 0)
+    }
+
+    /// Send an ACK-only SOL packet for received character data
+    fn ack_sol_packet(&mut self, seq: u8, count: u8) -> Result<(), String> {
+        let packet = self.build_v2_packet(IPMI_PAYLOAD_TYPE_SOL, &[0, seq, count, 0])?;
+        self.send_packet(&packet)
+    }
+
+    /// ACK incoming SOL data and suppress retransmitted duplicates
+    ///
+    /// Returns the decoded packet; data_len is cleared for duplicates.
+    fn handle_sol_packet(&mut self, pkt: &LanplusPacket) -> Option<IpmiRs> {
+        let mut rsp = Self::decode_sol_packet(pkt)?;
+        let seq = pkt.payload[0] & 0x0f;
+        if seq != 0 {
+            let count = rsp.data_len as u8;
+            let sol = &mut self.session.sol_data;
+            let duplicate =
+                seq == sol.last_received_sequence_number && count == sol.last_received_byte_count;
+            sol.last_received_sequence_number = seq;
+            sol.last_received_byte_count = count;
+
+            if let Err(e) = self.ack_sol_packet(seq, count) {
+                log_debug!("Failed to ACK SOL packet {}: {}", seq, e);
+            }
+            if duplicate {
+                log_debug!("Dropping duplicate SOL packet {}", seq);
+                rsp.data_len = 0;
+            }
+        }
+        Some(rsp)
+    }
+
+    /// Keep SOL data (or a deactivation notice) for the next recv_sol call
+    fn queue_sol_packet(&mut self, pkt: &LanplusPacket) {
+        if let Some(rsp) = self.handle_sol_packet(pkt) {
+            if rsp.data_len > 0 || Self::sol_inactive(&rsp) {
+                self.session.sol_queue.push_back(rsp);
+            }
+        }
+    }
+
+    /// Send one SOL packet and wait for its ACK, retransmitting on timeout
+    fn sol_wait_ack(&mut self, bytes: &[u8], seq: u8) -> Result<IpmiRs, String> {
+        for attempt in 0..self.retry_count {
+            let packet = self.build_v2_packet(IPMI_PAYLOAD_TYPE_SOL, bytes)?;
+            self.send_packet(&packet)?;
+
+            let deadline = Instant::now() + Duration::from_millis(IPMI_LANPLUS_SOL_ACK_TIMEOUT_MS);
+            loop {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                let pkt = match self.recv_packet(deadline - now)? {
+                    Some(pkt) => pkt,
+                    None => break,
+                };
+                if pkt.payload_type != IPMI_PAYLOAD_TYPE_SOL {
+                    log_debug!(
+                        "Discarding payload type 0x{:02x} while waiting for SOL ACK",
+                        pkt.payload_type
+                    );
+                    continue;
+                }
+
+                let is_ack = pkt.payload.len() >= 4 && pkt.payload[1] & 0x0f == seq;
+                self.queue_sol_packet(&pkt);
+                if is_ack {
+                    let mut ack = Self::decode_sol_packet(&pkt).unwrap();
+                    ack.data_len = 0;
+                    return Ok(ack);
+                }
+            }
+
+            log_info!(
+                "No ACK for SOL packet {} (attempt {}/{})",
+                seq,
+                attempt + 1,
+                self.retry_count
+            );
+        }
+
+        Err(format!("No ACK received for SOL packet {}", seq))
+    }
+
+    /// Send SOL character data / control bits, resending what the BMC did not accept
+    fn send_sol_packet(&mut self, sol: &SolPacket) -> Result<IpmiRs, String> {
+        let count = std::cmp::min(sol.character_count as usize, IPMI_BUF_SIZE);
+        let chars = &sol.data[..count];
+
+        let mut ctrl = 0u8;
+        for (set, bit) in [
+            (sol.is_nack, 0x40),
+            (sol.assert_ring_wor, 0x20),
+            (sol.generate_break, 0x10),
+            (sol.deassert_cts, 0x08),
+            (sol.deassert_dcd_dsr, 0x04),
+            (sol.flush_inbound, 0x02),
+            (sol.flush_outbound, 0x01),
+        ] {
+            if set {
+                ctrl |= bit;
+            }
+        }
+
+        let mut offset = 0;
+        let mut nacks = 0;
+        loop {
+            let seq = self.next_sol_seq();
+            let mut bytes = vec![seq, 0, 0, ctrl];
+            bytes.extend_from_slice(&chars[offset..]);
+
+            let ack = self.sol_wait_ack(&bytes, seq)?;
+            let (accepted, is_nack) = match ack.payload {
+                IpmiRsPayload::SolPacket {
+                    accepted_character_count,
+                    is_nack,
+                    ..
+                } => (accepted_character_count as usize, is_nack != 0),
+                _ => (0, true),
+            };
+
+            if is_nack {
+                // BMC 暂时无法接收字符，稍后重发
+                nacks += 1;
+                if nacks > self.retry_count {
+                    return Err(format!("SOL packet {} was NACKed by the BMC", seq));
+                }
+                std::thread::sleep(Duration::from_millis(IPMI_LANPLUS_SOL_NACK_DELAY_MS));
+                continue;
+            }
+
+            ctrl = 0;
+            offset += std::cmp::min(accepted, chars.len() - offset);
+            if offset >= chars.len() || accepted == 0 {
+                if offset < chars.len() {
+                    log_debug!("BMC accepted no characters of SOL packet {}", seq);
+                }
+                return Ok(ack);
+            }
+            log_debug!(
+                "Partial SOL ACK: {} of {} characters accepted",
+                accepted,
+                chars.len() - offset + accepted
+            );
+        }
+    }
+
+    /// Next IPMI message (rsSA .. checksum) carrying `req` and the state used to
+    /// match its response; a non-zero `bridging_level` wraps it in Send Message
+    pub(crate) fn next_request_message(
+        &mut self,
+        req: &IpmiRq,
+        bridging_level: u8,
+    ) -> (Vec<u8>, PendingRequest) {
+        self.curr_seq = (self.curr_seq + 1) & 0x3f;
+        build_request(req, self.curr_seq, bridging_level, &self.context)
+    }
+
+    /// Whether `pkt` is the response the pending IPMI request is waiting for
+    pub(crate) fn is_response_to(pkt: &LanplusPacket, pending: &PendingRequest) -> bool {
+        pkt.payload_type == IPMI_PAYLOAD_TYPE_IPMI
+            && pkt.payload.len() >= 8
+            && pending.matches(pkt.payload[4] >> 2, pkt.payload[5])
+    }
+
+    /// Decode an IPMI response packet into IpmiRs
+    pub(crate) fn command_response(pkt: &LanplusPacket) -> Result<IpmiRs, String> {
+        IpmiLanIntf::parse_message(
+            &pkt.payload,
+            IpmiSession {
+                authtype: pkt.authtype,
+                seq: pkt.seq,
+                id: pkt.session_id,
+                b_encrypted: pkt.encrypted as u8,
+                b_authenticated: pkt.authenticated as u8,
+                payloadtype: pkt.payload_type,
+                msglen: pkt.payload.len() as u16,
+            },
+        )
+    }
+
+    /// Send an IPMI request and wait for the matching response
+    fn send_command(&mut self, req: &IpmiRq, bridging_level: u8) -> Result<IpmiRs, String> {
+        let (msg, initial) = self.next_request_message(req, bridging_level);
+
+        let pkt = self.transact(IPMI_PAYLOAD_TYPE_IPMI, &msg, |pkt| {
+            Self::is_response_to(pkt, &initial)
+        })?;
+
+        let mut pending = initial;
+        let mut rsp = Self::command_response(&pkt)?;
+        loop {
+            if let Some(rsp) = pending.unwrap_response(rsp) {
+                return Ok(rsp);
+            }
+            rsp = self.recv_bridged_response(&pending)?;
+        }
+    }
+
+    /// Wait for a bridged response that follows an empty Send Message response
+    fn recv_bridged_response(&mut self, pending: &PendingRequest) -> Result<IpmiRs, String> {
+        loop {
+            let Some(pkt) = self.recv_packet(Duration::from_secs(self.timeout))? else {
+                return Err(format!(
+                    "No bridged response to command 0x{:02x}",
+                    pending.target_cmd
+                ));
+            };
+            if pkt.payload_type == IPMI_PAYLOAD_TYPE_SOL {
+                self.queue_sol_packet(&pkt);
+                continue;
+            }
+            if Self::is_response_to(&pkt, pending) {
+                return Self::command_response(&pkt);
+            }
+            log_debug!(
+                "Discarding unexpected packet with payload type 0x{:02x}",
+                pkt.payload_type
+            );
+        }
+    }
+
+    /// Build a request with the given APP netfn command and payload and send it
+    ///
+    /// Session management always talks to the BMC itself and is never bridged.
+    fn send_app_command(&mut self, cmd: u8, data: &mut [u8]) -> Result<IpmiRs, String> {
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = cmd;
+        if !data.is_empty() {
+            req.msg.data = data.as_mut_ptr();
+            req.msg.data_len = data.len() as u16;
+        }
+        self.send_command(&req, 0)
+    }
+
+    fn next_message_tag(&mut self) -> u8 {
+        self.session.message_tag = self.session.message_tag.wrapping_add(1);
+        self.session.message_tag
+    }
+
+    /// Get Channel Authentication Capabilities, checking for IPMI v2.0 support
+    fn get_auth_capabilities(&mut self) -> IpmiResult<()> {
+        let mut msg_data = [
+            IPMI_LANPLUS_CHANNEL_V2_DATA | IPMI_LANPLUS_CHANNEL_E,
+            self.privilege_level,
+        ];
+        let rsp = self
+            .send_app_command(IPMI_GET_CHANNEL_AUTH_CAP, &mut msg_data)
+            .map_err(|e| {
+                IpmiError::Session(format!(
+                    "Error issuing Get Channel Authentication Capabilities request: {}",
+                    e
+                ))
+            })?;
+
+        if rsp.ccode != 0 {
+            return Err(IpmiError::Session(format!(
+                "Get Auth Capabilities error: {}",
+                completion_code_to_string(rsp.ccode)
+            )));
+        }
+
+        let v20_data_available = rsp.data_len >= 4 && rsp.data[1] & 0x80 != 0;
+        if !v20_data_available || rsp.data[3] & 0x02 == 0 {
+            return Err(IpmiError::NotSupported(
+                "This BMC does not support IPMI v2 / RMCP+".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Open Session Request / Response: negotiate the cipher suite algorithms
+    fn open_session(&mut self) -> IpmiResult<()> {
+        let (auth_alg, integrity_alg, crypt_alg) = cipher_suite_algorithms(self.cipher_suite_id)
+            .ok_or_else(|| {
+                IpmiError::InvalidData(format!("Invalid cipher suite id {}", self.cipher_suite_id))
+            })?;
+        if crypt_alg != IPMI_CRYPT_NONE && crypt_alg != IPMI_CRYPT_AES_CBC_128 {
+            return Err(IpmiError::NotSupported(format!(
+                "Unsupported encryption algorithm {}",
+                crypt_alg_to_str(crypt_alg)
+            )));
+        }
+
+        {
+            let v2 = &mut self.session.v2_data;
+            v2.requested_auth_alg = auth_alg;
+            v2.requested_integrity_alg = integrity_alg;
+            v2.requested_crypt_alg = crypt_alg;
+            v2.console_id = IPMI_LANPLUS_CONSOLE_ID;
+        }
+
+        let tag = self.next_message_tag();
+        let mut msg = [0u8; 32];
+        msg[0] = tag;
+        // 请求的最大权限，0 表示由 BMC 选择与算法匹配的最高权限
+        msg[1] = 0;
+        msg[4..8].copy_from_slice(&IPMI_LANPLUS_CONSOLE_ID.to_le_bytes());
+        // Authentication payload
+        msg[8] = 0x00;
+        msg[11] = 0x08;
+        msg[12] = auth_alg;
+        // Integrity payload
+        msg[16] = 0x01;
+        msg[19] = 0x08;
+        msg[20] = integrity_alg;
+        // Confidentiality payload
+        msg[24] = 0x02;
+        msg[27] = 0x08;
+        msg[28] = crypt_alg;
+
+        self.session.v2_data.session_state = LanplusSessionState::OpenSessionSent;
+        let pkt = self
+            .transact(IPMI_PAYLOAD_TYPE_RMCP_OPEN_REQUEST, &msg, |pkt| {
+                pkt.payload_type == IPMI_PAYLOAD_TYPE_RMCP_OPEN_RESPONSE
+                    && pkt.payload.len() >= 2
+                    && pkt.payload[0] == tag
+            })
+            .map_err(|e| IpmiError::Session(format!("Open Session request failed: {}", e)))?;
+
+        let rsp = &pkt.payload;
+        if rsp[1] != 0 {
+            return Err(IpmiError::Session(format!(
+                "Error in open session response message : {}",
+                rakp_return_code_to_str(rsp[1])
+            )));
+        }
+        if rsp.len() < 36 {
+            return Err(IpmiError::Session(
+                "Open Session response too short".to_string(),
+            ));
+        }
+
+        let console_id = u32::from_le_bytes([rsp[4], rsp[5], rsp[6], rsp[7]]);
+        if console_id != IPMI_LANPLUS_CONSOLE_ID {
+            return Err(IpmiError::Session(
+                "Console session ID mismatch in open session response".to_string(),
+            ));
+        }
+
+        let v2 = &mut self.session.v2_data;
+        v2.max_priv_level = rsp[2] & 0x0f;
+        v2.bmc_id = u32::from_le_bytes([rsp[8], rsp[9], rsp[10], rsp[11]]);
+        v2.auth_alg = rsp[16] & 0x3f;
+        v2.integrity_alg = rsp[24] & 0x3f;
+        v2.crypt_alg = rsp[32] & 0x3f;
+
+        if v2.auth_alg != v2.requested_auth_alg
+            || v2.integrity_alg != v2.requested_integrity_alg
+            || v2.crypt_alg != v2.requested_crypt_alg
+        {
+            return Err(IpmiError::Session(format!(
+                "BMC selected unexpected algorithms: auth {} integrity {} crypt {}",
+                auth_alg_to_str(v2.auth_alg),
+                integrity_alg_to_str(v2.integrity_alg),
+                crypt_alg_to_str(v2.crypt_alg)
+            )));
+        }
+        v2.session_state = LanplusSessionState::OpenSessionReceived;
+
+        log_debug!("IPMIv2 / RMCP+ SESSION OPEN RESPONSE");
+        log_debug!("  Max Priv Level  : {}", privlvl_to_str(v2.max_priv_level));
+        log_debug!("  Console Sess ID : {:08x}", console_id);
+        log_debug!("  BMC Session ID  : {:08x}", v2.bmc_id);
+        log_debug!("  Auth alg        : {}", auth_alg_to_str(v2.auth_alg));
+        log_debug!(
+            "  Integrity alg   : {}",
+            integrity_alg_to_str(v2.integrity_alg)
+        );
+        log_debug!("  Crypt alg       : {}", crypt_alg_to_str(v2.crypt_alg));
+
+        Ok(())
+    }
+
+    /// RAKP 2 Key Exchange Authentication Code input
+    fn rakp2_hmac_input(&self) -> Vec<u8> {
+        let v2 = &self.session.v2_data;
+        let name = self.username_bytes();
+        let mut buf = Vec::with_capacity(58 + name.len());
+        buf.extend_from_slice(&v2.console_id.to_le_bytes());
+        buf.extend_from_slice(&v2.bmc_id.to_le_bytes());
+        buf.extend_from_slice(&v2.console_rand);
+        buf.extend_from_slice(&v2.bmc_rand);
+        buf.extend_from_slice(&v2.bmc_guid);
+        buf.push(v2.requested_role);
+        buf.push(name.len() as u8);
+        buf.extend_from_slice(name);
+        buf
+    }
+
+    /// RAKP 3 Key Exchange Authentication Code input
+    fn rakp3_hmac_input(&self) -> Vec<u8> {
+        let v2 = &self.session.v2_data;
+        let name = self.username_bytes();
+        let mut buf = Vec::with_capacity(22 + name.len());
+        buf.extend_from_slice(&v2.bmc_rand);
+        buf.extend_from_slice(&v2.console_id.to_le_bytes());
+        buf.push(v2.requested_role);
+        buf.push(name.len() as u8);
+        buf.extend_from_slice(name);
+        buf
+    }
+
+    /// RAKP 4 Integrity Check Value input
+    fn rakp4_hmac_input(&self) -> Vec<u8> {
+        let v2 = &self.session.v2_data;
+        let mut buf = Vec::with_capacity(36);
+        buf.extend_from_slice(&v2.console_rand);
+        buf.extend_from_slice(&v2.bmc_id.to_le_bytes());
+        buf.extend_from_slice(&v2.bmc_guid);
+        buf
+    }
+
+    /// Derive SIK, K1 and K2 once RAKP 2 has been validated
+    fn generate_session_keys(&mut self) -> Result<(), String> {
+        let auth_alg = self.session.v2_data.auth_alg;
+        let name = self.username_bytes().to_vec();
+
+        let mut input = Vec::with_capacity(34 + name.len());
+        input.extend_from_slice(&self.session.v2_data.console_rand);
+        input.extend_from_slice(&self.session.v2_data.bmc_rand);
+        input.push(self.session.v2_data.requested_role);
+        input.push(name.len() as u8);
+        input.extend_from_slice(&name);
+
+        // Kg 全零时使用用户密码生成 SIK
+        let kg = &self.session.v2_data.kg[..IPMI_KG_BUFFER_SIZE - 1];
+        let sik = if kg.iter().any(|&b| b != 0) {
+            lanplus_hmac(auth_alg, kg, &input)?
+        } else {
+            lanplus_hmac(auth_alg, self.password_key(), &input)?
+        };
+
+        // Const1/Const2 固定为 20 字节，与认证算法无关 (IPMI v2.0 13.32)
+        let k1 = lanplus_hmac(auth_alg, &sik, &[0x01; 20])?;
+        let k2 = lanplus_hmac(auth_alg, &sik, &[0x02; 20])?;
+
+        let v2 = &mut self.session.v2_data;
+        v2.sik[..sik.len()].copy_from_slice(&sik);
+        v2.sik_len = sik.len() as u8;
+        v2.k1[..k1.len()].copy_from_slice(&k1);
+        v2.k1_len = k1.len() as u8;
+        v2.k2[..k2.len()].copy_from_slice(&k2);
+        v2.k2_len = k2.len() as u8;
+
+        log_debug!("Generated session keys (SIK/K1/K2, {} bytes)", sik.len());
+        Ok(())
+    }
+
+    /// RAKP 1 / RAKP 2: exchange random numbers and authenticate the BMC
+    fn rakp1(&mut self) -> IpmiResult<()> {
+        let tag = self.next_message_tag();
+        let console_rand: [u8; 16] = rand::random();
+        let role = IPMI_LANPLUS_LOOKUP_NAME_ONLY | self.privilege_level;
+        self.session.v2_data.console_rand = console_rand;
+        self.session.v2_data.requested_role = role;
+
+        let name = self.username_bytes().to_vec();
+        let mut msg = Vec::with_capacity(28 + name.len());
+        msg.push(tag);
+        msg.extend_from_slice(&[0u8; 3]);
+        msg.extend_from_slice(&self.session.v2_data.bmc_id.to_le_bytes());
+        msg.extend_from_slice(&console_rand);
+        msg.push(role);
+        msg.extend_from_slice(&[0u8; 2]);
+        msg.push(name.len() as u8);
+        msg.extend_from_slice(&name);
+
+        self.session.v2_data.session_state = LanplusSessionState::Rakp1Sent;
+        let pkt = self
+            .transact(IPMI_PAYLOAD_TYPE_RAKP_1, &msg, |pkt| {
+                pkt.payload_type == IPMI_PAYLOAD_TYPE_RAKP_2
+                    && pkt.payload.len() >= 2
+                    && pkt.payload[0] == tag
+            })
+            .map_err(|e| IpmiError::Session(format!("RAKP 1 request failed: {}", e)))?;
+
+        let rsp = &pkt.payload;
+        self.session.v2_data.rakp2_return_code = rsp[1];
+        if rsp[1] != 0 {
+            return Err(IpmiError::Authentication(format!(
+                "RAKP 2 message indicates an error : {}",
+                rakp_return_code_to_str(rsp[1])
+            )));
+        }
+        if rsp.len() < 40 {
+            return Err(IpmiError::Session("RAKP 2 message too short".to_string()));
+        }
+
+        self.session.v2_data.bmc_rand.copy_from_slice(&rsp[8..24]);
+        self.session.v2_data.bmc_guid.copy_from_slice(&rsp[24..40]);
+        self.session.v2_data.session_state = LanplusSessionState::Rakp2Received;
+
+        let auth_alg = self.session.v2_data.auth_alg;
+        if auth_alg == IPMI_AUTH_RAKP_NONE {
+            return Ok(());
+        }
+
+        let expected = lanplus_hmac(auth_alg, self.password_key(), &self.rakp2_hmac_input())
+            .map_err(IpmiError::Authentication)?;
+        if rsp.len() < 40 + expected.len() || rsp[40..40 + expected.len()] != expected[..] {
+            return Err(IpmiError::Authentication(
+                "RAKP 2 HMAC is invalid".to_string(),
+            ));
+        }
+
+        self.generate_session_keys()
+            .map_err(IpmiError::Authentication)
+    }
+
+    /// RAKP 3 / RAKP 4: prove our identity and verify the session integrity key
+    fn rakp3(&mut self) -> IpmiResult<()> {
+        let tag = self.next_message_tag();
+        let auth_alg = self.session.v2_data.auth_alg;
+
+        let mut msg = Vec::with_capacity(8 + 32);
+        msg.push(tag);
+        msg.push(0); // status
+        msg.extend_from_slice(&[0u8; 2]);
+        msg.extend_from_slice(&self.session.v2_data.bmc_id.to_le_bytes());
+        if auth_alg != IPMI_AUTH_RAKP_NONE {
+            let authcode = lanplus_hmac(auth_alg, self.password_key(), &self.rakp3_hmac_input())
+                .map_err(IpmiError::Authentication)?;
+            msg.extend_from_slice(&authcode);
+        }
+
+        self.session.v2_data.session_state = LanplusSessionState::Rakp3Sent;
+        let pkt = self
+            .transact(IPMI_PAYLOAD_TYPE_RAKP_3, &msg, |pkt| {
+                pkt.payload_type == IPMI_PAYLOAD_TYPE_RAKP_4
+                    && pkt.payload.len() >= 2
+                    && pkt.payload[0] == tag
+            })
+            .map_err(|e| IpmiError::Session(format!("RAKP 3 request failed: {}", e)))?;
+
+        let rsp = &pkt.payload;
+        if rsp[1] != 0 {
+            return Err(IpmiError::Authentication(format!(
+                "RAKP 4 message indicates an error : {}",
+                rakp_return_code_to_str(rsp[1])
+            )));
+        }
+
+        if auth_alg != IPMI_AUTH_RAKP_NONE {
+            let icv_len = rakp4_icv_len(auth_alg);
+            let v2 = &self.session.v2_data;
+            let mut expected = lanplus_hmac(
+                auth_alg,
+                &v2.sik[..v2.sik_len as usize],
+                &self.rakp4_hmac_input(),
+            )
+            .map_err(IpmiError::Authentication)?;
+            expected.truncate(icv_len);
+            if rsp.len() < 8 + icv_len || rsp[8..8 + icv_len] != expected[..] {
+                return Err(IpmiError::Authentication(
+                    "RAKP 4 HMAC is invalid".to_string(),
+                ));
+            }
+        }
+
+        self.session.v2_data.session_state = LanplusSessionState::Active;
+        self.session.active = true;
+        self.session.out_seq = 1;
+        self.session.in_seq = 0;
+
+        log_debug!("IPMIv2 / RMCP+ SESSION OPENED SUCCESSFULLY");
+        Ok(())
+    }
+
+    /// Raise the session privilege level above the default USER level
+    fn set_session_privlvl(&mut self) -> IpmiResult<()> {
+        if self.privilege_level <= IPMI_SESSION_PRIV_USER {
+            self.session.privilege_level = self.privilege_level;
+            return Ok(());
+        }
+
+        let mut msg_data = [self.privilege_level];
+        let rsp = self
+            .send_app_command(IPMI_SET_SESSION_PRIVILEGE, &mut msg_data)
+            .map_err(|e| {
+                IpmiError::Session(format!(
+                    "Set Session Privilege Level to {} failed: {}",
+                    privlvl_to_str(self.privilege_level),
+                    e
+                ))
+            })?;
+
+        if rsp.ccode != 0 {
+            let reason = match rsp.ccode {
+                0x80 => "Requested privilege level not available for this user",
+                0x81 => "Requested privilege level exceeds user and/or channel limit",
+                0x82 => "Cannot disable User Level authentication",
+                cc => completion_code_to_string(cc),
+            };
+            return Err(IpmiError::Session(format!(
+                "Set Session Privilege Level to {} failed: {}",
+                privlvl_to_str(self.privilege_level),
+                reason
+            )));
+        }
+
+        if rsp.data_len < 1 {
+            return Err(IpmiError::Session(format!(
+                "Set Session Privilege Level to {} failed: short response",
+                privlvl_to_str(self.privilege_level)
+            )));
+        }
+
+        self.session.privilege_level = rsp.data[0] & 0x0f;
+        log_debug!(
+            "Set Session Privilege Level to {}",
+            privlvl_to_str(self.session.privilege_level)
+        );
+
+        Ok(())
+    }
+
+    /// Close the active session
+    fn close_session(&mut self) {
+        if !self.session.active {
+            return;
+        }
+
+        let bmc_id = self.session.v2_data.bmc_id;
+        let mut msg_data = bmc_id.to_le_bytes();
+        match self.send_app_command(IPMI_CLOSE_SESSION, &mut msg_data) {
+            Ok(rsp) if rsp.ccode == 0 => log_debug!("Closed Session {:08x}", bmc_id),
+            Ok(rsp) if rsp.ccode == 0x87 => {
+                log::error!("Failed to Close Session: invalid session ID {:08x}", bmc_id)
+            }
+            Ok(rsp) => log::error!(
+                "Close Session command failed: {}",
+                completion_code_to_string(rsp.ccode)
+            ),
+            Err(e) => log::error!("Close Session command failed: {}", e),
+        }
+
+        self.session.active = false;
+        self.session.v2_data.session_state = LanplusSessionState::Presession;
+    }
+
+    /// Run the full RMCP+ session establishment sequence
+    fn activate(&mut self) -> IpmiResult<()> {
+        self.session.active = false;
+        self.session.v2_data.session_state = LanplusSessionState::Presession;
+
+        self.get_auth_capabilities()?;
+        self.open_session()?;
+        self.rakp1()?;
+        self.rakp3()?;
+        if let Err(e) = self.set_session_privlvl() {
+            self.close_session();
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+impl IpmiIntf for IpmiLanplusIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        &mut self.context
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        if cipher_suite_algorithms(self.cipher_suite_id).is_none() {
+            return Err(IpmiError::InvalidData(format!(
+                "Invalid cipher suite id {}",
+                self.cipher_suite_id
+            )));
+        }
+
+        // 设置 LANPLUS 接口的默认参数
+        self.context.protocol.max_request_data_size = IPMI_LANPLUS_MAX_REQUEST_SIZE;
+        self.context.protocol.max_response_data_size = IPMI_LANPLUS_MAX_RESPONSE_SIZE;
+
+        Ok(())
+    }
+
+    fn open(&mut self) -> IpmiResult<()> {
+        if self.socket.is_some() {
+            return Ok(()); // 已经打开
+        }
+
+        let remote = self.resolve_target()?;
+
+        // 创建 UDP socket
+        let local_addr: SocketAddr = if remote.is_ipv6() {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        }
+        .parse()
+        .map_err(|e| IpmiError::System(format!("Invalid local address: {}", e)))?;
+
+        let socket = UdpSocket::bind(local_addr)
+            .map_err(|e| IpmiError::Network(format!("Failed to bind UDP socket: {}", e)))?;
+
+        // 连接到远程地址
+        socket
+            .connect(remote)
+            .map_err(|e| IpmiError::Network(format!("Failed to connect to {}: {}", remote, e)))?;
+
+        self.target_addr = Some(remote);
+        self.socket = Some(socket);
+
+        // 建立 RMCP+ 会话
+        if let Err(e) = self.activate() {
+            self.socket = None;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        // 关闭会话（如果有活动会话）
+        if self.socket.is_some() {
+            self.close_session();
+        }
+
+        // 关闭 socket
+        self.socket = None;
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        if self.socket.is_none() && self.open().is_err() {
+            return None;
+        }
+
+        let bridging_level = self.context.get_bridging_level();
+        match self.send_command(req, bridging_level) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                log::error!("Failed to send command: {}", e);
+                None
+            }
+        }
+    }
+
+    fn send_sol(&mut self, payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        let IpmiPayloadData::SolPacket(sol) = &payload.payload else {
+            return None;
+        };
+        if !self.session.active {
+            return None;
+        }
+
+        match self.send_sol_packet(sol) {
+            Ok(ack) => Some(ack),
+            Err(e) => {
+                log::error!("Failed to send SOL packet: {}", e);
+                None
+            }
+        }
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        if let Some(rsp) = self.session.sol_queue.pop_front() {
+            return Some(rsp);
+        }
+        if !self.session.active {
+            return None;
+        }
+
+        match self.recv_packet(Duration::from_millis(IPMI_LANPLUS_SOL_POLL_MS)) {
+            Ok(Some(pkt)) if pkt.payload_type == IPMI_PAYLOAD_TYPE_SOL => {
+                self.handle_sol_packet(&pkt)
+            }
+            Ok(_) => None,
+            Err(e) => {
+                log_debug!("SOL receive failed: {}", e);
+                None
+            }
+        }
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        if !self.session.active {
+            return Ok(()); // 没有活动会话
+        }
+
+        // Get Device ID 作为会话保活消息
+        let rsp = self
+            .send_app_command(0x01, &mut [])
+            .map_err(IpmiError::Network)?;
+        if rsp.ccode != 0 {
+            return Err(IpmiError::CompletionCode(rsp.ccode));
+        }
+        Ok(())
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.context.base.my_addr = addr as u32;
+        Ok(())
+    }
+
+    fn session_security(&self) -> Option<(bool, bool)> {
+        if !self.session.active {
+            return None;
+        }
+        let v2 = &self.session.v2_data;
+        Some((
+            v2.integrity_alg != IPMI_INTEGRITY_NONE,
+            v2.crypt_alg != IPMI_CRYPT_NONE,
+        ))
+    }
+
+    fn set_max_request_size(&mut self, size: u16) {
+        let mut size = size;
+        if self.session.v2_data.crypt_alg == IPMI_CRYPT_AES_CBC_128 {
+            // 加密负载只能是 16 字节的整数倍，并扣除 IV 和最小填充
+            size &= !(IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE as u16 - 1);
+            size = size.saturating_sub(IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE as u16 + 1);
+        }
+        self.context.protocol.max_request_data_size = size;
+    }
+
+    fn set_max_response_size(&mut self, size: u16) {
+        let mut size = size;
+        if self.session.v2_data.crypt_alg == IPMI_CRYPT_AES_CBC_128 {
+            size &= !(IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE as u16 - 1);
+            size = size.saturating_sub(IPMI_CRYPT_AES_CBC_128_BLOCK_SIZE as u16 + 1);
+        }
+        self.context.protocol.max_response_data_size = size;
+    }
+}
+
+impl Drop for IpmiLanplusIntf {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipmi::constants::{
+        IPMI_AUTH_RAKP_HMAC_SHA1, IPMI_AUTH_RAKP_HMAC_SHA256, IPMI_INTEGRITY_HMAC_SHA1_96,
+    };
+
+    fn active_intf() -> IpmiLanplusIntf {
+        let mut intf = IpmiLanplusIntf::new("127.0.0.1".to_string(), 0, IpmiContext::default());
+        intf.session.active = true;
+        intf.session.out_seq = 7;
+        let v2 = &mut intf.session.v2_data;
+        v2.console_id = IPMI_LANPLUS_CONSOLE_ID;
+        v2.bmc_id = 0x01020304;
+        v2.auth_alg = IPMI_AUTH_RAKP_HMAC_SHA1;
+        v2.integrity_alg = IPMI_INTEGRITY_HMAC_SHA1_96;
+        v2.crypt_alg = IPMI_CRYPT_AES_CBC_128;
+        v2.k1[..20].copy_from_slice(&[0x11; 20]);
+        v2.k1_len = 20;
+        v2.k2[..20].copy_from_slice(&[0x22; 20]);
+        v2.k2_len = 20;
+        intf
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sha256_session_keys() {
+        let mut intf = IpmiLanplusIntf::new("127.0.0.1".to_string(), 0, IpmiContext::default());
+        intf.set_credentials("admin".to_string(), "secret".to_string());
+        let v2 = &mut intf.session.v2_data;
+        v2.auth_alg = IPMI_AUTH_RAKP_HMAC_SHA256;
+        v2.console_rand = std::array::from_fn(|i| i as u8);
+        v2.bmc_rand = std::array::from_fn(|i| 16 + i as u8);
+        v2.requested_role = 0x14;
+        intf.generate_session_keys().unwrap();
+
+        let v2 = &intf.session.v2_data;
+        assert_eq!(
+            v2.sik[..v2.sik_len as usize],
+            hex("0c4b7464110cf18fd94ec46aa0242c66ab06157709c02ea7db1653aac04b1023")
+        );
+        assert_eq!(
+            v2.k1[..v2.k1_len as usize],
+            hex("b56faef1c37759ee6f92dccb3dfb8a7610c83e064883aa735d3e84c1cb0c5728")
+        );
+        assert_eq!(
+            v2.k2[..v2.k2_len as usize],
+            hex("45d88aac4a3d9b6ac2cdf022f1f31a5a82ac0aecfe8d8559b4060eb4a13b4334")
+        );
+    }
+
+    #[test]
+    fn test_setup_sets_default_data_sizes() {
+        let mut intf = IpmiLanplusIntf::new("127.0.0.1".to_string(), 0, IpmiContext::default());
+        intf.setup().unwrap();
+        assert_eq!(
+            intf.context.protocol.max_request_data_size,
+            IPMI_LANPLUS_MAX_REQUEST_SIZE
+        );
+        assert_eq!(
+            intf.context.protocol.max_response_data_size,
+            IPMI_LANPLUS_MAX_RESPONSE_SIZE
+        );
+    }
+
+    #[test]
+    fn test_presession_open_request_layout() {
+        let mut intf = IpmiLanplusIntf::new("127.0.0.1".to_string(), 0, IpmiContext::default());
+        let packet = intf
+            .build_v2_packet(IPMI_PAYLOAD_TYPE_RMCP_OPEN_REQUEST, &[0u8; 32])
+            .unwrap();
+        assert_eq!(&packet[0..4], &[0x06, 0x00, 0xff, 0x07]);
+        assert_eq!(packet[4], IPMI_SESSION_AUTHTYPE_RMCP_PLUS);
+        assert_eq!(packet[5], IPMI_PAYLOAD_TYPE_RMCP_OPEN_REQUEST);
+        assert_eq!(&packet[6..14], &[0u8; 8]);
+        assert_eq!(&packet[14..16], &32u16.to_le_bytes());
+        assert_eq!(packet.len(), IPMI_LANPLUS_SESSION_HDR + 32);
+    }
+
+    #[test]
+    fn test_secure_packet_roundtrip() {
+        let mut intf = active_intf();
+        let msg = [0x81, 0x1c, 0x63, 0x20, 0x04, 0x01, 0x00, 0x20, 0x01, 0x00];
+        let mut packet = intf.build_v2_packet(IPMI_PAYLOAD_TYPE_IPMI, &msg).unwrap();
+        assert_eq!(intf.session.out_seq, 8);
+        assert_eq!(
+            packet[5],
+            IPMI_PAYLOAD_TYPE_IPMI | IPMI_PAYLOAD_ENCRYPTED | IPMI_PAYLOAD_AUTHENTICATED
+        );
+        assert_eq!(&packet[6..10], &0x01020304u32.to_le_bytes());
+        assert_eq!(&packet[10..14], &7u32.to_le_bytes());
+        // AuthType .. Next Header is 4-byte aligned
+        assert_eq!((packet.len() - 4 - 12) % 4, 0);
+
+        // 模拟 BMC 回包：会话 ID 为控制台 ID
+        packet[6..10].copy_from_slice(&IPMI_LANPLUS_CONSOLE_ID.to_le_bytes());
+        let trailer = packet.len() - 12;
+        let authcode = integrity_authcode(
+            IPMI_INTEGRITY_HMAC_SHA1_96,
+            &[0x11; 20],
+            &[],
+            &packet[4..trailer],
+        )
+        .unwrap();
+        packet[trailer..].copy_from_slice(&authcode);
+
+        let pkt = intf.parse_packet(&packet).unwrap();
+        assert!(pkt.encrypted && pkt.authenticated);
+        assert_eq!(pkt.payload, msg);
+
+        // 篡改负载后完整性校验失败
+        packet[IPMI_LANPLUS_SESSION_HDR] ^= 0xff;
+        assert!(intf.parse_packet(&packet).is_err());
+    }
+
+    #[test]
+    fn test_unprotected_payload_rejected_in_secure_session() {
+        let mut intf = active_intf();
+        let msg = [0x81, 0x1c, 0x63, 0x20, 0x04, 0x01, 0x00, 0x20, 0x01, 0x00];
+        let mut packet = RmcpHeader::new_ipmi(0xff).to_bytes().to_vec();
+        packet.push(IPMI_SESSION_AUTHTYPE_RMCP_PLUS);
+        packet.push(IPMI_PAYLOAD_TYPE_IPMI);
+        packet.extend_from_slice(&IPMI_LANPLUS_CONSOLE_ID.to_le_bytes());
+        packet.extend_from_slice(&1u32.to_le_bytes());
+        packet.extend_from_slice(&(msg.len() as u16).to_le_bytes());
+        packet.extend_from_slice(&msg);
+        let err = intf.parse_packet(&packet).unwrap_err();
+        assert!(err.contains("Unauthenticated"));
+
+        // 只协商了加密时，未加密的负载同样拒绝
+        intf.session.v2_data.integrity_alg = IPMI_INTEGRITY_NONE;
+        let err = intf.parse_packet(&packet).unwrap_err();
+        assert!(err.contains("Unencrypted"));
+
+        // v1.5 报文不能绕过校验
+        let mut v15 = RmcpHeader::new_ipmi(0xff).to_bytes().to_vec();
+        v15.extend_from_slice(&[IPMI_SESSION_AUTHTYPE_NONE, 0, 0, 0, 0, 0, 0, 0, 0]);
+        v15.push(msg.len() as u8);
+        v15.extend_from_slice(&msg);
+        assert!(intf.parse_packet(&v15).is_err());
+
+        // 未协商任何保护的会话照常接受
+        intf.session.v2_data.crypt_alg = IPMI_CRYPT_NONE;
+        assert_eq!(intf.parse_packet(&packet).unwrap().payload, msg);
+        assert!(intf.parse_packet(&v15).is_ok());
+    }
+}
@@ -0,0 +1,13 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+pub mod async_lanplus;
+pub mod crypto;
+#[allow(clippy::module_inception)]
+pub mod lanplus;
+
+pub use async_lanplus::AsyncLanplusIntf;
+pub use lanplus::IpmiLanplusIntf;
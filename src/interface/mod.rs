@@ -4,5 +4,9 @@
  * SPDX-License-Identifier: GPL-2.0-or-later
  */
 
+pub mod dummy;
 pub mod lan;
+pub mod lanplus;
 pub mod open;
+pub mod replay;
+pub mod serial;
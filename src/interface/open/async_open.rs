@@ -0,0 +1,123 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! OpenIPMI 接口的异步版本
+//!
+//! 设备打开与初始化沿用 [`OpenIntf`] 的同步实现，请求通过 ioctl 提交后，
+//! 用 `AsyncFd` 等待设备可读，不再占用线程做 select。
+
+use super::open::{OpenIntf, IPMI_OPENIPMI_READ_TIMEOUT};
+use crate::error::{IpmiError, IpmiResult};
+use crate::ipmi::async_intf::{run_blocking, AsyncIpmiIntf, IpmiFuture};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs};
+use nix::errno::Errno;
+use std::os::fd::{AsRawFd, RawFd};
+use std::time::Duration;
+use tokio::io::unix::AsyncFd;
+
+pub struct AsyncOpenIntf {
+    inner: OpenIntf,
+    fd: Option<AsyncFd<RawFd>>,
+}
+
+impl AsyncOpenIntf {
+    pub fn new(intf: OpenIntf) -> Self {
+        Self {
+            inner: intf,
+            fd: None,
+        }
+    }
+
+    async fn open_device(&mut self) -> IpmiResult<()> {
+        if self.fd.is_some() {
+            return Ok(());
+        }
+
+        run_blocking(&mut self.inner, OpenIntf::default(), |intf| intf.open()).await??;
+
+        let fd = self
+            .inner
+            .fd
+            .as_ref()
+            .map(|f| f.as_raw_fd())
+            .ok_or_else(|| IpmiError::System("Interface not opened".to_string()))?;
+        self.fd = Some(AsyncFd::new(fd)?);
+        Ok(())
+    }
+
+    async fn recv_response(&mut self, msgid: i64) -> Option<IpmiRs> {
+        loop {
+            let afd = self.fd.as_ref()?;
+            let mut guard = match afd.readable().await {
+                Ok(guard) => guard,
+                Err(e) => {
+                    log::error!("I/O Error: {}", e);
+                    return None;
+                }
+            };
+
+            match self.inner.read_response(msgid) {
+                Ok(Some(rsp)) => return Some(rsp),
+                Ok(None) => continue,
+                Err(Errno::EAGAIN) => {
+                    guard.clear_ready();
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("Unable to receive msg:{}", e);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncIpmiIntf for AsyncOpenIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        self.inner.context()
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        self.inner.setup()
+    }
+
+    fn open(&mut self) -> IpmiFuture<'_, IpmiResult<()>> {
+        Box::pin(self.open_device())
+    }
+
+    fn close(&mut self) -> IpmiFuture<'_, ()> {
+        // 先从 reactor 注销，再关闭设备文件
+        self.fd = None;
+        self.inner.close();
+        Box::pin(async {})
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> IpmiFuture<'_, Option<IpmiRs>> {
+        let msgid = if self.fd.is_some() {
+            self.inner.send_request(req)
+        } else {
+            log::error!("Failed to send command: Interface not opened");
+            None
+        };
+
+        Box::pin(async move {
+            let msgid = msgid?;
+            let timeout = Duration::from_secs(IPMI_OPENIPMI_READ_TIMEOUT);
+            match tokio::time::timeout(timeout, self.recv_response(msgid)).await {
+                Ok(rsp) => rsp,
+                Err(_) => {
+                    log::error!("No data available");
+                    None
+                }
+            }
+        })
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.inner.set_my_addr(addr)
+    }
+}
@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: GPL-2.0-or-later
  */
 
+pub mod async_open;
 #[allow(clippy::module_inception)]
 pub mod open;
 
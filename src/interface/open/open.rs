@@ -306,35 +306,95 @@ impl IpmiIntf for OpenIntf {
     }
 
     fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        let msgid = self.send_request(req)?;
+        let fd = self.fd.as_ref()?.as_raw_fd();
+
+        // 等待响应
+        let mut timeval = nix::sys::time::TimeVal::new(
+            IPMI_OPENIPMI_READ_TIMEOUT as i64, // 秒
+            0,                                 // 微秒
+        );
+
+        loop {
+            let borrowfd = self.fd.as_ref()?.as_fd();
+            let mut fd_set = FdSet::new();
+            fd_set.insert(borrowfd);
+            match select(fd + 1, &mut fd_set, None, None, Some(&mut timeval)) {
+                Ok(0) => {
+                    log::error!("No data available");
+                    return None;
+                }
+                Ok(_) if !fd_set.contains(borrowfd) => {
+                    log::error!("No data available");
+                    return None;
+                }
+                Ok(_) => {}
+                Err(Errno::EINTR) => continue, // EINTR 处理
+                Err(e) => {
+                    log::error!("I/O Error: {}", e);
+                    return None;
+                }
+            }
+
+            match self.read_response(msgid) {
+                Ok(Some(rsp)) => return Some(rsp),
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Unable to receive msg:{}", e);
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn send_sol(&mut self, _payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        // open 接口不支持 SOL (Serial Over LAN)
+        None
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        // open 接口不支持 SOL (Serial Over LAN)
+        None
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        // 对于 open 接口，keepalive 通常不需要特殊操作
+        // 内核驱动会处理与 BMC 的连接
+        Ok(())
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        let mut a = addr as u32;
+        let fd = self
+            .fd
+            .as_ref()
+            .map(|f| f.as_raw_fd())
+            .ok_or_else(|| IpmiError::System("Interface not opened".to_string()))?;
+
+        match openipmi_ioctl::set_my_address_cmd(fd, &mut a) {
+            Ok(_) => {
+                self.context.set_my_addr(a);
+                // log_debug!("Set IPMB address to 0x{:x}", a);
+                Ok(())
+            }
+            Err(e) => Err(IpmiError::System(format!(
+                "Failed to set my address: {}",
+                e
+            ))),
+        }
+    }
+}
+
+impl OpenIntf {
+    /// 构造并通过 ioctl 提交请求，返回用于匹配响应的 msgid
+    pub(crate) fn send_request(&mut self, req: &IpmiRq) -> Option<i64> {
         //构造请求open接口的ioctl请求
-        let addr = IpmiAddr::default();
         let mut _req: IpmiReq = IpmiReq::default();
 
-        let mut recv = IpmiRecv::default();
-
         //uint8_t *data = NULL;
         let mut data: Vec<u8> = Vec::new();
         let mut data_len = 0;
 
-        //之前C是静态变量
-        let mut rsp = IpmiRs {
-            ccode: 0,
-            data: [0; IPMI_BUF_SIZE],
-            data_len: 0,
-            msg: Default::default(),
-            session: Default::default(),
-            payload: IpmiRsPayload::OpenSessionResponse {
-                message_tag: 1,
-                rakp_return_code: 0,
-                max_priv_level: 0,
-                console_id: 0,
-                bmc_id: 0,
-                auth_alg: 0,
-                integrity_alg: 0,
-                crypt_alg: 0,
-            },
-        };
-
         let mut bmc_addr = IpmiSystemInterfaceAddr {
             addr_type: IPMI_SYSTEM_INTERFACE_ADDR_TYPE,
             channel: IPMI_BMC_CHANNEL as i16,
@@ -460,65 +520,58 @@ impl IpmiIntf for OpenIntf {
         //     return None;
         // }
 
-        // 等待响应
+        Some(_req.msgid)
+    }
 
-        let mut timeval = nix::sys::time::TimeVal::new(
-            IPMI_OPENIPMI_READ_TIMEOUT as i64, // 秒
-            0,                                 // 微秒
-        );
+    /// 设备可读后取出一条消息，msgid 不匹配时返回 Ok(None) 继续等待
+    pub(crate) fn read_response(&mut self, msgid: i64) -> Result<Option<IpmiRs>, Errno> {
+        let fd = self.fd.as_ref().ok_or(Errno::EBADF)?.as_raw_fd();
+        let addr = IpmiAddr::default();
+        let mut recv = IpmiRecv::default();
 
-        let borrowfd = self.fd.as_ref()?.as_fd();
-        let mut fd_set = FdSet::new();
-        fd_set.insert(borrowfd);
+        //之前C是静态变量
+        let mut rsp = IpmiRs {
+            ccode: 0,
+            data: [0; IPMI_BUF_SIZE],
+            data_len: 0,
+            msg: Default::default(),
+            session: Default::default(),
+            payload: IpmiRsPayload::OpenSessionResponse {
+                message_tag: 1,
+                rakp_return_code: 0,
+                max_priv_level: 0,
+                console_id: 0,
+                bmc_id: 0,
+                auth_alg: 0,
+                integrity_alg: 0,
+                crypt_alg: 0,
+            },
+        };
 
-        loop {
-            let res = match select(fd + 1, &mut fd_set, None, None, Some(&mut timeval)) {
-                Ok(n) => n,
-                Err(Errno::EINTR) => continue, // EINTR 处理
-                Err(e) => {
-                    log::error!("I/O Error: {}", e);
-                    return None;
-                }
-            };
+        let my_addr = self.context().my_addr();
+        let transit_addr = self.context().transit_addr();
 
-            match res {
-                0 => {
-                    log::error!("No data available");
-                    return None;
-                }
-                _ if !fd_set.contains(borrowfd) => {
-                    //borrowfd
-                    log::error!("No data available");
-                    return None;
-                }
-                _ => {
-                    // 数据可读，准备接收结构体，局部地址变量赋值给指针
-                    recv.addr = &addr as *const _ as *mut u8;
-                    recv.addr_len = std::mem::size_of_val(&addr) as u32;
-                    recv.msg.data = rsp.data.as_mut_ptr(); //rsp存储返回数据,数组指针
-                                                           //recv.msg.data_len = std::mem::size_of_val(&rsp.data) as u16;
-                    recv.msg.data_len = rsp.data.len() as u16;
-
-                    // 读取到recv
-                    if let Err(e) = openipmi_ioctl::receive_msg_trunc(fd, &mut recv) {
-                        if e != Errno::EMSGSIZE {
-                            eprintln!("Unable to receive msg:{}", e);
-                            return None;
-                        }
-                    }
-                }
-            }
+        // 准备接收结构体，局部地址变量赋值给指针
+        recv.addr = &addr as *const _ as *mut u8;
+        recv.addr_len = std::mem::size_of_val(&addr) as u32;
+        recv.msg.data = rsp.data.as_mut_ptr(); //rsp存储返回数据,数组指针
+        recv.msg.data_len = rsp.data.len() as u16;
 
-            // 不相等继续循环
-            if _req.msgid != recv.msgid {
-                log::error!(
-                    "Received a response with unexpected ID {} vs. {}",
-                    recv.msgid,
-                    _req.msgid
-                );
-                continue;
+        // 读取到recv
+        if let Err(e) = openipmi_ioctl::receive_msg_trunc(fd, &mut recv) {
+            if e != Errno::EMSGSIZE {
+                return Err(e);
             }
-            break;
+        }
+
+        // 不相等继续等待
+        if msgid != recv.msgid {
+            log::error!(
+                "Received a response with unexpected ID {} vs. {}",
+                recv.msgid,
+                msgid
+            );
+            return Ok(None);
         }
 
         debug5!("Got message:");
@@ -594,44 +647,7 @@ impl IpmiIntf for OpenIntf {
             "return rsp.data      = {:02x?}",
             &rsp.data[0..rsp.data_len as usize]
         );
-        Some(rsp)
-    }
-
-    fn send_sol(&mut self, _payload: &IpmiV2Payload) -> Option<IpmiRs> {
-        // open 接口不支持 SOL (Serial Over LAN)
-        None
-    }
-
-    fn recv_sol(&mut self) -> Option<IpmiRs> {
-        // open 接口不支持 SOL (Serial Over LAN)
-        None
-    }
-
-    fn keepalive(&mut self) -> IpmiResult<()> {
-        // 对于 open 接口，keepalive 通常不需要特殊操作
-        // 内核驱动会处理与 BMC 的连接
-        Ok(())
-    }
-
-    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
-        let mut a = addr as u32;
-        let fd = self
-            .fd
-            .as_ref()
-            .map(|f| f.as_raw_fd())
-            .ok_or_else(|| IpmiError::System("Interface not opened".to_string()))?;
-
-        match openipmi_ioctl::set_my_address_cmd(fd, &mut a) {
-            Ok(_) => {
-                self.context.set_my_addr(a);
-                // log_debug!("Set IPMB address to 0x{:x}", a);
-                Ok(())
-            }
-            Err(e) => Err(IpmiError::System(format!(
-                "Failed to set my address: {}",
-                e
-            ))),
-        }
+        Ok(Some(rsp))
     }
 }
 
@@ -0,0 +1,319 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 请求录制与回放
+//!
+//! `--record <file>` 用 [`IpmiRecordIntf`] 包住任意接口，把每对请求/响应写成一行 JSON；
+//! `-I replay -D <file>` 再按请求内容逐字节匹配，把录下的响应交还给命令，
+//! 这样现场 BMC 的问题可以在本地离线重现。
+//!
+//! 文件第一行是 [`ReplayHeader`]，之后每行一个 [`ReplayEntry`]。
+
+pub mod record;
+
+pub use record::IpmiRecordIntf;
+
+use crate::error::{IpmiError, IpmiResult};
+use crate::interface::dummy::{DummyRequest, DummyResponse};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IpmiV2Payload};
+use crate::{log_debug, log_error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// 录制文件头，回放时据此恢复分段大小
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub version: u32,
+    /// 录制时使用的接口 (lan, open ...)
+    pub interface: String,
+    pub max_request_data_size: u16,
+    pub max_response_data_size: u16,
+}
+
+/// 一次请求及其响应
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub netfn: u8,
+    pub lun: u8,
+    pub cmd: u8,
+    /// 发送时上下文中的目标地址与通道，区分桥接请求
+    pub target_addr: u32,
+    pub target_channel: u8,
+    #[serde(serialize_with = "to_hex", deserialize_with = "from_hex")]
+    pub data: Vec<u8>,
+    /// None 表示没有收到响应
+    pub ccode: Option<u8>,
+    #[serde(default, serialize_with = "to_hex", deserialize_with = "from_hex")]
+    pub response: Vec<u8>,
+    /// 接口往返耗时
+    pub elapsed_us: u64,
+}
+
+type ReplayKey = (u8, u8, u8, u32, u8, Vec<u8>);
+
+impl ReplayEntry {
+    fn key(&self) -> ReplayKey {
+        (
+            self.netfn,
+            self.lun,
+            self.cmd,
+            self.target_addr,
+            self.target_channel,
+            self.data.clone(),
+        )
+    }
+}
+
+fn to_hex<S: Serializer>(data: &[u8], s: S) -> Result<S::Ok, S::Error> {
+    let text: Vec<String> = data.iter().map(|b| format!("{:02x}", b)).collect();
+    s.serialize_str(&text.join(" "))
+}
+
+fn from_hex<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+    let text = String::deserialize(d)?;
+    text.split_whitespace()
+        .map(|b| u8::from_str_radix(b, 16))
+        .collect::<Result<_, _>>()
+        .map_err(|_| serde::de::Error::custom(format!("invalid hex bytes \"{}\"", text)))
+}
+
+/// 同一请求的全部录制响应，按录制顺序依次给出，用完后重复最后一条
+struct Recorded {
+    entries: Vec<ReplayEntry>,
+    next: usize,
+}
+
+/// IPMI replay 接口
+pub struct IpmiReplayIntf {
+    pub context: IpmiContext,
+    pub path: PathBuf,
+    pub header: Option<ReplayHeader>,
+    recorded: HashMap<ReplayKey, Recorded>,
+}
+
+impl IpmiReplayIntf {
+    pub fn new(path: PathBuf, ctx: IpmiContext) -> Self {
+        Self {
+            context: ctx,
+            path,
+            header: None,
+            recorded: HashMap::new(),
+        }
+    }
+
+    /// 读入录制文件，返回文件头与条目
+    pub fn load(path: &Path) -> Result<(ReplayHeader, Vec<ReplayEntry>), String> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("Unable to open replay file {}: {}", path.display(), e))?;
+        let mut lines = BufReader::new(file).lines().enumerate();
+
+        let header: ReplayHeader = match lines.next() {
+            Some((_, Ok(line))) => serde_json::from_str(&line)
+                .map_err(|e| format!("{}:1: invalid replay header: {}", path.display(), e))?,
+            Some((_, Err(e))) => return Err(format!("{}: {}", path.display(), e)),
+            None => return Err(format!("{}: empty replay file", path.display())),
+        };
+        if header.version != REPLAY_FORMAT_VERSION {
+            return Err(format!(
+                "{}: unsupported replay format version {}",
+                path.display(),
+                header.version
+            ));
+        }
+
+        let mut entries = Vec::new();
+        for (i, line) in lines {
+            let line = line.map_err(|e| format!("{}: {}", path.display(), e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line)
+                .map_err(|e| format!("{}:{}: {}", path.display(), i + 1, e))?;
+            entries.push(entry);
+        }
+        Ok((header, entries))
+    }
+}
+
+impl IpmiIntf for IpmiReplayIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        &mut self.context
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        let (header, entries) = Self::load(&self.path).map_err(IpmiError::System)?;
+        self.context.protocol.max_request_data_size = header.max_request_data_size;
+        self.context.protocol.max_response_data_size = header.max_response_data_size;
+        log_debug!(
+            "Replaying {} requests recorded over {} from {}",
+            entries.len(),
+            header.interface,
+            self.path.display()
+        );
+
+        self.recorded.clear();
+        for entry in entries {
+            self.recorded
+                .entry(entry.key())
+                .or_insert_with(|| Recorded {
+                    entries: Vec::new(),
+                    next: 0,
+                })
+                .entries
+                .push(entry);
+        }
+        self.header = Some(header);
+        Ok(())
+    }
+
+    fn open(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn close(&mut self) {}
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        let dreq = DummyRequest::from_rq(req);
+        let key = (
+            dreq.netfn,
+            dreq.lun,
+            dreq.cmd,
+            self.context.target_addr(),
+            self.context.target_channel(),
+            dreq.data.clone(),
+        );
+        let Some(recorded) = self.recorded.get_mut(&key) else {
+            log_error!(
+                "No recorded response for netfn 0x{:02x} lun {} cmd 0x{:02x} target 0x{:02x}/{} data [{}] in {}",
+                dreq.netfn,
+                dreq.lun,
+                dreq.cmd,
+                key.3,
+                key.4,
+                dreq.data
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                self.path.display()
+            );
+            return None;
+        };
+
+        let idx = recorded.next.min(recorded.entries.len() - 1);
+        recorded.next += 1;
+        let entry = &recorded.entries[idx];
+        let ccode = entry.ccode?;
+        Some(DummyResponse::reply(&dreq, ccode, &entry.response).into_rs())
+    }
+
+    fn send_sol(&mut self, _payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        None
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        None
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.context.base.my_addr = addr as u32;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::dummy::IpmiDummyIntf;
+    use crate::ipmi::ipmi::{IPMI_NETFN_APP, IPMI_NETFN_STORAGE};
+    use crate::sim::{serve_unix, SimBmc, SimModel};
+    use std::os::unix::net::UnixListener;
+    use std::sync::{Arc, Mutex};
+
+    fn request(netfn: u8, cmd: u8, data: &mut [u8]) -> IpmiRq {
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(netfn);
+        req.msg.cmd = cmd;
+        req.msg.data = data.as_mut_ptr();
+        req.msg.data_len = data.len() as u16;
+        req
+    }
+
+    fn exchange(intf: &mut dyn IpmiIntf, netfn: u8, cmd: u8, data: &[u8]) -> Option<Vec<u8>> {
+        let mut data = data.to_vec();
+        let rsp = intf.sendrecv(&request(netfn, cmd, &mut data))?;
+        let mut out = vec![rsp.ccode];
+        out.extend_from_slice(&rsp.data[..rsp.data_len as usize]);
+        Some(out)
+    }
+
+    #[test]
+    fn test_record_then_replay() {
+        let dir = std::env::temp_dir().join(format!("utipmi-replay-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sock = dir.join("sim.sock");
+        let trace = dir.join("trace.jsonl");
+        let _ = std::fs::remove_file(&sock);
+
+        let model = SimModel::from_json(
+            r#"{ "device": { "manufacturer_id": 343 },
+                 "faults": [ { "netfn": 10, "cmd": "0x48", "action": "timeout" } ] }"#,
+        )
+        .unwrap();
+        let bmc = Arc::new(Mutex::new(SimBmc::new(model).unwrap()));
+        let listener = UnixListener::bind(&sock).unwrap();
+        std::thread::spawn(move || serve_unix(listener, bmc));
+
+        // 录制：Get Device ID、两次 Reserve SEL (响应不同)、一次无应答
+        let mut dummy = IpmiDummyIntf::new(Some(sock), IpmiContext::default());
+        dummy.set_timeout(1, 1);
+        let mut intf = IpmiRecordIntf::new(Box::new(dummy), &trace, "dummy").unwrap();
+        intf.setup().unwrap();
+        intf.open().unwrap();
+        let devid = exchange(&mut intf, IPMI_NETFN_APP, 0x01, &[]).unwrap();
+        let rsv1 = exchange(&mut intf, IPMI_NETFN_STORAGE, 0x42, &[]).unwrap();
+        let rsv2 = exchange(&mut intf, IPMI_NETFN_STORAGE, 0x42, &[]).unwrap();
+        assert_ne!(rsv1, rsv2);
+        assert!(exchange(&mut intf, IPMI_NETFN_STORAGE, 0x48, &[]).is_none());
+        intf.close();
+        drop(intf);
+
+        let mut replay = IpmiReplayIntf::new(trace, IpmiContext::default());
+        replay.setup().unwrap();
+        replay.open().unwrap();
+        assert_eq!(replay.header.as_ref().unwrap().interface, "dummy");
+        assert_eq!(replay.context.protocol.max_request_data_size, 38);
+        assert_eq!(
+            exchange(&mut replay, IPMI_NETFN_APP, 0x01, &[]),
+            Some(devid)
+        );
+        assert_eq!(
+            exchange(&mut replay, IPMI_NETFN_STORAGE, 0x42, &[]),
+            Some(rsv1)
+        );
+        assert_eq!(
+            exchange(&mut replay, IPMI_NETFN_STORAGE, 0x42, &[]),
+            Some(rsv2.clone())
+        );
+        assert_eq!(
+            exchange(&mut replay, IPMI_NETFN_STORAGE, 0x42, &[]),
+            Some(rsv2)
+        );
+        assert!(exchange(&mut replay, IPMI_NETFN_STORAGE, 0x48, &[]).is_none());
+        // 数据不同即视为未录制的请求
+        assert!(exchange(&mut replay, IPMI_NETFN_APP, 0x01, &[0x00]).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
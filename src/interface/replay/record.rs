@@ -0,0 +1,146 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! `--record`：透明包装另一个接口，记录经过的每一对请求/响应
+
+use super::{ReplayEntry, ReplayHeader, REPLAY_FORMAT_VERSION};
+use crate::error::{IpmiError, IpmiResult};
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IpmiV2Payload};
+use crate::log_error;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+pub struct IpmiRecordIntf {
+    inner: Box<dyn IpmiIntf>,
+    pub path: PathBuf,
+    interface: String,
+    out: File,
+    header_written: bool,
+}
+
+impl IpmiRecordIntf {
+    /// 创建 (覆盖) 录制文件，`interface` 记录被包装接口的名字
+    pub fn new(inner: Box<dyn IpmiIntf>, path: &Path, interface: &str) -> Result<Self, String> {
+        let out = File::create(path)
+            .map_err(|e| format!("Unable to create record file {}: {}", path.display(), e))?;
+        Ok(Self {
+            inner,
+            path: path.to_path_buf(),
+            interface: interface.to_string(),
+            out,
+            header_written: false,
+        })
+    }
+
+    /// 每行写完立即落盘，命令中途崩溃也保留已录制的部分
+    fn write_line<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(value)?;
+        line.push(b'\n');
+        self.out.write_all(&line)
+    }
+
+    fn record(&mut self, entry: &ReplayEntry) {
+        if let Err(e) = self.write_line(entry) {
+            log_error!("Unable to write record file {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+impl IpmiIntf for IpmiRecordIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        self.inner.context()
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        self.inner.setup()
+    }
+
+    fn open(&mut self) -> IpmiResult<()> {
+        self.inner.open()?;
+        if !self.header_written {
+            // 分段大小在 setup/open 之后才确定
+            let ctx = self.inner.context();
+            let header = ReplayHeader {
+                version: REPLAY_FORMAT_VERSION,
+                interface: self.interface.clone(),
+                max_request_data_size: ctx.protocol.max_request_data_size,
+                max_response_data_size: ctx.protocol.max_response_data_size,
+            };
+            self.write_line(&header).map_err(|e| {
+                IpmiError::System(format!(
+                    "Unable to write record file {}: {}",
+                    self.path.display(),
+                    e
+                ))
+            })?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.inner.close();
+        let _ = self.out.flush();
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        let (target_addr, target_channel) = {
+            let ctx = self.inner.context();
+            (ctx.target_addr(), ctx.target_channel())
+        };
+        let start = Instant::now();
+        let rsp = self.inner.sendrecv(req);
+        let elapsed_us = start.elapsed().as_micros() as u64;
+
+        let entry = ReplayEntry {
+            netfn: req.msg.netfn(),
+            lun: req.msg.lun(),
+            cmd: req.msg.cmd,
+            target_addr,
+            target_channel,
+            data: req.msg.data().unwrap_or(&[]).to_vec(),
+            ccode: rsp.as_ref().map(|r| r.ccode),
+            response: rsp
+                .as_ref()
+                .map(|r| r.data[..r.data_len.max(0) as usize].to_vec())
+                .unwrap_or_default(),
+            elapsed_us,
+        };
+        self.record(&entry);
+        rsp
+    }
+
+    fn send_sol(&mut self, payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        self.inner.send_sol(payload)
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        self.inner.recv_sol()
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        self.inner.keepalive()
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.inner.set_my_addr(addr)
+    }
+
+    fn set_max_request_size(&mut self, size: u16) {
+        self.inner.set_max_request_size(size)
+    }
+
+    fn set_max_response_size(&mut self, size: u16) {
+        self.inner.set_max_response_size(size)
+    }
+
+    fn session_security(&self) -> Option<(bool, bool)> {
+        self.inner.session_security()
+    }
+}
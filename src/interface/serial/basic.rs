@@ -0,0 +1,159 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! IPMI Serial Basic Mode 报文编解码 (IPMI v2.0 14.4)
+//!
+//! 报文体与 IPMB 消息相同，以 0xA0/0xA5 包围，特殊字符用 0xAA 转义；
+//! BMC 释放接收缓冲区时发送握手字符 0xA6。
+
+use crate::interface::lan::IpmiLanIntf;
+use crate::interface::open::open::ipmi_csum;
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IpmiSession};
+
+pub const BM_START: u8 = 0xA0;
+pub const BM_STOP: u8 = 0xA5;
+pub const BM_HANDSHAKE: u8 = 0xA6;
+pub const BM_ESCAPE: u8 = 0xAA;
+const BM_ESC: u8 = 0x1B;
+
+// (原字符, 转义后紧跟在 0xAA 之后的字符)
+const BM_ESCAPES: [(u8, u8); 5] = [
+    (BM_START, 0xB0),
+    (BM_STOP, 0xB5),
+    (BM_HANDSHAKE, 0xB6),
+    (BM_ESCAPE, 0xBA),
+    (BM_ESC, 0x3B),
+];
+
+/// 构造一个完整的 Basic Mode 帧
+pub fn encode_frame(msg: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(msg.len() * 2 + 2);
+    frame.push(BM_START);
+    for &b in msg {
+        match BM_ESCAPES.iter().find(|(raw, _)| *raw == b) {
+            Some((_, esc)) => frame.extend_from_slice(&[BM_ESCAPE, *esc]),
+            None => frame.push(b),
+        }
+    }
+    frame.push(BM_STOP);
+    frame
+}
+
+pub fn encode_request(req: &IpmiRq, rq_seq: u8) -> Vec<u8> {
+    encode_frame(&IpmiLanIntf::build_message(req, rq_seq))
+}
+
+/// 逐字节接收 Basic Mode 帧
+#[derive(Debug, Default)]
+pub struct BasicDecoder {
+    buf: Vec<u8>,
+    in_frame: bool,
+    escape: bool,
+    /// 收到的握手字符个数
+    pub handshakes: usize,
+}
+
+impl BasicDecoder {
+    /// 收到完整帧时返回去转义后的消息
+    pub fn feed(&mut self, b: u8) -> Option<Vec<u8>> {
+        match b {
+            BM_HANDSHAKE => {
+                self.handshakes += 1;
+                None
+            }
+            BM_START => {
+                self.buf.clear();
+                self.in_frame = true;
+                self.escape = false;
+                None
+            }
+            _ if !self.in_frame => None,
+            BM_STOP => {
+                self.in_frame = false;
+                if self.escape {
+                    self.escape = false;
+                    return None;
+                }
+                Some(std::mem::take(&mut self.buf))
+            }
+            BM_ESCAPE => {
+                self.escape = true;
+                None
+            }
+            _ if self.escape => {
+                self.escape = false;
+                match BM_ESCAPES.iter().find(|(_, esc)| *esc == b) {
+                    Some((raw, _)) => self.buf.push(*raw),
+                    // 非法转义序列，丢弃整帧
+                    None => self.in_frame = false,
+                }
+                None
+            }
+            _ => {
+                self.buf.push(b);
+                None
+            }
+        }
+    }
+}
+
+/// 校验并解析响应消息 (rqSA .. checksum)
+pub fn decode_response(msg: &[u8]) -> Result<IpmiRs, String> {
+    if msg.len() < 8 {
+        return Err("Basic mode message too short".to_string());
+    }
+    if ipmi_csum(&msg[..2]) != msg[2] || ipmi_csum(&msg[3..msg.len() - 1]) != msg[msg.len() - 1] {
+        return Err("Basic mode message checksum mismatch".to_string());
+    }
+    IpmiLanIntf::parse_message(msg, IpmiSession::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_escaping_round_trip() {
+        let msg = [
+            0x20,
+            BM_START,
+            BM_STOP,
+            BM_HANDSHAKE,
+            BM_ESCAPE,
+            BM_ESC,
+            0x01,
+        ];
+        let frame = encode_frame(&msg);
+        assert_eq!(
+            frame,
+            [0xA0, 0x20, 0xAA, 0xB0, 0xAA, 0xB5, 0xAA, 0xB6, 0xAA, 0xBA, 0xAA, 0x3B, 0x01, 0xA5]
+        );
+
+        let mut dec = BasicDecoder::default();
+        // 帧前的噪声与握手字符不影响解码
+        let mut out = None;
+        for &b in [0x55, BM_HANDSHAKE].iter().chain(frame.iter()) {
+            if let Some(m) = dec.feed(b) {
+                out = Some(m);
+            }
+        }
+        assert_eq!(out.unwrap(), msg);
+        assert_eq!(dec.handshakes, 1);
+    }
+
+    #[test]
+    fn test_decode_response_checksums() {
+        // Get Device ID 响应，seq 3
+        let msg = [0x81, 0x1c, 0x63, 0x20, 0x0c, 0x01, 0x00, 0x20, 0x01, 0xb2];
+        let rsp = decode_response(&msg).unwrap();
+        assert_eq!(rsp.msg.seq, 3);
+        assert_eq!(rsp.data_len, 2);
+
+        let mut bad = msg;
+        bad[9] ^= 0xff;
+        assert!(decode_response(&bad).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 串口接口：serial-basic (Basic Mode) 与 serial-terminal (Terminal Mode)
+//!
+//! 设备由 `-D <tty>[:<baud>][+rtscts|+xonxoff]` 指定，消息只发往直连的 BMC。
+
+pub mod basic;
+pub mod terminal;
+pub mod tty;
+
+pub use tty::{FlowControl, SerialConfig};
+
+use crate::error::IpmiResult;
+use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+use crate::ipmi::ipmi::{IpmiRq, IpmiRs, IpmiV2Payload};
+use crate::{log_debug, log_info};
+use std::time::{Duration, Instant};
+use tty::SerialPort;
+
+const SERIAL_TIMEOUT: u64 = 2; // seconds
+const SERIAL_RETRY: u32 = 4;
+
+// Basic Mode 至少支持 40 字节的 IPMB 消息 (去掉 7 字节消息头/校验和)
+const SERIAL_BM_MAX_REQUEST_SIZE: u16 = 32;
+const SERIAL_BM_MAX_RESPONSE_SIZE: u16 = 32;
+// Terminal Mode 至少支持 122 个十六进制字符，即 61 字节
+const SERIAL_TM_MAX_REQUEST_SIZE: u16 = 58;
+const SERIAL_TM_MAX_RESPONSE_SIZE: u16 = 57;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerialMode {
+    Basic,
+    Terminal,
+}
+
+enum SerialDecoder {
+    Basic(basic::BasicDecoder),
+    Terminal(terminal::TerminalDecoder),
+}
+
+impl SerialDecoder {
+    fn new(mode: SerialMode) -> Self {
+        match mode {
+            SerialMode::Basic => SerialDecoder::Basic(Default::default()),
+            SerialMode::Terminal => SerialDecoder::Terminal(Default::default()),
+        }
+    }
+
+    fn feed(&mut self, b: u8) -> Option<Result<IpmiRs, String>> {
+        match self {
+            SerialDecoder::Basic(dec) => dec.feed(b).map(|m| basic::decode_response(&m)),
+            SerialDecoder::Terminal(dec) => dec.feed(b).map(|m| terminal::decode_response(&m)),
+        }
+    }
+}
+
+/// IPMI 串口接口
+pub struct IpmiSerialIntf {
+    pub context: IpmiContext,
+    pub config: SerialConfig,
+    pub mode: SerialMode,
+    pub timeout: u64,
+    pub retry_count: u32,
+    port: Option<SerialPort>,
+    /// 6-bit IPMI message sequence number (rqSeq)
+    curr_seq: u8,
+}
+
+impl IpmiSerialIntf {
+    pub fn new(config: SerialConfig, mode: SerialMode, ctx: IpmiContext) -> Self {
+        Self {
+            context: ctx,
+            config,
+            mode,
+            timeout: SERIAL_TIMEOUT,
+            retry_count: SERIAL_RETRY,
+            port: None,
+            curr_seq: 0,
+        }
+    }
+
+    pub fn set_timeout(&mut self, timeout: u64, retry: u32) {
+        if timeout > 0 {
+            self.timeout = timeout;
+        }
+        if retry > 0 {
+            self.retry_count = retry;
+        }
+    }
+
+    fn encode(&self, req: &IpmiRq, rq_seq: u8) -> Vec<u8> {
+        match self.mode {
+            SerialMode::Basic => basic::encode_request(req, rq_seq),
+            SerialMode::Terminal => terminal::encode_request(req, rq_seq),
+        }
+    }
+
+    /// 发送请求并等待序号与命令匹配的响应，超时重发
+    fn send_command(&mut self, req: &IpmiRq) -> Result<IpmiRs, String> {
+        self.curr_seq = (self.curr_seq + 1) & 0x3f;
+        let rq_seq = self.curr_seq;
+        let frame = self.encode(req, rq_seq);
+        let timeout = Duration::from_secs(self.timeout);
+        let retry_count = self.retry_count;
+        let mode = self.mode;
+        let port = self.port.as_mut().ok_or("Serial port not open")?;
+
+        for attempt in 0..retry_count {
+            port.write_all(&frame).map_err(|e| e.to_string())?;
+
+            let mut decoder = SerialDecoder::new(mode);
+            let deadline = Instant::now() + timeout;
+            while let Some(b) = port.read_byte(deadline).map_err(|e| e.to_string())? {
+                let rsp = match decoder.feed(b) {
+                    None => continue,
+                    Some(Ok(rsp)) => rsp,
+                    Some(Err(e)) => {
+                        log_debug!("Discarding serial message: {}", e);
+                        continue;
+                    }
+                };
+                // Terminal Mode 下 BMC 可能回显请求本身
+                if rsp.msg.seq != rq_seq
+                    || rsp.msg.cmd != req.msg.cmd
+                    || rsp.msg.netfn != req.msg.netfn() + 1
+                {
+                    log_debug!(
+                        "Discarding response netfn 0x{:02x} seq 0x{:02x} cmd 0x{:02x}",
+                        rsp.msg.netfn,
+                        rsp.msg.seq,
+                        rsp.msg.cmd
+                    );
+                    continue;
+                }
+                return Ok(rsp);
+            }
+
+            log_info!(
+                "No response to command 0x{:02x} (attempt {}/{})",
+                req.msg.cmd,
+                attempt + 1,
+                retry_count
+            );
+        }
+
+        Err("No response from remote controller".to_string())
+    }
+}
+
+impl IpmiIntf for IpmiSerialIntf {
+    fn context(&mut self) -> &mut IpmiContext {
+        &mut self.context
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        let (rq, rs) = match self.mode {
+            SerialMode::Basic => (SERIAL_BM_MAX_REQUEST_SIZE, SERIAL_BM_MAX_RESPONSE_SIZE),
+            SerialMode::Terminal => (SERIAL_TM_MAX_REQUEST_SIZE, SERIAL_TM_MAX_RESPONSE_SIZE),
+        };
+        self.context.protocol.max_request_data_size = rq;
+        self.context.protocol.max_response_data_size = rs;
+        Ok(())
+    }
+
+    fn open(&mut self) -> IpmiResult<()> {
+        if self.port.is_none() {
+            self.port = Some(SerialPort::open(&self.config)?);
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.port = None;
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        if self.port.is_none() && self.open().is_err() {
+            return None;
+        }
+
+        match self.send_command(req) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                log::error!("Failed to send command: {}", e);
+                None
+            }
+        }
+    }
+
+    fn send_sol(&mut self, _payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        // 串口接口本身就是控制台，不支持 SOL
+        None
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        None
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.context.base.my_addr = addr as u32;
+        Ok(())
+    }
+
+    fn set_max_request_size(&mut self, size: u16) {
+        self.context.protocol.max_request_data_size = std::cmp::min(
+            size,
+            match self.mode {
+                SerialMode::Basic => SERIAL_BM_MAX_REQUEST_SIZE,
+                SerialMode::Terminal => SERIAL_TM_MAX_REQUEST_SIZE,
+            },
+        );
+    }
+
+    fn set_max_response_size(&mut self, size: u16) {
+        self.context.protocol.max_response_data_size = std::cmp::min(
+            size,
+            match self.mode {
+                SerialMode::Basic => SERIAL_BM_MAX_RESPONSE_SIZE,
+                SerialMode::Terminal => SERIAL_TM_MAX_RESPONSE_SIZE,
+            },
+        );
+    }
+}
+
+impl Drop for IpmiSerialIntf {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::open::open::ipmi_csum;
+    use crate::ipmi::ipmi::IPMI_NETFN_APP;
+    use nix::pty::openpty;
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::os::fd::AsRawFd;
+
+    /// 在 pty 主设备一端模拟 BMC：应答一个 Get Device ID 请求
+    fn fake_bmc(mut master: File, mode: SerialMode) -> File {
+        let mut basic = basic::BasicDecoder::default();
+        let mut term = terminal::TerminalDecoder::default();
+        let mut byte = [0u8; 1];
+        let req = loop {
+            master.read_exact(&mut byte).unwrap();
+            let msg = match mode {
+                SerialMode::Basic => basic.feed(byte[0]),
+                SerialMode::Terminal => term.feed(byte[0]),
+            };
+            if let Some(msg) = msg {
+                break msg;
+            }
+        };
+
+        let reply = match mode {
+            SerialMode::Basic => {
+                // rsSA netFn/LUN csum rqSA seq/LUN cmd -> rqSA netFn/LUN csum rsSA seq/LUN cmd cc data csum
+                assert_eq!(req[0], 0x20);
+                let mut msg = vec![req[3], (IPMI_NETFN_APP + 1) << 2];
+                msg.push(ipmi_csum(&msg));
+                msg.extend_from_slice(&[0x20, req[4], req[5], 0x00, 0x20, 0x01]);
+                msg.push(ipmi_csum(&msg[3..]));
+                let mut frame = vec![basic::BM_HANDSHAKE];
+                frame.extend(basic::encode_frame(&msg));
+                frame
+            }
+            SerialMode::Terminal => {
+                // 先回显请求
+                let mut out = format!("[{:02X} {:02X} {:02X}]\r\n", req[0], req[1], req[2]);
+                out.push_str(&format!(
+                    "[{:02X} {:02X} {:02X} 00 20 01]\r\n",
+                    (IPMI_NETFN_APP + 1) << 2,
+                    req[1],
+                    req[2]
+                ));
+                out.into_bytes()
+            }
+        };
+        master.write_all(&reply).unwrap();
+        // 主设备关闭会挂断 pty 并丢弃未读数据，交给调用方在读完后关闭
+        master
+    }
+
+    fn pty_round_trip(mode: SerialMode) {
+        let pty = openpty(None, None).unwrap();
+        let slave_path = format!("/proc/self/fd/{}", pty.slave.as_raw_fd());
+        let config = SerialConfig::parse(&format!("{}:115200", slave_path)).unwrap();
+
+        let master = File::from(pty.master);
+        let bmc = std::thread::spawn(move || fake_bmc(master, mode));
+
+        let mut intf = IpmiSerialIntf::new(config, mode, IpmiContext::default());
+        intf.set_timeout(2, 1);
+        intf.setup().unwrap();
+        intf.open().unwrap();
+
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = 0x01;
+        let rsp = intf.sendrecv(&req).unwrap();
+        drop(bmc.join().unwrap());
+
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(rsp.msg.cmd, 0x01);
+        assert_eq!(&rsp.data[..rsp.data_len as usize], &[0x20, 0x01]);
+        drop(pty.slave);
+    }
+
+    #[test]
+    fn test_serial_basic_over_pty() {
+        pty_round_trip(SerialMode::Basic);
+    }
+
+    #[test]
+    fn test_serial_terminal_over_pty() {
+        pty_round_trip(SerialMode::Terminal);
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! IPMI Serial Terminal Mode 报文编解码 (IPMI v2.0 14.7)
+//!
+//! 消息以十六进制 ASCII 表示并放在 `[` `]` 之间：
+//! 请求为 `[NetFn/LUN Seq/Bridge Cmd Data..]`，响应在 Cmd 之后多一个完成码，
+//! 不带校验和。方括号之外的字符 (回显以外的提示文本等) 一律忽略。
+
+use crate::ipmi::ipmi::{
+    IpmiRq, IpmiRs, IpmiRsMsg, IpmiRsPayload, IpmiSession, IPMI_BMC_SLAVE_ADDR, IPMI_BUF_SIZE,
+    IPMI_REMOTE_SWID,
+};
+use std::fmt::Write;
+
+const TM_START: u8 = b'[';
+const TM_STOP: u8 = b']';
+// 规范要求 BMC 至少支持 122 个字符的消息行
+const TM_MAX_LINE: usize = 256;
+
+pub fn encode_request(req: &IpmiRq, rq_seq: u8) -> Vec<u8> {
+    let mut line = String::from("[");
+    let header = [req.msg.netfn_lun, rq_seq << 2, req.msg.cmd];
+    let data = req.msg.data().unwrap_or(&[]);
+    for (i, b) in header.iter().chain(data.iter()).enumerate() {
+        if i > 0 {
+            line.push(' ');
+        }
+        let _ = write!(line, "{:02X}", b);
+    }
+    line.push_str("]\r");
+    line.into_bytes()
+}
+
+/// 逐字节收集 `[...]` 之间的文本
+#[derive(Debug, Default)]
+pub struct TerminalDecoder {
+    buf: Vec<u8>,
+    in_msg: bool,
+}
+
+impl TerminalDecoder {
+    /// 收到完整消息时返回解析出的字节，非法内容的消息被丢弃
+    pub fn feed(&mut self, b: u8) -> Option<Vec<u8>> {
+        match b {
+            TM_START => {
+                self.buf.clear();
+                self.in_msg = true;
+                None
+            }
+            TM_STOP if self.in_msg => {
+                self.in_msg = false;
+                parse_hex(&std::mem::take(&mut self.buf))
+            }
+            _ if self.in_msg => {
+                if self.buf.len() >= TM_MAX_LINE {
+                    self.in_msg = false;
+                } else {
+                    self.buf.push(b);
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 解析十六进制字节，字节之间可以有空白
+fn parse_hex(text: &[u8]) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = text
+        .iter()
+        .copied()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let s = std::str::from_utf8(pair).ok()?;
+            u8::from_str_radix(s, 16).ok()
+        })
+        .collect()
+}
+
+/// 将 `[NetFn/LUN Seq/Bridge Cmd CC Data..]` 转换为响应
+pub fn decode_response(msg: &[u8]) -> Result<IpmiRs, String> {
+    if msg.len() < 4 {
+        return Err("Terminal mode response too short".to_string());
+    }
+    let netfn_lun = msg[0];
+    let seq = msg[1] >> 2;
+    let cmd = msg[2];
+    let data = &msg[4..];
+    if data.len() > IPMI_BUF_SIZE {
+        return Err("Terminal mode response too long".to_string());
+    }
+
+    let mut rsp = IpmiRs {
+        ccode: msg[3],
+        data: [0; IPMI_BUF_SIZE],
+        data_len: data.len() as i32,
+        msg: IpmiRsMsg {
+            netfn: netfn_lun >> 2,
+            cmd,
+            seq,
+            lun: netfn_lun & 0x03,
+        },
+        session: IpmiSession::default(),
+        payload: IpmiRsPayload::IpmiResponse {
+            rq_addr: IPMI_REMOTE_SWID,
+            netfn: netfn_lun >> 2,
+            rq_lun: 0,
+            rs_addr: IPMI_BMC_SLAVE_ADDR as u8,
+            rq_seq: seq,
+            rs_lun: netfn_lun & 0x03,
+            cmd,
+        },
+    };
+    rsp.data[..data.len()].copy_from_slice(data);
+    Ok(rsp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipmi::ipmi::IPMI_NETFN_APP;
+
+    #[test]
+    fn test_encode_request() {
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = 0x38;
+        let mut data = [0x0e, 0x04];
+        req.msg.data = data.as_mut_ptr();
+        req.msg.data_len = data.len() as u16;
+        assert_eq!(encode_request(&req, 5), b"[18 14 38 0E 04]\r");
+    }
+
+    #[test]
+    fn test_terminal_decoder() {
+        let mut dec = TerminalDecoder::default();
+        let input = b"SMASH> [1C 14 01 00 20 01]\r\n[zz]";
+        let frames: Vec<Vec<u8>> = input.iter().filter_map(|&b| dec.feed(b)).collect();
+        assert_eq!(frames, vec![vec![0x1c, 0x14, 0x01, 0x00, 0x20, 0x01]]);
+
+        let rsp = decode_response(&frames[0]).unwrap();
+        assert_eq!(rsp.msg.netfn, IPMI_NETFN_APP + 1);
+        assert_eq!(rsp.msg.seq, 5);
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(&rsp.data[..rsp.data_len as usize], &[0x20, 0x01]);
+        assert!(decode_response(&[0x1c, 0x14]).is_err());
+    }
+}
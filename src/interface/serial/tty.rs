@@ -0,0 +1,205 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 串口设备的打开与 termios 配置
+
+use crate::error::{IpmiError, IpmiResult};
+use nix::poll::{poll, PollFd, PollFlags};
+use nix::sys::termios::{
+    self, BaudRate, ControlFlags, InputFlags, SetArg, SpecialCharacterIndices,
+};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::os::fd::AsFd;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::time::Instant;
+
+pub const SERIAL_DEFAULT_BAUD: u32 = 9600;
+
+// 单次 poll 的最长等待时间，超时判断以调用方的 deadline 为准
+const SERIAL_POLL_MS: u16 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowControl {
+    None,
+    RtsCts,
+    XonXoff,
+}
+
+/// `-D <tty>[:<baud>][+rtscts|+xonxoff]` 描述的串口参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerialConfig {
+    pub path: PathBuf,
+    pub baud: u32,
+    pub flow: FlowControl,
+}
+
+impl SerialConfig {
+    pub fn parse(devfile: &str) -> Result<Self, String> {
+        let (path, opts) = match devfile.split_once(':') {
+            Some((path, opts)) => (path, opts),
+            None => (devfile, ""),
+        };
+        if path.is_empty() {
+            return Err("Serial device not specified".to_string());
+        }
+
+        let mut parts = opts.split('+');
+        let baud = match parts.next() {
+            Some("") | None => SERIAL_DEFAULT_BAUD,
+            Some(speed) => speed
+                .parse::<u32>()
+                .ok()
+                .filter(|b| baud_rate(*b).is_some())
+                .ok_or_else(|| format!("Unsupported baud rate: {}", speed))?,
+        };
+
+        let mut flow = FlowControl::None;
+        for opt in parts {
+            flow = match opt {
+                "rtscts" => FlowControl::RtsCts,
+                "xonxoff" => FlowControl::XonXoff,
+                _ => return Err(format!("Unknown serial option: {}", opt)),
+            };
+        }
+
+        Ok(SerialConfig {
+            path: PathBuf::from(path),
+            baud,
+            flow,
+        })
+    }
+}
+
+fn baud_rate(baud: u32) -> Option<BaudRate> {
+    Some(match baud {
+        1200 => BaudRate::B1200,
+        2400 => BaudRate::B2400,
+        4800 => BaudRate::B4800,
+        9600 => BaudRate::B9600,
+        19200 => BaudRate::B19200,
+        38400 => BaudRate::B38400,
+        57600 => BaudRate::B57600,
+        115200 => BaudRate::B115200,
+        _ => return None,
+    })
+}
+
+/// 以 raw 模式打开的串口，读取带超时
+pub struct SerialPort {
+    file: File,
+    pending: VecDeque<u8>,
+}
+
+impl SerialPort {
+    pub fn open(config: &SerialConfig) -> IpmiResult<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(nix::libc::O_NOCTTY)
+            .open(&config.path)
+            .map_err(|e| {
+                IpmiError::Interface(format!(
+                    "Could not open serial device {}: {}",
+                    config.path.display(),
+                    e
+                ))
+            })?;
+
+        let mut tio = termios::tcgetattr(file.as_fd())
+            .map_err(|e| IpmiError::System(format!("tcgetattr failed: {}", e)))?;
+        termios::cfmakeraw(&mut tio);
+        let speed = baud_rate(config.baud).unwrap_or(BaudRate::B9600);
+        termios::cfsetspeed(&mut tio, speed)
+            .map_err(|e| IpmiError::System(format!("cfsetspeed failed: {}", e)))?;
+
+        tio.control_flags |= ControlFlags::CLOCAL | ControlFlags::CREAD;
+        tio.control_flags &= !(ControlFlags::CRTSCTS | ControlFlags::CSTOPB);
+        tio.input_flags &= !(InputFlags::IXON | InputFlags::IXOFF | InputFlags::IXANY);
+        match config.flow {
+            FlowControl::None => {}
+            FlowControl::RtsCts => tio.control_flags |= ControlFlags::CRTSCTS,
+            FlowControl::XonXoff => tio.input_flags |= InputFlags::IXON | InputFlags::IXOFF,
+        }
+        tio.control_chars[SpecialCharacterIndices::VMIN as usize] = 0;
+        tio.control_chars[SpecialCharacterIndices::VTIME as usize] = 0;
+
+        termios::tcsetattr(file.as_fd(), SetArg::TCSANOW, &tio)
+            .map_err(|e| IpmiError::System(format!("tcsetattr failed: {}", e)))?;
+        let _ = termios::tcflush(file.as_fd(), termios::FlushArg::TCIOFLUSH);
+
+        Ok(SerialPort {
+            file,
+            pending: VecDeque::new(),
+        })
+    }
+
+    pub fn write_all(&mut self, data: &[u8]) -> IpmiResult<()> {
+        self.file
+            .write_all(data)
+            .and_then(|_| self.file.flush())
+            .map_err(|e| IpmiError::Interface(format!("Serial write failed: {}", e)))
+    }
+
+    /// 读取一个字节，deadline 之前没有数据返回 None
+    pub fn read_byte(&mut self, deadline: Instant) -> IpmiResult<Option<u8>> {
+        loop {
+            if let Some(b) = self.pending.pop_front() {
+                return Ok(Some(b));
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            let wait = (deadline - now).as_millis().min(SERIAL_POLL_MS as u128) as u16;
+
+            let mut fds = [PollFd::new(self.file.as_fd(), PollFlags::POLLIN)];
+            match poll(&mut fds, wait) {
+                Ok(0) => continue,
+                Ok(_) => {}
+                Err(nix::errno::Errno::EINTR) => continue,
+                Err(e) => return Err(IpmiError::System(format!("poll failed: {}", e))),
+            }
+
+            let mut buf = [0u8; 64];
+            match self.file.read(&mut buf) {
+                Ok(n) => self.pending.extend(&buf[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(IpmiError::Interface(format!("Serial read failed: {}", e))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial_config_parse() {
+        assert_eq!(
+            SerialConfig::parse("/dev/ttyS0").unwrap(),
+            SerialConfig {
+                path: PathBuf::from("/dev/ttyS0"),
+                baud: SERIAL_DEFAULT_BAUD,
+                flow: FlowControl::None,
+            }
+        );
+        let cfg = SerialConfig::parse("/dev/ttyUSB1:115200+rtscts").unwrap();
+        assert_eq!(cfg.baud, 115200);
+        assert_eq!(cfg.flow, FlowControl::RtsCts);
+        assert_eq!(
+            SerialConfig::parse("/dev/ttyS1:+xonxoff").unwrap().flow,
+            FlowControl::XonXoff
+        );
+        assert!(SerialConfig::parse("/dev/ttyS0:12345").is_err());
+        assert!(SerialConfig::parse("/dev/ttyS0:9600+odd").is_err());
+        assert!(SerialConfig::parse(":9600").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 基于 tokio 的异步接口层
+//!
+//! [`AsyncIpmiIntf`] 是 [`IpmiIntf`] 的异步版本，返回的 future 都是 `Send`，
+//! 可以在同一个运行时里 `tokio::spawn` 出多个任务分别驱动不同的 BMC：
+//!
+//! ```ignore
+//! let mut intf = AsyncLanIntf::new(lan);
+//! intf.setup()?;
+//! intf.open().await?;
+//! // IpmiRq 含裸指针不是 Send，先取得 future 再 await，请求不必跨越 await 存活
+//! let pending = intf.sendrecv(&req);
+//! drop(req);
+//! let rsp = pending.await;
+//! intf.close().await;
+//! ```
+//!
+//! 已有的命令实现只认同步 trait，用 [`BlockingIntf`] 包一层即可复用。
+
+use super::intf::{IpmiContext, IpmiIntf};
+use super::ipmi::{IpmiRq, IpmiRs, IpmiV2Payload};
+use crate::error::{IpmiError, IpmiResult};
+use std::future::Future;
+use std::pin::Pin;
+
+/// 接口方法返回的 future，只借用接口本身
+pub type IpmiFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// 异步 IPMI 接口
+///
+/// `sendrecv` 在返回 future 之前就取走请求内容，future 本身不引用 `req`。
+/// 打开之后才能发送请求，不会像同步接口那样隐式 open。
+/// 返回装箱的 future 使 trait 可以作为 `Box<dyn AsyncIpmiIntf>` 混用不同接口。
+pub trait AsyncIpmiIntf: Send {
+    fn context(&mut self) -> &mut IpmiContext;
+
+    fn setup(&mut self) -> IpmiResult<()>;
+    fn open(&mut self) -> IpmiFuture<'_, IpmiResult<()>>;
+    fn close(&mut self) -> IpmiFuture<'_, ()>;
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> IpmiFuture<'_, Option<IpmiRs>>;
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()>;
+}
+
+impl<T: AsyncIpmiIntf + ?Sized> AsyncIpmiIntf for Box<T> {
+    fn context(&mut self) -> &mut IpmiContext {
+        (**self).context()
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        (**self).setup()
+    }
+
+    fn open(&mut self) -> IpmiFuture<'_, IpmiResult<()>> {
+        (**self).open()
+    }
+
+    fn close(&mut self) -> IpmiFuture<'_, ()> {
+        (**self).close()
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> IpmiFuture<'_, Option<IpmiRs>> {
+        (**self).sendrecv(req)
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        (**self).set_my_addr(addr)
+    }
+}
+
+/// 在阻塞线程池上执行同步接口的操作 (会话建立、设备初始化等多轮收发)
+///
+/// 执行期间 `intf` 暂时换成 `placeholder`；若 future 中途被取消，
+/// 接口保持为 placeholder 状态，需要重新构造。
+pub(crate) async fn run_blocking<I, R, F>(intf: &mut I, placeholder: I, f: F) -> IpmiResult<R>
+where
+    I: Send + 'static,
+    R: Send + 'static,
+    F: FnOnce(&mut I) -> R + Send + 'static,
+{
+    let mut owned = std::mem::replace(intf, placeholder);
+    let (owned, result) = tokio::task::spawn_blocking(move || {
+        let result = f(&mut owned);
+        (owned, result)
+    })
+    .await
+    .map_err(|e| IpmiError::System(format!("Interface task failed: {}", e)))?;
+    *intf = owned;
+    Ok(result)
+}
+
+/// 把异步接口适配成同步的 [`IpmiIntf`]，内部持有一个单线程运行时
+///
+/// 不能在另一个 tokio 运行时的上下文中调用 (block_on 会 panic)。
+pub struct BlockingIntf<A: AsyncIpmiIntf> {
+    runtime: tokio::runtime::Runtime,
+    intf: A,
+}
+
+impl<A: AsyncIpmiIntf> BlockingIntf<A> {
+    pub fn new(intf: A) -> IpmiResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| IpmiError::System(format!("Unable to start tokio runtime: {}", e)))?;
+        Ok(Self { runtime, intf })
+    }
+
+    pub fn into_inner(self) -> A {
+        self.intf
+    }
+}
+
+impl<A: AsyncIpmiIntf> IpmiIntf for BlockingIntf<A> {
+    fn context(&mut self) -> &mut IpmiContext {
+        self.intf.context()
+    }
+
+    fn setup(&mut self) -> IpmiResult<()> {
+        self.intf.setup()
+    }
+
+    fn open(&mut self) -> IpmiResult<()> {
+        self.runtime.block_on(self.intf.open())
+    }
+
+    fn close(&mut self) {
+        self.runtime.block_on(self.intf.close())
+    }
+
+    fn sendrecv(&mut self, req: &IpmiRq) -> Option<IpmiRs> {
+        self.runtime.block_on(self.intf.sendrecv(req))
+    }
+
+    fn send_sol(&mut self, _payload: &IpmiV2Payload) -> Option<IpmiRs> {
+        None
+    }
+
+    fn recv_sol(&mut self) -> Option<IpmiRs> {
+        None
+    }
+
+    fn keepalive(&mut self) -> IpmiResult<()> {
+        Ok(())
+    }
+
+    fn set_my_addr(&mut self, addr: u8) -> IpmiResult<()> {
+        self.intf.set_my_addr(addr)
+    }
+}
@@ -4,6 +4,8 @@
  * SPDX-License-Identifier: GPL-2.0-or-later
  */
 
+use std::path::PathBuf;
+
 /// IPMI Context Module - 重构后的上下文管理
 ///
 /// 按照职责分离原则，将原来的IpmiContext拆分为多个专门的上下文结构
@@ -210,6 +212,8 @@ pub struct IpmiContext {
     pub protocol: ProtocolContext,
     /// 输出上下文
     pub output: OutputContext,
+    /// 本地 SDR 缓存文件 (-S)，设置后从文件读取 SDR 记录
+    pub sdr_cache: Option<PathBuf>,
 }
 
 impl IpmiContext {
@@ -225,6 +229,7 @@ impl IpmiContext {
             bridging: None,
             protocol: ProtocolContext::default(),
             output: OutputContext::default(),
+            sdr_cache: None,
         }
     }
 
@@ -235,6 +240,7 @@ impl IpmiContext {
             bridging: None,
             protocol: ProtocolContext::default(),
             output,
+            sdr_cache: None,
         }
     }
 
@@ -245,6 +251,7 @@ impl IpmiContext {
             bridging: None,
             protocol: ProtocolContext::default(),
             output,
+            sdr_cache: None,
         }
     }
 
@@ -163,6 +163,36 @@ pub struct IpmiV2Data {
     pub k2_len: u8,
 }
 
+impl Default for IpmiV2Data {
+    fn default() -> Self {
+        IpmiV2Data {
+            session_state: LanplusSessionState::Presession,
+            requested_auth_alg: 0,
+            requested_integrity_alg: 0,
+            requested_crypt_alg: 0,
+            auth_alg: 0,
+            integrity_alg: 0,
+            crypt_alg: 0,
+            max_priv_level: 0,
+            console_id: 0,
+            bmc_id: 0,
+            console_rand: [0u8; 16],
+            bmc_rand: [0u8; 16],
+            bmc_guid: [0u8; 16],
+            requested_role: 0,
+            rakp2_return_code: 0,
+            sik: [0u8; IPMI_SIK_BUFFER_SIZE],
+            sik_len: 0,
+            kg: [0u8; IPMI_KG_BUFFER_SIZE],
+            k1: [0u8; IPMI_MAX_MD_SIZE],
+            k1_len: 0,
+            k2: [0u8; IPMI_MAX_MD_SIZE],
+            k2_len: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct SolData {
     pub max_inbound_payload_size: u16,
     pub max_outbound_payload_size: u16,
@@ -221,6 +251,11 @@ pub trait IpmiIntf {
     fn set_max_request_size(&mut self, _size: u16) {}
     fn set_max_response_size(&mut self, _size: u16) {}
 
+    /// RMCP+ 会话的 (完整性保护, 加密) 状态，非 lanplus 接口返回 None
+    fn session_security(&self) -> Option<(bool, bool)> {
+        None
+    }
+
     // fn get_max_request_data_size(&self) -> u16;
     // fn get_max_response_data_size(&self) -> u16;
 
@@ -246,4 +281,4 @@ pub trait IpmiIntfExt: IpmiIntf {
     }
 }
 // 为所有实现了 IpmiIntf 的类型自动实现扩展 trait
-impl<T: IpmiIntf> IpmiIntfExt for T {}
+impl<T: IpmiIntf + ?Sized> IpmiIntfExt for T {}
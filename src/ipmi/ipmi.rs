@@ -119,6 +119,25 @@ pub struct SolPacket {
     pub flush_outbound: bool,
 }
 
+impl Default for SolPacket {
+    fn default() -> Self {
+        Self {
+            data: [0; IPMI_BUF_SIZE],
+            character_count: 0,
+            packet_sequence_number: 0,
+            acked_packet_number: 0,
+            accepted_character_count: 0,
+            is_nack: false,
+            assert_ring_wor: false,
+            generate_break: false,
+            deassert_cts: false,
+            deassert_dcd_dsr: false,
+            flush_inbound: false,
+            flush_outbound: false,
+        }
+    }
+}
+
 pub enum IpmiPayloadData {
     IpmiRequest { rq_seq: u8, request: Box<IpmiRq> },
     IpmiResponse { rs_seq: u8, response: Box<IpmiRs> },
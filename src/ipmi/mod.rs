@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: GPL-2.0-or-later
  */
 
+pub mod async_intf;
 pub mod constants;
 pub mod context;
 pub mod intf;
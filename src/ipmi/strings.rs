@@ -432,6 +432,14 @@ const IPMI_PRIVLVL_VALS: &[U8Str] = &[
     },
 ];
 
+/// Lookup helper to translate a session privilege level to its name.
+pub fn privlvl_to_str(level: u8) -> &'static str {
+    IPMI_PRIVLVL_VALS
+        .iter()
+        .find(|v| v.val == level && !v.desc.is_empty())
+        .map_or("Unknown", |v| v.desc)
+}
+
 const IPMI_SET_IN_PROGRESS_VALS: &[U8Str] = &[
     U8Str {
         val: IPMI_SET_IN_PROGRESS_SET_COMPLETE,
@@ -1105,6 +1113,46 @@ const IPMI_CHANNEL_MEDIUM_VALS: &[U8Str] = &[
         val: IPMI_CHANNEL_MEDIUM_SYSTEM,
         desc: "System Interface",
     },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_ICMB_09,
+        desc: "ICMB v0.9",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_LAN,
+        desc: "802.3 LAN",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_SERIAL,
+        desc: "Serial/Modem",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_LAN_OTHER,
+        desc: "Other LAN",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_SMBUS_PCI,
+        desc: "PCI SMBus",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_SMBUS_1,
+        desc: "SMBus v1.0/v1.1",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_SMBUS_2,
+        desc: "SMBus v2.0",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_USB_1,
+        desc: "USB 1.x",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_USB_2,
+        desc: "USB 2.x",
+    },
+    U8Str {
+        val: IPMI_CHANNEL_MEDIUM_SYSTEM,
+        desc: "System Interface",
+    },
     U8Str {
         val: 0x00,
         desc: "",
@@ -1381,6 +1429,128 @@ const IPMI_ENCRYPTION_ALGORITHMS: &[U8Str] = &[
     },
 ];
 
+const IPMI_RAKP_RETURN_CODES: &[U8Str] = &[
+    U8Str {
+        val: 0x00,
+        desc: "no errors",
+    },
+    U8Str {
+        val: 0x01,
+        desc: "insufficient resources to create new session",
+    },
+    U8Str {
+        val: 0x02,
+        desc: "invalid session ID",
+    },
+    U8Str {
+        val: 0x03,
+        desc: "invalid payload type",
+    },
+    U8Str {
+        val: 0x04,
+        desc: "invalid authentication algorithm",
+    },
+    U8Str {
+        val: 0x05,
+        desc: "invalid integrity algorithm",
+    },
+    U8Str {
+        val: 0x06,
+        desc: "no matching integrity payload",
+    },
+    U8Str {
+        val: 0x07,
+        desc: "no matching integrity payload",
+    },
+    U8Str {
+        val: 0x08,
+        desc: "inactive session ID",
+    },
+    U8Str {
+        val: 0x09,
+        desc: "invalid role",
+    },
+    U8Str {
+        val: 0x0a,
+        desc: "unauthorized role or privilege level requested",
+    },
+    U8Str {
+        val: 0x0b,
+        desc: "insufficient resources to create a session at the requested role",
+    },
+    U8Str {
+        val: 0x0c,
+        desc: "invalid username length",
+    },
+    U8Str {
+        val: 0x0d,
+        desc: "unauthorized name",
+    },
+    U8Str {
+        val: 0x0e,
+        desc: "unauthorized GUID",
+    },
+    U8Str {
+        val: 0x0f,
+        desc: "invalid integrity check value",
+    },
+    U8Str {
+        val: 0x10,
+        desc: "invalid confidentiality algorithm",
+    },
+    U8Str {
+        val: 0x11,
+        desc: "no cipher suite match with proposed security algorithms",
+    },
+    U8Str {
+        val: 0x12,
+        desc: "illegal or unrecognized parameter",
+    },
+    U8Str {
+        val: 0xff,
+        desc: "",
+    },
+];
+
+fn u8str_lookup(vals: &[U8Str], val: u8) -> Option<&'static str> {
+    vals.iter()
+        .find(|v| v.val == val && !v.desc.is_empty())
+        .map(|v| v.desc)
+}
+
+/// RMCP+ 认证算法名称
+pub fn auth_alg_to_str(alg: u8) -> &'static str {
+    u8str_lookup(IPMI_AUTH_ALGORITHMS, alg).unwrap_or("unknown")
+}
+
+/// RMCP+ 完整性算法名称
+pub fn integrity_alg_to_str(alg: u8) -> &'static str {
+    u8str_lookup(IPMI_INTEGRITY_ALGORITHMS, alg).unwrap_or("unknown")
+}
+
+/// RMCP+ 加密算法名称
+pub fn crypt_alg_to_str(alg: u8) -> &'static str {
+    u8str_lookup(IPMI_ENCRYPTION_ALGORITHMS, alg).unwrap_or("unknown")
+}
+
+/// 通道介质类型名称
+pub fn channel_medium_to_str(medium: u8) -> &'static str {
+    u8str_lookup(IPMI_CHANNEL_MEDIUM_VALS, medium).unwrap_or("reserved")
+}
+
+/// 通道协议类型名称
+pub fn channel_protocol_to_str(protocol: u8) -> &'static str {
+    IPMI_CHANNEL_PROTOCOL_VALS
+        .iter()
+        .find(|v| v.val == protocol as u32 && !v.desc.is_empty())
+        .map_or("reserved", |v| v.desc)
+}
+
+/// Open Session / RAKP 消息状态码描述
+pub fn rakp_return_code_to_str(code: u8) -> &'static str {
+    u8str_lookup(IPMI_RAKP_RETURN_CODES, code).unwrap_or("unknown")
+}
+
 const IPMI_USER_ENABLE_STATUS_VALS: &[ValStr] = &[
     ValStr {
         val: 0x00,
@@ -1903,3 +2073,8 @@ const PICMG_BUSRES_SHMC_STATUS_VALS: &[OemValStr] = &[
         desc: "",
     },
 ];
+
+/// 参数设置进度状态（set-in-progress 参数）名称
+pub fn set_in_progress_to_str(state: u8) -> &'static str {
+    u8str_lookup(IPMI_SET_IN_PROGRESS_VALS, state).unwrap_or("Unknown value")
+}
@@ -153,6 +153,20 @@ pub fn ipmi_timestamp_date(stamp: u32, _time_in_utc: bool) -> String {
     }
 }
 
+/// 完整日期时间格式 (与 C 库 "%c" 相同)，用于 FRU 制造日期等字段
+pub fn ipmi_timestamp_string(stamp: u32) -> String {
+    if !ipmi_timestamp_is_valid(stamp) {
+        return "Unspecified".to_string();
+    }
+    format_timestamp(stamp, true, "%a %b %e %H:%M:%S %Y")
+}
+
+/// 解析 "MM/DD/YYYY HH:MM:SS" 格式的 UTC 时间，与 ipmi_timestamp_numeric 的输出格式对应
+pub fn ipmi_timestamp_parse(s: &str) -> Option<u32> {
+    let dt = chrono::NaiveDateTime::parse_from_str(s.trim(), "%m/%d/%Y %H:%M:%S").ok()?;
+    u32::try_from(dt.and_utc().timestamp()).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +182,13 @@ mod tests {
     fn test_invalid_timestamp() {
         assert_eq!(ipmi_timestamp_numeric(IPMI_TIME_UNSPECIFIED), "Unspecified");
     }
+
+    #[test]
+    fn test_timestamp_parse() {
+        let stamp = ipmi_timestamp_parse("03/15/2024 12:30:45").unwrap();
+        assert_eq!(stamp, 1_710_505_845);
+        assert_eq!(ipmi_timestamp_numeric(stamp), "03/15/2024 12:30:45");
+        assert_eq!(ipmi_timestamp_parse("2024-03-15 12:30:45"), None);
+        assert_eq!(ipmi_timestamp_parse("13/01/2024 00:00:00"), None);
+    }
 }
@@ -7,11 +7,13 @@
 pub mod commands;
 pub mod debug_control;
 pub mod error;
+pub mod fleet;
 pub mod helper;
 pub mod interface;
 pub mod ipmi;
 pub mod logger;
 pub mod logging;
+pub mod sim;
 
 // 在 src/lib.rs 中添加或确认存在
 use std::sync::atomic::AtomicUsize;
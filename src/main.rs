@@ -5,20 +5,35 @@
  */
 
 mod cli;
-use clap::Parser;
-use cli::{Cli, InterfaceType, MainCommand};
+use clap::{Parser, ValueEnum};
+use cli::{Cli, GlobalArgs, InterfaceType, MainCommand};
 use std::sync::atomic::Ordering;
+use utipmitool::commands::channel::ipmi_channel_main;
 use utipmitool::commands::chassis::ipmi_chassis_main;
+use utipmitool::commands::discover::{ipmi_discover_main, ipmi_ping_main, ProbeOptions};
+use utipmitool::commands::event::ipmi_event_main;
+use utipmitool::commands::fru::ipmi_fru_main;
 use utipmitool::commands::lan::ipmi_lan_main;
-use utipmitool::commands::mc::ipmi_mc_main;
+use utipmitool::commands::mc::watchdog::WatchdogCommand;
+use utipmitool::commands::mc::{ipmi_mc_main, McCommand};
+use utipmitool::commands::raw::ipmi_raw_main;
 use utipmitool::commands::sdr::ipmi_sdr_main;
 use utipmitool::commands::sel::ipmi_sel_main;
+use utipmitool::commands::sel::save::ipmi_sel_readraw;
+use utipmitool::commands::sel::SelCommand;
 use utipmitool::commands::sensor::ipmi_sensor_main;
 use utipmitool::commands::sensor::SensorCommand;
+use utipmitool::commands::session::ipmi_session_main;
+use utipmitool::commands::sol::ipmi_sol_main;
 use utipmitool::commands::user::ipmi_user_main;
 use utipmitool::debug_control;
+use utipmitool::fleet::{self, FleetRunner, FLEET_HOST_ENV};
+use utipmitool::interface::dummy::IpmiDummyIntf;
+use utipmitool::interface::lan::IpmiLanIntf;
+use utipmitool::interface::lanplus::IpmiLanplusIntf;
 use utipmitool::interface::open::open::OpenIntf; //open::OpenIntf
-                                                 //open::OpenIntf
+use utipmitool::interface::replay::{IpmiRecordIntf, IpmiReplayIntf};
+use utipmitool::interface::serial::{IpmiSerialIntf, SerialConfig, SerialMode};
 use utipmitool::ipmi::picmg::*;
 use utipmitool::ipmi::vita::*;
 use utipmitool::VERBOSE_LEVEL;
@@ -44,7 +59,7 @@ fn main() {
     if args.len() >= 2 && args[1] == "chassis" {
         // 没有子命令或者子命令是help，显示自定义帮助
         if args.len() == 2 || (args.len() == 3 && args[2] == "help") {
-            println!("Chassis Commands:  status, power, identify, restart_cause, boot-dev");
+            println!("Chassis Commands:  status, power, identify, policy, restart_cause, poh, bootdev, bootparam, selftest");
             return;
         }
     }
@@ -53,7 +68,7 @@ fn main() {
     use crate::debug_control;
     // debug_control::hide_loading_interface_message(); // 隐藏"Loading interface: Open"信息
 
-    let cli = match Cli::try_parse() {
+    let mut cli = match Cli::try_parse() {
         Ok(cli) => cli,
         Err(err) => {
             // 检查是否是用户命令相关的错误
@@ -122,6 +137,18 @@ fn main() {
         }
     }
 
+    // -H 给出多台主机时，由子进程逐台执行同一条命令
+    match std::env::var(FLEET_HOST_ENV) {
+        Ok(host) => cli.global.use_fleet_host(host),
+        Err(_) => {
+            if let Some(spec) = cli.global.hostname.clone() {
+                if fleet::is_host_list(&spec) {
+                    std::process::exit(run_fleet(&cli.global, &spec));
+                }
+            }
+        }
+    }
+
     init_logger(LogConfig {
         level: LogLevel::Debug,
         use_colors: true,
@@ -147,6 +174,19 @@ fn main() {
         }
     }
 
+    // 看门狗守护进程只在本机通过 open 接口运行
+    if let MainCommand::Mc {
+        subcmd: McCommand::Watchdog {
+            subcmd: WatchdogCommand::Daemon(_),
+        },
+    } = &cli.command
+    {
+        if !matches!(cli.global.interface, InterfaceType::Open) {
+            eprintln!("mc watchdog daemon requires the open interface (-I open)");
+            std::process::exit(1);
+        }
+    }
+
     let ctx = IpmiContext {
         base: IpmiBaseContext {
             my_addr: if cli.global.arg_addr != 0 {
@@ -163,30 +203,112 @@ fn main() {
         protocol: ProtocolContext::default(),
         output: OutputContext::new(cli.global.csv_output, cli.global.verbose)
             .with_event_only(cli.global.include_event_only),
+        sdr_cache: cli.global.sdr_cache.clone(),
+    };
+
+    // sel readraw 只在本地解码文件，不打开 BMC 接口
+    if let MainCommand::Sel {
+        subcmd: SelCommand::ReadRaw { file },
+    } = &cli.command
+    {
+        if let Err(e) = ipmi_sel_readraw(ctx, file) {
+            log::error!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // ping / discover 直接发送 RMCP 报文，不打开接口
+    let probe = match &cli.command {
+        MainCommand::Ping(args) => Some(ipmi_ping_main(args, &probe_options(&cli.global))),
+        MainCommand::Discover(args) => Some(ipmi_discover_main(args, &probe_options(&cli.global))),
+        _ => None,
     };
+    if let Some(result) = probe {
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
 
     // 加载接口
     // log_info!("Loading interface: {:?}", cli.global.interface);
-    let mut intf = match cli.global.interface {
+    let mut intf: Box<dyn IpmiIntf> = match cli.global.interface {
         InterfaceType::Open => {
             //ipmi_intf_load
 
             //intf.setup();
             //intf.open
             Box::new(OpenIntf::new(cli.global.devnum, ctx))
-        } //Some(InterfaceType::Lan) => load_lan_interface(),
-          //Some(InterfaceType::LanPlus) => load_lanplus_interface(),
-          //None => get_default_interface(), // 默认接口
-          // 其他接口处理...
+        }
+        InterfaceType::Lan => match load_lan_interface(&cli.global, ctx) {
+            Ok(intf) => Box::new(intf),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        InterfaceType::LanPlus => match load_lanplus_interface(&cli.global, ctx) {
+            Ok(intf) => Box::new(intf),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        InterfaceType::SerialBm | InterfaceType::SerialTerm => {
+            match load_serial_interface(&cli.global, ctx) {
+                Ok(intf) => Box::new(intf),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        InterfaceType::Dummy => {
+            let mut intf = IpmiDummyIntf::new(cli.global.devfile.clone(), ctx);
+            intf.set_timeout(cli.global.timeout as u64, cli.global.retries);
+            Box::new(intf)
+        }
+        InterfaceType::Replay => match cli.global.devfile.clone() {
+            Some(path) => Box::new(IpmiReplayIntf::new(path, ctx)),
+            None => {
+                eprintln!("The replay interface needs a record file: -D <file>");
+                std::process::exit(1);
+            }
+        },
+        //None => get_default_interface(), // 默认接口
+        // 其他接口处理...
     };
+    if let Some(path) = &cli.global.record {
+        let name = cli
+            .global
+            .interface
+            .to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default();
+        intf = match IpmiRecordIntf::new(intf, path, &name) {
+            Ok(rec) => Box::new(rec),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        };
+    }
     //会调用set_my_addr设置一个默认地址
     if let Err(e) = intf.setup() {
         eprintln!("Unable to setup interface: {}", e);
-        return;
+        std::process::exit(1);
     }
     if let Err(e) = intf.open() {
         eprintln!("{}", e);
-        return;
+        if matches!(
+            cli.global.interface,
+            InterfaceType::Lan | InterfaceType::LanPlus
+        ) {
+            eprintln!("Error: Unable to establish LAN session");
+        }
+        std::process::exit(1);
     }
     // log_info!("Interface opened successfully");
 
@@ -230,6 +352,7 @@ fn main() {
             ctx.set_transit_addr(cli.global.transit_addr as u32);
             ctx.set_transit_channel(cli.global.transit_channel);
         }
+        ctx.set_target_lun(cli.global.target_lun);
     });
 
     intf.with_context(|ctx| {
@@ -296,47 +419,239 @@ fn main() {
     debug_control::reset_debug_state();
 
     match cli.command {
-        MainCommand::Chassis { subcmd } => {
-            ipmi_chassis_main(subcmd, intf).unwrap_or_else(|e| log::error!("Error: {}", e))
-        }
-        MainCommand::Lan { subcmd } => {
-            ipmi_lan_main(subcmd, intf).unwrap_or_else(|e| log::error!("Error: {}", e))
-        }
-        MainCommand::Mc { subcmd } => {
-            ipmi_mc_main(subcmd, intf).unwrap_or_else(|e| log::error!("Error: {}", e))
-        }
+        MainCommand::Raw(args) => match ipmi_raw_main(args, intf) {
+            Ok(0) => {}
+            Ok(code) => std::process::exit(code),
+            Err(e) => {
+                log::error!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        MainCommand::Chassis { subcmd } => exit_on_error(ipmi_chassis_main(subcmd, intf)),
+        MainCommand::Lan { subcmd } => exit_on_error(ipmi_lan_main(subcmd, intf)),
+        MainCommand::Mc { subcmd } => exit_on_error(ipmi_mc_main(subcmd, intf)),
         MainCommand::Sensor { subcmd } => {
             let command = subcmd.unwrap_or(SensorCommand::List);
             // 标记为来自 sensor list 路径，避免 sdr list 的额外行
             intf.with_context(|ctx| ctx.output.set_from_sdr_list(false));
-            ipmi_sensor_main(command, intf).unwrap_or_else(|e| log::error!("Error: {}", e))
+            exit_on_error(ipmi_sensor_main(command, intf))
         }
 
-        MainCommand::Sel { subcmd } => {
-            ipmi_sel_main(subcmd, intf).unwrap_or_else(|e| log::error!("Error: {}", e))
-        }
+        MainCommand::Sel { subcmd } => exit_on_error(ipmi_sel_main(subcmd, intf)),
 
+        MainCommand::Fru { subcmd } => exit_on_error(ipmi_fru_main(subcmd, intf)),
         MainCommand::Sdr { subcmd } => {
             // sdr 路径由 sdr 模块自行设置 from_sdr_list
-            ipmi_sdr_main(subcmd, intf).unwrap_or_else(|e| log::error!("Error: {}", e))
+            exit_on_error(ipmi_sdr_main(subcmd, intf))
+        }
+        MainCommand::Channel { subcmd } => {
+            if let Err(e) = ipmi_channel_main(subcmd, intf) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        MainCommand::Session(args) => {
+            if let Err(e) = ipmi_session_main(args, intf) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
         }
         MainCommand::User { subcmd } => {
-            ipmi_user_main(subcmd, intf).unwrap_or_else(|e| {
+            if let Err(e) = ipmi_user_main(subcmd, intf) {
                 // 与ipmitool保持一致的错误输出格式
-                eprintln!("{}", e)
-            })
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        MainCommand::Sol { subcmd } => {
+            let escape = cli.global.sol_escape.unwrap_or('~');
+            exit_on_error(ipmi_sol_main(subcmd, intf, escape))
+        }
+        MainCommand::Event(args) => {
+            if let Err(e) = ipmi_event_main(args, intf) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        MainCommand::Ping(_) | MainCommand::Discover(_) => unreachable!(),
+    }
+}
+
+/// 命令失败时以非零状态退出，主机列表据此统计失败的主机
+fn exit_on_error<E: std::fmt::Display>(result: Result<(), E>) {
+    if let Err(e) = result {
+        log::error!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// 对 `-H` 列出的每台主机以子进程执行本次命令，返回进程退出码
+fn run_fleet(global: &GlobalArgs, spec: &str) -> i32 {
+    if !matches!(
+        global.interface,
+        InterfaceType::Lan | InterfaceType::LanPlus
+    ) {
+        eprintln!("A host list needs -I lan or -I lanplus");
+        return 1;
+    }
+    if global.record.is_some() {
+        eprintln!("--record cannot be used with a host list");
+        return 1;
+    }
+    let hosts = match fleet::parse_hosts(spec) {
+        Ok(hosts) => hosts,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let program = match std::env::current_exe() {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("Unable to locate executable: {}", e);
+            return 1;
+        }
+    };
+
+    let mut runner =
+        FleetRunner::new(program, std::env::args_os().skip(1).collect()).parallel(global.parallel);
+    // 交互输入只做一次，再经环境变量交给每个子进程
+    if global.password_prompt {
+        match global.resolve_password() {
+            Ok(password) => runner = runner.env("IPMI_PASSWORD", &password),
+            Err(e) => {
+                eprintln!("{}", e);
+                return 1;
+            }
+        }
+    }
+    if global.key_prompt {
+        match global.resolve_kg() {
+            Ok(Some(kg)) => runner = runner.env("IPMI_KGKEY", &String::from_utf8_lossy(&kg)),
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("{}", e);
+                return 1;
+            }
+        }
+    }
+
+    let group = global.group;
+    let results = runner.run(&hosts, |r| {
+        if !group {
+            print!("{}", r.prefixed_stdout());
         }
-        _ => unimplemented!(),
+        eprint!("{}", r.prefixed_stderr());
+    });
+    let results = match results {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    if group {
+        print!("{}", fleet::group_outputs(&results));
+    }
+
+    match fleet::failure_summary(&results) {
+        Some(summary) => {
+            eprintln!("{}", summary);
+            1
+        }
+        None => 0,
     }
 }
 
+/// ping / discover 使用的端口、超时与地址族
+fn probe_options(global: &GlobalArgs) -> ProbeOptions {
+    ProbeOptions {
+        port: global.port,
+        timeout: std::time::Duration::from_secs(global.timeout as u64),
+        retries: global.retries,
+        ipv4_only: global.ipv4,
+        ipv6_only: global.ipv6,
+        csv: global.csv_output,
+    }
+}
+
+/// 根据全局参数创建 IPMI v1.5 LAN 接口
+fn load_lan_interface(global: &GlobalArgs, ctx: IpmiContext) -> Result<IpmiLanIntf, String> {
+    let hostname = global
+        .hostname
+        .clone()
+        .ok_or_else(|| "No hostname specified!".to_string())?;
+    let password = global.resolve_password()?;
+
+    let mut intf = IpmiLanIntf::new(hostname, global.port, ctx);
+    intf.set_credentials(global.username.clone().unwrap_or_default(), password);
+    intf.set_privilege_level(global.privilege.as_u8());
+    if let Some(authtype) = &global.authtype {
+        intf.set_authtype(authtype.as_u8());
+    }
+    intf.set_timeout(global.timeout as u64, global.retries);
+    if global.ipv4 {
+        intf.set_address_family(nix::libc::AF_INET);
+    } else if global.ipv6 {
+        intf.set_address_family(nix::libc::AF_INET6);
+    }
+
+    Ok(intf)
+}
+
+/// 根据全局参数创建 IPMI v2.0 RMCP+ LANPLUS 接口
+fn load_lanplus_interface(
+    global: &GlobalArgs,
+    ctx: IpmiContext,
+) -> Result<IpmiLanplusIntf, String> {
+    let hostname = global
+        .hostname
+        .clone()
+        .ok_or_else(|| "No hostname specified!".to_string())?;
+    let password = global.resolve_password()?;
+
+    let mut intf = IpmiLanplusIntf::new(hostname, global.port, ctx);
+    intf.set_credentials(global.username.clone().unwrap_or_default(), password);
+    intf.set_privilege_level(global.privilege.as_u8());
+    if let Some(cipher_suite) = global.cipher_suite {
+        intf.set_cipher_suite(cipher_suite);
+    }
+    if let Some(kg) = global.resolve_kg()? {
+        intf.set_kg(&kg);
+    }
+    intf.set_timeout(global.timeout as u64, global.retries);
+    if global.ipv4 {
+        intf.set_address_family(nix::libc::AF_INET);
+    } else if global.ipv6 {
+        intf.set_address_family(nix::libc::AF_INET6);
+    }
+
+    Ok(intf)
+}
+
+/// 根据 -D 参数创建串口接口 (serial-basic / serial-terminal)
+fn load_serial_interface(global: &GlobalArgs, ctx: IpmiContext) -> Result<IpmiSerialIntf, String> {
+    let devfile = global
+        .devfile
+        .as_ref()
+        .ok_or_else(|| "No serial device specified (use -D <tty>)".to_string())?;
+    let config = SerialConfig::parse(&devfile.to_string_lossy())?;
+    let mode = match global.interface {
+        InterfaceType::SerialTerm => SerialMode::Terminal,
+        _ => SerialMode::Basic,
+    };
+
+    let mut intf = IpmiSerialIntf::new(config, mode, ctx);
+    intf.set_timeout(global.timeout as u64, global.retries);
+    Ok(intf)
+}
+
 fn ipmi_acquire_ipmb_address(intf: &mut dyn IpmiIntf) -> u8 {
     // 获取和显示IANA厂商ID
     let actual_id = get_manufacturer_id_from_device(intf);
-    
+
     log_debug!("Iana: {}", actual_id);
     log_debug!("");
-    
 
     // 先尝试 PICMG 扩展
     if picmg_discover(intf) != 0 {
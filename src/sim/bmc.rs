@@ -0,0 +1,1475 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 模拟 BMC 的状态与命令处理
+//!
+//! 只实现本工具实际发出的命令，其余请求一律返回 0xC1 (Invalid Command)。
+
+use super::model::{
+    ChassisModel, DeviceModel, Fault, FaultAction, LanParams, SensorModel, SimModel,
+};
+use crate::commands::event::IPMI_PLATFORM_EVENT;
+use crate::commands::sdr::sdr::{
+    SdrRecordFullSensor, SdrRecordHeader, GET_SDR, GET_SDR_REPO_INFO, SDR_RECORD_TYPE_FULL_SENSOR,
+};
+use crate::commands::sdr::sdr::{
+    GET_SENSOR_EVENT_ENABLE, GET_SENSOR_EVENT_STATUS, GET_SENSOR_HYSTERESIS, GET_SENSOR_READING,
+    GET_SENSOR_THRESHOLDS, SET_SENSOR_THRESHOLDS,
+};
+use crate::commands::sel::entry::SelEntry;
+use crate::commands::sel::sel::{
+    IPMI_CMD_ADD_SEL_ENTRY, IPMI_CMD_CLEAR_SEL, IPMI_CMD_DELETE_SEL_ENTRY,
+    IPMI_CMD_GET_SEL_ALLOC_INFO, IPMI_CMD_GET_SEL_ENTRY, IPMI_CMD_GET_SEL_INFO,
+    IPMI_CMD_GET_SEL_TIME, IPMI_CMD_RESERVE_SEL, IPMI_CMD_SET_SEL_TIME,
+};
+use crate::interface::dummy::{DummyRequest, DummyResponse};
+use crate::interface::open::open::ipmi_csum;
+use crate::ipmi::ipmi::{
+    IPMI_NETFN_APP, IPMI_NETFN_CHASSIS, IPMI_NETFN_SE, IPMI_NETFN_STORAGE, IPMI_NETFN_TRANSPORT,
+    IPMI_REMOTE_SWID,
+};
+use crate::log_debug;
+use std::collections::BTreeMap;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// 完成码
+const CC_OK: u8 = 0x00;
+const CC_PARAM_NOT_SUPPORTED: u8 = 0x80;
+const CC_PASSWORD_MISMATCH: u8 = 0x80;
+const CC_PASSWORD_MISMATCH_20: u8 = 0x81;
+const CC_SET_IN_PROGRESS: u8 = 0x81;
+const CC_WDT_NOT_INITIALIZED: u8 = 0x80;
+const CC_INV_CMD: u8 = 0xc1;
+const CC_TIMEOUT: u8 = 0xc3;
+const CC_RES_CANCELED: u8 = 0xc5;
+const CC_REQ_DATA_INV_LENGTH: u8 = 0xc7;
+const CC_PARAM_OUT_OF_RANGE: u8 = 0xc9;
+const CC_REQ_DATA_NOT_PRESENT: u8 = 0xcb;
+const CC_INV_DATA_FIELD_IN_REQ: u8 = 0xcc;
+
+const BMC_GET_DEVICE_ID: u8 = 0x01;
+const BMC_GET_SELF_TEST: u8 = 0x04;
+const IPMI_SET_ACPI_POWER_STATE: u8 = 0x06;
+const IPMI_GET_ACPI_POWER_STATE: u8 = 0x07;
+const BMC_SET_GLOBAL_ENABLES: u8 = 0x2e;
+const BMC_GET_GLOBAL_ENABLES: u8 = 0x2f;
+const BMC_GET_GUID: u8 = 0x37;
+const BMC_RESET_WATCHDOG_TIMER: u8 = 0x22;
+const BMC_SET_WATCHDOG_TIMER: u8 = 0x24;
+const BMC_GET_WATCHDOG_TIMER: u8 = 0x25;
+const IPMI_SEND_MSG: u8 = 0x34;
+const IPMI_GET_CHANNEL_AUTH_CAP: u8 = 0x38;
+const IPMI_GET_CHANNEL_ACCESS: u8 = 0x41;
+const IPMI_GET_CHANNEL_INFO: u8 = 0x42;
+const IPMI_SET_USER_ACCESS: u8 = 0x43;
+const IPMI_GET_USER_ACCESS: u8 = 0x44;
+const IPMI_SET_USER_NAME: u8 = 0x45;
+const IPMI_GET_USER_NAME: u8 = 0x46;
+const IPMI_SET_USER_PASSWORD: u8 = 0x47;
+const IPMI_SET_SYS_INFO: u8 = 0x58;
+const IPMI_GET_CHANNEL_CIPHER_SUITES: u8 = 0x54;
+const IPMI_GET_SYS_INFO: u8 = 0x59;
+
+const CHASSIS_GET_STATUS: u8 = 0x01;
+const CHASSIS_CONTROL: u8 = 0x02;
+const CHASSIS_IDENTIFY: u8 = 0x04;
+const CHASSIS_SET_POLICY: u8 = 0x06;
+const CHASSIS_GET_RESTART_CAUSE: u8 = 0x07;
+const CHASSIS_SET_BOOT_OPTIONS: u8 = 0x08;
+const CHASSIS_GET_BOOT_OPTIONS: u8 = 0x09;
+const CHASSIS_GET_POH: u8 = 0x0f;
+
+const GET_FRU_INFO: u8 = 0x10;
+const READ_FRU_DATA: u8 = 0x11;
+const WRITE_FRU_DATA: u8 = 0x12;
+const RESERVE_SDR_REPO: u8 = 0x22;
+
+const IPMI_SET_LAN_CONFIG: u8 = 0x01;
+const IPMI_GET_LAN_CONFIG: u8 = 0x02;
+
+pub const SIM_MAX_USERS: u8 = 10;
+// 通道 1 为 LAN，0x0f 为系统接口，0x0e 表示当前通道
+const SIM_LAN_CHANNEL: u8 = 0x01;
+const SIM_SYSTEM_CHANNEL: u8 = 0x0f;
+const SIM_CURRENT_CHANNEL: u8 = 0x0e;
+const SIM_VENDOR_ID: [u8; 3] = [0xf2, 0x1b, 0x00];
+// 套件 3 (SHA1) 与 17 (SHA256)，均为 AES-CBC-128
+const SIM_CIPHER_SUITES: [u8; 10] = [0xc0, 0x03, 0x01, 0x41, 0x81, 0xc0, 0x11, 0x03, 0x44, 0x81];
+const SIM_SEL_CAPACITY: usize = 512;
+const SIM_SDR_CAPACITY: u16 = 0x2000;
+
+// Get Sensor Thresholds 响应中的顺序
+const THRESHOLD_NAMES: [&str; 6] = ["lnc", "lcr", "lnr", "unc", "ucr", "unr"];
+
+/// 一次请求的处理结果
+#[derive(Debug, PartialEq, Eq)]
+pub enum SimReply {
+    Response(DummyResponse),
+    /// 故障注入：丢弃请求不应答
+    NoResponse,
+}
+
+struct SimSensor {
+    raw: u8,
+    unavailable: bool,
+    state: Vec<u8>,
+    /// LNC, LCR, LNR, UNC, UCR, UNR
+    thresholds: [Option<u8>; 6],
+    record: Option<SdrRecordFullSensor>,
+}
+
+impl SimSensor {
+    fn from_model(m: &SensorModel, record: Option<SdrRecordFullSensor>) -> Result<Self, String> {
+        let to_raw = |val: f64| {
+            record
+                .as_ref()
+                .and_then(|r| r.sdr_convert_sensor_value_to_raw(val))
+                .ok_or_else(|| {
+                    format!(
+                        "sensor 0x{:02x}: engineering values need a linear full sensor record",
+                        m.number
+                    )
+                })
+        };
+
+        let raw = match (m.raw, m.reading) {
+            (Some(raw), _) => raw,
+            (None, Some(val)) => to_raw(val)?,
+            (None, None) => 0,
+        };
+        let mut thresholds = [None; 6];
+        for (name, val) in &m.thresholds {
+            let idx = THRESHOLD_NAMES
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| format!("sensor 0x{:02x}: unknown threshold {}", m.number, name))?;
+            thresholds[idx] = Some(to_raw(*val)?);
+        }
+
+        Ok(SimSensor {
+            raw,
+            unavailable: m.unavailable,
+            state: m.state.clone(),
+            thresholds,
+            record,
+        })
+    }
+
+    /// 门限传感器按当前读数计算越限状态位
+    fn threshold_state(&self) -> u8 {
+        let Some(rec) = &self.record else {
+            return 0;
+        };
+        if !rec.cmn.is_threshold_sensor() {
+            return 0;
+        }
+        let val = rec.sdr_convert_sensor_reading(self.raw);
+        let mut state = 0u8;
+        for (i, t) in self.thresholds.iter().enumerate() {
+            let Some(t) = t else { continue };
+            let limit = rec.sdr_convert_sensor_reading(*t);
+            let crossed = if i < 3 { val <= limit } else { val >= limit };
+            if crossed {
+                state |= 1 << i;
+            }
+        }
+        state
+    }
+
+    fn reading(&self) -> Vec<u8> {
+        // 事件消息与扫描使能
+        let mut flags = 0xc0;
+        if self.unavailable {
+            flags |= 0x20;
+        }
+        let mut rsp = vec![self.raw, flags];
+        if self.state.is_empty() {
+            rsp.extend_from_slice(&[self.threshold_state(), 0x00]);
+        } else {
+            rsp.extend_from_slice(&self.state);
+        }
+        rsp
+    }
+}
+
+#[derive(Clone)]
+struct SimUser {
+    name: Vec<u8>,
+    password: Vec<u8>,
+    enabled: bool,
+    privilege: u8,
+    /// User Access 字节 bits 6:4：仅回拨、链路认证、IPMI 消息
+    access: u8,
+}
+
+struct ActiveFault {
+    fault: Fault,
+    seen: u32,
+}
+
+/// Watchdog Timer 状态，倒计时按真实时间流逝，在查询时结算
+#[derive(Default)]
+struct SimWatchdog {
+    initialized: bool,
+    timer_use: u8,
+    actions: u8,
+    pretimeout: u8,
+    expiration_flags: u8,
+    initial: u16,
+    started: Option<Instant>,
+}
+
+impl SimWatchdog {
+    fn present(&mut self) -> u16 {
+        let Some(start) = self.started else {
+            return self.initial;
+        };
+        let elapsed = (start.elapsed().as_millis() / 100).min(u16::MAX as u128) as u16;
+        let left = self.initial.saturating_sub(elapsed);
+        if left == 0 {
+            // 超时：记录 expiration flag 并停止计时器，不模拟复位动作本身
+            self.expiration_flags |= 1 << (self.timer_use & 0x07);
+            self.started = None;
+        }
+        left
+    }
+
+    fn get(&mut self) -> Vec<u8> {
+        let present = self.present();
+        let mut use_ = self.timer_use & !0x40;
+        if self.started.is_some() {
+            use_ |= 0x40;
+        }
+        let mut rsp = vec![use_, self.actions, self.pretimeout, self.expiration_flags];
+        rsp.extend_from_slice(&self.initial.to_le_bytes());
+        rsp.extend_from_slice(&present.to_le_bytes());
+        rsp
+    }
+
+    fn set(&mut self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        if d.len() < 6 {
+            return Err(CC_REQ_DATA_INV_LENGTH);
+        }
+        let initial = u16_at(d, 4);
+        if d[1] & 0x70 != 0 && d[2] as u32 * 10 > initial as u32 {
+            return Err(CC_INV_DATA_FIELD_IN_REQ);
+        }
+        self.initialized = true;
+        self.timer_use = d[0] & 0xbf;
+        self.actions = d[1] & 0x77;
+        self.pretimeout = d[2];
+        self.expiration_flags &= !d[3];
+        self.initial = initial;
+        // don't stop 位：运行中的计时器以新倒计时继续
+        self.started = if d[0] & 0x40 != 0 && self.started.is_some() {
+            Some(Instant::now())
+        } else {
+            None
+        };
+        Ok(vec![])
+    }
+
+    fn reset(&mut self) -> Result<Vec<u8>, u8> {
+        if !self.initialized {
+            return Err(CC_WDT_NOT_INITIALIZED);
+        }
+        self.started = Some(Instant::now());
+        Ok(vec![])
+    }
+}
+
+/// 模拟 BMC
+pub struct SimBmc {
+    device: DeviceModel,
+    sdr: Vec<Vec<u8>>,
+    sensors: BTreeMap<u8, SimSensor>,
+    sel: Vec<SelEntry>,
+    sel_time: Option<u32>,
+    sel_time_offset: i64,
+    sel_add_stamp: u32,
+    sel_erase_stamp: u32,
+    fru: BTreeMap<u8, Vec<u8>>,
+    users: BTreeMap<u8, SimUser>,
+    lan: LanParams,
+    chassis: ChassisModel,
+    sdr_reservation: u16,
+    sel_reservation: u16,
+    faults: Vec<ActiveFault>,
+    watchdog: SimWatchdog,
+    global_enables: u8,
+    /// System Info 字符串参数，按 (参数, 块号) 存放 16 字节块
+    sysinfo: BTreeMap<(u8, u8), Vec<u8>>,
+    sysinfo_set_state: u8,
+    acpi_state: [u8; 2],
+}
+
+fn now() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as u32)
+        .unwrap_or(0)
+}
+
+fn u16_at(data: &[u8], i: usize) -> u16 {
+    u16::from_le_bytes([data[i], data[i + 1]])
+}
+
+impl SimBmc {
+    pub fn new(model: SimModel) -> Result<Self, String> {
+        let mut full_sensors = BTreeMap::new();
+        for rec in &model.sdr {
+            let header =
+                SdrRecordHeader::from_le_bytes(rec).map_err(|e| format!("SDR record: {}", e))?;
+            if header.length as usize != rec.len() - 5 {
+                return Err(format!(
+                    "SDR record 0x{:04x}: length byte {} but {} bytes follow the header",
+                    header.id,
+                    header.length,
+                    rec.len() - 5
+                ));
+            }
+            if header.record_type == SDR_RECORD_TYPE_FULL_SENSOR {
+                let full = SdrRecordFullSensor::from_le_bytes(&rec[5..])
+                    .map_err(|e| format!("SDR record 0x{:04x}: {}", header.id, e))?;
+                full_sensors.insert(full.cmn.keys.sensor_num, full);
+            }
+        }
+
+        let mut sensors = BTreeMap::new();
+        for m in &model.sensors {
+            let sensor = SimSensor::from_model(m, full_sensors.remove(&m.number))?;
+            sensors.insert(m.number, sensor);
+        }
+
+        if model.sel.len() > SIM_SEL_CAPACITY {
+            return Err(format!(
+                "SEL holds at most {} records, model has {}",
+                SIM_SEL_CAPACITY,
+                model.sel.len()
+            ));
+        }
+        let mut sel = Vec::with_capacity(model.sel.len());
+        for raw in &model.sel {
+            if raw.len() != 16 {
+                return Err(format!("SEL record must be 16 bytes, got {}", raw.len()));
+            }
+            sel.push(SelEntry::from_raw_bytes(raw));
+        }
+
+        if model.device.guid.len() != 16 || model.device.self_test.len() != 2 {
+            return Err("device guid must be 16 bytes and self_test 2 bytes".to_string());
+        }
+
+        let mut users = BTreeMap::new();
+        for u in &model.users {
+            if u.id == 0 || u.id > SIM_MAX_USERS || u.name.len() > 16 || u.password.len() > 20 {
+                return Err(format!("invalid user entry {}", u.id));
+            }
+            users.insert(
+                u.id,
+                SimUser {
+                    name: u.name.as_bytes().to_vec(),
+                    password: u.password.as_bytes().to_vec(),
+                    enabled: u.enabled,
+                    privilege: u.privilege & 0x0f,
+                    access: 0x10,
+                },
+            );
+        }
+
+        Ok(SimBmc {
+            device: model.device,
+            sdr: model.sdr,
+            sensors,
+            sel,
+            sel_time: model.sel_time,
+            sel_time_offset: 0,
+            sel_add_stamp: 0,
+            sel_erase_stamp: 0,
+            fru: model.fru,
+            users,
+            lan: model.lan,
+            chassis: model.chassis,
+            sdr_reservation: 0,
+            sel_reservation: 0,
+            faults: model
+                .faults
+                .into_iter()
+                .map(|fault| ActiveFault { fault, seen: 0 })
+                .collect(),
+            watchdog: SimWatchdog::default(),
+            // Event Message Buffer 与 System Event Logging
+            global_enables: 0x0c,
+            sysinfo: BTreeMap::new(),
+            sysinfo_set_state: 0,
+            acpi_state: [0x00, 0x00],
+        })
+    }
+
+    /// 按用户名查找用户，返回 (用户 ID, 密码, 权限上限)
+    pub fn find_user(&self, name: &str) -> Option<(u8, Vec<u8>, u8)> {
+        self.users
+            .iter()
+            .find(|(_, u)| u.enabled && u.name == name.as_bytes())
+            .map(|(id, u)| (*id, u.password.clone(), u.privilege))
+    }
+
+    /// 厂商号，ASF pong 中也会用到
+    pub fn manufacturer_id(&self) -> u32 {
+        self.device.manufacturer_id
+    }
+
+    pub fn handle(&mut self, req: &DummyRequest) -> SimReply {
+        if let Some(action) = self.match_fault(req) {
+            log_debug!(
+                "Injecting {:?} for netfn 0x{:02x} cmd 0x{:02x}",
+                action,
+                req.netfn,
+                req.cmd
+            );
+            return match action {
+                FaultAction::Ccode { ccode } => {
+                    SimReply::Response(DummyResponse::reply(req, ccode, &[]))
+                }
+                FaultAction::Timeout => SimReply::NoResponse,
+                FaultAction::CancelReservation => {
+                    self.sdr_reservation = self.sdr_reservation.wrapping_add(1);
+                    self.sel_reservation = self.sel_reservation.wrapping_add(1);
+                    SimReply::Response(DummyResponse::reply(req, CC_RES_CANCELED, &[]))
+                }
+            };
+        }
+
+        let d = &req.data;
+        let result = match req.netfn {
+            IPMI_NETFN_APP => self.handle_app(req.cmd, d),
+            IPMI_NETFN_CHASSIS => self.handle_chassis(req.cmd, d),
+            IPMI_NETFN_SE => self.handle_sensor(req.cmd, d),
+            IPMI_NETFN_STORAGE => self.handle_storage(req.cmd, d),
+            IPMI_NETFN_TRANSPORT => self.handle_transport(req.cmd, d),
+            _ => Err(CC_INV_CMD),
+        };
+        let rsp = match result {
+            Ok(data) => DummyResponse::reply(req, CC_OK, &data),
+            Err(cc) => DummyResponse::reply(req, cc, &[]),
+        };
+        SimReply::Response(rsp)
+    }
+
+    fn match_fault(&mut self, req: &DummyRequest) -> Option<FaultAction> {
+        for f in self.faults.iter_mut() {
+            if f.fault.netfn != req.netfn || f.fault.cmd != req.cmd {
+                continue;
+            }
+            f.seen += 1;
+            if f.seen <= f.fault.skip {
+                continue;
+            }
+            if let Some(count) = f.fault.count {
+                if f.seen - f.fault.skip > count {
+                    continue;
+                }
+            }
+            return Some(f.fault.action.clone());
+        }
+        None
+    }
+
+    /// Get System Info Parameters：参数 1..=7 为字符串，未写入的块全为 0
+    fn get_sysinfo(&self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        if d.len() < 4 {
+            return Err(CC_REQ_DATA_INV_LENGTH);
+        }
+        let mut rsp = vec![0x11];
+        if d[0] & 0x80 != 0 {
+            return Ok(rsp);
+        }
+        match d[1] {
+            0 => rsp.push(self.sysinfo_set_state),
+            1..=7 => {
+                rsp.push(d[2]);
+                match self.sysinfo.get(&(d[1], d[2])) {
+                    Some(block) => rsp.extend_from_slice(block),
+                    None => rsp.extend_from_slice(&[0; 16]),
+                }
+            }
+            _ => return Err(CC_PARAM_NOT_SUPPORTED),
+        }
+        Ok(rsp)
+    }
+
+    fn set_sysinfo(&mut self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        match *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)? {
+            0 => {
+                let state = *d.get(1).ok_or(CC_REQ_DATA_INV_LENGTH)? & 0x03;
+                if state == 1 && self.sysinfo_set_state == 1 {
+                    return Err(CC_SET_IN_PROGRESS);
+                }
+                self.sysinfo_set_state = state;
+            }
+            param @ 1..=7 => {
+                if d.len() != 18 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                self.sysinfo.insert((param, d[1]), d[2..].to_vec());
+            }
+            _ => return Err(CC_PARAM_NOT_SUPPORTED),
+        }
+        Ok(vec![])
+    }
+
+    fn handle_app(&mut self, cmd: u8, d: &[u8]) -> Result<Vec<u8>, u8> {
+        match cmd {
+            BMC_GET_DEVICE_ID => {
+                let dev = &self.device;
+                let mfg = dev.manufacturer_id.to_le_bytes();
+                let mut rsp = vec![
+                    dev.device_id,
+                    // 置位表示提供 Device SDR
+                    dev.device_revision | 0x80,
+                    dev.firmware_major & 0x7f,
+                    dev.firmware_minor,
+                    dev.ipmi_version,
+                    dev.additional_support,
+                    mfg[0],
+                    mfg[1],
+                    mfg[2],
+                ];
+                rsp.extend_from_slice(&dev.product_id.to_le_bytes());
+                rsp.extend_from_slice(&dev.aux);
+                Ok(rsp)
+            }
+            BMC_GET_SELF_TEST => Ok(self.device.self_test.clone()),
+            BMC_GET_GUID => Ok(self.device.guid.clone()),
+            IPMI_GET_SYS_INFO => self.get_sysinfo(d),
+            IPMI_SET_SYS_INFO => self.set_sysinfo(d),
+            IPMI_GET_ACPI_POWER_STATE => Ok(self.acpi_state.to_vec()),
+            IPMI_SET_ACPI_POWER_STATE => {
+                if d.len() < 2 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                for (state, req) in self.acpi_state.iter_mut().zip(d) {
+                    if req & 0x80 != 0 {
+                        *state = req & 0x7f;
+                    }
+                }
+                Ok(vec![])
+            }
+            BMC_GET_GLOBAL_ENABLES => Ok(vec![self.global_enables]),
+            BMC_SET_GLOBAL_ENABLES => {
+                let enables = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)?;
+                // bit 4 保留
+                if enables & 0x10 != 0 {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                self.global_enables = enables;
+                Ok(vec![])
+            }
+            BMC_RESET_WATCHDOG_TIMER => self.watchdog.reset(),
+            BMC_SET_WATCHDOG_TIMER => self.watchdog.set(d),
+            BMC_GET_WATCHDOG_TIMER => Ok(self.watchdog.get()),
+            IPMI_SEND_MSG => self.send_message(d),
+            IPMI_GET_CHANNEL_AUTH_CAP => {
+                let req = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)?;
+                Self::channel_number(req & 0x0f)?;
+                // NONE, MD2, MD5, PASSWORD；启用非空用户，只支持 v1.5
+                let v2 = req & 0x80;
+                Ok(vec![SIM_LAN_CHANNEL, v2 | 0x17, 0x04, 0x01, 0, 0, 0, 0])
+            }
+            IPMI_GET_CHANNEL_INFO => {
+                let channel = Self::channel_number(*d.first().ok_or(CC_REQ_DATA_INV_LENGTH)?)?;
+                let mut rsp = if channel == SIM_LAN_CHANNEL {
+                    vec![channel, 0x04, 0x01, 0x80]
+                } else {
+                    vec![channel, 0x0c, 0x05, 0x00]
+                };
+                rsp.extend_from_slice(&SIM_VENDOR_ID);
+                rsp.extend_from_slice(&[0, 0]);
+                Ok(rsp)
+            }
+            IPMI_GET_CHANNEL_ACCESS => {
+                if d.len() < 2 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                if Self::channel_number(d[0])? != SIM_LAN_CHANNEL {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                // 告警禁用，始终可用；权限上限 ADMINISTRATOR
+                Ok(vec![0x22, 0x04])
+            }
+            IPMI_GET_CHANNEL_CIPHER_SUITES => {
+                if d.len() < 3 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                if Self::channel_number(d[0])? != SIM_LAN_CHANNEL || d[1] > 1 {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                let start = (d[2] & 0x3f) as usize * 16;
+                let mut rsp = vec![SIM_LAN_CHANNEL];
+                rsp.extend(SIM_CIPHER_SUITES.iter().skip(start).take(16));
+                Ok(rsp)
+            }
+            IPMI_GET_USER_ACCESS => {
+                let uid = *d.get(1).ok_or(CC_REQ_DATA_INV_LENGTH)? & 0x3f;
+                if uid == 0 || uid > SIM_MAX_USERS {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                let enabled = self.users.values().filter(|u| u.enabled).count() as u8;
+                let access = match self.users.get(&uid) {
+                    Some(u) => u.access | u.privilege,
+                    None => 0x0f,
+                };
+                // 用户 1 为固定名称的匿名用户
+                Ok(vec![SIM_MAX_USERS, 0x40 | enabled, 0x01, access])
+            }
+            IPMI_SET_USER_ACCESS => {
+                if d.len() < 3 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let uid = d[1] & 0x3f;
+                if uid == 0 || uid > SIM_MAX_USERS {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                let user = self.user_mut(uid);
+                user.privilege = d[2] & 0x0f;
+                if d[0] & 0x80 != 0 {
+                    user.access = d[0] & 0x70;
+                }
+                Ok(Vec::new())
+            }
+            IPMI_GET_USER_NAME => {
+                let uid = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)? & 0x3f;
+                if uid == 0 || uid > SIM_MAX_USERS {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                let mut name = vec![0u8; 16];
+                if let Some(u) = self.users.get(&uid) {
+                    name[..u.name.len()].copy_from_slice(&u.name);
+                }
+                Ok(name)
+            }
+            IPMI_SET_USER_NAME => {
+                if d.len() < 17 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let uid = d[0] & 0x3f;
+                if uid <= 1 || uid > SIM_MAX_USERS {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                let end = d[1..17].iter().position(|&b| b == 0).unwrap_or(16);
+                // 按原始字节保存，非 UTF-8 的名字也不会超过 16 字节
+                self.user_mut(uid).name = d[1..1 + end].to_vec();
+                Ok(Vec::new())
+            }
+            IPMI_SET_USER_PASSWORD => self.set_user_password(d),
+            _ => Err(CC_INV_CMD),
+        }
+    }
+
+    /// Send Message：内嵌请求交给同一个模型处理，应答直接内嵌在数据中返回
+    ///
+    /// 所有 IPMB 地址都视为本 BMC，嵌套的 Send Message 即双重桥接。
+    fn send_message(&mut self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        // channel, rsSA, netFn/rsLUN, csum, rqSA, rqSeq/rqLUN, cmd, data.., csum
+        if d.len() < 8 {
+            return Err(CC_REQ_DATA_INV_LENGTH);
+        }
+        let msg = &d[1..];
+        if ipmi_csum(&msg[..2]) != msg[2] || ipmi_csum(&msg[3..]) != 0 {
+            return Err(CC_INV_DATA_FIELD_IN_REQ);
+        }
+        let req = DummyRequest {
+            netfn: msg[1] >> 2,
+            lun: msg[1] & 0x03,
+            cmd: msg[5],
+            target_cmd: msg[5],
+            data: msg[6..msg.len() - 1].to_vec(),
+        };
+        let rsp = match self.handle(&req) {
+            SimReply::Response(rsp) => rsp,
+            SimReply::NoResponse => return Err(CC_TIMEOUT),
+        };
+
+        let mut out = vec![msg[3], (rsp.netfn << 2) | (msg[4] & 0x03)];
+        out.push(ipmi_csum(&out));
+        out.extend_from_slice(&[msg[0], (msg[4] & 0xfc) | rsp.lun, rsp.cmd, rsp.ccode]);
+        out.extend_from_slice(&rsp.data);
+        out.push(ipmi_csum(&out[3..]));
+        Ok(out)
+    }
+
+    /// 解析请求中的通道号，只有 LAN 与系统接口两个通道
+    fn channel_number(channel: u8) -> Result<u8, u8> {
+        match channel & 0x0f {
+            SIM_CURRENT_CHANNEL => Ok(SIM_LAN_CHANNEL),
+            ch @ (SIM_LAN_CHANNEL | SIM_SYSTEM_CHANNEL) => Ok(ch),
+            _ => Err(CC_INV_DATA_FIELD_IN_REQ),
+        }
+    }
+
+    fn user_mut(&mut self, uid: u8) -> &mut SimUser {
+        self.users.entry(uid).or_insert_with(|| SimUser {
+            name: Vec::new(),
+            password: Vec::new(),
+            enabled: false,
+            privilege: 0x0f,
+            access: 0x10,
+        })
+    }
+
+    fn set_user_password(&mut self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        if d.len() < 2 {
+            return Err(CC_REQ_DATA_INV_LENGTH);
+        }
+        let uid = d[0] & 0x3f;
+        let is20 = d[0] & 0x80 != 0;
+        if uid == 0 || uid > SIM_MAX_USERS {
+            return Err(CC_INV_DATA_FIELD_IN_REQ);
+        }
+        let size = if is20 { 20 } else { 16 };
+        let password = || -> Result<Vec<u8>, u8> {
+            let pw = d.get(2..2 + size).ok_or(CC_REQ_DATA_INV_LENGTH)?;
+            let end = pw.iter().position(|&b| b == 0).unwrap_or(size);
+            Ok(pw[..end].to_vec())
+        };
+
+        match d[1] & 0x03 {
+            0x00 => self.user_mut(uid).enabled = false,
+            0x01 => self.user_mut(uid).enabled = true,
+            0x02 => self.user_mut(uid).password = password()?,
+            _ => {
+                let stored = self.users.get(&uid).map(|u| u.password.clone());
+                if stored.as_deref() != Some(password()?.as_slice()) {
+                    return Err(if is20 {
+                        CC_PASSWORD_MISMATCH_20
+                    } else {
+                        CC_PASSWORD_MISMATCH
+                    });
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    fn handle_chassis(&mut self, cmd: u8, d: &[u8]) -> Result<Vec<u8>, u8> {
+        match cmd {
+            CHASSIS_GET_STATUS => {
+                let power = self.chassis.power_on as u8 | (self.chassis.policy & 0x03) << 5;
+                Ok(vec![power, 0x00, 0x00, 0x00])
+            }
+            CHASSIS_CONTROL => {
+                match *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)? {
+                    0x00 | 0x05 => self.chassis.power_on = false,
+                    0x01..=0x03 => self.chassis.power_on = true,
+                    0x04 => {}
+                    _ => return Err(CC_INV_DATA_FIELD_IN_REQ),
+                }
+                Ok(Vec::new())
+            }
+            CHASSIS_IDENTIFY => Ok(Vec::new()),
+            CHASSIS_SET_POLICY => {
+                let policy = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)? & 0x07;
+                match policy {
+                    0..=2 => self.chassis.policy = policy,
+                    3 => {}
+                    _ => return Err(CC_INV_DATA_FIELD_IN_REQ),
+                }
+                Ok(vec![0x07])
+            }
+            CHASSIS_GET_RESTART_CAUSE => Ok(vec![self.chassis.restart_cause & 0x0f, 0x00]),
+            CHASSIS_GET_POH => {
+                let mut rsp = vec![60];
+                rsp.extend_from_slice(&self.chassis.poh_hours.to_le_bytes());
+                Ok(rsp)
+            }
+            CHASSIS_SET_BOOT_OPTIONS => {
+                let param = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)? & 0x7f;
+                self.chassis.boot_params.insert(param, d[1..].to_vec());
+                Ok(Vec::new())
+            }
+            CHASSIS_GET_BOOT_OPTIONS => {
+                let param = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)? & 0x7f;
+                let data = self
+                    .chassis
+                    .boot_params
+                    .get(&param)
+                    .ok_or(CC_PARAM_NOT_SUPPORTED)?;
+                let mut rsp = vec![0x01, param];
+                rsp.extend_from_slice(data);
+                Ok(rsp)
+            }
+            _ => Err(CC_INV_CMD),
+        }
+    }
+
+    fn handle_sensor(&mut self, cmd: u8, d: &[u8]) -> Result<Vec<u8>, u8> {
+        if cmd == IPMI_PLATFORM_EVENT {
+            return self.platform_event(d);
+        }
+        let num = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)?;
+        let sensor = self.sensors.get_mut(&num).ok_or(CC_REQ_DATA_NOT_PRESENT)?;
+        match cmd {
+            GET_SENSOR_READING => Ok(sensor.reading()),
+            GET_SENSOR_THRESHOLDS => {
+                let mut mask = 0u8;
+                let mut rsp = vec![0u8];
+                for (i, t) in sensor.thresholds.iter().enumerate() {
+                    if t.is_some() {
+                        mask |= 1 << i;
+                    }
+                    rsp.push(t.unwrap_or(0));
+                }
+                rsp[0] = mask;
+                Ok(rsp)
+            }
+            SET_SENSOR_THRESHOLDS => {
+                if d.len() < 8 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                for i in 0..6 {
+                    if d[1] & (1 << i) != 0 {
+                        sensor.thresholds[i] = Some(d[2 + i]);
+                    }
+                }
+                Ok(Vec::new())
+            }
+            GET_SENSOR_HYSTERESIS => Ok(vec![0x00, 0x00]),
+            GET_SENSOR_EVENT_ENABLE | GET_SENSOR_EVENT_STATUS => {
+                Ok(vec![0xc0, 0x00, 0x00, 0x00, 0x00])
+            }
+            _ => Err(CC_INV_CMD),
+        }
+    }
+
+    /// Platform Event Message：作为系统事件记录写入 SEL
+    ///
+    /// 系统接口上的请求以 Generator ID 开头 (8 字节)，其他通道由请求者地址产生。
+    fn platform_event(&mut self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        let (gen_id, event) = match d.len() {
+            8 => (d[0], &d[1..]),
+            7 => (IPMI_REMOTE_SWID, d),
+            _ => return Err(CC_REQ_DATA_INV_LENGTH),
+        };
+        let mut raw = [0u8; 16];
+        raw[2] = 0x02; // system event record
+        raw[7] = gen_id;
+        raw[9..].copy_from_slice(event);
+        self.sel_append(&raw)?;
+        Ok(Vec::new())
+    }
+
+    /// 追加一条 SEL 记录，由 BMC 分配记录号与时间戳
+    fn sel_append(&mut self, raw: &[u8]) -> Result<u16, u8> {
+        if self.sel.len() >= SIM_SEL_CAPACITY {
+            return Err(0x80);
+        }
+        let mut entry = SelEntry::from_raw_bytes(raw);
+        entry.record_id = self.sel.iter().map(|e| e.record_id).max().unwrap_or(0) + 1;
+        // 带时间戳的记录由 BMC 填写时间
+        if entry.record_type < 0xe0 {
+            entry.data[..4].copy_from_slice(&self.sel_now().to_le_bytes());
+        }
+        self.sel_add_stamp = self.sel_now();
+        let id = entry.record_id;
+        self.sel.push(entry);
+        Ok(id)
+    }
+
+    fn handle_storage(&mut self, cmd: u8, d: &[u8]) -> Result<Vec<u8>, u8> {
+        match cmd {
+            GET_FRU_INFO => {
+                let id = *d.first().ok_or(CC_REQ_DATA_INV_LENGTH)?;
+                let fru = self.fru.get(&id).ok_or(CC_REQ_DATA_NOT_PRESENT)?;
+                let size = (fru.len() as u16).to_le_bytes();
+                Ok(vec![size[0], size[1], 0x00])
+            }
+            READ_FRU_DATA => {
+                if d.len() < 4 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let fru = self.fru.get(&d[0]).ok_or(CC_REQ_DATA_NOT_PRESENT)?;
+                let offset = u16_at(d, 1) as usize;
+                if offset >= fru.len() {
+                    return Err(CC_PARAM_OUT_OF_RANGE);
+                }
+                let end = (offset + d[3] as usize).min(fru.len());
+                let mut rsp = vec![(end - offset) as u8];
+                rsp.extend_from_slice(&fru[offset..end]);
+                Ok(rsp)
+            }
+            WRITE_FRU_DATA => {
+                if d.len() < 3 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let fru = self.fru.get_mut(&d[0]).ok_or(CC_REQ_DATA_NOT_PRESENT)?;
+                let offset = u16_at(d, 1) as usize;
+                let data = &d[3..];
+                if offset + data.len() > fru.len() {
+                    return Err(CC_PARAM_OUT_OF_RANGE);
+                }
+                fru[offset..offset + data.len()].copy_from_slice(data);
+                Ok(vec![data.len() as u8])
+            }
+            GET_SDR_REPO_INFO => {
+                let used: usize = self.sdr.iter().map(|r| r.len()).sum();
+                let free = SIM_SDR_CAPACITY.saturating_sub(used as u16);
+                let mut rsp = vec![0x51];
+                rsp.extend_from_slice(&(self.sdr.len() as u16).to_le_bytes());
+                rsp.extend_from_slice(&free.to_le_bytes());
+                rsp.extend_from_slice(&[0; 8]);
+                // 支持 Reserve SDR Repository
+                rsp.push(0x02);
+                Ok(rsp)
+            }
+            RESERVE_SDR_REPO => {
+                self.sdr_reservation = self.sdr_reservation.wrapping_add(1).max(1);
+                Ok(self.sdr_reservation.to_le_bytes().to_vec())
+            }
+            GET_SDR => self.get_sdr(d),
+            IPMI_CMD_GET_SEL_INFO => {
+                let mut rsp = vec![0x51];
+                rsp.extend_from_slice(&(self.sel.len() as u16).to_le_bytes());
+                let free = (SIM_SEL_CAPACITY - self.sel.len()) * 16;
+                rsp.extend_from_slice(&(free as u16).to_le_bytes());
+                rsp.extend_from_slice(&self.sel_add_stamp.to_le_bytes());
+                rsp.extend_from_slice(&self.sel_erase_stamp.to_le_bytes());
+                // 支持 Delete SEL 与 Reserve SEL
+                rsp.push(0x0a);
+                Ok(rsp)
+            }
+            IPMI_CMD_GET_SEL_ALLOC_INFO => {
+                let free = (SIM_SEL_CAPACITY - self.sel.len()) as u16;
+                let mut rsp = Vec::new();
+                rsp.extend_from_slice(&(SIM_SEL_CAPACITY as u16).to_le_bytes());
+                rsp.extend_from_slice(&16u16.to_le_bytes());
+                rsp.extend_from_slice(&free.to_le_bytes());
+                rsp.extend_from_slice(&free.to_le_bytes());
+                rsp.push(1);
+                Ok(rsp)
+            }
+            IPMI_CMD_RESERVE_SEL => {
+                self.sel_reservation = self.sel_reservation.wrapping_add(1).max(1);
+                Ok(self.sel_reservation.to_le_bytes().to_vec())
+            }
+            IPMI_CMD_GET_SEL_ENTRY => self.get_sel_entry(d),
+            IPMI_CMD_ADD_SEL_ENTRY => {
+                if d.len() < 16 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let id = self.sel_append(d)?;
+                Ok(id.to_le_bytes().to_vec())
+            }
+            IPMI_CMD_DELETE_SEL_ENTRY => {
+                if d.len() < 4 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                self.check_sel_reservation(u16_at(d, 0))?;
+                let idx = self.sel_index(u16_at(d, 2))?;
+                let id = self.sel.remove(idx).record_id;
+                self.sel_erase_stamp = self.sel_now();
+                Ok(id.to_le_bytes().to_vec())
+            }
+            IPMI_CMD_CLEAR_SEL => {
+                if d.len() < 6 || &d[2..5] != b"CLR" {
+                    return Err(CC_INV_DATA_FIELD_IN_REQ);
+                }
+                self.check_sel_reservation(u16_at(d, 0))?;
+                if d[5] == 0xaa {
+                    self.sel.clear();
+                    self.sel_erase_stamp = self.sel_now();
+                }
+                // 擦除立即完成
+                Ok(vec![0x01])
+            }
+            IPMI_CMD_GET_SEL_TIME => Ok(self.sel_now().to_le_bytes().to_vec()),
+            IPMI_CMD_SET_SEL_TIME => {
+                if d.len() < 4 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let t = u32::from_le_bytes([d[0], d[1], d[2], d[3]]);
+                match self.sel_time {
+                    Some(_) => self.sel_time = Some(t),
+                    None => self.sel_time_offset = t as i64 - now() as i64,
+                }
+                Ok(Vec::new())
+            }
+            _ => Err(CC_INV_CMD),
+        }
+    }
+
+    fn sel_now(&self) -> u32 {
+        self.sel_time
+            .unwrap_or_else(|| (now() as i64 + self.sel_time_offset) as u32)
+    }
+
+    /// 0 不是有效的预留号，未预留时任何需要预留的请求都被拒绝
+    fn check_sel_reservation(&self, id: u16) -> Result<(), u8> {
+        if id == 0 || id != self.sel_reservation {
+            return Err(CC_RES_CANCELED);
+        }
+        Ok(())
+    }
+
+    /// 0x0000 表示第一条，0xFFFF 表示最后一条
+    fn sel_index(&self, id: u16) -> Result<usize, u8> {
+        if self.sel.is_empty() {
+            return Err(CC_REQ_DATA_NOT_PRESENT);
+        }
+        match id {
+            0x0000 => Ok(0),
+            0xffff => Ok(self.sel.len() - 1),
+            _ => self
+                .sel
+                .iter()
+                .position(|e| e.record_id == id)
+                .ok_or(CC_REQ_DATA_NOT_PRESENT),
+        }
+    }
+
+    fn get_sel_entry(&self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        if d.len() < 6 {
+            return Err(CC_REQ_DATA_INV_LENGTH);
+        }
+        let (offset, len) = (d[4] as usize, d[5]);
+        // 只有部分读取才要求预留
+        if offset != 0 {
+            self.check_sel_reservation(u16_at(d, 0))?;
+        }
+        let idx = self.sel_index(u16_at(d, 2))?;
+        let next = self.sel.get(idx + 1).map_or(0xffff, |e| e.record_id);
+        let raw = self.sel[idx].to_raw_bytes();
+        let end = if len == 0xff {
+            16
+        } else {
+            (offset + len as usize).min(16)
+        };
+        if offset > end {
+            return Err(CC_PARAM_OUT_OF_RANGE);
+        }
+        let mut rsp = next.to_le_bytes().to_vec();
+        rsp.extend_from_slice(&raw[offset..end]);
+        Ok(rsp)
+    }
+
+    fn get_sdr(&self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        if d.len() < 6 {
+            return Err(CC_REQ_DATA_INV_LENGTH);
+        }
+        let (id, offset, len) = (u16_at(d, 2), d[4] as usize, d[5]);
+        if offset != 0 && u16_at(d, 0) != self.sdr_reservation {
+            return Err(CC_RES_CANCELED);
+        }
+        let idx = match id {
+            0x0000 if !self.sdr.is_empty() => 0,
+            _ => self
+                .sdr
+                .iter()
+                .position(|r| u16_at(r, 0) == id)
+                .ok_or(CC_REQ_DATA_NOT_PRESENT)?,
+        };
+        let rec = &self.sdr[idx];
+        let next = self.sdr.get(idx + 1).map_or(0xffff, |r| u16_at(r, 0));
+        let end = if len == 0xff {
+            rec.len()
+        } else {
+            (offset + len as usize).min(rec.len())
+        };
+        if offset > end {
+            return Err(CC_PARAM_OUT_OF_RANGE);
+        }
+        let mut rsp = next.to_le_bytes().to_vec();
+        rsp.extend_from_slice(&rec[offset..end]);
+        Ok(rsp)
+    }
+
+    fn handle_transport(&mut self, cmd: u8, d: &[u8]) -> Result<Vec<u8>, u8> {
+        match cmd {
+            IPMI_GET_LAN_CONFIG => {
+                if d.len() < 4 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let params = self
+                    .lan
+                    .get(&(d[0] & 0x0f))
+                    .ok_or(CC_INV_DATA_FIELD_IN_REQ)?;
+                // 参数版本 1.1
+                let mut rsp = vec![0x11];
+                if d[0] & 0x80 == 0 {
+                    rsp.extend_from_slice(params.get(&d[1]).ok_or(CC_PARAM_NOT_SUPPORTED)?);
+                }
+                Ok(rsp)
+            }
+            IPMI_SET_LAN_CONFIG => {
+                if d.len() < 3 {
+                    return Err(CC_REQ_DATA_INV_LENGTH);
+                }
+                let params = self
+                    .lan
+                    .get_mut(&(d[0] & 0x0f))
+                    .ok_or(CC_INV_DATA_FIELD_IN_REQ)?;
+                params.insert(d[1], d[2..].to_vec());
+                Ok(Vec::new())
+            }
+            _ => Err(CC_INV_CMD),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Full Sensor 记录：M=1, B=0，读数即原始值
+    const TEMP_SDR: &str = "0100 51 01 2f  20 00 30 07 01 7f 68 01 01  3f3f 3f3f 3f3f  00 01 00  \
+         00 01 00 00 00 00  00 00 00 00 00 ff 00  000000000000  0000 0000 00  c4 54656d70";
+
+    fn sim(extra: &str) -> SimBmc {
+        let json = format!(
+            r#"{{
+                "sdr": ["{}"],
+                "sensors": [{{ "number": "0x30", "reading": 45,
+                               "thresholds": {{ "unc": 40, "ucr": 50 }} }}],
+                "sel": ["0100 02 00000000 2000 04 01 30 01 51 00 00",
+                        "0200 02 00000000 2000 04 01 30 01 51 00 00"],
+                "users": [{{ "id": 2, "name": "admin", "password": "secret" }}]
+                {}
+            }}"#,
+            TEMP_SDR, extra
+        );
+        SimBmc::new(SimModel::from_json(&json).unwrap()).unwrap()
+    }
+
+    fn call(bmc: &mut SimBmc, netfn: u8, cmd: u8, data: &[u8]) -> DummyResponse {
+        let req = DummyRequest {
+            netfn,
+            cmd,
+            data: data.to_vec(),
+            ..Default::default()
+        };
+        match bmc.handle(&req) {
+            SimReply::Response(rsp) => rsp,
+            SimReply::NoResponse => panic!("no response"),
+        }
+    }
+
+    #[test]
+    fn test_sdr_and_sensor() {
+        let mut bmc = sim("");
+        let info = call(&mut bmc, IPMI_NETFN_STORAGE, GET_SDR_REPO_INFO, &[]);
+        assert_eq!(&info.data[..3], &[0x51, 0x01, 0x00]);
+
+        let rsv = call(&mut bmc, IPMI_NETFN_STORAGE, RESERVE_SDR_REPO, &[]).data;
+        let hdr = call(&mut bmc, IPMI_NETFN_STORAGE, GET_SDR, &[0, 0, 0, 0, 0, 5]);
+        assert_eq!(hdr.data, [0xff, 0xff, 0x01, 0x00, 0x51, 0x01, 0x2f]);
+        let body = call(
+            &mut bmc,
+            IPMI_NETFN_STORAGE,
+            GET_SDR,
+            &[rsv[0], rsv[1], 1, 0, 5, 3],
+        );
+        assert_eq!(body.data, [0xff, 0xff, 0x20, 0x00, 0x30]);
+        // 过期的预留
+        let stale = call(&mut bmc, IPMI_NETFN_STORAGE, GET_SDR, &[9, 9, 1, 0, 5, 3]);
+        assert_eq!(stale.ccode, CC_RES_CANCELED);
+
+        let rd = call(&mut bmc, IPMI_NETFN_SE, GET_SENSOR_READING, &[0x30]);
+        // 45 超过 UNC 但未到 UCR
+        assert_eq!(rd.data, [45, 0xc0, 0x08, 0x00]);
+        let th = call(&mut bmc, IPMI_NETFN_SE, GET_SENSOR_THRESHOLDS, &[0x30]);
+        assert_eq!(th.data, [0x18, 0, 0, 0, 40, 50, 0]);
+        call(
+            &mut bmc,
+            IPMI_NETFN_SE,
+            SET_SENSOR_THRESHOLDS,
+            &[0x30, 0x08, 0, 0, 0, 46, 0, 0],
+        );
+        let rd = call(&mut bmc, IPMI_NETFN_SE, GET_SENSOR_READING, &[0x30]);
+        assert_eq!(rd.data[2], 0x00);
+        let missing = call(&mut bmc, IPMI_NETFN_SE, GET_SENSOR_READING, &[0x31]);
+        assert_eq!(missing.ccode, CC_REQ_DATA_NOT_PRESENT);
+    }
+
+    #[test]
+    fn test_sel() {
+        let mut bmc = sim(r#", "sel_time": 1700000000"#);
+        let first = call(
+            &mut bmc,
+            IPMI_NETFN_STORAGE,
+            IPMI_CMD_GET_SEL_ENTRY,
+            &[0, 0, 0, 0, 0, 0xff],
+        );
+        assert_eq!(&first.data[..5], &[0x02, 0x00, 0x01, 0x00, 0x02]);
+
+        let mut add = vec![0u8; 16];
+        add[2] = 0x02;
+        let id = call(&mut bmc, IPMI_NETFN_STORAGE, IPMI_CMD_ADD_SEL_ENTRY, &add).data;
+        assert_eq!(id, [0x03, 0x00]);
+        let last = call(
+            &mut bmc,
+            IPMI_NETFN_STORAGE,
+            IPMI_CMD_GET_SEL_ENTRY,
+            &[0, 0, 0xff, 0xff, 0, 0xff],
+        );
+        assert_eq!(&last.data[5..9], &1700000000u32.to_le_bytes());
+
+        let del = call(
+            &mut bmc,
+            IPMI_NETFN_STORAGE,
+            IPMI_CMD_DELETE_SEL_ENTRY,
+            &[0, 0, 1, 0],
+        );
+        assert_eq!(del.ccode, CC_RES_CANCELED);
+        let rsv = call(&mut bmc, IPMI_NETFN_STORAGE, IPMI_CMD_RESERVE_SEL, &[]).data;
+        let del = call(
+            &mut bmc,
+            IPMI_NETFN_STORAGE,
+            IPMI_CMD_DELETE_SEL_ENTRY,
+            &[rsv[0], rsv[1], 1, 0],
+        );
+        assert_eq!(del.data, [0x01, 0x00]);
+        let info = call(&mut bmc, IPMI_NETFN_STORAGE, IPMI_CMD_GET_SEL_INFO, &[]);
+        assert_eq!(&info.data[1..3], &[0x02, 0x00]);
+
+        let clr = [rsv[0], rsv[1], b'C', b'L', b'R', 0xaa];
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_STORAGE, IPMI_CMD_CLEAR_SEL, &clr).data,
+            [0x01]
+        );
+        let empty = call(
+            &mut bmc,
+            IPMI_NETFN_STORAGE,
+            IPMI_CMD_GET_SEL_ENTRY,
+            &[0, 0, 0, 0, 0, 0xff],
+        );
+        assert_eq!(empty.ccode, CC_REQ_DATA_NOT_PRESENT);
+    }
+
+    #[test]
+    fn test_users() {
+        let mut bmc = sim("");
+        let name = call(&mut bmc, IPMI_NETFN_APP, IPMI_GET_USER_NAME, &[2]);
+        assert_eq!(&name.data[..6], b"admin\0");
+        let access = call(&mut bmc, IPMI_NETFN_APP, IPMI_GET_USER_ACCESS, &[1, 2]);
+        assert_eq!(access.data, [SIM_MAX_USERS, 0x41, 0x01, 0x14]);
+
+        let mut test = vec![2, 0x03];
+        test.extend_from_slice(&[0u8; 16]);
+        test[2..8].copy_from_slice(b"secret");
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, IPMI_SET_USER_PASSWORD, &test).ccode,
+            0
+        );
+        test[2] = b'S';
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, IPMI_SET_USER_PASSWORD, &test).ccode,
+            CC_PASSWORD_MISMATCH
+        );
+        assert_eq!(bmc.find_user("admin").unwrap().0, 2);
+
+        // 非 UTF-8 用户名按原始字节保存，读回时不会超过 16 字节
+        let mut set = vec![3u8];
+        set.extend_from_slice(&[0xff; 16]);
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, IPMI_SET_USER_NAME, &set).ccode,
+            0
+        );
+        let name = call(&mut bmc, IPMI_NETFN_APP, IPMI_GET_USER_NAME, &[3]);
+        assert_eq!(name.data, [0xff; 16]);
+    }
+
+    #[test]
+    fn test_oversized_sel_rejected() {
+        let sel = vec![r#""0100 02 00000000 2000 04 01 30 01 51 00 00""#; SIM_SEL_CAPACITY + 1];
+        let json = format!(r#"{{ "sel": [{}] }}"#, sel.join(","));
+        assert!(SimBmc::new(SimModel::from_json(&json).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_fault_injection() {
+        let mut bmc = sim(r#", "faults": [
+                { "netfn": 10, "cmd": "0x23", "action": "cancel_reservation", "skip": 1, "count": 1 },
+                { "netfn": 4, "cmd": "0x2d", "action": "timeout" },
+                { "netfn": 6, "cmd": 1, "action": "ccode", "ccode": "0xc3" }
+            ]"#);
+        let get = [0, 0, 0, 0, 0, 5];
+        assert_eq!(call(&mut bmc, IPMI_NETFN_STORAGE, GET_SDR, &get).ccode, 0);
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_STORAGE, GET_SDR, &get).ccode,
+            CC_RES_CANCELED
+        );
+        assert_eq!(call(&mut bmc, IPMI_NETFN_STORAGE, GET_SDR, &get).ccode, 0);
+
+        let req = DummyRequest {
+            netfn: IPMI_NETFN_SE,
+            cmd: GET_SENSOR_READING,
+            data: vec![0x30],
+            ..Default::default()
+        };
+        assert_eq!(bmc.handle(&req), SimReply::NoResponse);
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, BMC_GET_DEVICE_ID, &[]).ccode,
+            0xc3
+        );
+    }
+
+    #[test]
+    fn test_platform_event() {
+        let mut bmc = sim("");
+        // Platform Event Message 写入一条系统事件记录
+        let event = [0x41, 0x04, 0x01, 0x30, 0x01, 0x09, 0xff, 0xff];
+        let rsp = call(&mut bmc, IPMI_NETFN_SE, IPMI_PLATFORM_EVENT, &event);
+        assert_eq!(rsp.ccode, CC_OK);
+        let last = call(
+            &mut bmc,
+            IPMI_NETFN_STORAGE,
+            IPMI_CMD_GET_SEL_ENTRY,
+            &[0, 0, 0xff, 0xff, 0, 0xff],
+        );
+        assert_eq!(&last.data[2..5], &[0x03, 0x00, 0x02]);
+        assert_eq!(last.data[9], 0x41);
+        assert_eq!(&last.data[11..], &event[1..]);
+    }
+
+    #[test]
+    fn test_watchdog() {
+        let mut bmc = sim("");
+        // 未经 Set 初始化的计时器不能启动
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_RESET_WATCHDOG_TIMER, &[]);
+        assert_eq!(rsp.ccode, CC_WDT_NOT_INITIALIZED);
+
+        let set = [0x44, 0x01, 0x00, 0x10, 0xb8, 0x0b];
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, BMC_SET_WATCHDOG_TIMER, &set).ccode,
+            CC_OK
+        );
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_GET_WATCHDOG_TIMER, &[]);
+        assert_eq!(
+            rsp.data,
+            vec![0x04, 0x01, 0x00, 0x00, 0xb8, 0x0b, 0xb8, 0x0b]
+        );
+
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_RESET_WATCHDOG_TIMER, &[]);
+        assert_eq!(rsp.ccode, CC_OK);
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_GET_WATCHDOG_TIMER, &[]);
+        assert_eq!(rsp.data[0], 0x44);
+        assert!(u16_at(&rsp.data, 6) <= 3000);
+
+        // 倒计时为 0 时立即超时，置位 SMS/OS expiration flag 并停止
+        let set = [0x44, 0x00, 0x00, 0x00, 0x00, 0x00];
+        call(&mut bmc, IPMI_NETFN_APP, BMC_SET_WATCHDOG_TIMER, &set);
+        call(&mut bmc, IPMI_NETFN_APP, BMC_RESET_WATCHDOG_TIMER, &[]);
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_GET_WATCHDOG_TIMER, &[]);
+        assert_eq!(&rsp.data[..4], &[0x04, 0x00, 0x00, 0x10]);
+
+        // pre-timeout 间隔超过倒计时
+        let set = [0x04, 0x11, 0x0a, 0x00, 0x32, 0x00];
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_SET_WATCHDOG_TIMER, &set);
+        assert_eq!(rsp.ccode, CC_INV_DATA_FIELD_IN_REQ);
+    }
+
+    #[test]
+    fn test_guid_and_global_enables() {
+        let mut bmc = sim("");
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_GET_GUID, &[]);
+        assert_eq!(rsp.data.len(), 16);
+
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_SET_GLOBAL_ENABLES, &[0x0d]);
+        assert_eq!(rsp.ccode, CC_OK);
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_GET_GLOBAL_ENABLES, &[]);
+        assert_eq!(rsp.data, vec![0x0d]);
+        // 保留位不能置位
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, BMC_SET_GLOBAL_ENABLES, &[0x1c]);
+        assert_eq!(rsp.ccode, CC_INV_DATA_FIELD_IN_REQ);
+    }
+
+    #[test]
+    fn test_sysinfo_and_acpi() {
+        let mut bmc = sim("");
+        let mut block = vec![0x03, 0x00, 0x00, 0x04];
+        block.extend_from_slice(b"Linux");
+        block.resize(18, 0);
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, IPMI_SET_SYS_INFO, &[0, 1]).ccode,
+            CC_OK
+        );
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, IPMI_SET_SYS_INFO, &[0, 1]);
+        assert_eq!(rsp.ccode, CC_SET_IN_PROGRESS);
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, IPMI_SET_SYS_INFO, &block).ccode,
+            CC_OK
+        );
+        assert_eq!(
+            call(&mut bmc, IPMI_NETFN_APP, IPMI_SET_SYS_INFO, &[0, 0]).ccode,
+            CC_OK
+        );
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, IPMI_GET_SYS_INFO, &[0, 3, 0, 0]);
+        assert_eq!(&rsp.data[..7], &[0x11, 0x00, 0x00, 0x04, b'L', b'i', b'n']);
+
+        let rsp = call(
+            &mut bmc,
+            IPMI_NETFN_APP,
+            IPMI_SET_ACPI_POWER_STATE,
+            &[0x83, 0x7f],
+        );
+        assert_eq!(rsp.ccode, CC_OK);
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, IPMI_GET_ACPI_POWER_STATE, &[]);
+        assert_eq!(rsp.data, vec![0x03, 0x00]);
+    }
+
+    #[test]
+    fn test_channel_commands() {
+        let mut bmc = sim("");
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, IPMI_GET_CHANNEL_INFO, &[0x0e]);
+        assert_eq!(&rsp.data[..4], &[0x01, 0x04, 0x01, 0x80]);
+        let rsp = call(&mut bmc, IPMI_NETFN_APP, IPMI_GET_CHANNEL_INFO, &[0x05]);
+        assert_eq!(rsp.ccode, CC_INV_DATA_FIELD_IN_REQ);
+
+        let rsp = call(
+            &mut bmc,
+            IPMI_NETFN_APP,
+            IPMI_GET_CHANNEL_CIPHER_SUITES,
+            &[0x01, 0x00, 0x80],
+        );
+        assert_eq!(rsp.data.len(), 1 + SIM_CIPHER_SUITES.len());
+        let rsp = call(
+            &mut bmc,
+            IPMI_NETFN_APP,
+            IPMI_GET_CHANNEL_CIPHER_SUITES,
+            &[0x01, 0x00, 0x81],
+        );
+        assert_eq!(rsp.data, vec![0x01]);
+
+        // callin=off, link=on, ipmi=off, OPERATOR
+        let rsp = call(
+            &mut bmc,
+            IPMI_NETFN_APP,
+            IPMI_SET_USER_ACCESS,
+            &[0xe1, 0x02, 0x03, 0x00],
+        );
+        assert_eq!(rsp.ccode, CC_OK);
+        let rsp = call(
+            &mut bmc,
+            IPMI_NETFN_APP,
+            IPMI_GET_USER_ACCESS,
+            &[0x01, 0x02],
+        );
+        assert_eq!(rsp.data[3], 0x63);
+        // 只改权限时保留其他位
+        call(
+            &mut bmc,
+            IPMI_NETFN_APP,
+            IPMI_SET_USER_ACCESS,
+            &[0x01, 0x02, 0x04, 0x00],
+        );
+        let rsp = call(
+            &mut bmc,
+            IPMI_NETFN_APP,
+            IPMI_GET_USER_ACCESS,
+            &[0x01, 0x02],
+        );
+        assert_eq!(rsp.data[3], 0x64);
+    }
+}
@@ -0,0 +1,628 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 模拟器的 RMCP / IPMI v1.5 会话端 (`-I lan`)
+//!
+//! 会话建立命令与 ASF presence ping 在这里处理，其余命令交给 [`SimBmc`]。
+//! 认证码不对的报文和会话之外的普通命令与真实 BMC 一样直接丢弃。
+
+use super::bmc::SimReply;
+use super::SharedBmc;
+use crate::interface::dummy::DummyRequest;
+use crate::interface::lan::auth::{IpmiAuth, IPMI_SESSION_AUTHTYPE_NONE};
+use crate::interface::lan::rmcp::{AsfHeader, RmcpHeader, RmcpPong, ASF_ENTITY_IPMI};
+use crate::interface::open::open::ipmi_csum;
+use crate::ipmi::ipmi::{IPMI_BUF_SIZE, IPMI_NETFN_APP};
+use crate::{log_debug, log_info};
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+
+const IPMI_GET_CHANNEL_AUTH_CAP: u8 = 0x38;
+const IPMI_GET_SESSION_CHALLENGE: u8 = 0x39;
+const IPMI_ACTIVATE_SESSION: u8 = 0x3a;
+const IPMI_SET_SESSION_PRIVILEGE: u8 = 0x3b;
+const IPMI_CLOSE_SESSION: u8 = 0x3c;
+const IPMI_GET_SESSION_INFO: u8 = 0x3d;
+
+// NONE, MD2, MD5, PASSWORD
+const SIM_AUTH_SUPPORT: u8 = 0x17;
+const SIM_LAN_CHANNEL: u8 = 0x01;
+const IPMI_SESSION_PRIV_USER: u8 = 0x02;
+const SIM_SESSION_SLOTS: u8 = 4;
+
+/// Get Session Challenge 之后、Activate Session 之前的临时会话
+struct Challenge {
+    challenge: [u8; 16],
+    user_id: u8,
+    password: Vec<u8>,
+    max_privilege: u8,
+}
+
+struct LanSession {
+    /// Get Session Info / Close Session 使用的会话句柄
+    handle: u8,
+    user_id: u8,
+    peer: SocketAddr,
+    authtype: u8,
+    password: Vec<u8>,
+    privilege: u8,
+    max_privilege: u8,
+    /// 发往客户端的会话序号
+    out_seq: u32,
+}
+
+/// 解析后的请求报文
+struct LanRequest<'a> {
+    authtype: u8,
+    seq: u32,
+    session_id: u32,
+    authcode: Option<&'a [u8]>,
+    /// rsSA .. checksum
+    msg: &'a [u8],
+}
+
+impl<'a> LanRequest<'a> {
+    fn parse(pkt: &'a [u8]) -> Result<Self, String> {
+        let rmcp = RmcpHeader::from_bytes(pkt)?;
+        if !rmcp.is_ipmi() {
+            return Err(format!("unsupported RMCP class 0x{:02x}", rmcp.class));
+        }
+        if pkt.len() < 14 {
+            return Err("session header too short".to_string());
+        }
+        let authtype = pkt[4] & 0x0f;
+        let seq = u32::from_le_bytes([pkt[5], pkt[6], pkt[7], pkt[8]]);
+        let session_id = u32::from_le_bytes([pkt[9], pkt[10], pkt[11], pkt[12]]);
+        let mut offset = 13;
+        let authcode = if authtype != IPMI_SESSION_AUTHTYPE_NONE {
+            offset += 16;
+            pkt.get(13..29)
+        } else {
+            None
+        };
+        let len = *pkt.get(offset).ok_or("session header too short")? as usize;
+        let msg = pkt
+            .get(offset + 1..offset + 1 + len)
+            .ok_or("message truncated")?;
+        if msg.len() < 7 || ipmi_csum(&msg[..2]) != msg[2] || ipmi_csum(&msg[3..]) != 0 {
+            return Err("bad IPMI message checksum".to_string());
+        }
+        Ok(LanRequest {
+            authtype,
+            seq,
+            session_id,
+            authcode,
+            msg,
+        })
+    }
+
+    fn netfn(&self) -> u8 {
+        self.msg[1] >> 2
+    }
+
+    fn cmd(&self) -> u8 {
+        self.msg[5]
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.msg[6..self.msg.len() - 1]
+    }
+}
+
+/// RMCP 服务端
+pub struct SimLanServer {
+    socket: UdpSocket,
+    bmc: SharedBmc,
+    challenges: HashMap<u32, Challenge>,
+    sessions: HashMap<u32, LanSession>,
+    last_handle: u8,
+}
+
+fn new_session_id<T>(used: &HashMap<u32, T>) -> u32 {
+    loop {
+        let id = rand::random::<u32>();
+        if id != 0 && !used.contains_key(&id) {
+            return id;
+        }
+    }
+}
+
+impl SimLanServer {
+    pub fn bind(addr: SocketAddr, bmc: SharedBmc) -> io::Result<Self> {
+        Ok(SimLanServer {
+            socket: UdpSocket::bind(addr)?,
+            bmc,
+            challenges: HashMap::new(),
+            sessions: HashMap::new(),
+            last_handle: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn run(&mut self) -> io::Result<()> {
+        let mut buf = [0u8; IPMI_BUF_SIZE];
+        loop {
+            let (len, peer) = self.socket.recv_from(&mut buf)?;
+            match self.handle_packet(&buf[..len], peer) {
+                Ok(Some(rsp)) => {
+                    self.socket.send_to(&rsp, peer)?;
+                }
+                Ok(None) => {}
+                Err(e) => log_debug!("Dropping packet from {}: {}", peer, e),
+            }
+        }
+    }
+
+    fn handle_packet(&mut self, pkt: &[u8], peer: SocketAddr) -> Result<Option<Vec<u8>>, String> {
+        if let Some(pong) = self.presence_pong(pkt) {
+            return Ok(Some(pong));
+        }
+        let req = LanRequest::parse(pkt)?;
+        let is_app = req.netfn() == IPMI_NETFN_APP;
+
+        // 会话外只接受会话建立命令
+        if req.session_id == 0 {
+            let reply = match (is_app, req.cmd()) {
+                (true, IPMI_GET_CHANNEL_AUTH_CAP) => Ok(vec![
+                    SIM_LAN_CHANNEL,
+                    SIM_AUTH_SUPPORT,
+                    0x04, // 启用非空用户
+                    0x00,
+                    0x00,
+                    0x00,
+                    0x00,
+                    0x00,
+                ]),
+                (true, IPMI_GET_SESSION_CHALLENGE) => self.session_challenge(req.data()),
+                _ => return Err("command outside of a session".to_string()),
+            };
+            return Ok(Some(self.build_reply(&req, None, reply)));
+        }
+
+        if is_app && req.cmd() == IPMI_ACTIVATE_SESSION {
+            let chal = self
+                .challenges
+                .get(&req.session_id)
+                .ok_or("unknown temporary session ID")?;
+            // Activate Session 报文已按请求的认证类型计算认证码
+            let authtype = req.data().first().ok_or("activate session data missing")? & 0x0f;
+            verify(&req, authtype, &chal.password)?;
+            let reply = self.activate_session(&req, peer);
+            let rsp = self.build_reply(&req, Some(req.session_id), reply);
+            return Ok(Some(rsp));
+        }
+
+        let session = self
+            .sessions
+            .get(&req.session_id)
+            .ok_or("unknown session ID")?;
+        verify(&req, session.authtype, &session.password)?;
+
+        let reply = match (is_app, req.cmd()) {
+            (true, IPMI_SET_SESSION_PRIVILEGE) => self.set_privilege(&req),
+            (true, IPMI_CLOSE_SESSION) => self.close_session(&req),
+            (true, IPMI_GET_SESSION_INFO) => self.session_info(&req),
+            _ => {
+                let dreq = DummyRequest {
+                    netfn: req.netfn(),
+                    lun: req.msg[1] & 0x03,
+                    cmd: req.cmd(),
+                    target_cmd: 0,
+                    data: req.data().to_vec(),
+                };
+                let reply = self.bmc.lock().unwrap().handle(&dreq);
+                match reply {
+                    SimReply::Response(rsp) if rsp.ccode == 0 => Ok(rsp.data),
+                    SimReply::Response(rsp) => Err(rsp.ccode),
+                    SimReply::NoResponse => return Ok(None),
+                }
+            }
+        };
+        Ok(Some(self.build_reply(&req, Some(req.session_id), reply)))
+    }
+
+    /// ASF presence ping 的应答：支持 IPMI，ASF 1.0
+    fn presence_pong(&self, pkt: &[u8]) -> Option<Vec<u8>> {
+        let rmcp = RmcpHeader::from_bytes(pkt).ok()?;
+        let asf = AsfHeader::from_bytes(pkt.get(4..)?).ok()?;
+        if !rmcp.is_asf() || !asf.is_ping() {
+            return None;
+        }
+        let iana = self.bmc.lock().unwrap().manufacturer_id();
+        let pong = RmcpPong::new(&rmcp, asf.msg_tag, iana, 0, ASF_ENTITY_IPMI | 0x01);
+        Some(pong.to_bytes().to_vec())
+    }
+
+    fn session_challenge(&mut self, d: &[u8]) -> Result<Vec<u8>, u8> {
+        if d.len() < 17 {
+            return Err(0xc7);
+        }
+        let end = d[1..17].iter().position(|&b| b == 0).unwrap_or(16);
+        let name = String::from_utf8_lossy(&d[1..1 + end]).into_owned();
+        let (user_id, password, max_privilege) = match self.bmc.lock().unwrap().find_user(&name) {
+            Some(user) => user,
+            None if name.is_empty() => return Err(0x82),
+            None => return Err(0x81),
+        };
+
+        let id = new_session_id(&self.challenges);
+        let challenge: [u8; 16] = rand::random();
+        self.challenges.insert(
+            id,
+            Challenge {
+                challenge,
+                user_id,
+                password,
+                max_privilege,
+            },
+        );
+        let mut rsp = id.to_le_bytes().to_vec();
+        rsp.extend_from_slice(&challenge);
+        Ok(rsp)
+    }
+
+    fn activate_session(&mut self, req: &LanRequest, peer: SocketAddr) -> Result<Vec<u8>, u8> {
+        let d = req.data();
+        if d.len() < 22 {
+            return Err(0xc7);
+        }
+        let chal = &self.challenges[&req.session_id];
+        if d[2..18] != chal.challenge {
+            return Err(0x85);
+        }
+        let requested = d[1] & 0x0f;
+        if requested > chal.max_privilege {
+            return Err(0x86);
+        }
+        if self.sessions.len() >= SIM_SESSION_SLOTS as usize {
+            return Err(0x83);
+        }
+
+        let chal = self.challenges.remove(&req.session_id).unwrap();
+        let session = LanSession {
+            handle: self.new_handle(),
+            user_id: chal.user_id,
+            peer,
+            authtype: d[0] & 0x0f,
+            password: chal.password,
+            privilege: IPMI_SESSION_PRIV_USER.min(requested),
+            max_privilege: requested,
+            out_seq: u32::from_le_bytes([d[18], d[19], d[20], d[21]]),
+        };
+        let mut rsp = vec![session.authtype];
+        rsp.extend_from_slice(&req.session_id.to_le_bytes());
+        // 期望客户端使用的起始序号
+        rsp.extend_from_slice(&1u32.to_le_bytes());
+        rsp.push(session.max_privilege);
+        self.sessions.insert(req.session_id, session);
+        log_info!("Activated session {:08x}", req.session_id);
+        Ok(rsp)
+    }
+
+    fn set_privilege(&mut self, req: &LanRequest) -> Result<Vec<u8>, u8> {
+        let level = *req.data().first().ok_or(0xc7)? & 0x0f;
+        let session = self.sessions.get_mut(&req.session_id).unwrap();
+        if level > session.max_privilege {
+            return Err(0x81);
+        }
+        if level != 0 {
+            session.privilege = level;
+        }
+        Ok(vec![session.privilege])
+    }
+
+    /// 会话 ID 为 0 时按第 5 字节的句柄关闭
+    fn close_session(&mut self, req: &LanRequest) -> Result<Vec<u8>, u8> {
+        let d = req.data();
+        if d.len() < 4 {
+            return Err(0xc7);
+        }
+        let mut id = u32::from_le_bytes([d[0], d[1], d[2], d[3]]);
+        if id == 0 {
+            let handle = *d.get(4).ok_or(0xc7)?;
+            id = self.session_by_handle(handle).ok_or(0x88)?;
+        }
+        if self.sessions.remove(&id).is_none() {
+            return Err(0x87);
+        }
+        log_info!("Closed session {:08x}", id);
+        Ok(Vec::new())
+    }
+
+    /// 1..=255 循环分配，跳过仍在使用的句柄
+    fn new_handle(&mut self) -> u8 {
+        loop {
+            self.last_handle = self.last_handle.checked_add(1).unwrap_or(1);
+            if self.session_by_handle(self.last_handle).is_none() {
+                return self.last_handle;
+            }
+        }
+    }
+
+    fn session_by_handle(&self, handle: u8) -> Option<u32> {
+        self.sessions
+            .iter()
+            .find(|(_, s)| s.handle == handle)
+            .map(|(id, _)| *id)
+    }
+
+    /// Get Session Info：0 为当前会话，N 为按句柄排序的第 N 个会话，
+    /// 0xfe 按句柄、0xff 按会话 ID 查询
+    fn session_info(&self, req: &LanRequest) -> Result<Vec<u8>, u8> {
+        let d = req.data();
+        let index = *d.first().ok_or(0xc7)?;
+        let id = match index {
+            0 => Some(req.session_id),
+            0xfe => Some(self.session_by_handle(*d.get(1).ok_or(0xc7)?).ok_or(0xcc)?),
+            0xff => {
+                if d.len() < 5 {
+                    return Err(0xc7);
+                }
+                let id = u32::from_le_bytes([d[1], d[2], d[3], d[4]]);
+                self.sessions.contains_key(&id).then_some(id).ok_or(0xcc)?;
+                Some(id)
+            }
+            n if n > SIM_SESSION_SLOTS => return Err(0xcc),
+            n => {
+                let mut ids: Vec<(u8, u32)> = self
+                    .sessions
+                    .iter()
+                    .map(|(id, s)| (s.handle, *id))
+                    .collect();
+                ids.sort();
+                ids.get(n as usize - 1).map(|(_, id)| *id)
+            }
+        };
+
+        let mut rsp = vec![0, SIM_SESSION_SLOTS, self.sessions.len() as u8];
+        let Some(session) = id.and_then(|id| self.sessions.get(&id)) else {
+            // 空槽位只返回计数
+            return Ok(rsp);
+        };
+        rsp[0] = session.handle;
+        rsp.extend_from_slice(&[session.user_id, session.privilege, SIM_LAN_CHANNEL]);
+        let ip = match session.peer.ip() {
+            IpAddr::V4(ip) => ip.octets(),
+            IpAddr::V6(_) => [0; 4],
+        };
+        rsp.extend_from_slice(&ip);
+        // 模拟器拿不到对端 MAC
+        rsp.extend_from_slice(&[0; 6]);
+        rsp.extend_from_slice(&session.peer.port().to_be_bytes());
+        Ok(rsp)
+    }
+
+    /// 构造应答报文，session_id 为 None 时不带会话
+    fn build_reply(
+        &mut self,
+        req: &LanRequest,
+        session_id: Option<u32>,
+        reply: Result<Vec<u8>, u8>,
+    ) -> Vec<u8> {
+        let (ccode, data) = match reply {
+            Ok(data) => (0, data),
+            Err(cc) => (cc, Vec::new()),
+        };
+        let rq = req.msg;
+        let mut msg = vec![rq[3], ((req.netfn() + 1) << 2) | (rq[4] & 0x03)];
+        msg.push(ipmi_csum(&msg));
+        msg.extend_from_slice(&[rq[0], (rq[4] & 0xfc) | (rq[1] & 0x03), rq[5], ccode]);
+        msg.extend_from_slice(&data);
+        msg.push(ipmi_csum(&msg[3..]));
+
+        // Activate Session 之后会话才存在，失败时按会话外应答
+        let session = session_id.and_then(|id| self.sessions.get_mut(&id).map(|s| (id, s)));
+        let (authtype, seq, id, authcode) = match session {
+            Some((id, s)) => {
+                let seq = s.out_seq;
+                s.out_seq = s.out_seq.wrapping_add(1);
+                let mut auth = IpmiAuth::new_with_password(s.authtype, String::new());
+                let code = auth.calculate_authcode(id, seq, &s.password, &msg).ok();
+                (s.authtype, seq, id, code)
+            }
+            None => (IPMI_SESSION_AUTHTYPE_NONE, 0, 0, None),
+        };
+
+        let mut pkt = RmcpHeader::new_ipmi(0xff).to_bytes().to_vec();
+        pkt.push(authtype);
+        pkt.extend_from_slice(&seq.to_le_bytes());
+        pkt.extend_from_slice(&id.to_le_bytes());
+        if authtype != IPMI_SESSION_AUTHTYPE_NONE {
+            pkt.extend_from_slice(&authcode.unwrap_or([0; 16]));
+        }
+        pkt.push(msg.len() as u8);
+        pkt.extend_from_slice(&msg);
+        pkt
+    }
+}
+
+/// 校验请求的认证码，`authtype` 为会话协商的认证类型
+///
+/// 只有协商为 NONE 的会话才接受不带认证码的报文。
+fn verify(req: &LanRequest, authtype: u8, password: &[u8]) -> Result<(), String> {
+    if req.authtype != authtype {
+        return Err("session authtype mismatch".to_string());
+    }
+    if authtype == IPMI_SESSION_AUTHTYPE_NONE {
+        return Ok(());
+    }
+    let code = req.authcode.ok_or("authcode missing")?;
+    let auth = IpmiAuth::new_with_password(req.authtype, String::new());
+    let expected: [u8; 16] = code.try_into().unwrap();
+    match auth.verify_authcode(&expected, req.session_id, req.seq, password, req.msg) {
+        Ok(true) => Ok(()),
+        _ => Err("authcode mismatch".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::lan::auth::IPMI_SESSION_AUTHTYPE_MD5;
+    use crate::interface::lan::IpmiLanIntf;
+    use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+    use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_STORAGE};
+    use crate::sim::bmc::SimBmc;
+    use crate::sim::model::SimModel;
+    use std::sync::{Arc, Mutex};
+
+    fn start_server() -> SocketAddr {
+        let model = SimModel::from_json(
+            r#"{
+                "users": [{ "id": 2, "name": "admin", "password": "secret" }],
+                "sel_time": 1700000000,
+                "faults": [{ "netfn": 6, "cmd": 4, "action": "timeout" }]
+            }"#,
+        )
+        .unwrap();
+        let bmc = Arc::new(Mutex::new(SimBmc::new(model).unwrap()));
+        let mut server = SimLanServer::bind("127.0.0.1:0".parse().unwrap(), bmc).unwrap();
+        let addr = server.local_addr().unwrap();
+        std::thread::spawn(move || server.run());
+        addr
+    }
+
+    fn lan_intf(addr: SocketAddr, password: &str) -> IpmiLanIntf {
+        let mut intf =
+            IpmiLanIntf::new("127.0.0.1".to_string(), addr.port(), IpmiContext::default());
+        intf.set_credentials("admin".to_string(), password.to_string());
+        intf.set_authtype(IPMI_SESSION_AUTHTYPE_MD5);
+        intf.set_timeout(1, 1);
+        intf
+    }
+
+    #[test]
+    fn test_lan_session_against_simulator() {
+        let addr = start_server();
+        let mut intf = lan_intf(addr, "secret");
+        intf.setup().unwrap();
+        intf.open().unwrap();
+
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+        req.msg.cmd = 0x48; // Get SEL Time
+        let rsp = intf.sendrecv(&req).unwrap();
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(&rsp.data[..4], &1700000000u32.to_le_bytes());
+
+        // 注入超时的命令得不到应答
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = 0x04;
+        assert!(intf.sendrecv(&req).is_none());
+        intf.close();
+
+        // 密码错误时 Activate Session 被丢弃
+        let mut bad = lan_intf(addr, "wrong");
+        assert!(bad.open().is_err());
+    }
+
+    #[test]
+    fn test_verify_requires_negotiated_authtype() {
+        let msg = [0x20, 0x18, 0xc8, 0x81, 0x04, 0x3a, 0x41];
+        let none = LanRequest {
+            authtype: IPMI_SESSION_AUTHTYPE_NONE,
+            seq: 1,
+            session_id: 0x1234,
+            authcode: None,
+            msg: &msg,
+        };
+        // MD5 会话里不带认证码的报文不能绕过密码校验
+        assert!(verify(&none, IPMI_SESSION_AUTHTYPE_MD5, b"secret").is_err());
+        assert!(verify(&none, IPMI_SESSION_AUTHTYPE_NONE, b"secret").is_ok());
+
+        let code = [0u8; 16];
+        let md5 = LanRequest {
+            authtype: IPMI_SESSION_AUTHTYPE_MD5,
+            authcode: Some(&code),
+            ..none
+        };
+        assert!(verify(&md5, IPMI_SESSION_AUTHTYPE_MD5, b"secret").is_err());
+        assert!(verify(&md5, IPMI_SESSION_AUTHTYPE_NONE, b"secret").is_err());
+    }
+
+    #[test]
+    fn test_bridged_requests_over_lan_session() {
+        let addr = start_server();
+        let mut intf = lan_intf(addr, "secret");
+        intf.setup().unwrap();
+        intf.open().unwrap();
+
+        // 单重桥接：经通道 7 发往 0x82
+        let ctx = intf.context();
+        ctx.set_my_addr(0x20);
+        ctx.set_target_addr(0x82);
+        ctx.set_target_channel(7);
+        assert_eq!(ctx.get_bridging_level(), 1);
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_STORAGE);
+        req.msg.cmd = 0x48; // Get SEL Time
+        let rsp = intf.sendrecv(&req).unwrap();
+        assert_eq!(rsp.msg.cmd, 0x48);
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(&rsp.data[..4], &1700000000u32.to_le_bytes());
+
+        // 双重桥接：先经中转控制器 0x72
+        intf.context().enable_bridging(0x72, 0);
+        assert_eq!(intf.context().get_bridging_level(), 2);
+        let rsp = intf.sendrecv(&req).unwrap();
+        assert_eq!(rsp.msg.cmd, 0x48);
+        assert_eq!(rsp.data_len, 4);
+
+        // 被桥接命令的完成码原样返回
+        req.msg.cmd = 0x7f;
+        assert_eq!(intf.sendrecv(&req).unwrap().ccode, 0xc1);
+        intf.close();
+    }
+
+    #[test]
+    fn test_session_info_and_close_by_handle() {
+        let addr = start_server();
+        let mut first = lan_intf(addr, "secret");
+        first.setup().unwrap();
+        first.open().unwrap();
+        let mut second = lan_intf(addr, "secret");
+        second.setup().unwrap();
+        second.open().unwrap();
+
+        let mut data = [0u8; 2];
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = IPMI_GET_SESSION_INFO;
+        req.msg.data = data.as_mut_ptr();
+        req.msg.data_len = 1;
+        let rsp = first.sendrecv(&req).unwrap();
+        assert_eq!(&rsp.data[..6], &[1, SIM_SESSION_SLOTS, 2, 2, 0x04, 0x01]);
+        assert_eq!(&rsp.data[6..10], &[127, 0, 0, 1]);
+        assert_eq!(rsp.data_len, 18);
+
+        // 第 3 个槽位为空
+        data[0] = 3;
+        let rsp = first.sendrecv(&req).unwrap();
+        assert_eq!(
+            &rsp.data[..rsp.data_len as usize],
+            &[0, SIM_SESSION_SLOTS, 2]
+        );
+
+        // 第一个会话按句柄关闭第二个会话
+        let mut close = [0, 0, 0, 0, 2];
+        req.msg.cmd = IPMI_CLOSE_SESSION;
+        req.msg.data = close.as_mut_ptr();
+        req.msg.data_len = close.len() as u16;
+        assert_eq!(first.sendrecv(&req).unwrap().ccode, 0);
+        assert_eq!(first.sendrecv(&req).unwrap().ccode, 0x88);
+
+        data = [0xfe, 2];
+        req.msg.cmd = IPMI_GET_SESSION_INFO;
+        req.msg.data = data.as_mut_ptr();
+        req.msg.data_len = 2;
+        assert_eq!(first.sendrecv(&req).unwrap().ccode, 0xcc);
+        first.close();
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! BMC 模拟器 (utipmi-sim)
+//!
+//! 从 JSON 模型构造一个 BMC，通过 Unix socket 应答 `-I dummy`，
+//! 通过本地 UDP 端口应答 `-I lan`。
+
+pub mod bmc;
+pub mod lan;
+pub mod model;
+
+pub use bmc::{SimBmc, SimReply};
+pub use lan::SimLanServer;
+pub use model::SimModel;
+
+use crate::interface::dummy::DummyRequest;
+use crate::log_debug;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixListener;
+use std::sync::{Arc, Mutex};
+
+pub type SharedBmc = Arc<Mutex<SimBmc>>;
+
+/// 接受 dummy 接口的连接，每个连接一个线程
+pub fn serve_unix(listener: UnixListener, bmc: SharedBmc) -> io::Result<()> {
+    for conn in listener.incoming() {
+        let mut conn = conn?;
+        let bmc = bmc.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = serve_connection(&mut conn, &bmc) {
+                log_debug!("dummy connection closed: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// 依次处理一个连接上的请求，直到对端关闭
+pub fn serve_connection<S: Read + Write>(stream: &mut S, bmc: &SharedBmc) -> io::Result<()> {
+    loop {
+        let req = match DummyRequest::read_from(stream) {
+            Ok(req) => req,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        log_debug!(
+            "dummy request netfn 0x{:02x} cmd 0x{:02x} data {:02x?}",
+            req.netfn,
+            req.cmd,
+            req.data
+        );
+        let reply = bmc.lock().unwrap().handle(&req);
+        if let SimReply::Response(rsp) = reply {
+            rsp.write_to(stream)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::dummy::IpmiDummyIntf;
+    use crate::ipmi::intf::{IpmiContext, IpmiIntf};
+    use crate::ipmi::ipmi::{IpmiRq, IPMI_NETFN_APP};
+
+    #[test]
+    fn test_dummy_intf_against_simulator() {
+        let dir = std::env::temp_dir().join(format!("utipmi-sim-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sim.sock");
+        let _ = std::fs::remove_file(&path);
+
+        let model = SimModel::from_json(r#"{ "device": { "manufacturer_id": 343 } }"#).unwrap();
+        let bmc = Arc::new(Mutex::new(SimBmc::new(model).unwrap()));
+        let listener = UnixListener::bind(&path).unwrap();
+        std::thread::spawn(move || serve_unix(listener, bmc));
+
+        let mut intf = IpmiDummyIntf::new(Some(path), IpmiContext::default());
+        intf.setup().unwrap();
+        intf.open().unwrap();
+        let mut req = IpmiRq::default();
+        req.msg.netfn_mut(IPMI_NETFN_APP);
+        req.msg.cmd = 0x01;
+        let rsp = intf.sendrecv(&req).unwrap();
+        assert_eq!(rsp.ccode, 0);
+        assert_eq!(&rsp.data[6..9], &[0x57, 0x01, 0x00]);
+
+        req.msg.cmd = 0x7f;
+        assert_eq!(intf.sendrecv(&req).unwrap().ccode, 0xc1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,327 @@
+/*
+ * SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later
+ */
+
+//! 模拟器的 JSON 模型文件
+//!
+//! 字节串写成十六进制字符串 (`"51 01 33"`，空白可省略)，单字节数值既可以写十进制
+//! 也可以写 `"0x.."`。SDR 与 SEL 以原始记录给出，加载时用命令模块里的记录结构校验。
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// 通道号 -> (参数号 -> 参数数据)
+pub type LanParams = BTreeMap<u8, BTreeMap<u8, Vec<u8>>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimModel {
+    pub device: DeviceModel,
+    /// 完整 SDR 记录 (含 5 字节记录头)
+    #[serde(deserialize_with = "hex_list")]
+    pub sdr: Vec<Vec<u8>>,
+    pub sensors: Vec<SensorModel>,
+    /// 16 字节 SEL 记录
+    #[serde(deserialize_with = "hex_list")]
+    pub sel: Vec<Vec<u8>>,
+    /// 固定的 SEL 时间，缺省使用系统时间
+    pub sel_time: Option<u32>,
+    /// FRU ID -> 存储区内容
+    #[serde(deserialize_with = "hex_map")]
+    pub fru: BTreeMap<u8, Vec<u8>>,
+    pub users: Vec<UserModel>,
+    #[serde(deserialize_with = "hex_map_map")]
+    pub lan: LanParams,
+    pub chassis: ChassisModel,
+    pub faults: Vec<Fault>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeviceModel {
+    #[serde(deserialize_with = "byte")]
+    pub device_id: u8,
+    #[serde(deserialize_with = "byte")]
+    pub device_revision: u8,
+    #[serde(deserialize_with = "byte")]
+    pub firmware_major: u8,
+    /// BCD 编码的次版本号
+    #[serde(deserialize_with = "byte")]
+    pub firmware_minor: u8,
+    #[serde(deserialize_with = "byte")]
+    pub ipmi_version: u8,
+    #[serde(deserialize_with = "byte")]
+    pub additional_support: u8,
+    pub manufacturer_id: u32,
+    pub product_id: u16,
+    #[serde(deserialize_with = "hex")]
+    pub aux: Vec<u8>,
+    /// Get System GUID 原样返回的 16 字节
+    #[serde(deserialize_with = "hex")]
+    pub guid: Vec<u8>,
+    /// Get Self Test Results 的两个结果字节
+    #[serde(deserialize_with = "hex")]
+    pub self_test: Vec<u8>,
+}
+
+impl Default for DeviceModel {
+    fn default() -> Self {
+        DeviceModel {
+            device_id: 0x20,
+            device_revision: 0x01,
+            firmware_major: 0x01,
+            firmware_minor: 0x00,
+            ipmi_version: 0x02,
+            // Chassis, Bridge, IPMB Event Generator/Receiver, FRU, SEL, SDR, Sensor
+            additional_support: 0xbf,
+            manufacturer_id: 0,
+            product_id: 0,
+            aux: Vec::new(),
+            // SMBIOS 字节序的版本 1 GUID
+            guid: vec![
+                0x00, 0x80, 0xcc, 0xb4, 0x38, 0xa8, 0xee, 0x11, 0x80, 0x00, 0x52, 0x54, 0x00, 0x12,
+                0x34, 0x56,
+            ],
+            self_test: vec![0x55, 0x00],
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SensorModel {
+    #[serde(deserialize_with = "byte")]
+    pub number: u8,
+    /// 工程单位读数，需要对应的 Full Sensor 记录
+    pub reading: Option<f64>,
+    /// 原始读数，优先于 reading
+    #[serde(deserialize_with = "opt_byte")]
+    pub raw: Option<u8>,
+    /// 离散传感器的状态字节，门限传感器缺省按门限计算
+    #[serde(deserialize_with = "hex")]
+    pub state: Vec<u8>,
+    pub unavailable: bool,
+    /// lnr/lcr/lnc/unc/ucr/unr -> 工程单位门限
+    pub thresholds: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserModel {
+    #[serde(deserialize_with = "byte")]
+    pub id: u8,
+    pub name: String,
+    pub password: String,
+    pub enabled: bool,
+    /// 通道权限上限，4 = ADMINISTRATOR
+    #[serde(deserialize_with = "byte")]
+    pub privilege: u8,
+}
+
+impl Default for UserModel {
+    fn default() -> Self {
+        UserModel {
+            id: 0,
+            name: String::new(),
+            password: String::new(),
+            enabled: true,
+            privilege: 0x04,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChassisModel {
+    pub power_on: bool,
+    /// 0 = always-off, 1 = previous, 2 = always-on
+    #[serde(deserialize_with = "byte")]
+    pub policy: u8,
+    #[serde(deserialize_with = "byte")]
+    pub restart_cause: u8,
+    pub poh_hours: u32,
+    /// 启动参数号 -> 参数数据
+    #[serde(deserialize_with = "hex_map")]
+    pub boot_params: BTreeMap<u8, Vec<u8>>,
+}
+
+/// 故障注入：匹配 netfn/cmd 的请求先放过 skip 个，之后的 count 个 (缺省不限) 触发
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fault {
+    #[serde(deserialize_with = "byte")]
+    pub netfn: u8,
+    #[serde(deserialize_with = "byte")]
+    pub cmd: u8,
+    #[serde(default)]
+    pub skip: u32,
+    #[serde(default)]
+    pub count: Option<u32>,
+    #[serde(flatten)]
+    pub action: FaultAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum FaultAction {
+    /// 以指定完成码应答
+    Ccode {
+        #[serde(deserialize_with = "byte")]
+        ccode: u8,
+    },
+    /// 不应答
+    Timeout,
+    /// 作废当前 SDR/SEL 预留并返回 0xC5
+    CancelReservation,
+}
+
+impl SimModel {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read model {}: {}", path.display(), e))?;
+        Self::from_json(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    }
+}
+
+pub fn parse_hex(s: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = s
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace() && *c != b':' && *c != b'-')
+        .collect();
+    if digits.len() % 2 != 0 {
+        return Err(format!("odd number of hex digits in \"{}\"", s));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|p| u8::from_str_radix(p, 16).ok())
+                .ok_or_else(|| format!("invalid hex byte in \"{}\"", s))
+        })
+        .collect()
+}
+
+fn parse_byte(s: &str) -> Result<u8, String> {
+    let r = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(h) => u8::from_str_radix(h, 16),
+        None => s.parse::<u8>(),
+    };
+    r.map_err(|_| format!("invalid byte value \"{}\"", s))
+}
+
+struct ByteVisitor;
+
+impl<'de> Visitor<'de> for ByteVisitor {
+    type Value = u8;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a byte as integer or \"0x..\" string")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<u8, E> {
+        u8::try_from(v).map_err(|_| E::custom(format!("{} out of range for a byte", v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<u8, E> {
+        u8::try_from(v).map_err(|_| E::custom(format!("{} out of range for a byte", v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<u8, E> {
+        parse_byte(v).map_err(E::custom)
+    }
+}
+
+fn byte<'de, D: Deserializer<'de>>(d: D) -> Result<u8, D::Error> {
+    d.deserialize_any(ByteVisitor)
+}
+
+fn opt_byte<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u8>, D::Error> {
+    #[derive(Deserialize)]
+    struct Wrap(#[serde(deserialize_with = "byte")] u8);
+    Ok(Option::<Wrap>::deserialize(d)?.map(|w| w.0))
+}
+
+fn hex<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+    let s = String::deserialize(d)?;
+    parse_hex(&s).map_err(de::Error::custom)
+}
+
+fn hex_list<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Vec<u8>>, D::Error> {
+    Vec::<String>::deserialize(d)?
+        .iter()
+        .map(|s| parse_hex(s).map_err(de::Error::custom))
+        .collect()
+}
+
+fn hex_map<'de, D: Deserializer<'de>>(d: D) -> Result<BTreeMap<u8, Vec<u8>>, D::Error> {
+    BTreeMap::<String, String>::deserialize(d)?
+        .iter()
+        .map(|(k, v)| {
+            let key = parse_byte(k).map_err(de::Error::custom)?;
+            Ok((key, parse_hex(v).map_err(de::Error::custom)?))
+        })
+        .collect()
+}
+
+fn hex_map_map<'de, D: Deserializer<'de>>(d: D) -> Result<LanParams, D::Error> {
+    BTreeMap::<String, BTreeMap<String, String>>::deserialize(d)?
+        .iter()
+        .map(|(chan, params)| {
+            let chan = parse_byte(chan).map_err(de::Error::custom)?;
+            let params = params
+                .iter()
+                .map(|(k, v)| {
+                    let key = parse_byte(k).map_err(de::Error::custom)?;
+                    Ok((key, parse_hex(v).map_err(de::Error::custom)?))
+                })
+                .collect::<Result<_, D::Error>>()?;
+            Ok((chan, params))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_parse() {
+        let model = SimModel::from_json(
+            r#"{
+                "device": { "device_id": "0x21", "manufacturer_id": 343 },
+                "sel": ["0100 02 00000000 2000 04 01 30 01 51 00 00"],
+                "lan": { "1": { "3": "c0 a8 00 02" } },
+                "faults": [
+                    { "netfn": "0x0a", "cmd": "0x23", "action": "ccode", "ccode": "0xc3" },
+                    { "netfn": 4, "cmd": 45, "action": "timeout", "skip": 1, "count": 2 }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(model.device.device_id, 0x21);
+        assert_eq!(model.device.ipmi_version, 0x02);
+        assert_eq!(model.sel[0].len(), 16);
+        assert_eq!(model.lan[&1][&3], vec![0xc0, 0xa8, 0x00, 0x02]);
+        assert_eq!(model.faults[0].action, FaultAction::Ccode { ccode: 0xc3 });
+        assert_eq!(model.faults[1].action, FaultAction::Timeout);
+        assert_eq!(model.faults[1].count, Some(2));
+
+        assert!(SimModel::from_json(r#"{ "sdr": ["01 2"] }"#).is_err());
+        assert!(SimModel::from_json(r#"{ "device": { "device_id": 300 } }"#).is_err());
+        assert!(SimModel::from_json(r#"{ "bogus": 1 }"#).is_err());
+    }
+
+    #[test]
+    fn test_example_model() {
+        SimModel::from_json(include_str!("../../doc/utipmi-sim-model.json")).unwrap();
+    }
+}
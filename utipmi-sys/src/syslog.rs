@@ -1,7 +1,7 @@
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 
-pub use libc::{LOG_CONS, LOG_ERR, LOG_LOCAL4, LOG_NOTICE, LOG_WARNING};
+pub use libc::{LOG_CONS, LOG_DAEMON, LOG_ERR, LOG_LOCAL4, LOG_NOTICE, LOG_PID, LOG_WARNING};
 
 fn cstring_sanitize(input: &str) -> CString {
     match CString::new(input) {